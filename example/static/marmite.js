@@ -224,4 +224,24 @@ document.addEventListener("DOMContentLoaded", function() {
     const isDarkMode = themeSwitcher.scheme === 'dark';
     changeGiscusTheme(isDarkMode ? 'dark' : 'light');
   }
-});
\ No newline at end of file
+});
+// Keep the ticked task checkboxes of `tasklist_interactive` on the browser
+document.addEventListener("DOMContentLoaded", function() {
+  const checkboxes = document.querySelectorAll('input[type="checkbox"][data-task]');
+  if (!checkboxes.length) return;
+  const key = 'marmiteTasks:' + window.location.pathname;
+  let saved = null;
+  try {
+    saved = JSON.parse(localStorage.getItem(key));
+  } catch (e) {}
+  checkboxes.forEach((checkbox) => {
+    if (saved && checkbox.dataset.task in saved) {
+      checkbox.checked = saved[checkbox.dataset.task];
+    }
+    checkbox.addEventListener('change', () => {
+      const state = {};
+      checkboxes.forEach((task) => { state[task.dataset.task] = task.checked; });
+      localStorage.setItem(key, JSON.stringify(state));
+    });
+  });
+});
@@ -13,40 +13,55 @@ import Fuse from "https://cdnjs.cloudflare.com/ajax/libs/fuse.js/7.0.0/fuse.basi
         const response = await fetch('./static/search_index.json');
         const data = await response.json();
         const fuse = new Fuse(data, fuseOptions);
+        const rootElement = document.querySelector(".marmite-search-bar-result");
+        const resultsElement = document.querySelector("#marmite-search-bar-result");
+
+        const showResults = (items) => {
+            if (items.length > 0) {
+                items.forEach((item) => {
+                    const elementList = document.createElement("li");
+                    const resultElement = document.createElement("a");
+                    resultElement.href = `${item.slug}.html`;
+                    resultElement.innerText = item.title;
+                    elementList.appendChild(resultElement);
+                    resultsElement.appendChild(elementList);
+                });
+            } else {
+                const elementList = document.createElement("li");
+                const resultElement = document.createElement("span");
+                resultElement.textContent = "No results found";
+                elementList.appendChild(resultElement);
+                resultsElement.appendChild(elementList);
+            }
+            rootElement.setAttribute("style", "display: block;");
+        };
+
         document.getElementById("marmite-search-input").addEventListener("input", (event) => {
             event.preventDefault();
 
             // Clear previous results
-            const rootElement = document.querySelector(".marmite-search-bar-result");
             rootElement.setAttribute("style", "display: none;");
-
-            const resultsElement = document.querySelector("#marmite-search-bar-result");
             resultsElement.innerHTML = "";
 
-            // Search for results
+            // Search for results, limiting here to 10 items
             const searchPattern = event.target.value;
             if (searchPattern?.length > 2) {
-                const results = fuse.search(searchPattern);
-                if(results?.length > 0) {
-                    // Build the results list, limiting here to 10 items
-                    results.slice(0, 10).forEach((result) => {
-                        const elementList = document.createElement("li");
-                        const resultElement = document.createElement("a");
-                        resultElement.href = `${result.item.slug}.html`;
-                        resultElement.innerText = result.item.title;
-                        elementList.appendChild(resultElement);
-                        resultsElement.appendChild(elementList);
-                    });
-                } else {
-                    const elementList = document.createElement("li");
-                    const resultElement = document.createElement("span");
-                    resultElement.textContent = "No results found";
-                    elementList.appendChild(resultElement);
-                    resultsElement.appendChild(elementList);
-                }
-                rootElement.setAttribute("style", "display: block;");
+                showResults(fuse.search(searchPattern).slice(0, 10).map((result) => result.item));
             }
         });
+
+        // `?tags=rust,async` lists the content tagged with all the given tags
+        const tagsParam = new URLSearchParams(window.location.search).get("tags");
+        if (tagsParam) {
+            const wanted = tagsParam.split(/[,+]/).map((tag) => tag.trim().toLowerCase()).filter(Boolean);
+            const tagged = data.filter((item) => {
+                const itemTags = (item.tags || []).map((tag) => tag.toLowerCase());
+                return wanted.every((tag) => itemTags.includes(tag));
+            });
+            toggleSearchBar();
+            document.getElementById("marmite-search-input").value = wanted.join(" + ");
+            showResults(tagged);
+        }
     } catch (error) {
         console.error('Error loading search data:', error);
     }
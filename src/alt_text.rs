@@ -0,0 +1,262 @@
+use crate::config::AltTextConfig;
+use crate::frontmatter::FrontmatterEditor;
+use crate::re;
+use crate::site::{get_content_folder, Data};
+use crate::spellcheck::collect_markdown_files;
+use log::{info, warn};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// An image without alt text and the line of the body where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingAlt {
+    pub line: usize,
+    pub src: String,
+}
+
+/// Find the markdown and html images with an empty or missing alt text,
+/// images inside fenced code blocks are skipped
+pub fn find_missing_alt(body: &str) -> Vec<MissingAlt> {
+    let markdown_re = Regex::new(re::CAPTURE_MARKDOWN_IMAGE_WITHOUT_ALT)
+        .expect("Markdown image regex should compile");
+    let img_re = Regex::new(re::MATCH_IMG_HTMLTAG).expect("Img tag regex should compile");
+    let src_re =
+        Regex::new(re::CAPTURE_SRC_FROM_IMG_HTMLTAG).expect("Img src regex should compile");
+    let alt_re = Regex::new(re::CAPTURE_ALT_FROM_IMG).expect("Img alt regex should compile");
+
+    let mut missing = Vec::new();
+    let mut in_code_block = false;
+    for (index, line) in body.lines().enumerate() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        for captures in markdown_re.captures_iter(line) {
+            missing.push(MissingAlt {
+                line: index + 1,
+                src: captures[2].to_string(),
+            });
+        }
+        for tag in img_re.find_iter(line) {
+            let has_alt = alt_re
+                .captures(tag.as_str())
+                .is_some_and(|alt| !alt[1].trim().is_empty());
+            if let (false, Some(src)) = (has_alt, src_re.captures(tag.as_str())) {
+                missing.push(MissingAlt {
+                    line: index + 1,
+                    src: src[1].to_string(),
+                });
+            }
+        }
+    }
+    missing
+}
+
+/// Write the `suggestions` (keyed by image src) as the alt text of the images
+/// found by `find_missing_alt`, the rest of the body is kept as written
+pub fn apply_alt_text(body: &str, suggestions: &HashMap<String, String>) -> String {
+    let markdown_re = Regex::new(re::CAPTURE_MARKDOWN_IMAGE_WITHOUT_ALT)
+        .expect("Markdown image regex should compile");
+    let img_re = Regex::new(re::MATCH_IMG_HTMLTAG).expect("Img tag regex should compile");
+    let src_re =
+        Regex::new(re::CAPTURE_SRC_FROM_IMG_HTMLTAG).expect("Img src regex should compile");
+    let alt_re = Regex::new(re::CAPTURE_ALT_FROM_IMG).expect("Img alt regex should compile");
+
+    let mut output = String::with_capacity(body.len());
+    let mut in_code_block = false;
+    for line in body.split_inclusive('\n') {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        }
+        if in_code_block || is_fence(line) {
+            output.push_str(line);
+            continue;
+        }
+        let line = markdown_re.replace_all(line, |captures: &regex::Captures| {
+            match suggestions.get(&captures[2]) {
+                Some(alt) => {
+                    let alt = alt.replace('[', "\\[").replace(']', "\\]");
+                    format!("![{alt}]({})", &captures[1])
+                }
+                None => captures[0].to_string(),
+            }
+        });
+        let line = img_re.replace_all(&line, |tag: &regex::Captures| {
+            let tag = &tag[0];
+            let alt = src_re
+                .captures(tag)
+                .and_then(|src| suggestions.get(&src[1]))
+                .filter(|_| {
+                    alt_re
+                        .captures(tag)
+                        .is_none_or(|alt| alt[1].trim().is_empty())
+                });
+            let Some(alt) = alt else {
+                return tag.to_string();
+            };
+            let alt = alt
+                .replace('&', "&amp;")
+                .replace('"', "&quot;")
+                .replace('<', "&lt;");
+            if alt_re.is_match(tag) {
+                alt_re.replace(tag, format!("alt=\"{alt}\"")).to_string()
+            } else {
+                tag.replacen("<img", &format!("<img alt=\"{alt}\""), 1)
+            }
+        });
+        output.push_str(&line);
+    }
+    output
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Local path of the image relative to the markdown file or the content folder,
+/// remote images are returned as is
+fn resolve_image(src: &str, file_dir: &Path, content_folder: &Path) -> Option<String> {
+    if src.starts_with("http://") || src.starts_with("https://") {
+        return Some(src.to_string());
+    }
+    let src = src.trim_start_matches('/');
+    [file_dir.join(src), content_folder.join(src)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+/// Ask the configured captioning `command` or `endpoint` for the alt text of `image`
+fn suggest_alt_text(image: &str, config: &AltTextConfig) -> Result<String, String> {
+    let caption = if let Some(command) = &config.command {
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or("Empty alt_text command")?;
+        // the command may run in the `external_commands.working_dir`
+        let image = std::path::absolute(image)
+            .ok()
+            .filter(|path| path.is_file())
+            .map_or_else(
+                || image.to_string(),
+                |path| path.to_string_lossy().to_string(),
+            );
+        let args: Vec<&str> = parts.chain([image.as_str()]).collect();
+        crate::converters::run_program(program, &args, "")?
+    } else if let Some(endpoint) = &config.endpoint {
+        let bytes = if image.starts_with("http://") || image.starts_with("https://") {
+            ureq::get(image)
+                .call()
+                .and_then(|mut response| response.body_mut().read_to_vec())
+                .map_err(|e| e.to_string())?
+        } else {
+            fs::read(image).map_err(|e| e.to_string())?
+        };
+        ureq::post(endpoint)
+            .header("Content-Type", "application/octet-stream")
+            .send(&bytes[..])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| e.to_string())?
+    } else {
+        return Err("no `alt_text` command or endpoint configured".to_string());
+    };
+    let caption = caption.trim().replace('\n', " ");
+    if caption.is_empty() {
+        return Err("empty caption".to_string());
+    }
+    Ok(caption)
+}
+
+/// Handle the `--alt-text` command, lists the images without alt text and the
+/// suggested captions, `--fix` writes the suggestions to the markdown files
+pub fn run(
+    config_path: &Arc<PathBuf>,
+    input_folder: &Arc<PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) -> Result<(), String> {
+    let mut site_data = Data::from_file(config_path.as_path());
+    site_data.site.override_from_cli_args(args);
+    let config = &site_data.site.alt_text;
+    let captioning = config.command.is_some() || config.endpoint.is_some();
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    let files = collect_markdown_files(&content_folder);
+
+    let mut total = 0;
+    let mut unfixed = 0;
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut editor = match FrontmatterEditor::parse(&text) {
+            Ok(editor) => editor,
+            Err(e) => {
+                warn!("Skipping {}: {e}", path.display());
+                continue;
+            }
+        };
+        let first_line = text[..text.len() - editor.body().len()]
+            .matches('\n')
+            .count();
+        let file_dir = path.parent().unwrap_or(&content_folder);
+        let display_path = path.strip_prefix(input_folder.as_path()).unwrap_or(path);
+
+        let mut suggestions: HashMap<String, String> = HashMap::new();
+        for image in find_missing_alt(editor.body()) {
+            if captioning && !suggestions.contains_key(&image.src) {
+                let suggestion = resolve_image(&image.src, file_dir, &content_folder)
+                    .ok_or_else(|| "image not found".to_string())
+                    .and_then(|image| suggest_alt_text(&image, config));
+                match suggestion {
+                    Ok(alt) => {
+                        suggestions.insert(image.src.clone(), alt);
+                    }
+                    Err(e) => warn!("No alt text suggested for {}: {e}", image.src),
+                }
+            }
+            let suggestion = suggestions.get(&image.src);
+            match suggestion {
+                Some(alt) => println!(
+                    "{}:{}: {} has no alt text, suggested: `{alt}`",
+                    display_path.display(),
+                    first_line + image.line,
+                    image.src
+                ),
+                None => println!(
+                    "{}:{}: {} has no alt text",
+                    display_path.display(),
+                    first_line + image.line,
+                    image.src
+                ),
+            }
+            total += 1;
+            if !args.fix || suggestion.is_none() {
+                unfixed += 1;
+            }
+        }
+
+        if args.fix && !suggestions.is_empty() {
+            editor.set_body(apply_alt_text(editor.body(), &suggestions));
+            fs::write(path, editor.to_source())
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            info!("Wrote alt text in {}", path.display());
+        }
+    }
+
+    if unfixed > 0 {
+        return Err(format!("Found {unfixed} images without alt text"));
+    }
+    if total > 0 {
+        println!("Wrote the alt text of {total} images");
+    } else {
+        println!("No images without alt text found in {} files", files.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/alt_text.rs"]
+mod tests;
@@ -0,0 +1,86 @@
+use crate::config::BadgesConfig;
+use crate::site::Data;
+use log::{error, info};
+use std::fs;
+use std::path::Path;
+
+/// Approximate width of a Verdana 11px character, badges don't need exact metrics
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+const RSS_COLOR: &str = "#f26522";
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A flat `label | value` badge in the style of shields.io
+pub fn badge_svg(label: &str, value: &str, color: &str) -> String {
+    let label_width = label.chars().count() * CHAR_WIDTH + PADDING;
+    let value_width = value.chars().count() * CHAR_WIDTH + PADDING;
+    let width = label_width + value_width;
+    let (label, value, color) = (escape_xml(label), escape_xml(value), escape_xml(color));
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+<title>{label}: {value}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="14">{label}</text><text x="{value_x}" y="14">{value}</text>
+</g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+/// Badges computed from the posts, drafts excluded: `posts.svg` with the count,
+/// `updated.svg` with the date of the latest post or update and `rss.svg`
+pub fn collect_badges(site_data: &Data, config: &BadgesConfig) -> Vec<(&'static str, String)> {
+    let posts: Vec<_> = site_data
+        .posts
+        .iter()
+        .filter(|post| post.stream.as_deref() != Some("draft"))
+        .collect();
+    let updated = posts
+        .iter()
+        .filter_map(|post| post.updated.or(post.date))
+        .max()
+        .map_or_else(
+            || "never".to_string(),
+            |date| date.format("%Y-%m-%d").to_string(),
+        );
+    vec![
+        (
+            "posts.svg",
+            badge_svg("posts", &posts.len().to_string(), &config.color),
+        ),
+        ("updated.svg", badge_svg("updated", &updated, &config.color)),
+        ("rss.svg", badge_svg("rss", "subscribe", RSS_COLOR)),
+    ]
+}
+
+/// Write the badges to `{badges.path}/` so READMEs and other sites can embed them
+pub fn write_badges(site_data: &Data, output_path: &Path) {
+    let config = &site_data.site.badges;
+    let badges_path = output_path.join(&config.path);
+    if let Err(e) = fs::create_dir_all(&badges_path) {
+        error!("Failed to create {}: {e}", badges_path.display());
+        return;
+    }
+    for (filename, svg) in collect_badges(site_data, config) {
+        let badge_path = badges_path.join(filename);
+        if let Err(e) = fs::write(&badge_path, svg) {
+            error!("Failed to write {}: {e}", badge_path.display());
+        }
+    }
+    info!("Generated badges in {}", badges_path.display());
+}
+
+#[cfg(test)]
+#[path = "tests/badges.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use crate::content::Content;
+use crate::re;
+use frontmatter_gen::Frontmatter;
+use log::{info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Sidecar file keeping the metadata fetched for each bookmarked url
+pub const BOOKMARKS_CACHE: &str = "bookmarks.json";
+
+/// Largest page read for the metadata of a bookmark, the head is at its start
+const BOOKMARK_MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+/// A link saved with a note, from the `bookmark: <url>` frontmatter, the title,
+/// description and favicon are read from the page with `fetch_bookmarks`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Bookmark {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub favicon: Option<String>,
+}
+
+/// Bookmark of the `bookmark` frontmatter key
+pub fn get_bookmark(frontmatter: &Frontmatter) -> Option<Bookmark> {
+    let url = frontmatter.get("bookmark")?.as_str()?.trim();
+    if url.is_empty() {
+        return None;
+    }
+    Some(Bookmark {
+        url: url.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Read the title (`og:title` or `<title>`), the description (`description` or
+/// `og:description`) and the favicon of the page at `url`, the favicon defaults
+/// to `/favicon.ico` of the same host
+pub fn parse_metadata(html: &str, url: &str) -> Bookmark {
+    let tag_re = Regex::new(re::CAPTURE_META_OR_LINK_TAG).expect("Meta tag regex should compile");
+    let attribute_re =
+        Regex::new(re::CAPTURE_HTML_ATTRIBUTES).expect("Attribute regex should compile");
+    let title_re = Regex::new(re::CAPTURE_TITLE_TAG).expect("Title regex should compile");
+
+    let mut bookmark = Bookmark {
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let mut og_title = None;
+    let mut og_description = None;
+    for tag in tag_re.captures_iter(html) {
+        let attributes: BTreeMap<String, String> = attribute_re
+            .captures_iter(&tag[2])
+            .map(|attribute| {
+                let value = attribute.get(2).or_else(|| attribute.get(3));
+                (
+                    attribute[1].to_lowercase(),
+                    value.map_or("", |v| v.as_str()).trim().to_string(),
+                )
+            })
+            .collect();
+        let attribute = |name: &str| attributes.get(name).filter(|value| !value.is_empty());
+        if tag[1].eq_ignore_ascii_case("link") {
+            let is_icon = attribute("rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("icon"))
+            });
+            if is_icon && bookmark.favicon.is_none() {
+                bookmark.favicon = attribute("href").and_then(|href| resolve_url(url, href));
+            }
+            continue;
+        }
+        let Some(content) = attribute("content") else {
+            continue;
+        };
+        match attribute("name")
+            .or_else(|| attribute("property"))
+            .map(|name| name.to_lowercase())
+            .as_deref()
+        {
+            Some("description") => bookmark.description = Some(content.clone()),
+            Some("og:description") => og_description = Some(content.clone()),
+            Some("og:title") => og_title = Some(content.clone()),
+            _ => {}
+        }
+    }
+    bookmark.title = og_title.or_else(|| {
+        title_re
+            .captures(html)
+            .map(|title| title[1].split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|title| !title.is_empty())
+    });
+    bookmark.description = bookmark.description.or(og_description);
+    bookmark.favicon = bookmark
+        .favicon
+        .or_else(|| resolve_url(url, "/favicon.ico"));
+    bookmark
+}
+
+fn resolve_url(base: &str, href: &str) -> Option<String> {
+    url::Url::parse(base)
+        .and_then(|base| base.join(href))
+        .ok()
+        .map(String::from)
+}
+
+fn fetch_metadata(url: &str) -> Result<Bookmark, String> {
+    let html = crate::http::get_text(url, "text/html", BOOKMARK_MAX_BYTES)?;
+    Ok(parse_metadata(&html, url))
+}
+
+/// Fill the bookmark of the content from the `cache`, the urls not cached yet are
+/// fetched when `fetch` is set, the content without a `title` gets the page title
+/// and the content without a `description` gets the page description
+pub fn apply_metadata(content: &mut Content, cache: &mut BTreeMap<String, Bookmark>, fetch: bool) {
+    let Some(bookmark) = content.bookmark.as_mut() else {
+        return;
+    };
+    if fetch && !cache.contains_key(&bookmark.url) {
+        match fetch_metadata(&bookmark.url) {
+            Ok(metadata) => {
+                info!("Fetched bookmark metadata of {}", bookmark.url);
+                cache.insert(bookmark.url.clone(), metadata);
+            }
+            Err(e) => warn!("Failed to fetch bookmark {}: {e}", bookmark.url),
+        }
+    }
+    let Some(metadata) = cache.get(&bookmark.url) else {
+        return;
+    };
+    bookmark.title = bookmark.title.take().or_else(|| metadata.title.clone());
+    bookmark.description = bookmark
+        .description
+        .take()
+        .or_else(|| metadata.description.clone());
+    bookmark.favicon = bookmark.favicon.take().or_else(|| metadata.favicon.clone());
+    if content.title == bookmark.url {
+        if let Some(title) = &bookmark.title {
+            content.title.clone_from(title);
+        }
+    }
+    if content.description.is_none() {
+        content.description.clone_from(&bookmark.description);
+    }
+}
+
+/// Bookmarked posts and pages for the `bookmarks.html` board and feed,
+/// the latest first, drafts excluded
+pub fn collect_bookmarks<'a>(contents: impl Iterator<Item = &'a Content>) -> Vec<Content> {
+    let mut bookmarks: Vec<Content> = contents
+        .filter(|content| content.bookmark.is_some())
+        .filter(|content| content.stream.as_deref() != Some("draft"))
+        .cloned()
+        .collect();
+    bookmarks.sort_by(|a, b| b.date.cmp(&a.date));
+    bookmarks
+}
+
+#[cfg(test)]
+#[path = "tests/bookmarks.rs"]
+mod tests;
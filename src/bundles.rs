@@ -0,0 +1,118 @@
+use crate::config::Marmite;
+use crate::content::Content;
+use crate::converters::is_content_source;
+use crate::re;
+use log::{error, info};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Folder of a page bundle, `my-post/` for the `my-post/index.md` source,
+/// `None` for any other source and for the `index` at the root of the `content_folder`,
+/// which is the input folder itself on sites without a `content/` folder
+pub fn bundle_folder(path: &Path, content_folder: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_str()?;
+    if file_name.split('.').next() != Some("index") {
+        return None;
+    }
+    let folder = path.parent()?;
+    if folder.file_name().is_none() || folder == content_folder {
+        return None;
+    }
+    Some(folder.to_path_buf())
+}
+
+/// Source path named after the bundle folder, e.g: `2024-01-01-my-post/index.md` is
+/// `2024-01-01-my-post.md`, so the slug, date and stream come from the folder name
+pub fn bundle_name_path(folder: &Path, path: &Path) -> PathBuf {
+    let name = folder.file_name().unwrap_or_default().to_string_lossy();
+    match path.extension() {
+        Some(ext) => folder.with_file_name(format!("{name}.{}", ext.to_string_lossy())),
+        None => folder.to_path_buf(),
+    }
+}
+
+/// `{slug}/{file}` when the relative `url` points to a file inside the bundle
+pub fn bundle_url(url: &str, folder: &Path, slug: &str) -> Option<String> {
+    // absolute urls, `mailto:`, `data:` and links out of the bundle are kept
+    if url.contains(':') || url.contains("..") || url.starts_with(['/', '#', '?']) {
+        return None;
+    }
+    let url = url.trim_start_matches("./");
+    let file = url.split(['?', '#']).next().unwrap_or(url);
+    let path = folder.join(file);
+    (!file.is_empty() && path.is_file()).then(|| format!("{slug}/{url}"))
+}
+
+/// Point the relative `src` and `href` to files of the bundle to the
+/// copies next to the rendered page, other links are kept
+pub fn rewrite_links(html: &str, folder: &Path, slug: &str) -> String {
+    let attr_re =
+        Regex::new(re::CAPTURE_SRC_OR_HREF_VALUE).expect("Src or href regex should compile");
+    attr_re
+        .replace_all(html, |caps: &regex::Captures| {
+            let url = bundle_url(&caps[2], folder, slug).unwrap_or_else(|| caps[2].to_string());
+            format!("{}{url}", &caps[1])
+        })
+        .to_string()
+}
+
+/// Copy the files of the bundle of `content` to `{output_dir}/{slug}/`,
+/// the content sources of the folder are not copied
+pub fn copy_bundle_assets(
+    content: &Content,
+    output_dir: &Path,
+    content_folder: &Path,
+    site: &Marmite,
+) {
+    let Some(folder) = content
+        .source_path
+        .as_deref()
+        .and_then(|path| bundle_folder(path, content_folder))
+    else {
+        return;
+    };
+    let destination = output_dir.join(&content.slug);
+    let mut copied = 0;
+    for entry in WalkDir::new(&folder)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| {
+            !is_content_source(
+                entry.path(),
+                &site.input_converters,
+                &site.content_extensions,
+            )
+        })
+    {
+        let relative = entry.path().strip_prefix(&folder).unwrap_or(entry.path());
+        let target = destination.join(relative);
+        if let Some(parent) = target.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create bundle folder {}: {e:?}", parent.display());
+                continue;
+            }
+        }
+        match fs::copy(entry.path(), &target) {
+            Ok(_) => copied += 1,
+            Err(e) => error!(
+                "Failed to copy bundle file {}: {e:?}",
+                entry.path().display()
+            ),
+        }
+    }
+    if copied > 0 {
+        info!(
+            "Copied {copied} files of bundle '{}' to '{}/'",
+            folder.display(),
+            destination.display()
+        );
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/bundles.rs"]
+mod tests;
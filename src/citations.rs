@@ -0,0 +1,221 @@
+use crate::re;
+use log::{error, warn};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// A bibliography entry parsed from a BibTeX file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BibEntry {
+    pub key: String,
+    pub kind: String,
+    pub fields: HashMap<String, String>,
+}
+
+/// Load the BibTeX file, returns an empty bibliography if the file doesn't exist
+pub fn load_bibliography(path: &Path) -> HashMap<String, BibEntry> {
+    if !path.exists() {
+        return HashMap::new();
+    }
+    match fs::read_to_string(path) {
+        Ok(text) => parse_bibtex(&text),
+        Err(e) => {
+            error!("Failed to read bibliography {}: {e}", path.display());
+            HashMap::new()
+        }
+    }
+}
+
+/// Minimal BibTeX parser supporting `{}` and `""` delimited values and bare numbers
+/// e.g: `@article{key, author = {Doe, John}, title = "A {Title}", year = 2024}`
+pub fn parse_bibtex(text: &str) -> HashMap<String, BibEntry> {
+    let mut entries = HashMap::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '@' {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let kind: String = take_while(&chars, &mut i, |c| c != '{' && c != '(');
+        let kind = kind.trim().to_lowercase();
+        i += 1; // skip `{`
+        if matches!(kind.as_str(), "comment" | "preamble" | "string") {
+            skip_value(&chars, &mut i, '}');
+            continue;
+        }
+        let key: String = take_while(&chars, &mut i, |c| c != ',' && c != '}');
+        let mut fields = HashMap::new();
+
+        while i < chars.len() && chars[i] != '}' {
+            i += 1; // skip `,`
+            let name: String = take_while(&chars, &mut i, |c| c != '=' && c != '}');
+            if i >= chars.len() || chars[i] == '}' {
+                break;
+            }
+            i += 1; // skip `=`
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            let value = match chars.get(i) {
+                Some('{') => {
+                    i += 1;
+                    skip_value(&chars, &mut i, '}')
+                }
+                Some('"') => {
+                    i += 1;
+                    skip_value(&chars, &mut i, '"')
+                }
+                _ => take_while(&chars, &mut i, |c| c != ',' && c != '}'),
+            };
+            while i < chars.len() && chars[i] != ',' && chars[i] != '}' {
+                i += 1;
+            }
+            let value = value.replace(['{', '}'], "");
+            let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            fields.insert(name.trim().to_lowercase(), value);
+        }
+        i += 1; // skip closing `}`
+
+        let key = key.trim().to_string();
+        if !key.is_empty() {
+            entries.insert(key.clone(), BibEntry { key, kind, fields });
+        }
+    }
+    entries
+}
+
+fn take_while(chars: &[char], i: &mut usize, predicate: impl Fn(char) -> bool) -> String {
+    let start = *i;
+    while *i < chars.len() && predicate(chars[*i]) {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+/// Read until the closing delimiter, handling nested braces, and skip it
+fn skip_value(chars: &[char], i: &mut usize, closing: char) -> String {
+    let start = *i;
+    let mut depth = 0;
+    while *i < chars.len() {
+        let c = chars[*i];
+        if c == closing && depth == 0 {
+            break;
+        }
+        if c == '{' {
+            depth += 1;
+        } else if c == '}' {
+            depth -= 1;
+        }
+        *i += 1;
+    }
+    let value = chars[start..(*i).min(chars.len())].iter().collect();
+    *i += 1;
+    value
+}
+
+/// Replace `[@key]` and `[@key1; @key2]` citations with numbered links and
+/// append a bibliography section listing the cited entries in citation order.
+/// Citations inside `<code>` and `<pre>` blocks are left untouched.
+/// Returns None when the html has no known citation.
+pub fn apply_citations(html: &str, bibliography: &HashMap<String, BibEntry>) -> Option<String> {
+    if bibliography.is_empty() || !html.contains("[@") {
+        return None;
+    }
+    let citation_re = Regex::new(re::CAPTURE_CITATION_KEYS).expect("Citation regex should compile");
+    let code_re = Regex::new(re::MATCH_CODE_BLOCKS).expect("Code block regex should compile");
+
+    let mut cited: Vec<&BibEntry> = Vec::new();
+    let mut replace_citations = |text: &str| -> String {
+        citation_re
+            .replace_all(text, |caps: &regex::Captures| {
+                let mut links = Vec::new();
+                for key in caps[1].split(';').map(|k| k.trim().trim_start_matches('@')) {
+                    let Some(entry) = bibliography.get(key) else {
+                        warn!("Citation key `{key}` not found in bibliography");
+                        return caps[0].to_string();
+                    };
+                    let number = match cited.iter().position(|e| e.key == entry.key) {
+                        Some(pos) => pos + 1,
+                        None => {
+                            cited.push(entry);
+                            cited.len()
+                        }
+                    };
+                    links.push(format!(r##"<a href="#ref-{key}">{number}</a>"##));
+                }
+                format!(r#"<sup class="citation">[{}]</sup>"#, links.join(", "))
+            })
+            .to_string()
+    };
+
+    let mut result = String::new();
+    let mut last = 0;
+    for code in code_re.find_iter(html) {
+        result.push_str(&replace_citations(&html[last..code.start()]));
+        result.push_str(code.as_str());
+        last = code.end();
+    }
+    result.push_str(&replace_citations(&html[last..]));
+
+    if cited.is_empty() {
+        return None;
+    }
+
+    result.push_str("\n<section class=\"bibliography\">\n<h2>References</h2>\n<ol>\n");
+    for entry in &cited {
+        let _ = writeln!(
+            result,
+            r#"<li id="ref-{}">{}</li>"#,
+            entry.key,
+            format_entry(entry)
+        );
+    }
+    result.push_str("</ol>\n</section>");
+    Some(result)
+}
+
+/// Format an entry as `Author (Year). Title. Container. Link`
+pub fn format_entry(entry: &BibEntry) -> String {
+    let field = |name: &str| entry.fields.get(name).filter(|v| !v.is_empty());
+    let mut parts = Vec::new();
+
+    let mut first = field("author")
+        .map(|author| author.replace(" and ", ", "))
+        .unwrap_or_default();
+    if let Some(year) = field("year") {
+        first = format!("{first} ({year})").trim().to_string();
+    }
+    if !first.is_empty() {
+        parts.push(first);
+    }
+    if let Some(title) = field("title") {
+        parts.push(format!("<em>{title}</em>"));
+    }
+    if let Some(container) = field("journal")
+        .or_else(|| field("booktitle"))
+        .or_else(|| field("publisher"))
+    {
+        parts.push(container.clone());
+    }
+
+    let mut formatted = parts.join(". ");
+    if !formatted.is_empty() {
+        formatted.push('.');
+    }
+    let link = field("url")
+        .cloned()
+        .or_else(|| field("doi").map(|doi| format!("https://doi.org/{doi}")));
+    if let Some(link) = link {
+        let _ = write!(formatted, r#" <a href="{link}">{link}</a>"#);
+    }
+    formatted
+}
+
+#[cfg(test)]
+#[path = "tests/citations.rs"]
+mod tests;
@@ -19,6 +19,11 @@ pub struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
+    /// Format of the error reported on failure, `json` prints a single line
+    /// `{"error": "<kind>", "code": <exit code>, "message": "..."}` to stderr
+    #[arg(long, value_name = "FORMAT", default_value = "text", value_parser = ["text", "json"])]
+    pub error_format: String,
+
     /// Detect changes and rebuild the site automatically
     #[arg(long, short)]
     pub watch: bool,
@@ -27,6 +32,16 @@ pub struct Cli {
     #[arg(long)]
     pub serve: bool,
 
+    /// Command used by the preview to open sources in the editor
+    /// `{file}` and `{line}` are replaced e.g: "code -g {file}:{line}"
+    #[arg(long, requires = "serve")]
+    pub editor: Option<String>,
+
+    /// Build only the pages of the content changed on a git range e.g: `origin/main...HEAD`
+    /// and list their URLs in `preview.json`, for reviewing pull requests
+    #[arg(long, value_name = "GIT_RANGE", conflicts_with_all = ["watch", "serve"])]
+    pub only_changed: Option<String>,
+
     /// Address to bind the server
     #[arg(long, default_value = "0.0.0.0:8000", requires = "serve")]
     pub bind: String,
@@ -48,6 +63,10 @@ pub struct Cli {
     #[arg(long)]
     pub start_theme: Option<String>,
 
+    /// Create a minimal theme wired to all template context, as a starting point for theme authors
+    #[arg(long)]
+    pub new_theme: Option<String>,
+
     /// Download and set a theme from a remote URL or local folder
     #[arg(long)]
     pub set_theme: Option<String>,
@@ -63,10 +82,20 @@ pub struct Cli {
     #[arg(long)]
     pub init_site: bool,
 
-    /// Force the rebuild of the site even if no changes detected
+    /// Force the rebuild of the site even if no changes detected,
+    /// with `--install-hooks` replace the existing git hooks
     #[arg(long)]
     pub force: bool,
 
+    /// Install git pre-commit and pre-push hooks checking lint rules, duplicate
+    /// slugs and broken links of the changed content
+    #[arg(long)]
+    pub install_hooks: bool,
+
+    /// Check the content changed for a git hook, run by the installed hooks
+    #[arg(long, value_name = "HOOK", value_parser = ["pre-commit", "pre-push"], hide = true)]
+    pub hook_check: Option<String>,
+
     /// List all available shortcodes
     #[arg(long)]
     pub shortcodes: bool,
@@ -75,6 +104,47 @@ pub struct Cli {
     #[arg(long)]
     pub show_urls: bool,
 
+    /// Check the spelling of titles, descriptions and content
+    /// using the bundled dictionaries, the system dictionary for the site language
+    /// and the project word lists in `input_folder/dictionaries`
+    #[arg(long)]
+    pub spellcheck: bool,
+
+    /// List posts not updated in `freshness.months` (default 12)
+    /// the most linked posts first
+    #[arg(long)]
+    pub freshness: bool,
+
+    /// List the content only reachable by its url: not listed on a tag,
+    /// stream or other group page, not in the menu and not linked
+    #[arg(long)]
+    pub orphans: bool,
+
+    /// Show the links in and out of each content and its centrality
+    /// on the internal link graph, the most central first
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Check titles, headings and descriptions against the `lint` rules
+    /// from the configuration file
+    #[arg(long, group = "fixable")]
+    pub lint: bool,
+
+    /// List the images without alt text, suggesting one when `alt_text`
+    /// captioning is configured
+    #[arg(long, group = "fixable")]
+    pub alt_text: bool,
+
+    /// Rewrite the title and heading casing reported by `--lint` or write
+    /// the alt text suggested by `--alt-text`
+    #[arg(long, requires = "fixable")]
+    pub fix: bool,
+
+    /// Rename the `frontmatter_aliases` keys to their canonical names and write
+    /// the `uuid` when `feed_guid: uuid` is set, in the content markdown files
+    #[arg(long)]
+    pub normalize_frontmatter: bool,
+
     /// Create a new markdown file in the input folder
     #[command(flatten)]
     pub create: Create,
@@ -215,4 +285,32 @@ pub struct Configuration {
     /// Custom shortcode pattern (regex) [default: <!-- \.(\w+)(?:\s+([^-][\s\S]*?))?\s*--> or from config file]
     #[arg(long)]
     pub shortcode_pattern: Option<String>,
+
+    /// Include content marked with `draft: true` [default: false or from config file]
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub drafts: Option<bool>,
+
+    /// Include posts dated in the future [default: false or from config file]
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub future: Option<bool>,
+
+    /// Build profile kept by the `<!-- if profile == "name" -->` blocks of the content
+    /// [default: production or from config file]
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Threads used to render the pages and process the images, 0 uses all the cores
+    /// [default: 0 or from config file]
+    #[arg(long, short = 'j', value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Process the images one at a time with bounded memory, for small machines
+    /// [default: false or from config file]
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub low_memory: Option<bool>,
+
+    /// `age` identity file decrypting the `.age` content of the private folder
+    /// [default: `MARMITE_PRIVATE_KEY` env var, private content is skipped when not set]
+    #[arg(long, value_name = "IDENTITY_FILE")]
+    pub private_key: Option<PathBuf>,
 }
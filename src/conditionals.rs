@@ -0,0 +1,95 @@
+use crate::re;
+use log::warn;
+use regex::Regex;
+
+/// A `<!-- if profile ... -->` block being read
+struct Block {
+    /// The lines of the current branch are kept
+    keep: bool,
+    /// The enclosing block keeps its lines
+    parent_keep: bool,
+    in_else: bool,
+}
+
+/// Keep the lines of the markdown blocks matching the build `profile`:
+///
+/// ```markdown
+/// <!-- if profile == "preview" -->
+/// TODO: check the numbers before publishing
+/// <!-- else -->
+/// Numbers checked on 2025-01-10
+/// <!-- endif -->
+/// ```
+///
+/// `!=` is also accepted and blocks can be nested, the markers must be on
+/// their own line and are ignored inside fenced code blocks
+pub fn apply_conditionals(markdown: &str, profile: &str) -> String {
+    if !markdown.contains("<!--") {
+        return markdown.to_string();
+    }
+    let if_re = Regex::new(re::CAPTURE_PROFILE_CONDITION).expect("Condition regex should compile");
+    let else_re = Regex::new(re::MATCH_ELSE_MARKER).expect("Else regex should compile");
+    let endif_re = Regex::new(re::MATCH_ENDIF_MARKER).expect("Endif regex should compile");
+
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut fence: Option<String> = None;
+    let mut output = String::with_capacity(markdown.len());
+    for line in markdown.split_inclusive('\n') {
+        let keep = blocks.last().is_none_or(|block| block.keep);
+        let trimmed = line.trim();
+        if let Some(marker) = &fence {
+            if trimmed.starts_with(marker.as_str())
+                && trimmed
+                    .trim_start_matches(marker.chars().next().unwrap_or('`'))
+                    .is_empty()
+            {
+                fence = None;
+            }
+        } else if let Some(marker) = fence_marker(trimmed) {
+            fence = Some(marker);
+        } else if let Some(caps) = if_re.captures(trimmed) {
+            let matches = (&caps[2] == profile) == (&caps[1] == "==");
+            blocks.push(Block {
+                keep: keep && matches,
+                parent_keep: keep,
+                in_else: false,
+            });
+            continue;
+        } else if else_re.is_match(trimmed) {
+            match blocks.last_mut() {
+                Some(block) if !block.in_else => {
+                    block.keep = block.parent_keep && !block.keep;
+                    block.in_else = true;
+                }
+                _ => warn!("`<!-- else -->` without a matching `<!-- if profile -->`"),
+            }
+            continue;
+        } else if endif_re.is_match(trimmed) {
+            if blocks.pop().is_none() {
+                warn!("`<!-- endif -->` without a matching `<!-- if profile -->`");
+            }
+            continue;
+        }
+        if keep {
+            output.push_str(line);
+        }
+    }
+    if !blocks.is_empty() {
+        warn!(
+            "{} `<!-- if profile -->` blocks not closed with `<!-- endif -->`",
+            blocks.len()
+        );
+    }
+    output
+}
+
+/// The backticks or tildes opening a fenced code block
+pub fn fence_marker(line: &str) -> Option<String> {
+    let marker_char = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let marker: String = line.chars().take_while(|c| *c == marker_char).collect();
+    (marker.len() >= 3).then_some(marker)
+}
+
+#[cfg(test)]
+#[path = "tests/conditionals.rs"]
+mod tests;
@@ -1,9 +1,14 @@
-use log::{error, info};
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
-use std::{collections::HashMap, path::Path, process, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Arc,
+};
 
 use crate::cli::Cli;
+use crate::errors::{self, ErrorKind};
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub enum ImageProvider {
@@ -20,12 +25,23 @@ pub struct RenderOptions {
     pub ignore_empty_links: bool,
     #[serde(default = "default_render_figure_with_caption")]
     pub figure_with_caption: bool,
+    /// `contains-task-list`, `task-list-item` and `task-list-item-checkbox`
+    /// classes on the task lists
+    #[serde(default)]
+    pub tasklist_classes: bool,
+    /// Task checkboxes readers can tick, kept on the browser by `marmite.js`
+    #[serde(default)]
+    pub tasklist_interactive: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct ParseOptions {
     #[serde(default = "default_parse_relaxed_tasklist_matching")]
     pub relaxed_tasklist_matching: bool,
+    /// Curly quotes, `--` en dashes, `---` em dashes and `...` ellipsis,
+    /// the code spans and blocks are kept as written
+    #[serde(default)]
+    pub smart: bool,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -71,6 +87,42 @@ pub struct ParserOptions {
     pub parse: ParseOptions,
     #[serde(default)]
     pub extension: ExtensionOptions,
+    #[serde(default)]
+    pub math: MathOptions,
+}
+
+/// How `$...$` and `$$...$$` math is rendered
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MathMode {
+    /// Dollar signs are regular text
+    #[default]
+    None,
+    /// Kept as `\(...\)` and `\[...\]` for KaTeX or MathJax on the browser
+    Client,
+    /// Rendered at build time by `inline_command` and `display_command`
+    Server,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MathOptions {
+    #[serde(default)]
+    pub mode: MathMode,
+    /// Receives the TeX on stdin and writes the HTML or MathML to stdout
+    #[serde(default = "default_math_inline_command")]
+    pub inline_command: String,
+    #[serde(default = "default_math_display_command")]
+    pub display_command: String,
+}
+
+impl Default for MathOptions {
+    fn default() -> Self {
+        Self {
+            mode: MathMode::None,
+            inline_command: default_math_inline_command(),
+            display_command: default_math_display_command(),
+        }
+    }
 }
 
 impl Default for RenderOptions {
@@ -79,6 +131,8 @@ impl Default for RenderOptions {
             unsafe_: default_render_unsafe(),
             ignore_empty_links: default_render_ignore_empty_links(),
             figure_with_caption: default_render_figure_with_caption(),
+            tasklist_classes: false,
+            tasklist_interactive: false,
         }
     }
 }
@@ -87,6 +141,7 @@ impl Default for ParseOptions {
     fn default() -> Self {
         Self {
             relaxed_tasklist_matching: default_parse_relaxed_tasklist_matching(),
+            smart: false,
         }
     }
 }
@@ -170,12 +225,38 @@ pub struct Marmite {
     #[serde(default = "default_authors_title")]
     pub authors_title: String,
 
+    #[serde(default = "default_backlinks_title")]
+    pub backlinks_title: String,
+
+    #[serde(default = "default_bookmarks_title")]
+    pub bookmarks_title: String,
+
+    /// Fetch the title, description and favicon of the `bookmark` urls missing
+    /// from `.marmite/bookmarks.json`, the cache is used offline
+    #[serde(default)]
+    pub fetch_bookmarks: bool,
+
     #[serde(default)]
     pub enable_search: bool,
 
+    /// Render a `section-{folder}.html` page for every folder of the content folder
+    /// and expose `content.section` and `content.breadcrumbs`
+    #[serde(default)]
+    pub enable_sections: bool,
+
     #[serde(default = "default_enable_related_content")]
     pub enable_related_content: bool,
 
+    /// Append a `backlinks_title` section listing the content linking to it to the
+    /// html of each content, so themes not rendering `content.back_links` show them
+    #[serde(default)]
+    pub backlinks_section: bool,
+
+    /// Add `data-preview-*` attributes to the internal links and write
+    /// `link-previews.json` so themes can show hover cards
+    #[serde(default)]
+    pub link_previews: bool,
+
     #[serde(default = "default_search_title")]
     pub search_title: String,
 
@@ -206,6 +287,11 @@ pub struct Marmite {
     #[serde(default = "default_date_format")]
     pub default_date_format: String,
 
+    /// Date format of the content written in other languages (`lang` frontmatter)
+    /// keyed by language, the site `language` uses `default_date_format`
+    #[serde(default = "default_date_formats")]
+    pub date_formats: HashMap<String, String>,
+
     #[serde(default = "default_menu")]
     pub menu: Option<Vec<(String, String)>>,
 
@@ -215,15 +301,36 @@ pub struct Marmite {
     #[serde(default)]
     pub authors: HashMap<String, Author>,
 
+    /// Author profiles keyed by username, read from the content folder or the
+    /// folder of the configuration file, `authors` entries take precedence
+    #[serde(default = "default_authors_file")]
+    pub authors_file: String,
+
     #[serde(default)]
     pub streams: HashMap<String, StreamConfig>,
 
     #[serde(default)]
     pub series: HashMap<String, SeriesConfig>,
 
+    #[serde(default)]
+    pub tags: HashMap<String, TagConfig>,
+
+    /// Combinations of tags rendered on their own page e.g: `rust+async`
+    /// on `tag-rust+async.html` listing the posts tagged with both
+    #[serde(default)]
+    pub tag_intersections: Vec<String>,
+
+    #[serde(default)]
+    pub taxonomies: HashMap<String, TaxonomyConfig>,
+
     #[serde(default)]
     pub toc: bool,
 
+    /// Paragraphs taken as the summary of the content without a `<!-- more -->` marker,
+    /// `0` leaves those without summary so listings show the description
+    #[serde(default)]
+    pub summary_paragraphs: usize,
+
     #[serde(default)]
     pub json_feed: bool,
 
@@ -251,6 +358,11 @@ pub struct Marmite {
     #[serde(default = "default_true")]
     pub enable_shortcodes: bool,
 
+    /// Replace `{{ site.* }}`, `{{ build.* }}` and `{{ content.* }}` on the content body,
+    /// opt-in as existing content can have these as literal text
+    #[serde(default)]
+    pub enable_tokens: bool,
+
     #[serde(default)]
     pub shortcode_pattern: Option<String>,
 
@@ -268,6 +380,159 @@ pub struct Marmite {
 
     #[serde(default = "default_gallery_thumb_size")]
     pub gallery_thumb_size: u32,
+
+    #[serde(default)]
+    pub contact_form: Option<ContactFormConfig>,
+
+    #[serde(default)]
+    pub activity_feed: bool,
+
+    #[serde(default = "default_webmentions_file")]
+    pub webmentions_file: String,
+
+    #[serde(default)]
+    pub slug: SlugConfig,
+
+    #[serde(default)]
+    pub frontmatter_aliases: HashMap<String, String>,
+
+    #[serde(default)]
+    pub frontmatter_schema: FrontmatterSchema,
+
+    #[serde(default)]
+    pub fence_renderers: HashMap<String, String>,
+
+    /// Commands converting other content formats to markdown, keyed by the
+    /// file extension, none by default as they run external programs
+    #[serde(default)]
+    pub input_converters: HashMap<String, String>,
+
+    #[serde(default)]
+    pub external_commands: ExternalCommandsConfig,
+
+    #[serde(default)]
+    pub content_extensions: ContentExtensionsConfig,
+
+    #[serde(default = "default_bibliography")]
+    pub bibliography: String,
+
+    #[serde(default)]
+    pub glossary: GlossaryConfig,
+
+    #[serde(default)]
+    pub now: NowConfig,
+
+    #[serde(default)]
+    pub uses: UsesConfig,
+
+    #[serde(default)]
+    pub cv: CvConfig,
+
+    #[serde(default)]
+    pub projects: ProjectsConfig,
+
+    #[serde(default)]
+    pub badges: BadgesConfig,
+
+    #[serde(default)]
+    pub syntax_highlight: SyntaxHighlightConfig,
+
+    #[serde(default)]
+    pub heading_anchors: HeadingAnchorsConfig,
+
+    #[serde(default)]
+    pub external_links: ExternalLinksConfig,
+
+    /// Ordered steps rewriting the rendered html of the content
+    #[serde(default)]
+    pub html_pipeline: Vec<PipelineStep>,
+
+    #[serde(default)]
+    pub reading_time: ReadingTimeConfig,
+
+    #[serde(default)]
+    pub lint: LintConfig,
+
+    #[serde(default)]
+    pub alt_text: AltTextConfig,
+
+    #[serde(default)]
+    pub verification: VerificationConfig,
+
+    #[serde(default)]
+    pub origins: OriginsConfig,
+
+    /// Generate `humans.txt` from the site and `authors`
+    #[serde(default)]
+    pub humans_txt: bool,
+
+    #[serde(default)]
+    pub security_txt: Option<SecurityTxtConfig>,
+
+    #[serde(default)]
+    pub integrity: IntegrityConfig,
+
+    #[serde(default)]
+    pub fetch_json: FetchJsonConfig,
+
+    #[serde(default)]
+    pub freshness: FreshnessConfig,
+
+    #[serde(default)]
+    pub year_review: YearReviewConfig,
+
+    #[serde(default)]
+    pub photos: PhotosConfig,
+
+    /// Render the hidden `orphans/index.html` page listing the content
+    /// reported by `--orphans`
+    #[serde(default)]
+    pub orphans_page: bool,
+
+    #[serde(default)]
+    pub build_metadata: bool,
+
+    #[serde(default = "default_editor_url")]
+    pub editor_url: String,
+
+    #[serde(default)]
+    pub drafts: bool,
+
+    #[serde(default)]
+    pub future: bool,
+
+    /// Threads rendering the pages and processing the images, 0 uses all the
+    /// available cores, e.g: `jobs: 2` on a shared CI runner
+    #[serde(default)]
+    pub jobs: usize,
+
+    /// Decode the gallery images one at a time with bounded allocations, read
+    /// only the head of the photos and use 2 threads unless `jobs` is set, for
+    /// builds on machines with little memory
+    #[serde(default)]
+    pub low_memory: bool,
+
+    /// Folder inside the content folder holding the `.age` encrypted content
+    #[serde(default = "default_private_folder")]
+    pub private_folder: String,
+
+    /// Build profile checked by the `<!-- if profile == "name" -->` blocks of the content
+    #[serde(default = "default_profile")]
+    pub profile: String,
+
+    /// `age` identity file decrypting the `private_folder`, only from `--private-key`
+    /// or the `MARMITE_PRIVATE_KEY` env var, never from the config file
+    #[serde(skip)]
+    pub private_key: Option<std::path::PathBuf>,
+
+    #[serde(default)]
+    pub updated_from_mtime: bool,
+
+    #[serde(default)]
+    pub render_expired: bool,
+
+    #[serde(default)]
+    pub feed_guid: FeedGuid,
 }
 
 fn default_true() -> bool {
@@ -286,6 +551,8 @@ impl Marmite {
             archives_title: default_archives_title(),
             archives_content_title: default_archives_content_title(),
             authors_title: default_authors_title(),
+            backlinks_title: default_backlinks_title(),
+            bookmarks_title: default_bookmarks_title(),
             streams_title: default_streams_title(),
             streams_content_title: default_streams_content_title(),
             series_title: default_series_title(),
@@ -295,25 +562,60 @@ impl Marmite {
             static_path: default_static_path(),
             media_path: default_media_path(),
             default_date_format: default_date_format(),
+            date_formats: default_date_formats(),
             menu: default_menu(),
             show_next_prev_links: default_true(),
             enable_shortcodes: default_true(),
+            profile: default_profile(),
             build_sitemap: default_true(),
             publish_urls_json: default_true(),
             gallery_path: default_gallery_path(),
             gallery_create_thumbnails: default_true(),
             gallery_thumb_size: default_gallery_thumb_size(),
+            webmentions_file: default_webmentions_file(),
+            bibliography: default_bibliography(),
+            editor_url: default_editor_url(),
+            private_folder: default_private_folder(),
+            authors_file: default_authors_file(),
             ..Default::default()
         }
     }
 
+    /// Profile of `username` from `authors`, a placeholder named after the
+    /// username when the author is not configured
+    pub fn author_profile(&self, username: &str) -> Author {
+        self.authors
+            .get(username)
+            .cloned()
+            .unwrap_or_else(|| Author {
+                username: username.to_string(),
+                name: username.to_string(),
+                avatar: Some("static/avatar-placeholder.png".to_string()),
+                ..Default::default()
+            })
+    }
+
+    /// Names of the custom taxonomies, sorted, skipping the ones that clash with the builtin groups
+    pub fn taxonomy_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .taxonomies
+            .keys()
+            .filter(|name| !BUILTIN_TAXONOMIES.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Get the resolved templates path based on theme configuration
     pub fn get_templates_path(&self, input_folder: &Path) -> std::path::PathBuf {
         if let Some(theme) = &self.theme {
             let theme_path = input_folder.join(theme);
             if !theme_path.exists() {
-                error!("Theme folder '{}' does not exist", theme_path.display());
-                process::exit(1);
+                errors::exit(
+                    ErrorKind::Config,
+                    &format!("Theme folder '{}' does not exist", theme_path.display()),
+                );
             }
             theme_path.join(&self.templates_path)
         } else {
@@ -326,8 +628,10 @@ impl Marmite {
         if let Some(theme) = &self.theme {
             let theme_path = input_folder.join(theme);
             if !theme_path.exists() {
-                error!("Theme folder '{}' does not exist", theme_path.display());
-                process::exit(1);
+                errors::exit(
+                    ErrorKind::Config,
+                    &format!("Theme folder '{}' does not exist", theme_path.display()),
+                );
             }
             theme_path.join(&self.static_path)
         } else {
@@ -427,113 +731,932 @@ impl Marmite {
         if let Some(shortcode_pattern) = &cli_args.configuration.shortcode_pattern {
             self.shortcode_pattern = Some(shortcode_pattern.clone());
         }
+        if let Some(drafts) = cli_args.configuration.drafts {
+            self.drafts = drafts;
+        }
+        if let Some(future) = cli_args.configuration.future {
+            self.future = future;
+        }
+        if let Some(profile) = &cli_args.configuration.profile {
+            self.profile.clone_from(profile);
+        }
+        if let Some(jobs) = cli_args.configuration.jobs {
+            self.jobs = jobs;
+        }
+        if let Some(low_memory) = cli_args.configuration.low_memory {
+            self.low_memory = low_memory;
+        }
+        if let Some(private_key) = cli_args
+            .configuration
+            .private_key
+            .clone()
+            .or_else(|| std::env::var_os("MARMITE_PRIVATE_KEY").map(Into::into))
+        {
+            self.private_key = Some(private_key);
+        }
+        crate::converters::configure_commands(&self.external_commands, &cli_args.input_folder);
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// Threads of the build when `low_memory` is set and `jobs` is not, every
+/// thread can hold a decoded image and a rendered page at the same time
+const LOW_MEMORY_JOBS: usize = 2;
+
+/// Threads of the pool for `jobs`, 0 uses all the cores unless `low_memory`
+/// caps them to `LOW_MEMORY_JOBS`, an explicit `jobs` is always kept
+fn thread_count(jobs: usize, low_memory: bool) -> usize {
+    if jobs == 0 && low_memory {
+        LOW_MEMORY_JOBS
+    } else {
+        jobs
+    }
+}
+
+/// Threads of the pool from the `jobs` and `low_memory` of the config file,
+/// overridden by the CLI, a config that can't be read uses the defaults
+fn pool_threads(config: &str, jobs: Option<usize>, low_memory: Option<bool>) -> usize {
+    #[derive(Deserialize, Default)]
+    struct Threads {
+        #[serde(default)]
+        jobs: usize,
+        #[serde(default)]
+        low_memory: bool,
+    }
+    let threads: Threads = serde_yaml::from_str(config).unwrap_or_default();
+    thread_count(
+        jobs.unwrap_or(threads.jobs),
+        low_memory.unwrap_or(threads.low_memory),
+    )
+}
+
+/// Size the thread pool shared by all the parallel steps of the build, rayon
+/// builds the global pool once so this runs before the first build and a `jobs`
+/// change in watch mode is only applied on the next run
+pub fn configure_thread_pool(config_path: &Path, cli_args: &Cli) {
+    let config = std::fs::read_to_string(config_path).unwrap_or_default();
+    let jobs = pool_threads(
+        &config,
+        cli_args.configuration.jobs,
+        cli_args.configuration.low_memory,
+    );
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build_global()
+    {
+        Ok(()) => debug!("Building with {} threads", rayon::current_num_threads()),
+        Err(e) => warn!("Failed to limit the build to {jobs} threads: {e}"),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct Author {
+    /// Key of the author in `authors`, filled when the site is built
+    #[serde(default)]
+    pub username: String,
+    /// Display name, defaults to the username
+    #[serde(default)]
     pub name: String,
     pub avatar: Option<String>,
     pub bio: Option<String>,
     pub links: Option<Vec<(String, String)>>,
+    /// Personal website of the author
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct StreamConfig {
     pub display_name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub card_image: Option<String>,
+    #[serde(default)]
+    pub banner_image: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub struct SeriesConfig {
-    pub display_name: String,
+/// Per tag defaults used when content tagged with it does not define its own,
+/// also read from the `tags/{tag}.md` landing files of the content folder
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct TagConfig {
+    #[serde(default)]
     pub description: Option<String>,
+    #[serde(default)]
+    pub card_image: Option<String>,
+    #[serde(default)]
+    pub banner_image: Option<String>,
+    /// Markdown shown on top of the tag page, the body of `tags/{tag}.md`
+    #[serde(default)]
+    pub intro: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
-pub struct FileMapping {
-    pub source: String,
-    pub dest: String,
+/// What identifies a content on RSS and JSON feeds
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedGuid {
+    /// The content url, changes when the slug changes
+    #[default]
+    Url,
+    /// A UUID generated on the first build and kept in `.marmite/content-ids.json`
+    Uuid,
 }
 
-/// Generates a default configuration file
-/// this function writes to `marmite.yaml` in the input folder
-/// the YAML file will contain the default configuration
-/// default configuration is taken from serde default values
-pub fn generate(input_folder: &Path, cli_args: &Arc<Cli>) {
-    let config_path = input_folder.join(cli_args.config.as_str());
-    // If the file already exists, do not overwrite
-    if config_path.exists() {
-        error!("Config file already exists: {}", config_path.display());
-        return;
-    }
-    let mut config = Marmite::new();
-    config.override_from_cli_args(cli_args);
-    let config_str = match serde_yaml::to_string(&config) {
-        Ok(s) => s,
-        Err(e) => {
-            error!("Failed to serialize config: {e}");
-            return;
-        }
-    };
-    if let Err(e) = std::fs::write(&config_path, config_str) {
-        error!("Failed to write config file: {e}");
-        return;
-    }
-    info!("Config file generated: {}", config_path.display());
+/// Names used by the builtin groups and its pages, can't be used as custom taxonomies
+pub const BUILTIN_TAXONOMIES: [&str; 11] = [
+    "tag", "tags", "archive", "archives", "author", "authors", "stream", "streams", "series",
+    "pages", "index",
+];
+
+/// A custom taxonomy collected from the frontmatter key with the same name,
+/// e.g: `categories: [rust, web]` renders `categories.html` and `categories-rust.html`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct TaxonomyConfig {
+    /// Title of the `{taxonomy}.html` group page, defaults to the taxonomy name
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Title of the `{taxonomy}-{term}.html` list pages, `$term` is replaced by the term
+    #[serde(default)]
+    pub content_title: Option<String>,
 }
 
-// Defaults
+impl TaxonomyConfig {
+    pub fn title(&self, name: &str) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            let mut chars = name.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+    }
 
-fn default_name() -> String {
-    "Home".to_string()
+    pub fn content_title(&self, term: &str) -> String {
+        self.content_title
+            .as_deref()
+            .unwrap_or("Posts in '$term'")
+            .replace("$term", term)
+    }
 }
 
-fn default_footer() -> String {
-    r#"<div>Powered by <a href="https://github.com/rochacbruno/marmite">Marmite</a> | <small><a href="https://creativecommons.org/licenses/by-nc-sa/4.0/">CC-BY_NC-SA</a></small></div>"#.to_string()
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SeriesConfig {
+    pub display_name: String,
+    pub description: Option<String>,
 }
 
-fn default_pagination() -> usize {
-    10usize
+/// Rules applied when generating content slugs
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct SlugConfig {
+    /// Slugs generated from the title are truncated on a word boundary
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Slugs that cannot be used by content, e.g: `tags`, `archive`, `media`
+    #[serde(default)]
+    pub reserved: Vec<String>,
+    /// Remove stop words from slugs generated from the title
+    #[serde(default)]
+    pub remove_stop_words: bool,
+    /// Custom stop words list, when empty a default english list is used
+    #[serde(default)]
+    pub stop_words: Vec<String>,
+    /// Lowercase content slugs and media file names
+    #[serde(default)]
+    pub lowercase: bool,
+    /// Replace runs of dashes with a single dash in slugs and media file names
+    #[serde(default)]
+    pub collapse_dashes: bool,
+    /// Normalize unicode (NFC) in slugs and media file names, so the same
+    /// text typed with composed or decomposed characters gives the same URL
+    #[serde(default)]
+    pub normalize_unicode: bool,
+    /// What to do when contents share a slug
+    #[serde(default)]
+    pub duplicates: DuplicateSlugs,
 }
 
-fn default_authors_title() -> String {
-    "Authors".to_string()
+/// How the contents sharing a slug are handled, the first one of each group
+/// (pages first, then the oldest post, then by source path) keeps its slug
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateSlugs {
+    /// Report the duplicates, the latest content rendered overwrites the others
+    #[default]
+    Error,
+    /// Append `-1`, `-2` ... to the slugs of the other contents
+    Suffix,
+    /// Prefix the slugs of the other contents with their date e.g: `2024-01-01-slug`,
+    /// content without date or still duplicated gets the suffix
+    Date,
 }
 
-fn default_tags_title() -> String {
-    "Tags".to_string()
+impl SlugConfig {
+    /// Whether any of the URL normalization rules is enabled
+    pub fn normalizes_urls(&self) -> bool {
+        self.lowercase || self.collapse_dashes || self.normalize_unicode
+    }
 }
 
-fn default_tags_content_title() -> String {
-    "Posts tagged with '$tag'".to_string()
+/// Posts not updated in `months` are listed by `--freshness`
+/// and on the `meta/freshness/` page when `page` is enabled
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FreshnessConfig {
+    #[serde(default = "default_freshness_months")]
+    pub months: usize,
+    #[serde(default)]
+    pub page: bool,
 }
 
-fn default_streams_content_title() -> String {
-    "Posts from '$stream'".to_string()
+impl Default for FreshnessConfig {
+    fn default() -> Self {
+        FreshnessConfig {
+            months: default_freshness_months(),
+            page: false,
+        }
+    }
 }
 
-fn default_series_title() -> String {
-    "Series".to_string()
+/// Extensions of the content sources besides `.md`, `.html`, `.ipynb`
+/// and the `input_converters`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ContentExtensionsConfig {
+    /// Rendered as markdown, the `import` and `export` lines of `.mdx` are dropped
+    #[serde(default = "default_markdown_extensions")]
+    pub markdown: Vec<String>,
+    /// Rendered as preformatted text, the first line is the title
+    /// unless the frontmatter has one
+    #[serde(default)]
+    pub text: Vec<String>,
 }
 
-fn default_series_content_title() -> String {
-    "Posts from '$series' series".to_string()
+impl Default for ContentExtensionsConfig {
+    fn default() -> Self {
+        ContentExtensionsConfig {
+            markdown: default_markdown_extensions(),
+            text: Vec::new(),
+        }
+    }
 }
 
-fn default_pages_title() -> String {
-    "Pages".to_string()
+/// Stats of each past year (post count, top tags, most linked posts, words)
+/// are rendered on `review-{year}.html` when `enabled`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct YearReviewConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also review the current year, which is skipped until it is over
+    #[serde(default)]
+    pub include_current: bool,
+    /// Number of tags and linked posts listed
+    #[serde(default = "default_year_review_top")]
+    pub top: usize,
 }
 
-fn default_archives_title() -> String {
-    "Archive".to_string()
+impl Default for YearReviewConfig {
+    fn default() -> Self {
+        YearReviewConfig {
+            enabled: false,
+            include_current: false,
+            top: default_year_review_top(),
+        }
+    }
 }
 
-fn default_streams_title() -> String {
-    "Streams".to_string()
+/// Images of `folder` inside the media folder published as posts of `stream`,
+/// dated and captioned from their EXIF, and listed on `{stream}-wall.html`
+/// when `enabled`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PhotosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_photos_folder")]
+    pub folder: String,
+    #[serde(default = "default_photos_stream")]
+    pub stream: String,
+    /// One post per subfolder of `folder` instead of one post per photo
+    #[serde(default)]
+    pub albums: bool,
+    /// Title of the photo wall page
+    #[serde(default = "default_photos_title")]
+    pub title: String,
 }
 
-fn default_archives_content_title() -> String {
-    "Posts from '$year'".to_string()
+impl Default for PhotosConfig {
+    fn default() -> Self {
+        PhotosConfig {
+            enabled: false,
+            folder: default_photos_folder(),
+            stream: default_photos_stream(),
+            albums: false,
+            title: default_photos_title(),
+        }
+    }
+}
+
+/// Capitalization style enforced on titles and headings by `--lint`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TitleCase {
+    /// Only the first word is capitalized: `How to build a site`
+    Sentence,
+    /// Major words are capitalized: `How to Build a Site`
+    Title,
+}
+
+/// Frontmatter rules checked before every build, the build fails listing all
+/// the violations, dates are always checked
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct FrontmatterSchema {
+    /// Keys every post and page must set e.g: `[title, description]`
+    #[serde(default)]
+    pub required: Vec<String>,
+    /// Values accepted by a key e.g: `stream: [index, news]`, lists like `tags`
+    /// are checked item by item
+    #[serde(default)]
+    pub allowed: HashMap<String, Vec<String>>,
+}
+
+/// Style rules checked by `--lint`, every rule is disabled when not set
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub title_case: Option<TitleCase>,
+    /// Deepest heading level allowed in the content e.g: `3` rejects `####`
+    #[serde(default)]
+    pub max_heading_depth: Option<usize>,
+    #[serde(default)]
+    pub description_min_length: Option<usize>,
+    #[serde(default)]
+    pub description_max_length: Option<usize>,
+    /// Words kept as written when recasing e.g: `Rust`, `Marmite`
+    #[serde(default)]
+    pub proper_nouns: Vec<String>,
+}
+
+/// Captioning used by `--alt-text` to suggest the missing alt text, `command` is run
+/// with the image path (or URL) as last argument and `endpoint` receives the image
+/// bytes as a POST, both must answer with the caption as plain text
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct AltTextConfig {
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+/// Glossary terms are read from `file` (a `term: definition` YAML mapping
+/// relative to the content folder) and listed on the `{slug}.html` page
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GlossaryConfig {
+    #[serde(default = "default_glossary_file")]
+    pub file: String,
+    #[serde(default = "default_glossary_slug")]
+    pub slug: String,
+    #[serde(default = "default_glossary_title")]
+    pub title: String,
+}
+
+impl Default for GlossaryConfig {
+    fn default() -> Self {
+        GlossaryConfig {
+            file: default_glossary_file(),
+            slug: default_glossary_slug(),
+            title: default_glossary_title(),
+        }
+    }
+}
+
+/// The `/now` page and the status updates listed on it and in `status.rss`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct NowConfig {
+    /// `- {date, text}` list of short updates in the content folder
+    #[serde(default = "default_now_file")]
+    pub file: String,
+    /// Slug of the content the statuses are listed on, e.g: `now.md`
+    #[serde(default = "default_now_slug")]
+    pub slug: String,
+    #[serde(default = "default_now_title")]
+    pub title: String,
+}
+
+impl Default for NowConfig {
+    fn default() -> Self {
+        NowConfig {
+            file: default_now_file(),
+            slug: default_now_slug(),
+            title: default_now_title(),
+        }
+    }
+}
+
+/// The `/uses` page generated from the hardware, software and services of a data file
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct UsesConfig {
+    /// `category: [items]` mapping, relative to the input folder
+    #[serde(default = "default_uses_file")]
+    pub file: String,
+    #[serde(default = "default_uses_slug")]
+    pub slug: String,
+    #[serde(default = "default_uses_title")]
+    pub title: String,
+}
+
+impl Default for UsesConfig {
+    fn default() -> Self {
+        UsesConfig {
+            file: default_uses_file(),
+            slug: default_uses_slug(),
+            title: default_uses_title(),
+        }
+    }
+}
+
+/// The `/cv/` page rendered from a JSON Resume data file
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CvConfig {
+    /// JSON Resume (or the same structure in YAML), relative to the input folder
+    #[serde(default = "default_cv_file")]
+    pub file: String,
+    /// Folder of the page, rendered to `{slug}/index.html`
+    #[serde(default = "default_cv_slug")]
+    pub slug: String,
+    #[serde(default = "default_cv_title")]
+    pub title: String,
+    /// Command printing the page to `{slug}/{slug}.pdf`, e.g:
+    /// `weasyprint {input} {output}`, `{input}` is the rendered html
+    #[serde(default)]
+    pub pdf_command: Option<String>,
+}
+
+impl Default for CvConfig {
+    fn default() -> Self {
+        CvConfig {
+            file: default_cv_file(),
+            slug: default_cv_slug(),
+            title: default_cv_title(),
+            pdf_command: None,
+        }
+    }
+}
+
+/// The portfolio page listing the repositories with their forge metadata
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ProjectsConfig {
+    /// `github:owner/name`, `codeberg:owner/name` or the repository urls
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// The portfolio is rendered to `{slug}.html` and each project to `{slug}/{project}.html`
+    #[serde(default = "default_projects_slug")]
+    pub slug: String,
+    #[serde(default = "default_projects_title")]
+    pub title: String,
+    /// Fetch the metadata from the forge APIs, `.marmite/projects.json` is used when disabled
+    #[serde(default = "default_true")]
+    pub fetch: bool,
+    /// Hours the fetched metadata is kept before fetching it again
+    #[serde(default = "default_projects_refresh_hours")]
+    pub refresh_hours: i64,
+}
+
+impl Default for ProjectsConfig {
+    fn default() -> Self {
+        ProjectsConfig {
+            repos: Vec::new(),
+            slug: default_projects_slug(),
+            title: default_projects_title(),
+            fetch: true,
+            refresh_hours: default_projects_refresh_hours(),
+        }
+    }
+}
+
+/// SVG badges with the post count, the last update and the feed,
+/// written to `{path}/posts.svg`, `{path}/updated.svg` and `{path}/rss.svg`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct BadgesConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_badges_path")]
+    pub path: String,
+    /// Background of the badge values, e.g: `#007ec6`
+    #[serde(default = "default_badges_color")]
+    pub color: String,
+}
+
+impl Default for BadgesConfig {
+    fn default() -> Self {
+        BadgesConfig {
+            enabled: false,
+            path: default_badges_path(),
+            color: default_badges_color(),
+        }
+    }
+}
+
+/// Ids and permalinks of the content headings
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct HeadingAnchorsConfig {
+    /// Inject the `a.anchor` permalink, the id is set on the heading otherwise
+    #[serde(default = "default_true")]
+    pub link: bool,
+    /// Text of the permalink, empty shows the stylesheet icon on hover
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub position: AnchorPosition,
+}
+
+impl Default for HeadingAnchorsConfig {
+    fn default() -> Self {
+        HeadingAnchorsConfig {
+            link: true,
+            symbol: String::new(),
+            position: AnchorPosition::default(),
+        }
+    }
+}
+
+/// Where the heading permalink goes
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorPosition {
+    #[default]
+    Before,
+    After,
+}
+
+/// Attributes of the links pointing off-site, set on every content when `enabled`
+/// or by the `external_links` step of the `html_pipeline`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ExternalLinksConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Browsing context of the links, empty to open them in the same tab
+    #[serde(default = "default_external_links_target")]
+    pub target: String,
+    #[serde(default = "default_external_links_rel")]
+    pub rel: String,
+    /// Add `nofollow` to the `rel`, search engines don't follow the links
+    #[serde(default)]
+    pub nofollow: bool,
+}
+
+impl Default for ExternalLinksConfig {
+    fn default() -> Self {
+        ExternalLinksConfig {
+            enabled: false,
+            target: default_external_links_target(),
+            rel: default_external_links_rel(),
+            nofollow: false,
+        }
+    }
+}
+
+/// Limits of the external programs run by the build: `input_converters`,
+/// `fence_renderers`, the `math` renderer, the `command` steps of the
+/// `html_pipeline`, the `alt_text` captioning and the cv `pdf_command`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ExternalCommandsConfig {
+    /// Seconds a command can run before it is killed, `0` for no limit
+    #[serde(default = "default_external_commands_timeout")]
+    pub timeout: u64,
+    /// Bytes a command can write to stdout before it is killed, `0` for no limit
+    #[serde(default = "default_external_commands_max_output")]
+    pub max_output: usize,
+    /// Folder the commands run in, relative to the input folder,
+    /// defaults to the folder marmite runs in
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// Environment variables passed to the commands, all of them when empty
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+impl Default for ExternalCommandsConfig {
+    fn default() -> Self {
+        ExternalCommandsConfig {
+            timeout: default_external_commands_timeout(),
+            max_output: default_external_commands_max_output(),
+            working_dir: None,
+            env: Vec::new(),
+        }
+    }
+}
+
+/// A step of the `html_pipeline`, one of `pipeline::BUILTIN_STEPS` or `command`
+/// piping the html through an external program, e.g: a plugin written in any language
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PipelineStep {
+    pub step: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Program of the `command` step, reads the html on stdin and writes it to stdout
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+/// Server side highlighting of fenced code blocks, the highlighted spans
+/// get css classes styled by the generated `static/syntax.css`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SyntaxHighlightConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// One of the syntect themes, e.g: `InspiredGitHub`, `Solarized (light)`
+    #[serde(default = "default_syntax_theme")]
+    pub theme: String,
+    /// Theme used when the reader prefers a dark color scheme, `null` to disable
+    #[serde(default = "default_syntax_dark_theme")]
+    pub dark_theme: Option<String>,
+}
+
+impl Default for SyntaxHighlightConfig {
+    fn default() -> Self {
+        SyntaxHighlightConfig {
+            enabled: false,
+            theme: default_syntax_theme(),
+            dark_theme: default_syntax_dark_theme(),
+        }
+    }
+}
+
+/// Speeds used for `content.reading_time_minutes`, CJK text has no spaces
+/// between words so it is counted by character
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ReadingTimeConfig {
+    #[serde(default = "default_words_per_minute")]
+    pub words_per_minute: usize,
+    #[serde(default = "default_cjk_chars_per_minute")]
+    pub cjk_chars_per_minute: usize,
+    /// Leave the code blocks out of the word count
+    #[serde(default = "default_true")]
+    pub skip_code: bool,
+}
+
+impl Default for ReadingTimeConfig {
+    fn default() -> Self {
+        ReadingTimeConfig {
+            words_per_minute: default_words_per_minute(),
+            cjk_chars_per_minute: default_cjk_chars_per_minute(),
+            skip_code: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FileMapping {
+    pub source: String,
+    pub dest: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ContactFormConfig {
+    #[serde(default = "default_contact_form_title")]
+    pub title: String,
+    #[serde(default = "default_contact_form_slug")]
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Formspree form id, used to build the form action when `endpoint` is not set
+    #[serde(default)]
+    pub formspree: Option<String>,
+    /// Any URL accepting a POST from the form, takes precedence over `formspree`
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default = "default_contact_form_honeypot")]
+    pub honeypot: String,
+    #[serde(default)]
+    pub success_url: Option<String>,
+    #[serde(default = "default_contact_form_submit_label")]
+    pub submit_label: String,
+    #[serde(default = "default_contact_form_fields")]
+    pub fields: Vec<ContactFormField>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ContactFormField {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(rename = "type", default = "default_contact_form_field_type")]
+    pub type_: String,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+impl ContactFormConfig {
+    /// Resolve the URL the form will be posted to
+    /// `endpoint` wins over `formspree`, returns None when neither is set
+    pub fn action(&self) -> Option<String> {
+        if let Some(endpoint) = self.endpoint.as_ref().filter(|e| !e.is_empty()) {
+            return Some(endpoint.clone());
+        }
+        self.formspree
+            .as_ref()
+            .filter(|id| !id.is_empty())
+            .map(|id| format!("https://formspree.io/f/{id}"))
+    }
+}
+
+/// Site ownership tokens of the search engines and any other `<meta name content>`
+/// pair to emit in the head of every page, e.g: `meta: {p:domain_verify: abc}`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct VerificationConfig {
+    #[serde(default)]
+    pub google: Option<String>,
+    #[serde(default)]
+    pub bing: Option<String>,
+    #[serde(default)]
+    pub yandex: Option<String>,
+    #[serde(default)]
+    pub meta: BTreeMap<String, String>,
+}
+
+impl VerificationConfig {
+    /// The `(name, content)` pairs of the meta tags, search engines first
+    pub fn meta_tags(&self) -> Vec<(String, String)> {
+        let engines = [
+            ("google-site-verification", &self.google),
+            ("msvalidate.01", &self.bing),
+            ("yandex-verification", &self.yandex),
+        ];
+        engines
+            .into_iter()
+            .filter_map(|(name, token)| {
+                token
+                    .as_ref()
+                    .filter(|token| !token.is_empty())
+                    .map(|token| (name.to_string(), token.clone()))
+            })
+            .chain(self.meta.clone())
+            .collect()
+    }
+}
+
+/// Other origins serving the same build, e.g: a Tor onion service and mirrors.
+/// Pages link to their copy on each origin while `url` stays the canonical one
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct OriginsConfig {
+    /// Onion address announced to Tor Browser by the `onion-location` meta
+    #[serde(default)]
+    pub onion: Option<String>,
+    /// Mirror urls added as `<link rel="alternate">`
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+}
+
+/// Fields of `/.well-known/security.txt` (RFC 9116), `contact` is required and
+/// `Expires` is set `expires_days` after each build
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SecurityTxtConfig {
+    #[serde(default)]
+    pub contact: Vec<String>,
+    #[serde(default = "default_security_txt_expires_days")]
+    pub expires_days: u32,
+    #[serde(default)]
+    pub encryption: Option<String>,
+    #[serde(default)]
+    pub policy: Option<String>,
+    #[serde(default)]
+    pub acknowledgments: Option<String>,
+    #[serde(default)]
+    pub hiring: Option<String>,
+    /// Defaults to the site `language`
+    #[serde(default)]
+    pub preferred_languages: Option<String>,
+}
+
+/// Manifest listing the sha256 of every file of the site, written last so
+/// mirrors and readers can check the files weren't changed after the build
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct IntegrityConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path of the manifest in the output folder
+    #[serde(default = "default_integrity_manifest")]
+    pub manifest: String,
+    /// Command signing the manifest, `{manifest}` is replaced by its path,
+    /// e.g: `minisign -S -s keys/site.key -m {manifest}`
+    #[serde(default)]
+    pub sign_command: Option<String>,
+}
+
+impl Default for IntegrityConfig {
+    fn default() -> Self {
+        IntegrityConfig {
+            enabled: false,
+            manifest: default_integrity_manifest(),
+            sign_command: None,
+        }
+    }
+}
+
+/// Responses of the `fetch_json` template function, cached in `.marmite/fetch/`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FetchJsonConfig {
+    /// Never fetch, templates get the cached responses whatever their age
+    #[serde(default)]
+    pub offline: bool,
+    /// Seconds a response is cached when the template doesn't pass `ttl`
+    #[serde(default = "default_fetch_json_ttl")]
+    pub ttl: i64,
+}
+
+impl Default for FetchJsonConfig {
+    fn default() -> Self {
+        FetchJsonConfig {
+            offline: false,
+            ttl: default_fetch_json_ttl(),
+        }
+    }
+}
+
+/// Generates a default configuration file
+/// this function writes to `marmite.yaml` in the input folder
+/// the YAML file will contain the default configuration
+/// default configuration is taken from serde default values
+pub fn generate(input_folder: &Path, cli_args: &Arc<Cli>) {
+    let config_path = input_folder.join(cli_args.config.as_str());
+    // If the file already exists, do not overwrite
+    if config_path.exists() {
+        error!("Config file already exists: {}", config_path.display());
+        return;
+    }
+    let mut config = Marmite::new();
+    config.override_from_cli_args(cli_args);
+    let config_str = match serde_yaml::to_string(&config) {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to serialize config: {e}");
+            return;
+        }
+    };
+    if let Err(e) = std::fs::write(&config_path, config_str) {
+        error!("Failed to write config file: {e}");
+        return;
+    }
+    info!("Config file generated: {}", config_path.display());
+}
+
+// Defaults
+
+fn default_name() -> String {
+    "Home".to_string()
+}
+
+fn default_footer() -> String {
+    r#"<div>Powered by <a href="https://github.com/rochacbruno/marmite">Marmite</a> | <small><a href="https://creativecommons.org/licenses/by-nc-sa/4.0/">CC-BY_NC-SA</a></small></div>"#.to_string()
+}
+
+fn default_pagination() -> usize {
+    10usize
+}
+
+fn default_authors_title() -> String {
+    "Authors".to_string()
+}
+
+fn default_backlinks_title() -> String {
+    "Backlinks".to_string()
+}
+
+fn default_bookmarks_title() -> String {
+    "Bookmarks".to_string()
+}
+
+fn default_tags_title() -> String {
+    "Tags".to_string()
+}
+
+fn default_tags_content_title() -> String {
+    "Posts tagged with '$tag'".to_string()
+}
+
+fn default_streams_content_title() -> String {
+    "Posts from '$stream'".to_string()
+}
+
+fn default_series_title() -> String {
+    "Series".to_string()
+}
+
+fn default_series_content_title() -> String {
+    "Posts from '$series' series".to_string()
+}
+
+fn default_pages_title() -> String {
+    "Pages".to_string()
+}
+
+fn default_archives_title() -> String {
+    "Archive".to_string()
+}
+
+fn default_streams_title() -> String {
+    "Streams".to_string()
+}
+
+fn default_archives_content_title() -> String {
+    "Posts from '$year'".to_string()
 }
 
 fn default_content_path() -> String {
@@ -556,6 +1679,20 @@ fn default_date_format() -> String {
     "%b %e, %Y".to_string()
 }
 
+fn default_date_formats() -> HashMap<String, String> {
+    [
+        ("en", "%b %e, %Y"),
+        ("pt", "%-d de %B de %Y"),
+        ("es", "%-d de %B de %Y"),
+        ("fr", "%-d %B %Y"),
+        ("de", "%-d. %B %Y"),
+        ("it", "%-d %B %Y"),
+    ]
+    .into_iter()
+    .map(|(lang, format)| (lang.to_string(), format.to_string()))
+    .collect()
+}
+
 fn default_menu() -> Option<Vec<(String, String)>> {
     vec![
         ("Tags".to_string(), "tags.html".to_string()),
@@ -656,6 +1793,14 @@ fn default_extension_alerts() -> bool {
     true
 }
 
+fn default_math_inline_command() -> String {
+    "katex --format mathml".to_string()
+}
+
+fn default_math_display_command() -> String {
+    "katex --display-mode --format mathml".to_string()
+}
+
 fn default_gallery_path() -> String {
     "gallery".to_string()
 }
@@ -663,3 +1808,203 @@ fn default_gallery_path() -> String {
 fn default_gallery_thumb_size() -> u32 {
     50
 }
+
+fn default_markdown_extensions() -> Vec<String> {
+    vec!["markdown".to_string(), "mdx".to_string()]
+}
+
+fn default_editor_url() -> String {
+    "vscode://file/{file}".to_string()
+}
+
+fn default_freshness_months() -> usize {
+    12
+}
+
+fn default_year_review_top() -> usize {
+    5
+}
+
+fn default_photos_folder() -> String {
+    "photos".to_string()
+}
+
+fn default_photos_stream() -> String {
+    "photos".to_string()
+}
+
+fn default_photos_title() -> String {
+    "Photos".to_string()
+}
+
+fn default_private_folder() -> String {
+    "private".to_string()
+}
+
+fn default_words_per_minute() -> usize {
+    200
+}
+
+fn default_cjk_chars_per_minute() -> usize {
+    500
+}
+
+fn default_authors_file() -> String {
+    "authors.yaml".to_string()
+}
+
+fn default_security_txt_expires_days() -> u32 {
+    180
+}
+
+fn default_integrity_manifest() -> String {
+    "integrity.json".to_string()
+}
+
+fn default_fetch_json_ttl() -> i64 {
+    3600
+}
+
+fn default_glossary_file() -> String {
+    "glossary.yaml".to_string()
+}
+
+fn default_glossary_slug() -> String {
+    "glossary".to_string()
+}
+
+fn default_glossary_title() -> String {
+    "Glossary".to_string()
+}
+
+fn default_now_file() -> String {
+    "status.yaml".to_string()
+}
+
+fn default_now_slug() -> String {
+    "now".to_string()
+}
+
+fn default_now_title() -> String {
+    "Now".to_string()
+}
+
+fn default_uses_file() -> String {
+    "data/uses.yaml".to_string()
+}
+
+fn default_uses_slug() -> String {
+    "uses".to_string()
+}
+
+fn default_uses_title() -> String {
+    "Uses".to_string()
+}
+
+fn default_cv_file() -> String {
+    "data/resume.json".to_string()
+}
+
+fn default_cv_slug() -> String {
+    "cv".to_string()
+}
+
+fn default_cv_title() -> String {
+    "CV".to_string()
+}
+
+fn default_profile() -> String {
+    "production".to_string()
+}
+
+fn default_projects_slug() -> String {
+    "projects".to_string()
+}
+
+fn default_projects_title() -> String {
+    "Projects".to_string()
+}
+
+fn default_projects_refresh_hours() -> i64 {
+    24
+}
+
+fn default_badges_path() -> String {
+    "badges".to_string()
+}
+
+fn default_badges_color() -> String {
+    "#007ec6".to_string()
+}
+
+fn default_external_links_target() -> String {
+    "_blank".to_string()
+}
+
+fn default_external_links_rel() -> String {
+    "noopener noreferrer".to_string()
+}
+
+fn default_external_commands_timeout() -> u64 {
+    60
+}
+
+fn default_external_commands_max_output() -> usize {
+    16 * 1024 * 1024
+}
+
+fn default_syntax_theme() -> String {
+    "InspiredGitHub".to_string()
+}
+
+fn default_syntax_dark_theme() -> Option<String> {
+    Some("base16-ocean.dark".to_string())
+}
+
+fn default_bibliography() -> String {
+    "references.bib".to_string()
+}
+
+fn default_webmentions_file() -> String {
+    "webmentions.json".to_string()
+}
+
+fn default_contact_form_title() -> String {
+    "Contact".to_string()
+}
+
+fn default_contact_form_slug() -> String {
+    "contact".to_string()
+}
+
+fn default_contact_form_honeypot() -> String {
+    "_gotcha".to_string()
+}
+
+fn default_contact_form_submit_label() -> String {
+    "Send".to_string()
+}
+
+fn default_contact_form_field_type() -> String {
+    "text".to_string()
+}
+
+fn default_contact_form_fields() -> Vec<ContactFormField> {
+    [
+        ("name", "text"),
+        ("email", "email"),
+        ("message", "textarea"),
+    ]
+    .iter()
+    .map(|(name, type_)| ContactFormField {
+        name: (*name).to_string(),
+        label: None,
+        type_: (*type_).to_string(),
+        required: true,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/config.rs"]
+mod tests;
@@ -1,24 +1,31 @@
+use crate::bookmarks::{get_bookmark, Bookmark};
+use crate::bundles::{bundle_folder, bundle_name_path, bundle_url, rewrite_links};
 use crate::cli::Cli;
-use crate::config::Marmite;
+use crate::config::{Author, DuplicateSlugs, Marmite, ReadingTimeConfig, SlugConfig};
+use crate::errors::{ErrorKind, Failure};
+use crate::graph::ContentMetrics;
 use crate::image_provider;
 use crate::parser::{
     append_references, get_html_with_options, get_links_to, get_table_of_contents_from_html,
-    parse_front_matter,
+    get_toc_entries_from_html, parse_front_matter, TocEntry,
 };
+use crate::photos::Photo;
+use crate::private::{decrypt, plain_path, PRIVATE_EXTENSION};
 use crate::re;
 use crate::site::{get_content_folder, Data};
 use chrono::{NaiveDate, NaiveDateTime};
 use frontmatter_gen::{Frontmatter, Value};
-use log::error;
+use log::{error, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::hash_map::Entry;
+use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::path::Path;
-use std::process;
 use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
 
 #[derive(Debug, Clone, Serialize)]
 pub enum Kind {
@@ -27,6 +34,10 @@ pub enum Kind {
     Author,
     Stream,
     Series,
+    /// A custom taxonomy declared in `taxonomies` config, holds the taxonomy name
+    Taxonomy(String),
+    /// A folder of the content folder, with `enable_sections`
+    Section,
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -53,11 +64,19 @@ impl GroupedContent {
             match self.kind {
                 Kind::Series => {
                     // Series should be sorted chronologically (oldest to newest)
-                    contents.sort_by(|a, b| a.date.cmp(&b.date));
+                    contents.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| compare_weight(a, b)));
+                }
+                Kind::Section => {
+                    // Sections mix pages and posts, weight first as in a documentation
+                    contents.sort_by(|a, b| {
+                        compare_weight(a, b)
+                            .then_with(|| b.date.cmp(&a.date))
+                            .then_with(|| a.title.cmp(&b.title))
+                    });
                 }
                 _ => {
                     // All other content types sort newest first
-                    contents.sort_by(|a, b| b.date.cmp(&a.date));
+                    contents.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| compare_weight(a, b)));
                 }
             }
         }
@@ -69,7 +88,7 @@ impl GroupedContent {
             self.map.iter().map(|(k, v)| (k, v.clone())).collect();
 
         match self.kind {
-            Kind::Tag => {
+            Kind::Tag | Kind::Taxonomy(_) => {
                 // sort by number of contents
                 vec.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
             }
@@ -77,7 +96,7 @@ impl GroupedContent {
                 // sort by year, newest first
                 vec.sort_by(|a, b| b.0.cmp(a.0));
             }
-            Kind::Author | Kind::Stream | Kind::Series => {
+            Kind::Author | Kind::Stream | Kind::Series | Kind::Section => {
                 // sort alphabetically
                 vec.sort_by(|a, b| a.0.cmp(b.0));
             }
@@ -86,29 +105,202 @@ impl GroupedContent {
     }
 }
 
+/// Per author aggregation exposed to author pages as `author_activity`
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct AuthorActivity {
+    /// Contents where the author is the primary (first) author
+    pub authored: Vec<Content>,
+    /// Contents where the author is listed after the primary author
+    pub co_authored: Vec<Content>,
+    /// Tags used on all the author contents with its count, most used first
+    pub tags: Vec<(String, usize)>,
+}
+
+impl AuthorActivity {
+    pub fn from_contents(username: &str, contents: &[Content]) -> Self {
+        let mut activity = Self::default();
+        let mut tag_count: HashMap<String, usize> = HashMap::new();
+
+        for content in contents {
+            if content.stream.as_deref() == Some("draft") {
+                continue;
+            }
+            match content.authors.iter().position(|a| a == username) {
+                Some(0) => activity.authored.push(content.clone()),
+                Some(_) => activity.co_authored.push(content.clone()),
+                None => continue,
+            }
+            for tag in &content.tags {
+                *tag_count.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+
+        activity.authored.sort_by(|a, b| b.date.cmp(&a.date));
+        activity.co_authored.sort_by(|a, b| b.date.cmp(&a.date));
+        activity.tags = tag_count.into_iter().collect();
+        activity
+            .tags
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        activity
+    }
+}
+
+/// Attribution of a content with several authors, rendered as co-author bylines
+/// e.g: "Alice, Bob and Carol" or "Alice written with Bob and Carol"
+#[derive(Debug, Deserialize, Clone, Serialize, Default, PartialEq)]
+pub struct Byline {
+    /// Profile of the primary (first) author
+    pub author: Author,
+    /// Profiles of the co-authors, in the frontmatter order
+    pub written_with: Vec<Author>,
+    /// Names of all the authors joined for display
+    pub names: String,
+}
+
+impl Byline {
+    /// Byline of the author profiles, `None` unless the content has co-authors
+    pub fn from_profiles(profiles: &[Author]) -> Option<Self> {
+        let (author, written_with) = profiles.split_first()?;
+        if written_with.is_empty() {
+            return None;
+        }
+        let names: Vec<&str> = profiles.iter().map(|a| a.name.as_str()).collect();
+        let (last, rest) = names.split_last()?;
+        Some(Byline {
+            author: author.clone(),
+            written_with: written_with.to_vec(),
+            names: format!("{} and {last}", rest.join(", ")),
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Serialize, Default)]
 pub struct Content {
     pub title: String,
     pub description: Option<String>,
+    /// Language of the content from the `lang` frontmatter, `None` is the site `language`
+    pub lang: Option<String>,
     pub slug: String,
     pub html: String,
     pub tags: Vec<String>,
+    /// Terms of the custom taxonomies, keyed by taxonomy name e.g: `{"categories": ["rust"]}`
+    pub taxonomies: HashMap<String, Vec<String>>,
     pub date: Option<NaiveDateTime>,
-    pub extra: Option<Value>,
+    /// Last edit date from `updated` (or `last_modified`) in frontmatter,
+    /// falls back to the file modification time when `updated_from_mtime` is set
+    pub updated: Option<NaiveDateTime>,
+    /// Date from `expires` in frontmatter, expired content is left out of listings and feeds
+    pub expires: Option<NaiveDateTime>,
+    /// Set when `expires` is past, only rendered (with a noindex meta) when `render_expired` is set
+    pub expired: bool,
+    /// From `weight` (or `order`) in frontmatter, lower first, orders the content
+    /// sharing a date and the undated pages
+    pub weight: Option<i64>,
+    /// Old urls from `aliases` in frontmatter, each one gets a page redirecting to the content
+    pub aliases: Vec<String>,
+    /// Html of the content before the `<!-- more -->` marker, or of its first
+    /// `summary_paragraphs`, shown by listings and feeds instead of the full content
+    pub summary_html: Option<String>,
+    /// The `extra` frontmatter merged with every frontmatter key marmite doesn't use,
+    /// exposed to templates as `content.extra.{key}`
+    pub extra: HashMap<String, Value>,
+    /// Words of the rendered content, each CJK character counts as a word
+    pub word_count: usize,
+    /// Minutes to read the content at the `reading_time` speeds, at least 1
+    pub reading_time_minutes: usize,
+    /// Ticked and total items of the task lists, e.g: the progress of a project page
+    pub tasks_done: usize,
+    pub tasks_total: usize,
     pub links_to: Option<Vec<String>>,
     pub back_links: Vec<Self>,
+    /// Degrees and centrality of the content on the internal link graph
+    pub metrics: ContentMetrics,
     pub card_image: Option<String>,
     pub banner_image: Option<String>,
+    /// Link of the `bookmark` frontmatter, the body of the content is the note
+    pub bookmark: Option<Bookmark>,
+    /// Photos of the content generated for the `photos` stream
+    pub photos: Vec<Photo>,
     pub authors: Vec<String>,
+    pub author_roles: HashMap<String, String>,
+    /// Profiles of the `authors`, in the same order, from the `authors` config
+    /// and `authors.yaml` or a placeholder for the authors not configured
+    pub author_profiles: Vec<Author>,
+    /// Primary author and co-authors, only set when the content has co-authors
+    pub byline: Option<Byline>,
     pub stream: Option<String>,
     pub series: Option<String>,
+    /// Position of the post in its series, starting at 1, the series is ordered by date
+    pub series_index: Option<usize>,
+    /// Number of posts in the series
+    pub series_total: Option<usize>,
+    pub series_prev: Option<Box<Content>>,
+    pub series_next: Option<Box<Content>>,
+    /// Folder of the source relative to the content folder e.g: `docs/install`,
+    /// only set with `enable_sections`
+    pub section: Option<String>,
+    /// `(name, url)` of the section pages from the top level section down to `section`
+    pub breadcrumbs: Vec<(String, String)>,
     pub pinned: bool,
     pub toc: Option<String>,
+    /// Nested headings of the table of contents, empty unless `toc` is enabled
+    pub toc_entries: Vec<TocEntry>,
     pub modified_time: Option<i64>,
     pub comments: Option<bool>,
+    /// Set to `false` in frontmatter to disable glossary term linking
+    pub glossary: Option<bool>,
+    /// Set with `draft: true` in frontmatter, drafts are only built with `--drafts`
+    pub draft: bool,
+    /// Set with `noindex: true` in frontmatter, adds a noindex robots meta and
+    /// leaves the content out of the sitemap
+    pub noindex: bool,
+    /// Set with `noai: true` in frontmatter, adds a noai robots meta and
+    /// disallows the content to AI crawlers in `robots.txt`
+    pub noai: bool,
+    /// Template used to render the content, from `template` in frontmatter,
+    /// `content.html` when not set
+    pub template: Option<String>,
+    /// Stable id used as the feed GUID, from `uuid` in frontmatter
+    /// or generated when `feed_guid: uuid` is set
+    pub uuid: Option<String>,
+    /// Url of the original of a syndicated content, from `canonical_url` in frontmatter,
+    /// replaces the link to the generated page in the head and the feeds
+    pub canonical_url: Option<String>,
     pub next: Option<Box<Content>>,
     pub previous: Option<Box<Content>>,
     pub source_path: Option<std::path::PathBuf>,
+    pub share_links: Option<ShareLinks>,
+}
+
+/// Share URLs computed at build time, exposed to templates as `content.share_links`
+#[derive(Debug, Deserialize, Clone, Serialize, Default, PartialEq)]
+pub struct ShareLinks {
+    /// Absolute URL of the content, used by copy-link buttons
+    pub url: String,
+    /// Instance agnostic mastodon share (the user picks the instance)
+    pub mastodon: String,
+    pub bluesky: String,
+    pub email: String,
+}
+
+impl ShareLinks {
+    /// Build the share links for the content, returns None when `site.url`
+    /// is not set as the share targets require an absolute URL
+    pub fn new(title: &str, slug: &str, site: &Marmite) -> Option<Self> {
+        if site.url.is_empty() {
+            return None;
+        }
+        let url = format!("{}/{slug}.html", site.url.trim_end_matches('/'));
+        let encoded_url = urlencoding::encode(&url);
+        let encoded_title = urlencoding::encode(title);
+        let encoded_text = urlencoding::encode(&format!("{title} {url}"));
+        Some(Self {
+            mastodon: format!("https://toot.kytta.dev/?text={encoded_text}"),
+            bluesky: format!("https://bsky.app/intent/compose?text={encoded_text}"),
+            email: format!("mailto:?subject={encoded_title}&body={encoded_url}"),
+            url,
+        })
+    }
 }
 
 impl Content {
@@ -116,27 +308,89 @@ impl Content {
     /// then parse the markdown content to html and return a Content struct
     /// if the file is a fragment, the markdown content will be modified to include the references
     /// if is a regular content then content will be modified to include the `markdown_header`
-    /// and `markdown_footer` and references
+    /// and `markdown_footer` and references.
+    /// `index` sources in a folder of the `content_folder` are page bundles
     pub fn from_markdown(
         path: &Path,
         fragments: Option<&HashMap<String, String>>,
         site: &Marmite,
         modified_time: Option<i64>,
-    ) -> Result<Content, String> {
-        let file_content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-        let (frontmatter, raw_markdown) = parse_front_matter(&file_content)?;
-        let (title, markdown_without_title) = get_title(&frontmatter, raw_markdown);
+        content_folder: Option<&Path>,
+    ) -> Result<Content, Failure> {
+        let source_path = path.to_path_buf();
+        let (file_content, parsed_path) =
+            if path.extension().is_some_and(|ext| ext == PRIVATE_EXTENSION) {
+                let identity = site
+                    .private_key
+                    .as_deref()
+                    .ok_or_else(|| format!("No private key to decrypt {}", path.display()))?;
+                (decrypt(path, identity)?, plain_path(path))
+            } else {
+                let file_content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+                (file_content, path.to_path_buf())
+            };
+        // private content is parsed as if it was not encrypted
+        let path = parsed_path.as_path();
+        // a page bundle `my-post/index.md` is named after its folder
+        let bundle = content_folder.and_then(|folder| bundle_folder(path, folder));
+        let bundle_path = bundle
+            .as_deref()
+            .map(|folder| bundle_name_path(folder, path));
+        let named_path = bundle_path.as_deref().unwrap_or(path);
+        let file_content = crate::converters::source_to_markdown(
+            path,
+            file_content,
+            &site.input_converters,
+            &site.content_extensions,
+        )?;
+        let (mut frontmatter, raw_markdown) = parse_front_matter(&file_content)?;
+        // includes are looked up in the content folder, or the folder of the file
+        let include_root = content_folder
+            .or_else(|| path.parent())
+            .unwrap_or(Path::new(""));
+        let (raw_markdown, included) =
+            crate::includes::apply_includes(raw_markdown, path, include_root)?;
+        let raw_markdown = crate::conditionals::apply_conditionals(&raw_markdown, &site.profile);
+        let raw_markdown = raw_markdown.as_str();
+        apply_frontmatter_aliases(&mut frontmatter, &site.frontmatter_aliases);
+        let is_html = path.extension().is_some_and(|ext| ext == "html");
+        let bookmark = get_bookmark(&frontmatter);
+        let (title, markdown_without_title) = if is_html {
+            get_html_title(&frontmatter, raw_markdown, named_path)
+        } else if let Some(bookmark) = bookmark
+            .as_ref()
+            .filter(|_| frontmatter.get("title").is_none())
+        {
+            // the body of a bookmark is the note, titled by the url until the metadata is fetched
+            (bookmark.url.clone(), raw_markdown.trim().to_string())
+        } else {
+            get_title(&frontmatter, raw_markdown)
+        };
 
         let is_fragment = path
             .file_name()
             .and_then(|name| name.to_str())
             .is_some_and(|s| s.starts_with('_'));
+        let summary_source = if is_fragment {
+            None
+        } else {
+            split_summary(&markdown_without_title).map(str::to_string)
+        };
         let default_parser_options = crate::config::ParserOptions::default();
         let parser_options = site
             .markdown_parser
             .as_ref()
             .unwrap_or(&default_parser_options);
-        let html = if is_fragment {
+        // the summary is rendered apart, it needs the definitions of its footnotes
+        let footnotes = if is_html || !parser_options.extension.footnotes {
+            String::new()
+        } else {
+            crate::parser::footnote_definitions(&markdown_without_title)
+        };
+        let html = if is_html {
+            // HTML content is used as is, skipping markdown rendering
+            markdown_without_title
+        } else if is_fragment {
             let references_path = path.with_file_name("_references.md");
             let mut raw_markdown = raw_markdown.to_string();
             if path != references_path {
@@ -158,12 +412,90 @@ impl Content {
         } else {
             get_html_with_options(&markdown_without_title, parser_options)
         };
+        let html = crate::parser::heading_anchors(&html, &site.heading_anchors);
+        let html = crate::fences::render_fences(&html, &site.fence_renderers);
+        let html = crate::fences::diagram_containers(&html);
+        let html = crate::highlight::highlight_code(&html, &site.syntax_highlight);
+        let html = normalize_media_urls(&html, &site.media_path, &site.slug);
+        let html = crate::pipeline::run_pipeline(&html, site);
+        let summary_html = match summary_source {
+            Some(mut summary) => {
+                if !is_html {
+                    if !footnotes.is_empty() {
+                        summary.push_str(format!("\n\n{footnotes}").as_str());
+                    }
+                    if let Some(references) = fragments.and_then(|f| f.get("references")) {
+                        summary.push_str(format!("\n\n{references}").as_str());
+                    }
+                    summary = get_html_with_options(&summary, parser_options);
+                }
+                let summary = crate::parser::strip_footnotes(&summary);
+                let summary = crate::fences::render_fences(&summary, &site.fence_renderers);
+                let summary = crate::fences::diagram_containers(&summary);
+                let summary = crate::highlight::highlight_code(&summary, &site.syntax_highlight);
+                let summary = normalize_media_urls(&summary, &site.media_path, &site.slug);
+                Some(crate::pipeline::run_pipeline(&summary, site))
+            }
+            None if !is_fragment => first_paragraphs(&html, site.summary_paragraphs)
+                .map(|summary| crate::parser::strip_footnotes(&summary)),
+            None => None,
+        };
+
+        let (words, cjk_chars) = count_words(&html, site.reading_time.skip_code);
+        let word_count = words + cjk_chars;
+        let reading_time_minutes = reading_time(words, cjk_chars, &site.reading_time);
+        let (tasks_done, tasks_total) = crate::parser::task_counts(&html);
 
         let description = get_description(&frontmatter);
+        let lang = frontmatter
+            .get("lang")
+            .and_then(|lang| lang.as_str())
+            .map(str::trim)
+            .filter(|lang| !lang.is_empty())
+            .map(String::from);
         let tags = get_tags(&frontmatter);
-        let slug = get_slug(&frontmatter, path);
-        let date = get_date(&frontmatter, path);
-        let extra = frontmatter.get("extra").map(std::borrow::ToOwned::to_owned);
+        let taxonomies = site
+            .taxonomy_names()
+            .into_iter()
+            .filter_map(|name| {
+                let terms = get_terms(&frontmatter, &name);
+                (!terms.is_empty()).then_some((name, terms))
+            })
+            .collect();
+        let slug = get_slug_with_config(&frontmatter, named_path, &site.slug);
+        if site.slug.reserved.contains(&slug) {
+            return Err(Failure::new(
+                ErrorKind::Content,
+                format!(
+                    "Slug '{slug}' used by {} is reserved - set a different `slug` or `title`",
+                    path.display()
+                ),
+            ));
+        }
+        // files of the bundle are copied to `{slug}/` next to the rendered page
+        let (html, summary_html) = match &bundle {
+            Some(folder) => (
+                rewrite_links(&html, folder, &slug),
+                summary_html.map(|summary| rewrite_links(&summary, folder, &slug)),
+            ),
+            None => (html, summary_html),
+        };
+        let bundled = |url: String| match &bundle {
+            Some(folder) => bundle_url(&url, folder, &slug).unwrap_or(url),
+            None => url,
+        };
+        let date =
+            get_date(&frontmatter, named_path).map_err(|e| Failure::new(ErrorKind::Content, e))?;
+        let updated = get_updated(&frontmatter, path).or_else(|| {
+            modified_time
+                .filter(|_| site.updated_from_mtime)
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|dt| dt.naive_utc())
+        });
+        let expires = get_expires(&frontmatter, path);
+        let weight = get_weight(&frontmatter);
+        let aliases = get_terms(&frontmatter, "aliases");
+        let extra = get_extra(&frontmatter, site);
         let links_to = get_links_to(&html);
         let back_links = Vec::new(); // will be mutated later
 
@@ -175,25 +507,38 @@ impl Content {
             }
         }
 
-        let card_image = get_card_image(&frontmatter, &html, path, &slug, &site.media_path);
-        let banner_image = get_banner_image(&frontmatter, path, &slug, &site.media_path);
+        let card_image = get_card_image(&frontmatter, &html, path, &slug, &site.media_path)
+            .map(|url| normalize_media_url(&url, &site.media_path, &site.slug))
+            .map(bundled);
+        let banner_image = get_banner_image(&frontmatter, path, &slug, &site.media_path)
+            .map(|url| normalize_media_url(&url, &site.media_path, &site.slug))
+            .map(bundled);
         let authors = get_authors(&frontmatter, Some(site.default_author.clone()));
+        let author_roles = get_author_roles(&frontmatter);
+        let author_profiles = authors
+            .iter()
+            .map(|username| site.author_profile(username))
+            .collect::<Vec<_>>();
+        let byline = Byline::from_profiles(&author_profiles);
         let pinned = frontmatter
             .get("pinned")
             .is_some_and(|p| p.as_bool().unwrap_or(false));
 
-        let toc = if frontmatter
+        let (toc, toc_entries) = if frontmatter
             .get("toc")
             .map_or(site.toc, |t| t.as_bool().unwrap_or(site.toc))
         {
-            Some(get_table_of_contents_from_html(&html))
+            (
+                Some(get_table_of_contents_from_html(&html)),
+                get_toc_entries_from_html(&html),
+            )
         } else {
-            None
+            (None, Vec::new())
         };
 
         let stream = if date.is_some() {
             // For posts with dates, determine stream from frontmatter or filename patterns
-            Some(determine_stream(&frontmatter, path))
+            Some(determine_stream(&frontmatter, named_path))
         } else {
             // For pages without dates, stream is None (pages don't have streams)
             None
@@ -202,54 +547,180 @@ impl Content {
         let series = determine_series(&frontmatter);
 
         let comments = get_comments(&frontmatter);
-        let content = Content {
+        let glossary = frontmatter.get("glossary").and_then(Value::as_bool);
+        let draft = frontmatter
+            .get("draft")
+            .is_some_and(|d| d.as_bool().unwrap_or(false));
+        let noindex = frontmatter
+            .get("noindex")
+            .is_some_and(|d| d.as_bool().unwrap_or(false));
+        let noai = frontmatter
+            .get("noai")
+            .is_some_and(|d| d.as_bool().unwrap_or(false));
+        let template = frontmatter
+            .get("template")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let uuid = frontmatter
+            .get("uuid")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let canonical_url = frontmatter
+            .get("canonical_url")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string);
+        let share_links = ShareLinks::new(&title, &slug, site);
+
+        let mut content = Content {
             title,
             description,
+            lang,
             slug,
             html,
             tags,
+            taxonomies,
             date,
+            updated,
+            expires,
+            expired: false,
+            weight,
+            aliases,
+            summary_html,
             extra,
+            word_count,
+            reading_time_minutes,
+            tasks_done,
+            tasks_total,
             links_to,
             back_links,
+            metrics: ContentMetrics::default(),
             card_image,
             banner_image,
+            bookmark,
+            photos: Vec::new(),
             authors,
+            author_roles,
+            author_profiles,
+            byline,
             stream,
             series,
+            series_index: None,
+            series_total: None,
+            section: None,
+            breadcrumbs: Vec::new(),
+            series_prev: None,
+            series_next: None,
             pinned,
             toc,
-            modified_time,
+            toc_entries,
+            modified_time: modified_time
+                .map(|time| time.max(crate::includes::latest_modified(&included).unwrap_or(0))),
             comments,
+            glossary,
+            draft,
+            noindex,
+            noai,
+            template,
+            uuid,
+            canonical_url,
             next: None,
             previous: None,
-            source_path: Some(path.to_path_buf()),
+            source_path: Some(source_path),
+            share_links,
         };
+        apply_og_defaults(&mut content, site);
         Ok(content)
     }
 }
 
+/// Fill `description`, `card_image` and `banner_image` from the stream and tags
+/// configuration when the content does not define its own, so social previews are never blank.
+/// Stream defaults take precedence over tag defaults, tags are checked in order.
+pub fn apply_og_defaults(content: &mut Content, site: &Marmite) {
+    let mut defaults: Vec<(&Option<String>, &Option<String>, &Option<String>)> = Vec::new();
+    if let Some(stream) = content.stream.as_ref().and_then(|s| site.streams.get(s)) {
+        defaults.push((
+            &stream.description,
+            &stream.card_image,
+            &stream.banner_image,
+        ));
+    }
+    for tag in &content.tags {
+        if let Some(tag_config) = site
+            .tags
+            .get(tag)
+            .or_else(|| site.tags.get(&slug::slugify(tag)))
+        {
+            defaults.push((
+                &tag_config.description,
+                &tag_config.card_image,
+                &tag_config.banner_image,
+            ));
+        }
+    }
+
+    for (description, card_image, banner_image) in defaults {
+        if content.description.is_none() {
+            content.description.clone_from(description);
+        }
+        if content.banner_image.is_none() {
+            content.banner_image.clone_from(banner_image);
+        }
+        if content.card_image.is_none() {
+            content.card_image = card_image.clone().or_else(|| banner_image.clone());
+        }
+    }
+}
+
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, Default)]
 pub struct ContentBuilder {
     title: Option<String>,
     description: Option<String>,
+    lang: Option<String>,
     slug: Option<String>,
     html: Option<String>,
     tags: Option<Vec<String>>,
+    taxonomies: Option<HashMap<String, Vec<String>>>,
     date: Option<NaiveDateTime>,
-    extra: Option<Value>,
+    updated: Option<NaiveDateTime>,
+    expires: Option<NaiveDateTime>,
+    weight: Option<i64>,
+    aliases: Option<Vec<String>>,
+    summary_html: Option<String>,
+    extra: Option<HashMap<String, Value>>,
+    word_count: Option<usize>,
+    reading_time_minutes: Option<usize>,
+    tasks_done: Option<usize>,
+    tasks_total: Option<usize>,
     links_to: Option<Vec<String>>,
     back_links: Option<Vec<Content>>,
+    metrics: Option<ContentMetrics>,
     card_image: Option<String>,
     banner_image: Option<String>,
+    bookmark: Option<Bookmark>,
+    photos: Option<Vec<Photo>>,
     authors: Option<Vec<String>>,
+    author_roles: Option<HashMap<String, String>>,
+    author_profiles: Option<Vec<Author>>,
+    byline: Option<Byline>,
     stream: Option<String>,
     series: Option<String>,
     pinned: Option<bool>,
     toc: Option<String>,
+    toc_entries: Option<Vec<TocEntry>>,
     comments: Option<bool>,
+    glossary: Option<bool>,
+    draft: Option<bool>,
+    noindex: Option<bool>,
+    noai: Option<bool>,
+    template: Option<String>,
+    uuid: Option<String>,
+    canonical_url: Option<String>,
     source_path: Option<std::path::PathBuf>,
+    share_links: Option<ShareLinks>,
 }
 
 #[allow(dead_code)]
@@ -268,6 +739,11 @@ impl ContentBuilder {
         self
     }
 
+    pub fn lang(mut self, lang: String) -> Self {
+        self.lang = Some(lang);
+        self
+    }
+
     pub fn slug(mut self, slug: String) -> Self {
         self.slug = Some(slug);
         self
@@ -283,12 +759,58 @@ impl ContentBuilder {
         self
     }
 
+    pub fn taxonomies(mut self, taxonomies: HashMap<String, Vec<String>>) -> Self {
+        self.taxonomies = Some(taxonomies);
+        self
+    }
+
     pub fn date(mut self, date: NaiveDateTime) -> Self {
         self.date = Some(date);
         self
     }
 
-    pub fn extra(mut self, extra: Value) -> Self {
+    pub fn updated(mut self, updated: NaiveDateTime) -> Self {
+        self.updated = Some(updated);
+        self
+    }
+
+    pub fn expires(mut self, expires: NaiveDateTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    pub fn weight(mut self, weight: i64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = Some(aliases);
+        self
+    }
+
+    pub fn word_count(mut self, word_count: usize) -> Self {
+        self.word_count = Some(word_count);
+        self
+    }
+
+    pub fn reading_time_minutes(mut self, reading_time_minutes: usize) -> Self {
+        self.reading_time_minutes = Some(reading_time_minutes);
+        self
+    }
+
+    pub fn tasks(mut self, done: usize, total: usize) -> Self {
+        self.tasks_done = Some(done);
+        self.tasks_total = Some(total);
+        self
+    }
+
+    pub fn summary_html(mut self, summary_html: String) -> Self {
+        self.summary_html = Some(summary_html);
+        self
+    }
+
+    pub fn extra(mut self, extra: HashMap<String, Value>) -> Self {
         self.extra = Some(extra);
         self
     }
@@ -303,6 +825,11 @@ impl ContentBuilder {
         self
     }
 
+    pub fn metrics(mut self, metrics: ContentMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     pub fn card_image(mut self, card_image: String) -> Self {
         self.card_image = Some(card_image);
         self
@@ -318,6 +845,31 @@ impl ContentBuilder {
         self
     }
 
+    pub fn author_roles(mut self, author_roles: HashMap<String, String>) -> Self {
+        self.author_roles = Some(author_roles);
+        self
+    }
+
+    pub fn author_profiles(mut self, author_profiles: Vec<Author>) -> Self {
+        self.author_profiles = Some(author_profiles);
+        self
+    }
+
+    pub fn byline(mut self, byline: Byline) -> Self {
+        self.byline = Some(byline);
+        self
+    }
+
+    pub fn bookmark(mut self, bookmark: Bookmark) -> Self {
+        self.bookmark = Some(bookmark);
+        self
+    }
+
+    pub fn photos(mut self, photos: Vec<Photo>) -> Self {
+        self.photos = Some(photos);
+        self
+    }
+
     pub fn stream(mut self, stream: String) -> Self {
         self.stream = Some(stream);
         self
@@ -338,43 +890,142 @@ impl ContentBuilder {
         self
     }
 
+    pub fn toc_entries(mut self, toc_entries: Vec<TocEntry>) -> Self {
+        self.toc_entries = Some(toc_entries);
+        self
+    }
+
     pub fn comments(mut self, comments: bool) -> Self {
         self.comments = Some(comments);
         self
     }
 
+    pub fn glossary(mut self, glossary: bool) -> Self {
+        self.glossary = Some(glossary);
+        self
+    }
+
+    pub fn draft(mut self, draft: bool) -> Self {
+        self.draft = Some(draft);
+        self
+    }
+
+    pub fn noindex(mut self, noindex: bool) -> Self {
+        self.noindex = Some(noindex);
+        self
+    }
+
+    pub fn noai(mut self, noai: bool) -> Self {
+        self.noai = Some(noai);
+        self
+    }
+
+    pub fn template(mut self, template: String) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn uuid(mut self, uuid: String) -> Self {
+        self.uuid = Some(uuid);
+        self
+    }
+
+    pub fn canonical_url(mut self, canonical_url: String) -> Self {
+        self.canonical_url = Some(canonical_url);
+        self
+    }
+
     pub fn source_path(mut self, source_path: std::path::PathBuf) -> Self {
         self.source_path = Some(source_path);
         self
     }
 
+    pub fn share_links(mut self, share_links: ShareLinks) -> Self {
+        self.share_links = Some(share_links);
+        self
+    }
+
     pub fn build(self) -> Content {
         Content {
             title: self.title.unwrap_or_default(),
             description: self.description,
+            lang: self.lang,
             slug: self.slug.unwrap_or_default(),
             html: self.html.unwrap_or_default(),
             tags: self.tags.unwrap_or_default(),
+            taxonomies: self.taxonomies.unwrap_or_default(),
             date: self.date,
-            extra: self.extra,
+            updated: self.updated,
+            expires: self.expires,
+            expired: false,
+            weight: self.weight,
+            aliases: self.aliases.unwrap_or_default(),
+            summary_html: self.summary_html,
+            extra: self.extra.unwrap_or_default(),
+            word_count: self.word_count.unwrap_or_default(),
+            reading_time_minutes: self.reading_time_minutes.unwrap_or_default(),
+            tasks_done: self.tasks_done.unwrap_or_default(),
+            tasks_total: self.tasks_total.unwrap_or_default(),
             links_to: self.links_to,
             back_links: self.back_links.unwrap_or_default(),
+            metrics: self.metrics.unwrap_or_default(),
             card_image: self.card_image,
             banner_image: self.banner_image,
+            bookmark: self.bookmark,
+            photos: self.photos.unwrap_or_default(),
             authors: self.authors.unwrap_or_default(),
+            author_roles: self.author_roles.unwrap_or_default(),
+            author_profiles: self.author_profiles.unwrap_or_default(),
+            byline: self.byline,
             stream: self.stream,
             series: self.series,
+            series_index: None,
+            series_total: None,
+            section: None,
+            breadcrumbs: Vec::new(),
+            series_prev: None,
+            series_next: None,
             pinned: self.pinned.unwrap_or_default(),
             toc: self.toc,
+            toc_entries: self.toc_entries.unwrap_or_default(),
             modified_time: None,
             comments: self.comments,
+            glossary: self.glossary,
+            draft: self.draft.unwrap_or_default(),
+            noindex: self.noindex.unwrap_or_default(),
+            noai: self.noai.unwrap_or_default(),
+            template: self.template,
+            uuid: self.uuid,
+            canonical_url: self.canonical_url,
             next: None,
             previous: None,
             source_path: self.source_path,
+            share_links: self.share_links,
         }
     }
 }
 
+/// Title for `.html` content, from the frontmatter, else from the first `<h1>`
+/// (removed from the html as the template renders the title), else the filename
+/// return (title, html)
+pub fn get_html_title(frontmatter: &Frontmatter, html: &str, path: &Path) -> (String, String) {
+    if let Some(Value::String(title)) = frontmatter.get("title") {
+        return (title.to_string(), html.to_string());
+    }
+    let h1_re = Regex::new(re::CAPTURE_H1_TEXT).expect("H1 regex should compile");
+    if let Some(h1) = h1_re.captures(html) {
+        let tags_re = Regex::new(re::MATCH_HTML_TAGS).expect("HTML tags regex should compile");
+        let title = tags_re.replace_all(&h1[1], "").trim().to_string();
+        return (title, html.replacen(&h1[0], "", 1).trim().to_string());
+    }
+    let title = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(remove_date_from_filename)
+        .unwrap_or_default();
+    (title, html.to_string())
+}
+
 /// Try to get the title from the frontmatter
 /// If not found, get the first line of the markdown without the leading '#'
 /// If no lines are found, return an empty string
@@ -412,19 +1063,46 @@ pub fn get_comments(frontmatter: &Frontmatter) -> Option<bool> {
     None
 }
 
+/// Copy the values of aliased keys to their canonical names
+/// e.g: `categories: [a, b]` with alias `categories: tags` becomes `tags: [a, b]`
+/// so content imported from other generators works without rewriting.
+/// A canonical key already present in the frontmatter is never overwritten.
+pub fn apply_frontmatter_aliases(frontmatter: &mut Frontmatter, aliases: &HashMap<String, String>) {
+    let mut sorted_aliases: Vec<(&String, &String)> = aliases.iter().collect();
+    sorted_aliases.sort();
+    for (alias, canonical) in sorted_aliases {
+        if frontmatter.get(canonical).is_some() {
+            continue;
+        }
+        if let Some(value) = frontmatter.get(alias).cloned() {
+            frontmatter.insert(canonical.clone(), value);
+        }
+    }
+}
+
 /// Try to get the slug from the frontmatter
 /// If not found, get the title from the frontmatter
 /// If not found, get the filename without the date and stream prefix
 /// If a stream is not the default `index`, prepend it to the slug
 /// return the slug
 pub fn get_slug<'a>(frontmatter: &'a Frontmatter, path: &'a Path) -> String {
+    get_slug_with_config(frontmatter, path, &SlugConfig::default())
+}
+
+/// Same as `get_slug` but slugs generated from the title follow the
+/// `slug` config rules (stop words removal and max length)
+pub fn get_slug_with_config<'a>(
+    frontmatter: &'a Frontmatter,
+    path: &'a Path,
+    config: &SlugConfig,
+) -> String {
     let stream = determine_stream(frontmatter, path);
     let mut final_slug: String;
 
     if let Some(slug) = frontmatter.get("slug") {
         final_slug = slug::slugify(slug.to_string());
     } else if let Some(title) = frontmatter.get("title") {
-        final_slug = slug::slugify(title.to_string());
+        final_slug = slugify_with_config(&title.to_string(), config);
     } else {
         final_slug = path
             .file_stem()
@@ -438,7 +1116,127 @@ pub fn get_slug<'a>(frontmatter: &'a Frontmatter, path: &'a Path) -> String {
         final_slug = format!("{stream}-{final_slug}");
     }
 
-    final_slug
+    let normalized = normalize_url_path(&final_slug, config);
+    if normalized != final_slug {
+        warn!(
+            "URL of {} changed by slug rules: {final_slug}.html -> {normalized}.html",
+            path.display()
+        );
+    }
+    normalized
+}
+
+/// Apply the `lowercase`, `collapse_dashes` and `normalize_unicode` rules
+/// to every segment of an url path, the path separators are kept
+pub fn normalize_url_path(path: &str, config: &SlugConfig) -> String {
+    if !config.normalizes_urls() {
+        return path.to_string();
+    }
+    path.split('/')
+        .map(|segment| {
+            let mut segment = if config.normalize_unicode {
+                segment.nfc().collect::<String>()
+            } else {
+                segment.to_string()
+            };
+            if config.lowercase {
+                segment = segment.to_lowercase();
+            }
+            if config.collapse_dashes {
+                while segment.contains("--") {
+                    segment = segment.replace("--", "-");
+                }
+            }
+            segment
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Normalize the file part of an url pointing to the media folder,
+/// other urls are returned untouched
+pub fn normalize_media_url(url: &str, media_path: &str, config: &SlugConfig) -> String {
+    let prefix_len = url.len() - url.trim_start_matches("./").trim_start_matches('/').len();
+    let (prefix, path) = url.split_at(prefix_len);
+    match path
+        .strip_prefix(media_path)
+        .and_then(|p| p.strip_prefix('/'))
+    {
+        Some(file) => format!("{prefix}{media_path}/{}", normalize_url_path(file, config)),
+        None => url.to_string(),
+    }
+}
+
+/// Rewrite every `src`/`href` pointing to the media folder so the links
+/// match the media files renamed by the slug normalization rules
+pub fn normalize_media_urls(html: &str, media_path: &str, config: &SlugConfig) -> String {
+    if !config.normalizes_urls() {
+        return html.to_string();
+    }
+    let attr_re =
+        Regex::new(re::CAPTURE_SRC_OR_HREF_VALUE).expect("Src or href regex should compile");
+    attr_re
+        .replace_all(html, |caps: &regex::Captures| {
+            format!(
+                "{}{}",
+                &caps[1],
+                normalize_media_url(&caps[2], media_path, config)
+            )
+        })
+        .to_string()
+}
+
+const DEFAULT_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on",
+    "or", "that", "the", "this", "to", "was", "with",
+];
+
+/// Slugify the text removing stop words (if enabled) and truncating
+/// the result on a word boundary to the configured `max_length`
+pub fn slugify_with_config(text: &str, config: &SlugConfig) -> String {
+    let slug = slug::slugify(text);
+    let mut words: Vec<&str> = slug.split('-').filter(|w| !w.is_empty()).collect();
+
+    if config.remove_stop_words {
+        let without_stop_words: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|word| {
+                if config.stop_words.is_empty() {
+                    !DEFAULT_STOP_WORDS.contains(word)
+                } else {
+                    !config.stop_words.iter().any(|stop| stop == word)
+                }
+            })
+            .collect();
+        // Keep the original words when the title is made only of stop words
+        if !without_stop_words.is_empty() {
+            words = without_stop_words;
+        }
+    }
+
+    let slug = words.join("-");
+    match config.max_length {
+        Some(max_length) if max_length > 0 && slug.len() > max_length => {
+            let mut truncated = String::new();
+            for word in &words {
+                let extra = if truncated.is_empty() { 0 } else { 1 };
+                if truncated.len() + extra + word.len() > max_length {
+                    break;
+                }
+                if !truncated.is_empty() {
+                    truncated.push('-');
+                }
+                truncated.push_str(word);
+            }
+            if truncated.is_empty() {
+                // First word alone is longer than the limit
+                truncated = slug.chars().take(max_length).collect();
+            }
+            truncated
+        }
+        _ => slug,
+    }
 }
 
 /// Determine stream from frontmatter or filename
@@ -545,33 +1343,55 @@ fn extract_stream_from_s_pattern(filename: &str) -> Option<String> {
 }
 
 pub fn get_tags(frontmatter: &Frontmatter) -> Vec<String> {
-    let tags: Vec<String> = match frontmatter.get("tags") {
-        Some(Value::Array(tags)) => tags
+    get_terms(frontmatter, "tags")
+}
+
+/// Get the terms of a taxonomy from the frontmatter `key`,
+/// either a list `[a, b]` or a comma separated string `a, b`
+pub fn get_terms(frontmatter: &Frontmatter, key: &str) -> Vec<String> {
+    let terms: Vec<String> = match frontmatter.get(key) {
+        Some(Value::Array(terms)) => terms
             .iter()
             .map(Value::to_string)
             .map(|t| t.trim_matches('"').to_string())
             .collect(),
-        Some(Value::String(tags)) => tags.split(',').map(str::trim).map(String::from).collect(),
+        Some(Value::String(terms)) => terms.split(',').map(str::trim).map(String::from).collect(),
         _ => Vec::new(),
     };
 
-    // Remove empty tags but keep original names
-    tags.iter()
-        .filter(|tag| !tag.is_empty())
+    // Remove empty terms but keep original names
+    terms
+        .iter()
+        .filter(|term| !term.is_empty())
         .map(|t| t.trim().to_string())
         .collect()
 }
 
+/// Get the author name from an `authors` entry, that can be either
+/// a plain username or a structured author `{name: X, role: editor}`
+fn get_author_name(value: &Value) -> Option<String> {
+    let name = match value {
+        Value::Object(author) => author.get("name")?.to_string(),
+        other => other.to_string(),
+    };
+    let name = name.trim_matches('"').trim().to_string();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// Authors of the content from `authors` (or `author`), falling back to the
+/// `default_author`, blank and repeated names are skipped so a co-authored
+/// content is listed once on each author page
 pub fn get_authors(frontmatter: &Frontmatter, default_author: Option<String>) -> Vec<String> {
     let mut authors: Vec<String> = match frontmatter.get("authors") {
-        Some(Value::Array(authors)) => authors
-            .iter()
-            .map(Value::to_string)
-            .map(|t| t.trim_matches('"').to_string())
-            .collect(),
+        Some(Value::Array(authors)) => authors.iter().filter_map(get_author_name).collect(),
         Some(Value::String(authors)) => authors
             .split(',')
             .map(str::trim)
+            .filter(|author| !author.is_empty())
             .map(String::from)
             .collect(),
         _ => Vec::new(),
@@ -579,16 +1399,14 @@ pub fn get_authors(frontmatter: &Frontmatter, default_author: Option<String>) ->
     // If authors is empty, try to get single author from frontmatter
     if authors.is_empty() {
         authors = match frontmatter.get("author") {
-            Some(Value::Array(authors)) => authors
-                .iter()
-                .map(Value::to_string)
-                .map(|t| t.trim_matches('"').to_string())
-                .collect(),
+            Some(Value::Array(authors)) => authors.iter().filter_map(get_author_name).collect(),
             Some(Value::String(authors)) => authors
                 .split(',')
                 .map(str::trim)
+                .filter(|author| !author.is_empty())
                 .map(String::from)
                 .collect(),
+            Some(author @ Value::Object(_)) => get_author_name(author).into_iter().collect(),
             _ => Vec::new(),
         };
     }
@@ -599,34 +1417,259 @@ pub fn get_authors(frontmatter: &Frontmatter, default_author: Option<String>) ->
             }
         }
     }
+    let mut seen = HashSet::new();
+    authors.retain(|author| seen.insert(author.clone()));
     authors
 }
 
-/// Tries to get `date` from the front-matter metadata, else from filename
+/// Get the roles of structured authors e.g: `authors: [{name: X, role: editor}]`
+/// returns a map of author name to role, authors without a role are not included
+pub fn get_author_roles(frontmatter: &Frontmatter) -> HashMap<String, String> {
+    let entries = match frontmatter
+        .get("authors")
+        .or_else(|| frontmatter.get("author"))
+    {
+        Some(Value::Array(authors)) => authors.iter().collect::<Vec<_>>(),
+        Some(author @ Value::Object(_)) => vec![author],
+        _ => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let Value::Object(author) = entry else {
+                return None;
+            };
+            let name = get_author_name(entry)?;
+            let role = author
+                .get("role")?
+                .to_string()
+                .trim_matches('"')
+                .to_string();
+            Some((name, role))
+        })
+        .collect()
+}
+
+/// Count the words of the rendered html as `(words, cjk_chars)`, CJK text
+/// (Chinese, Japanese) is counted by character as it has no spaces between words
+pub fn count_words(html: &str, skip_code: bool) -> (usize, usize) {
+    let html = if skip_code {
+        let pre_re = Regex::new(re::MATCH_PRE_BLOCKS).expect("Pre blocks regex should compile");
+        pre_re.replace_all(html, " ")
+    } else {
+        std::borrow::Cow::Borrowed(html)
+    };
+    let tags_re = Regex::new(re::MATCH_HTML_TAGS).expect("HTML tags regex should compile");
+    let entities_re =
+        Regex::new(re::MATCH_HTML_ENTITIES).expect("HTML entities regex should compile");
+    let text = tags_re.replace_all(&html, " ");
+    let text = entities_re.replace_all(&text, " ");
+
+    let mut words = 0;
+    let mut cjk_chars = 0;
+    for token in text.split_whitespace() {
+        let mut has_word = false;
+        for c in token.chars() {
+            if is_cjk(c) {
+                cjk_chars += 1;
+            } else if c.is_alphanumeric() {
+                has_word = true;
+            }
+        }
+        if has_word {
+            words += 1;
+        }
+    }
+    (words, cjk_chars)
+}
+
+/// Han ideographs, hiragana and katakana, hangul is space separated and counted as words
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+/// Minutes to read `words` and `cjk_chars` at the configured speeds, rounded up
+pub fn reading_time(words: usize, cjk_chars: usize, config: &ReadingTimeConfig) -> usize {
+    let words_per_minute = config.words_per_minute.max(1);
+    let cjk_chars_per_minute = config.cjk_chars_per_minute.max(1);
+    (words * cjk_chars_per_minute + cjk_chars * words_per_minute)
+        .div_ceil(words_per_minute * cjk_chars_per_minute)
+        .max(1)
+}
+
+/// Tries to get `date` from the front-matter metadata, else from filename,
+/// an invalid date is an error as the content would silently become a page
 /// Input examples:
 ///   frontmatter = Frontmatter {date: Value("2024-10-10")}
 ///   path = "2024-01-01-myfile.md"
-pub fn get_date(frontmatter: &Frontmatter, path: &Path) -> Option<NaiveDateTime> {
+pub fn get_date(frontmatter: &Frontmatter, path: &Path) -> Result<Option<NaiveDateTime>, String> {
     if let Some(input) = frontmatter.get("date").and_then(|v| v.as_str()) {
-        match try_to_parse_date(input) {
-            Ok(date) => return Some(date),
-            Err(e) => {
-                error!(
-                    "ERROR: Invalid date format {} when parsing {}, {}",
-                    input,
-                    path.display(),
-                    e
-                );
-                process::exit(1);
-            }
+        return try_to_parse_date(input).map(Some).map_err(|e| {
+            format!(
+                "Invalid date format {} when parsing {}, {}",
+                input,
+                path.display(),
+                e
+            )
+        });
+    }
+    Ok(extract_date_from_filename(path))
+}
+
+/// Tries to get the last edit date from `updated` or `last_modified` in the front-matter
+/// Input examples:
+///   frontmatter = Frontmatter {updated: Value("2024-10-10")}
+///   frontmatter = Frontmatter {last_modified: Value("2024-10-10 10:30")}
+pub fn get_updated(frontmatter: &Frontmatter, path: &Path) -> Option<NaiveDateTime> {
+    let input = frontmatter
+        .get("updated")
+        .or_else(|| frontmatter.get("last_modified"))
+        .and_then(|v| v.as_str())?;
+    match try_to_parse_date(input) {
+        Ok(date) => Some(date),
+        Err(e) => {
+            error!(
+                "Invalid updated date format {} when parsing {}, {}",
+                input,
+                path.display(),
+                e
+            );
+            None
         }
     }
-    extract_date_from_filename(path)
+}
+
+/// Tries to get the expiry date from `expires` in the front-matter
+/// Input examples:
+///   frontmatter = Frontmatter {expires: Value("2024-10-10 18:00")}
+pub fn get_expires(frontmatter: &Frontmatter, path: &Path) -> Option<NaiveDateTime> {
+    let input = frontmatter.get("expires").and_then(|v| v.as_str())?;
+    match try_to_parse_date(input) {
+        Ok(date) => Some(date),
+        Err(e) => {
+            error!(
+                "Invalid expires date format {} when parsing {}, {}",
+                input,
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Text before the `<!-- more -->` marker, `None` when the content has no marker
+pub fn split_summary(text: &str) -> Option<&str> {
+    let more_re = Regex::new(re::MATCH_MORE_MARKER).expect("More marker regex should compile");
+    more_re
+        .find(text)
+        .map(|marker| text[..marker.start()].trim_end())
+}
+
+/// First `paragraphs` paragraphs of the html, `None` when `paragraphs` is 0
+/// or the html has no paragraph
+pub fn first_paragraphs(html: &str, paragraphs: usize) -> Option<String> {
+    if paragraphs == 0 {
+        return None;
+    }
+    let p_re = Regex::new(re::MATCH_PARAGRAPHS).expect("Paragraphs regex should compile");
+    let summary: Vec<&str> = p_re
+        .find_iter(html)
+        .take(paragraphs)
+        .map(|p| p.as_str())
+        .collect();
+    (!summary.is_empty()).then(|| summary.join("\n"))
+}
+
+/// Frontmatter keys read by marmite, the other keys go to `extra`
+const FRONTMATTER_KEYS: [&str; 30] = [
+    "title",
+    "description",
+    "lang",
+    "slug",
+    "date",
+    "updated",
+    "last_modified",
+    "expires",
+    "tags",
+    "author",
+    "authors",
+    "stream",
+    "series",
+    "pinned",
+    "toc",
+    "draft",
+    "noindex",
+    "noai",
+    "template",
+    "uuid",
+    "canonical_url",
+    "comments",
+    "glossary",
+    "card_image",
+    "banner_image",
+    "weight",
+    "order",
+    "aliases",
+    "bookmark",
+    "extra",
+];
+
+/// Gets the `extra` map of the front-matter with the keys marmite doesn't use
+/// e.g: `github_repo: foo/bar` is `content.extra.github_repo`, keys of the `extra`
+/// object win over the top level keys, custom taxonomies and aliases are not extra
+pub fn get_extra(frontmatter: &Frontmatter, site: &Marmite) -> HashMap<String, Value> {
+    let taxonomies = site.taxonomy_names();
+    let mut extra: HashMap<String, Value> = frontmatter
+        .iter()
+        .filter(|(key, _)| {
+            !FRONTMATTER_KEYS.contains(&key.as_str())
+                && !taxonomies.contains(key)
+                && !site.frontmatter_aliases.contains_key(key.as_str())
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    if let Some(object) = frontmatter.get("extra").and_then(Value::as_object) {
+        for (key, value) in object.iter() {
+            extra.insert(key.clone(), value.clone());
+        }
+    }
+    extra
+}
+
+/// Gets the integer `weight` or its `order` alias from the front-matter
+/// Input examples:
+///   frontmatter = Frontmatter {weight: Value(10)}
+///   frontmatter = Frontmatter {order: Value("-1")}
+#[allow(clippy::cast_possible_truncation)]
+pub fn get_weight(frontmatter: &Frontmatter) -> Option<i64> {
+    let value = frontmatter
+        .get("weight")
+        .or_else(|| frontmatter.get("order"))?;
+    match value {
+        Value::String(weight) => weight.trim().parse().ok(),
+        _ => value
+            .as_f64()
+            .filter(|weight| weight.fract() == 0.0)
+            .map(|weight| weight as i64),
+    }
+}
+
+/// Orders the content by `weight`, lower first, the content without a weight goes last
+pub fn compare_weight(a: &Content, b: &Content) -> std::cmp::Ordering {
+    match (a.weight, b.weight) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
 }
 
 /// Tries to parse 3 different date formats or return Error.
 /// input: "2024-01-01 15:40:56" | "2024-01-01 15:40" | "2024-01-01"
-fn try_to_parse_date(input: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+pub fn try_to_parse_date(input: &str) -> Result<NaiveDateTime, chrono::ParseError> {
     // Fix input to match the format "2023-02-08 19:03:32" or "2023-02-08 19:03" or "2023-02-08"
     // even if the input is on format 2020-01-19T21:05:12.984Z or 2020-01-19T21:05:12+0000
     let re = Regex::new(re::CAPTURE_DATE_PREFIX_FROM_TEXT)
@@ -674,6 +1717,33 @@ fn extract_date_from_filename(path: &Path) -> Option<NaiveDateTime> {
     None
 }
 
+/// A new random UUID (version 4 format) for the content at `source`,
+/// built from the hash of the source path and the current time
+pub fn new_content_id(source: &str) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let hash = |seed: u8| {
+        let mut hasher = DefaultHasher::new();
+        (seed, source, nanos).hash(&mut hasher);
+        hasher.finish()
+    };
+    let mut id = (u128::from(hash(0)) << 64) | u128::from(hash(1));
+    // version 4 and RFC 4122 variant bits
+    id = (id & !(0xF << 76)) | (0x4 << 76);
+    id = (id & !(0x3 << 62)) | (0x2 << 62);
+    let hex = format!("{id:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
 pub fn check_for_duplicate_slugs(contents: &Vec<&Content>) -> Result<(), String> {
     let mut seen = HashSet::new();
 
@@ -686,6 +1756,60 @@ pub fn check_for_duplicate_slugs(contents: &Vec<&Content>) -> Result<(), String>
     Ok(())
 }
 
+/// Give the contents sharing a slug a unique one following the `strategy`, the
+/// first content of each group (pages first, then the oldest post, then by source
+/// path) keeps its slug so the result doesn't depend on the order the files were read
+pub fn resolve_duplicate_slugs(contents: &mut [Content], strategy: DuplicateSlugs) {
+    if strategy == DuplicateSlugs::Error {
+        return;
+    }
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, content) in contents.iter().enumerate() {
+        groups.entry(content.slug.clone()).or_default().push(i);
+    }
+    let mut taken: HashSet<String> = groups.keys().cloned().collect();
+    let mut duplicates: Vec<(String, Vec<usize>)> = groups
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1)
+        .collect();
+    duplicates.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (slug, mut group) in duplicates {
+        group.sort_by(|&a, &b| {
+            contents[a]
+                .date
+                .cmp(&contents[b].date)
+                .then_with(|| contents[a].source_path.cmp(&contents[b].source_path))
+        });
+        let mut suffix = 0;
+        for &i in &group[1..] {
+            let dated = match (strategy, contents[i].date) {
+                (DuplicateSlugs::Date, Some(date)) => {
+                    Some(format!("{}-{slug}", date.format("%Y-%m-%d")))
+                }
+                _ => None,
+            }
+            .filter(|dated| !taken.contains(dated));
+            let new_slug = dated.unwrap_or_else(|| loop {
+                suffix += 1;
+                let candidate = format!("{slug}-{suffix}");
+                if !taken.contains(&candidate) {
+                    break candidate;
+                }
+            });
+            warn!(
+                "Duplicate slug '{slug}' of {} renamed to '{new_slug}'",
+                contents[i]
+                    .source_path
+                    .as_deref()
+                    .map_or(contents[i].title.clone(), |path| path.display().to_string())
+            );
+            taken.insert(new_slug.clone());
+            contents[i].slug = new_slug;
+        }
+    }
+}
+
 /// Create a new file with the given text as title and slug
 pub fn new(input_folder: &Path, text: &str, cli_args: &Arc<Cli>, config_path: &Path) {
     let content_folder = get_content_folder(&Data::from_file(config_path).site, input_folder);
@@ -0,0 +1,344 @@
+use crate::config::{ContentExtensionsConfig, ExternalCommandsConfig};
+use crate::sidecar::sidecar_path;
+use log::error;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{mpsc, LazyLock, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Limits of the external commands, set from `external_commands` when the
+/// configuration is loaded so every command of the build runs within them
+static COMMAND_LIMITS: LazyLock<RwLock<CommandLimits>> =
+    LazyLock::new(|| RwLock::new(CommandLimits::default()));
+
+/// Sidecar folder holding a folder of cached output per renderer
+const SIDECAR_FOLDER_RENDERS: &str = "renders";
+
+/// Bytes of stderr kept for the error message of a failed command
+const MAX_STDERR: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandLimits {
+    pub timeout: Option<Duration>,
+    pub max_output: Option<usize>,
+    pub working_dir: Option<PathBuf>,
+    /// Environment variables passed to the commands, all of them when `None`
+    pub env: Option<Vec<String>>,
+    /// `.marmite/` folder of the site caching the output of the renderers
+    pub cache_folder: Option<PathBuf>,
+}
+
+impl Default for CommandLimits {
+    fn default() -> Self {
+        CommandLimits::new(&ExternalCommandsConfig::default(), Path::new(""))
+    }
+}
+
+impl CommandLimits {
+    pub fn new(config: &ExternalCommandsConfig, input_folder: &Path) -> Self {
+        CommandLimits {
+            timeout: (config.timeout > 0).then(|| Duration::from_secs(config.timeout)),
+            max_output: (config.max_output > 0).then_some(config.max_output),
+            working_dir: config
+                .working_dir
+                .as_ref()
+                .map(|folder| input_folder.join(folder)),
+            env: (!config.env.is_empty()).then(|| config.env.clone()),
+            cache_folder: (!input_folder.as_os_str().is_empty())
+                .then(|| sidecar_path(input_folder, SIDECAR_FOLDER_RENDERS)),
+        }
+    }
+}
+
+/// Apply the `external_commands` limits to the commands run from now on
+pub fn configure_commands(config: &ExternalCommandsConfig, input_folder: &Path) {
+    let limits = CommandLimits::new(config, input_folder);
+    match COMMAND_LIMITS.write() {
+        Ok(mut current) => *current = limits,
+        Err(e) => error!("Failed to set the external commands limits: {e}"),
+    }
+}
+
+/// Folder caching the output of the `name` renderer, e.g: `math`, inside the
+/// `.marmite/` folder of the site so other users of the machine can't plant the
+/// markup inlined in the pages. `None`, nothing is cached, until a site is configured
+pub fn render_cache_dir(name: &str) -> Option<PathBuf> {
+    COMMAND_LIMITS
+        .read()
+        .ok()?
+        .cache_folder
+        .as_ref()
+        .map(|folder| folder.join(name))
+}
+
+/// Convert the content source file to markdown based on its extension
+/// - `.md` and `.html` files are returned as is
+/// - `.ipynb` files are converted natively (see `notebook`)
+/// - `content_extensions.markdown` files are returned as is, except for `.mdx`
+///   which drops the `import` and `export` lines
+/// - `content_extensions.text` files become a preformatted block
+/// - extensions listed on `input_converters` (e.g: `rst`, `adoc`) are piped
+///   through the configured external command, the frontmatter block is kept
+///   and only the body is sent to the converter.
+pub fn source_to_markdown(
+    path: &Path,
+    source: String,
+    converters: &HashMap<String, String>,
+    extensions: &ContentExtensionsConfig,
+) -> Result<String, String> {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return Ok(source);
+    };
+    if extension == "ipynb" {
+        return crate::notebook::notebook_to_markdown(&source);
+    }
+    if extensions.markdown.iter().any(|ext| ext == extension) {
+        return Ok(if extension == "mdx" {
+            mdx_to_markdown(&source)
+        } else {
+            source
+        });
+    }
+    if extensions.text.iter().any(|ext| ext == extension) {
+        let (frontmatter, body) = split_frontmatter(&source);
+        return Ok(text_to_markdown(frontmatter, body));
+    }
+    let Some(command) = converters.get(extension) else {
+        return Ok(source);
+    };
+    let (frontmatter, body) = split_frontmatter(&source);
+    let converted = run_with_stdin(command, body)
+        .map_err(|e| format!("Failed to convert {} with `{command}`: {e}", path.display()))?;
+    if frontmatter.is_empty() {
+        Ok(converted)
+    } else {
+        Ok(format!("{}\n\n{converted}", frontmatter.trim_end()))
+    }
+}
+
+/// Returns true if the file is a content source that can be converted to markdown
+/// `.html` files are content only when starting with a `---` frontmatter block
+/// so other html files on the content folder are left alone.
+pub fn is_content_source(
+    path: &Path,
+    converters: &HashMap<String, String>,
+    extensions: &ContentExtensionsConfig,
+) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("md" | "ipynb") => true,
+        Some("html") => fs::read_to_string(path).is_ok_and(|html| html.starts_with("---")),
+        Some(ext) => {
+            converters.contains_key(ext)
+                || extensions.markdown.iter().any(|e| e == ext)
+                || extensions.text.iter().any(|e| e == ext)
+        }
+        None => false,
+    }
+}
+
+/// MDX without the components: the `import` and `export` lines outside
+/// of the code blocks are dropped, the rest is markdown
+fn mdx_to_markdown(source: &str) -> String {
+    let mut in_code_block = false;
+    source
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+            }
+            in_code_block || !(line.starts_with("import ") || line.starts_with("export "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Plain text as a preformatted block, the first line becomes the title
+/// when the frontmatter has none
+fn text_to_markdown(frontmatter: &str, body: &str) -> String {
+    let has_title = frontmatter.lines().any(|line| line.starts_with("title:"));
+    let (heading, text) = match body.trim_start_matches(['\n', '\r']).split_once('\n') {
+        Some((title, text)) if !has_title => (format!("# {}\n\n", title.trim()), text),
+        None if !has_title => (format!("# {}\n\n", body.trim()), ""),
+        _ => (String::new(), body),
+    };
+    let text = text.trim_matches(['\n', '\r']);
+    // the fence is longer than any backtick run of the text
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let markdown = format!("{heading}{fence}text\n{text}\n{fence}\n");
+    if frontmatter.is_empty() {
+        markdown
+    } else {
+        format!("{}\n\n{markdown}", frontmatter.trim_end())
+    }
+}
+
+/// Split a `---` delimited frontmatter block from the body of the source
+fn split_frontmatter(source: &str) -> (&str, &str) {
+    let Some(rest) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return ("", source);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return ("", source);
+    };
+    let frontmatter_end = source.len() - rest.len() + end + "\n---".len();
+    let body = source[frontmatter_end..]
+        .split_once('\n')
+        .map_or("", |(_, body)| body);
+    (&source[..frontmatter_end], body)
+}
+
+/// Run the command passing `input` on stdin and return its stdout
+/// the command is split on whitespace, no shell is involved.
+pub fn run_with_stdin(command: &str, input: &str) -> Result<String, String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().ok_or("Empty command")?;
+    let args: Vec<&str> = parts.collect();
+    run_program(program, &args, input)
+}
+
+/// Run `program` passing `input` on stdin and return its stdout, within the
+/// `external_commands` limits: a command running longer than the `timeout` or
+/// writing more than `max_output` is killed
+pub fn run_program<S: AsRef<OsStr>>(
+    program: &str,
+    args: &[S],
+    input: &str,
+) -> Result<String, String> {
+    let limits = COMMAND_LIMITS
+        .read()
+        .map(|limits| limits.clone())
+        .unwrap_or_default();
+    run_limited(program, args, input, &limits)
+}
+
+fn run_limited<S: AsRef<OsStr>>(
+    program: &str,
+    args: &[S],
+    input: &str,
+    limits: &CommandLimits,
+) -> Result<String, String> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(folder) = &limits.working_dir {
+        command.current_dir(folder);
+    }
+    if let Some(names) = &limits.env {
+        command.env_clear().envs(
+            std::env::vars_os().filter(|(key, _)| names.iter().any(|name| key == name.as_str())),
+        );
+    }
+    let started = Instant::now();
+    let mut child = command.spawn().map_err(|e| e.to_string())?;
+
+    // stdin is written and the output read apart so a command filling
+    // one of the pipes doesn't wait forever on the other
+    let stdin = child.stdin.take();
+    let input = input.to_string();
+    thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            // the command may exit without reading all of its input
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+    let stderr = child.stderr.take();
+    let stderr_reader = thread::spawn(move || {
+        let mut text = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = (&mut stderr).take(MAX_STDERR).read_to_end(&mut text);
+            let _ = io::copy(&mut stderr, &mut io::sink());
+        }
+        text
+    });
+    let stdout = child.stdout.take();
+    let max_output = limits.max_output;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(read_output(stdout, max_output));
+    });
+
+    let output = match limits.timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+    let output = match output {
+        Some(Ok(output)) => output,
+        Some(Err(e)) => {
+            kill(&mut child);
+            return Err(e);
+        }
+        None => {
+            kill(&mut child);
+            return Err(timed_out(limits));
+        }
+    };
+    let status = wait(&mut child, limits.timeout.map(|timeout| started + timeout))
+        .ok_or_else(|| timed_out(limits))?;
+    if !status.success() {
+        let stderr = stderr_reader.join().unwrap_or_default();
+        return Err(String::from_utf8_lossy(&stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output).to_string())
+}
+
+/// The stdout of the command, an error once it is larger than `max_output`
+fn read_output(stdout: Option<impl Read>, max_output: Option<usize>) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    let Some(stdout) = stdout else {
+        return Ok(output);
+    };
+    let limit = max_output
+        .and_then(|max| u64::try_from(max).ok())
+        .map_or(u64::MAX, |max| max.saturating_add(1));
+    stdout
+        .take(limit)
+        .read_to_end(&mut output)
+        .map_err(|e| e.to_string())?;
+    match max_output {
+        Some(max) if output.len() > max => Err(format!("Output larger than {max} bytes")),
+        _ => Ok(output),
+    }
+}
+
+/// Wait for the command to exit, `None` when it is killed at the `deadline`
+fn wait(child: &mut Child, deadline: Option<Instant>) -> Option<ExitStatus> {
+    let Some(deadline) = deadline else {
+        return child.wait().ok();
+    };
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+            _ => {
+                kill(child);
+                return None;
+            }
+        }
+    }
+}
+
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn timed_out(limits: &CommandLimits) -> String {
+    format!("Timed out after {:?}", limits.timeout.unwrap_or_default())
+}
+
+#[cfg(test)]
+#[path = "tests/converters.rs"]
+mod tests;
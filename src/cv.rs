@@ -0,0 +1,80 @@
+use crate::config::CvConfig;
+use log::error;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Load the [JSON Resume](https://jsonresume.org/schema) of the cv page,
+/// `.yaml` and `.yml` files are read as YAML with the same structure,
+/// returns `None` if the file doesn't exist or is not a resume object
+pub fn load_resume(path: &Path) -> Option<Value> {
+    if !path.exists() {
+        return None;
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to read resume {}: {e}", path.display());
+            return None;
+        }
+    };
+    let is_yaml = path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+    let resume = if is_yaml {
+        serde_yaml::from_str::<Value>(&text).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str::<Value>(&text).map_err(|e| e.to_string())
+    };
+    match resume {
+        Ok(resume) if resume.is_object() => Some(resume),
+        Ok(_) => {
+            error!("Resume {} must be an object", path.display());
+            None
+        }
+        Err(e) => {
+            error!("Failed to parse resume {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Url of the exported PDF when `pdf_command` is set, e.g: `cv/cv.pdf`
+pub fn pdf_url(config: &CvConfig) -> Option<String> {
+    config
+        .pdf_command
+        .as_ref()
+        .map(|_| format!("{}/{}.pdf", config.slug, config.slug))
+}
+
+/// Arguments of the `pdf_command` split on whitespace with the `{input}`
+/// and `{output}` placeholders replaced by the html and pdf paths
+fn pdf_args(command: &str, input: &Path, output: &Path) -> Vec<String> {
+    command
+        .split_whitespace()
+        .map(|part| {
+            part.replace("{input}", &input.to_string_lossy())
+                .replace("{output}", &output.to_string_lossy())
+        })
+        .collect()
+}
+
+/// Print the rendered cv page at `input` to the `output` PDF with the
+/// `pdf_command`, no shell is involved
+pub fn export_pdf(command: &str, input: &Path, output: &Path) -> Result<(), String> {
+    // the command may run in the `external_commands.working_dir`
+    let input = std::path::absolute(input).map_err(|e| e.to_string())?;
+    let output = std::path::absolute(output).map_err(|e| e.to_string())?;
+    let args = pdf_args(command, &input, &output);
+    let (program, args) = args.split_first().ok_or("Empty cv pdf_command")?;
+    crate::converters::run_program(program, args, "")
+        .map_err(|e| format!("Failed to run `{program}`: {e}"))?;
+    if !output.is_file() {
+        return Err(format!("`{program}` did not write {}", output.display()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/cv.rs"]
+mod tests;
@@ -7,6 +7,10 @@ use std::path::Path;
 use std::sync::LazyLock;
 use tera::Tera;
 
+// The `debug-embed` feature embeds the files on debug builds too, a binary of
+// any profile runs with no supporting files, the example folder is only read
+// when compiling
+
 #[derive(Embed, Debug)]
 #[folder = "$CARGO_MANIFEST_DIR/example/static/"]
 pub struct Static;
@@ -19,10 +23,18 @@ pub struct Templates;
 #[folder = "$CARGO_MANIFEST_DIR/example/theme_template/"]
 pub struct ThemeTemplate;
 
+#[derive(Embed, Debug)]
+#[folder = "$CARGO_MANIFEST_DIR/example/theme_minimal/"]
+pub struct ThemeMinimal;
+
 #[derive(Embed, Debug)]
 #[folder = "$CARGO_MANIFEST_DIR/example/shortcodes/"]
 pub struct Shortcodes;
 
+#[derive(Embed, Debug)]
+#[folder = "$CARGO_MANIFEST_DIR/example/dictionaries/"]
+pub struct Dictionaries;
+
 pub static EMBEDDED_SHORTCODES: LazyLock<Vec<(String, Vec<u8>)>> = LazyLock::new(|| {
     let mut files: Vec<(String, Vec<u8>)> = Vec::new();
 
@@ -0,0 +1,107 @@
+use log::error;
+use serde::Serialize;
+use serde_json::json;
+use std::error::Error;
+use std::fmt;
+use std::process;
+use std::sync::OnceLock;
+
+/// Format of the error reported when marmite exits, `text` or `json` from `--error-format`
+static ERROR_FORMAT: OnceLock<String> = OnceLock::new();
+
+/// Category of the error ending a run, each one exits with its own code so
+/// CI pipelines can branch on it, `2` is left to the invalid arguments of clap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    Other,
+    Config,
+    Content,
+    Template,
+    Io,
+    Links,
+}
+
+impl ErrorKind {
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Config => 3,
+            ErrorKind::Content => 4,
+            ErrorKind::Template => 5,
+            ErrorKind::Io => 6,
+            ErrorKind::Links => 7,
+        }
+    }
+}
+
+/// An error with the category it exits with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl Failure {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Failure {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for Failure {}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::new(ErrorKind::Other, message)
+    }
+}
+
+/// Category of an error returned by a command, io errors not wrapped in a `Failure`
+/// are `io` and any other error is `other`
+pub fn kind_of(error: &(dyn Error + 'static)) -> ErrorKind {
+    if let Some(failure) = error.downcast_ref::<Failure>() {
+        failure.kind
+    } else if error.is::<std::io::Error>() {
+        ErrorKind::Io
+    } else {
+        ErrorKind::Other
+    }
+}
+
+pub fn set_format(format: &str) {
+    let _ = ERROR_FORMAT.set(format.to_string());
+}
+
+/// The error as reported with `--error-format json`, a single line
+pub fn json_report(kind: ErrorKind, message: &str) -> String {
+    json!({
+        "error": kind,
+        "code": kind.code(),
+        "message": message,
+    })
+    .to_string()
+}
+
+/// Report the error in the `--error-format` and exit with the code of its `kind`,
+/// the json report is the last line written to stderr
+pub fn exit(kind: ErrorKind, message: &str) -> ! {
+    if ERROR_FORMAT.get().is_some_and(|format| format == "json") {
+        eprintln!("{}", json_report(kind, message));
+    } else {
+        error!("{message}");
+    }
+    process::exit(kind.code())
+}
+
+#[cfg(test)]
+#[path = "tests/errors.rs"]
+mod tests;
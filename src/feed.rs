@@ -1,22 +1,21 @@
-use log::info;
-use rss::{ChannelBuilder, ItemBuilder};
+use chrono::NaiveDateTime;
+use log::{error, info};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
+use tera::{Context, Tera};
 
 use crate::config::Marmite;
 use crate::content::Content;
+use crate::re;
 
-pub fn generate_rss(
-    contents: &[Content],
-    output_path: &Path,
-    filename: &str,
-    config: &Marmite,
-) -> Result<(), String> {
-    let date_format = "%a, %d %b %Y %H:%M:%S GMT"; // Loose RFC-822 format
+const RSS_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT"; // Loose RFC-822 format
 
-    let feed_url = if !config.url.starts_with("http://") && !config.url.starts_with("https://") {
+/// Returns the site url including the protocol, used as base for feed links
+fn get_feed_url(config: &Marmite) -> String {
+    if !config.url.starts_with("http://") && !config.url.starts_with("https://") {
         let protocol = if config.https.unwrap_or(false) {
             "https://"
         } else {
@@ -25,14 +24,82 @@ pub fn generate_rss(
         format!("{}{}", protocol, &config.url)
     } else {
         config.url.to_string()
-    };
+    }
+}
+
+/// Link of a feed entry, the `canonical_url` of syndicated content
+/// or the generated page under `base_url`
+fn content_link(content: &Content, base_url: &str) -> String {
+    content
+        .canonical_url
+        .clone()
+        .unwrap_or_else(|| format!("{base_url}/{}.html", content.slug))
+}
+
+/// An item of the `rss.xml` template, dates are already formatted
+#[derive(Serialize, Debug)]
+struct RssItem {
+    title: String,
+    link: String,
+    guid: String,
+    guid_permalink: bool,
+    description: Option<String>,
+    content: Option<String>,
+    pub_date: String,
+    /// RFC-3339 date of the last update, rendered as `dc:date`
+    updated: Option<String>,
+    author: Option<String>,
+    categories: Vec<String>,
+    source: Option<String>,
+}
+
+/// Render the `rss.xml` template with the feed items and save it as `{filename}.rss`,
+/// the template can be overridden by the site or the theme to change the
+/// namespaces and add extension elements
+fn write_rss(
+    items: &[RssItem],
+    output_path: &Path,
+    filename: &str,
+    title: &str,
+    config: &Marmite,
+    tera: &Tera,
+) -> Result<(), String> {
+    let feed_url = get_feed_url(config);
+    let mut context = Context::new();
+    context.insert("site", config);
+    context.insert("feed_name", filename);
+    context.insert("feed_title", title);
+    context.insert("feed_link", &feed_url);
+    context.insert("items", items);
+    context.insert("pub_date", &items.first().map(|item| &item.pub_date));
+    context.insert(
+        "last_build_date",
+        &chrono::Utc::now().format(RSS_DATE_FORMAT).to_string(),
+    );
+    context.insert(
+        "image",
+        &(!config.card_image.is_empty()).then(|| format!("{feed_url}/{}", &config.card_image)),
+    );
 
-    let mut channel = ChannelBuilder::default()
-        .title(&config.name)
-        .link(&feed_url)
-        .description(&config.tagline)
-        .generator("marmite".to_string())
-        .build();
+    let rss = tera
+        .render("rss.xml", &context)
+        .map_err(|e| format!("Failed to render rss.xml: {e:?}"))?;
+    let feed_path = output_path.join(format!("{filename}.rss"));
+    let mut file = File::create(&feed_path).map_err(|e| e.to_string())?;
+    file.write_all(rss.as_bytes()).map_err(|e| e.to_string())?;
+    info!("Generated {}", &feed_path.display());
+
+    Ok(())
+}
+
+pub fn generate_rss(
+    contents: &[Content],
+    output_path: &Path,
+    filename: &str,
+    config: &Marmite,
+    tera: &Tera,
+) -> Result<(), String> {
+    let feed_url = get_feed_url(config);
 
     // Filter out content with stream "draft" and content without dates
     let filtered_contents: Vec<&Content> = contents
@@ -46,62 +113,40 @@ pub fn generate_rss(
         })
         .collect();
 
-    for content in filtered_contents.iter().take(15) {
-        // Safe to unwrap here because we filtered for content with dates
-        let content_date = content
-            .date
-            .expect("Content should have date - filtered above");
-        let mut item = ItemBuilder::default()
-            .title(content.title.clone())
-            .link(format!("{}/{}.html", &feed_url, &content.slug))
-            .description(content.description.clone())
-            .guid(
-                rss::GuidBuilder::default()
-                    .value(format!("{}/{}.html", &feed_url, &content.slug))
-                    .build(),
-            )
-            .pub_date(content_date.format(date_format).to_string())
-            .content(content.html.clone())
-            .source(
-                rss::SourceBuilder::default()
-                    .url(&feed_url)
-                    .title(filename.to_string())
-                    .build(),
-            )
-            .build();
-
-        if let Some(author) = content.authors.first() {
-            item.author = Some(author.clone());
-        }
-        item.categories = content
-            .tags
-            .iter()
-            .map(|tag| rss::CategoryBuilder::default().name(tag.clone()).build())
-            .collect();
-        channel.items.push(item);
-    }
-
-    if let Some(latest_item) = channel.items.first() {
-        channel.pub_date = latest_item.pub_date.clone();
-    }
-
-    channel.last_build_date = Some(chrono::Utc::now().format(date_format).to_string());
-
-    if !config.card_image.is_empty() {
-        channel.image = Some(
-            rss::ImageBuilder::default()
-                .url(format!("{}/{}", &feed_url, &config.card_image))
-                .build(),
-        );
-    }
-
-    let rss = channel.to_string();
-    let feed_path = output_path.join(format!("{filename}.rss"));
-    let mut file = File::create(&feed_path).map_err(|e| e.to_string())?;
-    file.write_all(rss.as_bytes()).map_err(|e| e.to_string())?;
-    info!("Generated {}", &feed_path.display());
+    let items: Vec<RssItem> = filtered_contents
+        .iter()
+        .take(15)
+        .map(|content| {
+            // Safe to unwrap here because we filtered for content with dates
+            let content_date = content
+                .date
+                .expect("Content should have date - filtered above");
+            let link = content_link(content, &feed_url);
+            RssItem {
+                title: content.title.clone(),
+                guid: content
+                    .uuid
+                    .as_ref()
+                    .map_or_else(|| link.clone(), |uuid| format!("urn:uuid:{uuid}")),
+                guid_permalink: content.uuid.is_none(),
+                link,
+                description: content
+                    .summary_html
+                    .clone()
+                    .or_else(|| content.description.clone()),
+                content: Some(content.html.clone()),
+                pub_date: content_date.format(RSS_DATE_FORMAT).to_string(),
+                updated: content
+                    .updated
+                    .map(|updated| updated.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+                author: content.authors.first().cloned(),
+                categories: content.tags.clone(),
+                source: Some(filename.to_string()),
+            }
+        })
+        .collect();
 
-    Ok(())
+    write_rss(&items, output_path, filename, &config.name, config, tera)
 }
 
 /// Struct to represent a JSON feed for a Content
@@ -127,6 +172,8 @@ pub struct JsonFeedItem {
     // content_text: String,
     summary: String,
     date_published: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<String>,
     image: String,
     authors: Vec<JsonFeedAuthor>,
     tags: Vec<String>,
@@ -165,21 +212,33 @@ pub fn generate_json(
         })
         .collect();
 
+    // JSON feed summary is plain text
+    let tags_re = Regex::new(re::MATCH_HTML_TAGS).expect("HTML tags regex should compile");
     for content in filtered_contents.iter().take(15) {
         // Safe to unwrap here because we filtered for content with dates
         let content_date = content
             .date
             .expect("Content should have date - filtered above");
+        let url = content_link(content, &config.url);
         let item = JsonFeedItem {
-            id: format!("{}/{}.html", &config.url, &content.slug),
-            url: format!("{}/{}.html", &config.url, &content.slug),
+            id: content
+                .uuid
+                .as_ref()
+                .map_or_else(|| url.clone(), |uuid| format!("urn:uuid:{uuid}")),
+            url,
             title: content.title.clone(),
             content_html: content.html.clone(),
             // content_text: content.html.clone(), // requires stripping HTML tags
-            summary: content.description.clone().unwrap_or(String::new()),
+            summary: content.summary_html.as_deref().map_or_else(
+                || content.description.clone().unwrap_or(String::new()),
+                |summary| tags_re.replace_all(summary, "").trim().to_string(),
+            ),
             // date_published: content.date.unwrap().to_string(),
             // date published should be in RFC-822 format
             date_published: content_date.format(date_format).to_string(),
+            date_modified: content
+                .updated
+                .map(|updated| updated.format(date_format).to_string()),
             image: content.card_image.clone().unwrap_or(String::new()),
             authors: content
                 .authors
@@ -233,6 +292,192 @@ pub fn generate_json(
     Ok(())
 }
 
+/// An entry of the activity feed, either a backlink created by one of the
+/// site posts or a webmention received from an external site.
+#[derive(Serialize, Debug, Clone)]
+pub struct ActivityItem {
+    pub title: String,
+    pub link: String,
+    pub source: String,
+    pub target: String,
+    pub description: String,
+    pub date: NaiveDateTime,
+}
+
+/// Collect one activity item for every internal link between two contents,
+/// the item is dated by the content that creates the link.
+pub fn collect_backlink_activity(contents: &[Content]) -> Vec<ActivityItem> {
+    let mut items = Vec::new();
+    for source in contents {
+        if source
+            .stream
+            .as_ref()
+            .is_some_and(|stream| stream == "draft")
+        {
+            continue;
+        }
+        let Some(links_to) = &source.links_to else {
+            continue;
+        };
+        let Some(date) = source.date.or_else(|| {
+            source
+                .modified_time
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|dt| dt.naive_utc())
+        }) else {
+            continue;
+        };
+        let mut seen = Vec::new();
+        for link in links_to {
+            let target_slug = link.split('#').next().unwrap_or_default();
+            if target_slug == source.slug || seen.contains(&target_slug) {
+                continue;
+            }
+            let Some(target) = contents.iter().find(|c| c.slug == target_slug) else {
+                continue;
+            };
+            seen.push(target_slug);
+            items.push(ActivityItem {
+                title: format!("{} linked to {}", source.title, target.title),
+                link: format!("{}.html", source.slug),
+                source: format!("{}.html", source.slug),
+                target: format!("{}.html", target.slug),
+                description: source.description.clone().unwrap_or_default(),
+                date,
+            });
+        }
+    }
+    items
+}
+
+/// Load received webmentions from a JSON file in the jf2 format exported
+/// by services like webmention.io, e.g: `{"children": [{"wm-target": ..}]}`
+pub fn load_webmentions(path: &Path) -> Vec<ActivityItem> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) => {
+            error!("Failed to read webmentions file {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    let json: serde_json::Value = match serde_json::from_str(&data) {
+        Ok(json) => json,
+        Err(e) => {
+            error!("Failed to parse webmentions file {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    let entries = json
+        .get("children")
+        .and_then(|c| c.as_array())
+        .or_else(|| json.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let source = entry
+                .get("wm-source")
+                .or_else(|| entry.get("url"))
+                .and_then(|v| v.as_str())?;
+            let target = entry.get("wm-target").and_then(|v| v.as_str())?;
+            let date = entry
+                .get("wm-received")
+                .or_else(|| entry.get("published"))
+                .and_then(|v| v.as_str())
+                .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.naive_utc())?;
+            let author = entry
+                .get("author")
+                .and_then(|a| a.get("name"))
+                .and_then(|n| n.as_str())
+                .filter(|n| !n.is_empty())
+                .unwrap_or(source);
+            let action = match entry.get("wm-property").and_then(|v| v.as_str()) {
+                Some("like-of") => "liked",
+                Some("repost-of") => "reposted",
+                Some("in-reply-to") => "replied to",
+                Some("bookmark-of") => "bookmarked",
+                _ => "mentioned",
+            };
+            let description = entry
+                .get("content")
+                .and_then(|c| c.get("text"))
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string();
+            Some(ActivityItem {
+                title: format!("{author} {action} {target}"),
+                link: source.to_string(),
+                source: source.to_string(),
+                target: target.to_string(),
+                description,
+                date,
+            })
+        })
+        .collect()
+}
+
+/// Generate the activity feed with the latest backlinks and webmentions
+/// saves the feed to the output path with the given filename and extension .rss
+pub fn generate_activity_rss(
+    items: &[ActivityItem],
+    output_path: &Path,
+    filename: &str,
+    config: &Marmite,
+    tera: &Tera,
+) -> Result<(), String> {
+    let title = format!("{} - Activity", &config.name);
+    generate_items_rss(items, output_path, filename, &title, config, tera)
+}
+
+/// Generate a feed titled `title` with the latest 30 items, relative links
+/// are resolved against the site url
+pub fn generate_items_rss(
+    items: &[ActivityItem],
+    output_path: &Path,
+    filename: &str,
+    title: &str,
+    config: &Marmite,
+    tera: &Tera,
+) -> Result<(), String> {
+    let feed_url = get_feed_url(config);
+
+    let mut sorted_items: Vec<&ActivityItem> = items.iter().collect();
+    sorted_items.sort_by(|a, b| b.date.cmp(&a.date));
+
+    let rss_items: Vec<RssItem> = sorted_items
+        .iter()
+        .take(30)
+        .map(|activity| {
+            let link = if activity.link.starts_with("http") {
+                activity.link.clone()
+            } else {
+                format!("{}/{}", &feed_url, &activity.link)
+            };
+            RssItem {
+                title: activity.title.clone(),
+                guid: format!("{link}#{}", &activity.target),
+                guid_permalink: false,
+                link,
+                description: Some(activity.description.clone()),
+                content: None,
+                pub_date: activity.date.format(RSS_DATE_FORMAT).to_string(),
+                updated: None,
+                author: None,
+                categories: Vec::new(),
+                source: None,
+            }
+        })
+        .collect();
+
+    write_rss(&rss_items, output_path, filename, title, config, tera)
+}
+
 #[cfg(test)]
 #[path = "tests/feed.rs"]
 mod tests;
@@ -0,0 +1,94 @@
+use crate::re;
+use log::{debug, error};
+use regex::Regex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Replace the code fences of the configured languages with the SVG generated
+/// by an external renderer, e.g: `dot: dot -Tsvg` receives the fence source on
+/// stdin and must write the SVG to stdout.
+/// Fences that fail to render are kept as regular code blocks.
+pub fn render_fences(html: &str, renderers: &HashMap<String, String>) -> String {
+    if renderers.is_empty() {
+        return html.to_string();
+    }
+    let re = Regex::new(re::CAPTURE_LANGUAGE_AND_CODE_FROM_PRE)
+        .expect("Code fence regex should compile");
+    let cache_dir = crate::converters::render_cache_dir("fences");
+    re.replace_all(html, |caps: &regex::Captures| {
+        let lang = &caps[1];
+        let Some(command) = renderers.get(lang) else {
+            return caps[0].to_string();
+        };
+        let source = unescape_html(&caps[2]);
+        match render_fence(command, &source, cache_dir.as_deref()) {
+            Ok(svg) => format!(r#"<div class="fence-render fence-{lang}">{svg}</div>"#),
+            Err(e) => {
+                error!("Failed to render `{lang}` fence with `{command}`: {e}");
+                caps[0].to_string()
+            }
+        }
+    })
+    .to_string()
+}
+
+/// Wrap the `mermaid` and `dot` (or `graphviz`) fences not rendered by a
+/// `fence_renderers` command in the containers rendered on the browser,
+/// `<pre class="mermaid">` for MermaidJS and `<pre class="graphviz">` for Viz.js.
+/// The source is kept html escaped so the text of the container is the diagram
+pub fn diagram_containers(html: &str) -> String {
+    let re = Regex::new(re::CAPTURE_LANGUAGE_AND_CODE_FROM_PRE)
+        .expect("Code fence regex should compile");
+    re.replace_all(html, |caps: &regex::Captures| match &caps[1] {
+        "mermaid" => format!(r#"<pre class="mermaid">{}</pre>"#, &caps[2]),
+        "dot" | "graphviz" => format!(r#"<pre class="graphviz">{}</pre>"#, &caps[2]),
+        _ => caps[0].to_string(),
+    })
+    .to_string()
+}
+
+/// Rendered SVGs are cached in `.marmite/renders/fences/` by the hash of the
+/// command and fence source so unchanged diagrams don't spawn the renderer on
+/// every rebuild
+fn render_fence(command: &str, source: &str, cache_dir: Option<&Path>) -> Result<String, String> {
+    let mut hasher = DefaultHasher::new();
+    command.hash(&mut hasher);
+    source.hash(&mut hasher);
+    let cache_path = cache_dir.map(|dir| dir.join(format!("{:016x}.svg", hasher.finish())));
+    if let Some(cache_path) = &cache_path {
+        if let Ok(svg) = fs::read_to_string(cache_path) {
+            debug!("Using cached fence render {}", cache_path.display());
+            return Ok(svg);
+        }
+    }
+
+    let output = crate::converters::run_with_stdin(command, source)?;
+
+    // Remove the xml prolog and doctype so the svg can be inlined in the html
+    let svg = output
+        .find("<svg")
+        .map(|start| output[start..].trim().to_string())
+        .ok_or("Renderer output is not an SVG")?;
+
+    if let (Some(cache_dir), Some(cache_path)) = (cache_dir, cache_path) {
+        if let Err(e) = fs::create_dir_all(cache_dir).and_then(|()| fs::write(&cache_path, &svg)) {
+            debug!("Failed to cache fence render {}: {e}", cache_path.display());
+        }
+    }
+    Ok(svg)
+}
+
+pub fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+#[path = "tests/fences.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+use crate::content::Content;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// A post that was not updated within the configured number of months
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StaleContent {
+    pub title: String,
+    pub slug: String,
+    pub last_updated: NaiveDateTime,
+    pub months: i64,
+    /// Number of posts and pages linking to this content
+    pub inbound_links: usize,
+}
+
+/// The latest of the content date, its `updated` date and the source file modification time
+pub fn last_updated(content: &Content) -> Option<NaiveDateTime> {
+    let modified = content
+        .modified_time
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.naive_utc());
+    let modified = match (content.updated, modified) {
+        (Some(updated), Some(modified)) => Some(updated.max(modified)),
+        (updated, modified) => updated.or(modified),
+    };
+    match (content.date, modified) {
+        (Some(date), Some(modified)) => Some(date.max(modified)),
+        (date, modified) => date.or(modified),
+    }
+}
+
+/// Posts not updated in `months`, the most linked content comes first
+/// as it is the most valuable to keep evergreen, then the oldest
+pub fn collect_stale(posts: &[Content], months: usize, now: NaiveDateTime) -> Vec<StaleContent> {
+    let mut stale: Vec<StaleContent> = posts
+        .iter()
+        .filter(|post| post.stream.as_deref() != Some("draft"))
+        .filter_map(|post| {
+            let last_updated = last_updated(post)?;
+            let age = (now - last_updated).num_days() / 30;
+            if age < i64::try_from(months).unwrap_or(i64::MAX) {
+                return None;
+            }
+            Some(StaleContent {
+                title: post.title.clone(),
+                slug: post.slug.clone(),
+                last_updated,
+                months: age,
+                inbound_links: post.back_links.len(),
+            })
+        })
+        .collect();
+    stale.sort_by(|a, b| {
+        b.inbound_links
+            .cmp(&a.inbound_links)
+            .then(b.months.cmp(&a.months))
+            .then(a.title.cmp(&b.title))
+    });
+    stale
+}
+
+/// Render the report as a plain text table for the terminal
+pub fn format_table(stale: &[StaleContent]) -> String {
+    let slug_width = stale
+        .iter()
+        .map(|s| s.slug.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut table = format!(
+        "{:>6}  {:>5}  {:<10}  {:<slug_width$}  Title\n",
+        "Months", "Links", "Updated", "Slug"
+    );
+    for item in stale {
+        let _ = writeln!(
+            table,
+            "{:>6}  {:>5}  {:<10}  {:<slug_width$}  {}",
+            item.months,
+            item.inbound_links,
+            item.last_updated.format("%Y-%m-%d"),
+            item.slug,
+            item.title
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+#[path = "tests/freshness.rs"]
+mod tests;
@@ -0,0 +1,249 @@
+use crate::config::FeedGuid;
+use crate::content::new_content_id;
+use crate::sidecar::{load_sidecar, sidecar_path};
+use crate::site::{get_content_folder, Data};
+use crate::spellcheck::collect_markdown_files;
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Line based editor for the YAML frontmatter of a markdown source, the lines
+/// of the keys that are not changed are kept as written, including comments
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterEditor {
+    lines: Vec<String>,
+    body: String,
+    newline: &'static str,
+}
+
+impl FrontmatterEditor {
+    /// Split the source into the frontmatter lines and the body, a source without
+    /// frontmatter gets an empty one, only YAML (`---`) frontmatter can be edited
+    pub fn parse(source: &str) -> Result<Self, String> {
+        if source.starts_with("+++") || source.starts_with('{') {
+            return Err("only YAML frontmatter can be rewritten".to_string());
+        }
+        let newline = if source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let Some(rest) = source
+            .strip_prefix("---\r\n")
+            .or_else(|| source.strip_prefix("---\n"))
+        else {
+            return Ok(Self {
+                lines: Vec::new(),
+                body: source.to_string(),
+                newline,
+            });
+        };
+
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            offset += line.len();
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line == "---" || line == "..." {
+                return Ok(Self {
+                    lines,
+                    body: rest[offset..].to_string(),
+                    newline,
+                });
+            }
+            lines.push(line.to_string());
+        }
+        Err("frontmatter is not closed with `---`".to_string())
+    }
+
+    /// Index of the first and after the last line of a top level `key`,
+    /// including its indented or list continuation lines
+    fn key_range(&self, key: &str) -> Option<(usize, usize)> {
+        let start = self
+            .lines
+            .iter()
+            .position(|line| split_field(line).is_some_and(|(k, _, _)| k == key))?;
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|line| !line.starts_with([' ', '\t', '-']))
+            .map_or(self.lines.len(), |position| start + 1 + position);
+        Some((start, end))
+    }
+
+    /// The inline value of `key` without quotes, `None` if the key is missing
+    /// or its value is a block e.g: a list on the following lines
+    pub fn get(&self, key: &str) -> Option<String> {
+        let (start, _) = self.key_range(key)?;
+        let (_, _, value) = split_field(&self.lines[start])?;
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    /// Set `key` to `value`, replacing the lines of the key in place or
+    /// appending it to the frontmatter, the quote style of the key is kept
+    pub fn set(&mut self, key: &str, value: &str) {
+        if let Some((start, end)) = self.key_range(key) {
+            let quote = split_field(&self.lines[start]).map_or("", |(_, quote, _)| quote);
+            let line = format!("{key}: {}", format_value(value, quote));
+            self.lines.splice(start..end, [line]);
+        } else {
+            self.lines
+                .push(format!("{key}: {}", format_value(value, "")));
+        }
+    }
+
+    /// Rename `from` to `to` keeping its value, a key already named `to` is never
+    /// overwritten, returns whether the key was renamed
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        if self.key_range(to).is_some() {
+            return false;
+        }
+        let Some((start, _)) = self.key_range(from) else {
+            return false;
+        };
+        let line = &self.lines[start];
+        let colon = line.find(':').unwrap_or(line.len());
+        self.lines[start] = format!("{to}{}", &line[colon..]);
+        true
+    }
+
+    /// Remove `key` and its continuation lines, returns whether the key was found
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some((start, end)) = self.key_range(key) else {
+            return false;
+        };
+        self.lines.drain(start..end);
+        true
+    }
+
+    /// The markdown after the frontmatter
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn set_body(&mut self, body: String) {
+        self.body = body;
+    }
+
+    /// The source with the edited frontmatter, a source that had no frontmatter
+    /// only gets one when a key was set
+    pub fn to_source(&self) -> String {
+        if self.lines.is_empty() {
+            return self.body.clone();
+        }
+        let newline = self.newline;
+        format!(
+            "---{newline}{}{newline}---{newline}{}",
+            self.lines.join(newline),
+            self.body
+        )
+    }
+}
+
+/// Split a frontmatter line into key, quote and value e.g: `title: "Hello"`
+pub fn split_field(line: &str) -> Option<(&str, &str, &str)> {
+    if line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    let (key, value) = line.split_once(':')?;
+    let value = value.trim();
+    for quote in ["\"", "'"] {
+        if value.len() > 1 && value.starts_with(quote) && value.ends_with(quote) {
+            return Some((key.trim(), quote, &value[1..value.len() - 1]));
+        }
+    }
+    Some((key.trim(), "", value))
+}
+
+/// Format `value` as a YAML scalar, keeping `quote` when possible, plain
+/// when it can't be mistaken for another type or syntax, else double quoted
+fn format_value(value: &str, quote: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value != value.trim()
+        || value.starts_with([
+            '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`', '[', ']', '{', '}', ',', '?',
+            '-', ':',
+        ])
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || matches!(
+            value.to_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+        );
+    match quote {
+        "'" if !value.contains('\'') => format!("'{value}'"),
+        "" if !needs_quotes => value.to_string(),
+        _ => format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
+
+/// Handle the `--normalize-frontmatter` command, renames the `frontmatter_aliases`
+/// keys to their canonical names and, when `feed_guid: uuid` is set, writes the
+/// content uuid (from `.marmite/content-ids.json` or a new one) as `uuid:`
+pub fn run(
+    config_path: &Arc<PathBuf>,
+    input_folder: &Arc<PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) -> Result<(), String> {
+    let mut site_data = Data::from_file(config_path.as_path());
+    site_data.site.override_from_cli_args(args);
+    let site = &site_data.site;
+    let content_folder = get_content_folder(site, input_folder.as_path());
+    let content_ids: BTreeMap<String, String> =
+        load_sidecar(&sidecar_path(input_folder.as_path(), "content-ids.json"));
+    let mut aliases: Vec<(&String, &String)> = site.frontmatter_aliases.iter().collect();
+    aliases.sort();
+
+    let files = collect_markdown_files(&content_folder);
+    let mut changed = 0;
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut editor = match FrontmatterEditor::parse(&text) {
+            Ok(editor) => editor,
+            Err(e) => {
+                warn!("Skipping {}: {e}", path.display());
+                continue;
+            }
+        };
+        for (alias, canonical) in &aliases {
+            editor.rename(alias, canonical);
+        }
+        if site.feed_guid == FeedGuid::Uuid && editor.get("uuid").is_none() && !is_fragment(path) {
+            let source = path
+                .strip_prefix(&content_folder)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let uuid = content_ids
+                .get(&source)
+                .cloned()
+                .unwrap_or_else(|| new_content_id(&source));
+            editor.set("uuid", &uuid);
+        }
+        let normalized = editor.to_source();
+        if normalized != text {
+            fs::write(path, &normalized)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            info!("Normalized frontmatter of {}", path.display());
+            changed += 1;
+        }
+    }
+    println!(
+        "Normalized the frontmatter of {changed} of {} files",
+        files.len()
+    );
+    Ok(())
+}
+
+fn is_fragment(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('_'))
+}
+
+#[cfg(test)]
+#[path = "tests/frontmatter.rs"]
+mod tests;
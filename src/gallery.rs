@@ -1,4 +1,4 @@
-use image::{imageops::FilterType, ImageError};
+use image::{imageops::FilterType, ImageError, ImageReader, Limits};
 use log::{error, info};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -8,6 +8,11 @@ use std::path::Path;
 use std::sync::Arc;
 use walkdir::WalkDir;
 
+/// Largest allocation decoding an image with `low_memory`, enough for a 48
+/// megapixel RGBA photo or a 24 megapixel one with 16 bit channels, the bigger
+/// images keep the original as thumbnail instead of growing the process memory
+const LOW_MEMORY_MAX_ALLOC: u64 = 256 * 1024 * 1024;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GalleryItem {
     pub thumb: String,
@@ -50,11 +55,14 @@ struct GalleryConfig {
     images: Option<Vec<ImageDescription>>,
 }
 
+/// Galleries of the subfolders of `gallery_path`, with `low_memory` the
+/// galleries and their images are processed one at a time
 pub fn process_galleries(
     media_path: &Path,
     gallery_path: &str,
     create_thumbnails: bool,
     thumb_size: u32,
+    low_memory: bool,
 ) -> HashMap<String, Gallery> {
     let gallery_dir = media_path.join(gallery_path);
     info!("Processing galleries from: {}", gallery_dir.display());
@@ -77,24 +85,32 @@ pub fn process_galleries(
         }
     };
 
-    let galleries: HashMap<String, Gallery> = entries
-        .par_iter()
-        .filter_map(|entry| {
-            let path = entry.path();
-            if !path.is_dir() {
-                return None;
-            }
+    let gallery_of = |entry: &fs::DirEntry| {
+        let path = entry.path();
+        if !path.is_dir() {
+            return None;
+        }
 
-            let folder_name = path.file_name().and_then(|n| n.to_str())?;
-            let gallery = process_single_gallery(&path, folder_name, create_thumbnails, thumb_size);
-            info!(
-                "Found gallery: {} with {} files",
-                folder_name,
-                gallery.files.len()
-            );
-            Some((folder_name.to_string(), gallery))
-        })
-        .collect();
+        let folder_name = path.file_name().and_then(|n| n.to_str())?;
+        let gallery = process_single_gallery(
+            &path,
+            folder_name,
+            create_thumbnails,
+            thumb_size,
+            low_memory,
+        );
+        info!(
+            "Found gallery: {} with {} files",
+            folder_name,
+            gallery.files.len()
+        );
+        Some((folder_name.to_string(), gallery))
+    };
+    let galleries: HashMap<String, Gallery> = if low_memory {
+        entries.iter().filter_map(gallery_of).collect()
+    } else {
+        entries.par_iter().filter_map(gallery_of).collect()
+    };
 
     info!("Total galleries found: {}", galleries.len());
     galleries
@@ -105,6 +121,7 @@ fn process_single_gallery(
     folder_name: &str,
     create_thumbnails: bool,
     thumb_size: u32,
+    low_memory: bool,
 ) -> Gallery {
     let config_path = gallery_path.join("gallery.yaml");
     let config = Arc::new(load_gallery_config(&config_path));
@@ -125,31 +142,33 @@ fn process_single_gallery(
         .collect();
 
     let config_clone = Arc::clone(&config);
-    let mut files: Vec<GalleryItem> = image_entries
-        .par_iter()
-        .filter_map(|entry| {
-            let path = entry.path();
-            let filename = path.file_name().and_then(|n| n.to_str())?;
-
-            // Skip thumbnails directory
-            if path.parent()? == thumbnails_dir {
-                return None;
-            }
+    let item_of = |entry: &walkdir::DirEntry| {
+        let path = entry.path();
+        let filename = path.file_name().and_then(|n| n.to_str())?;
 
-            let thumb_name = if create_thumbnails {
-                generate_thumbnail(path, &thumbnails_dir, thumb_size)
-                    .unwrap_or_else(|| filename.to_string())
-            } else {
-                filename.to_string()
-            };
+        // Skip thumbnails directory
+        if path.parent()? == thumbnails_dir {
+            return None;
+        }
+
+        let thumb_name = if create_thumbnails {
+            generate_thumbnail(path, &thumbnails_dir, thumb_size, low_memory)
+                .unwrap_or_else(|| filename.to_string())
+        } else {
+            filename.to_string()
+        };
 
-            Some(GalleryItem {
-                thumb: format!("thumbnails/{thumb_name}"),
-                image: filename.to_string(),
-                description: get_description(filename, &config_clone),
-            })
+        Some(GalleryItem {
+            thumb: format!("thumbnails/{thumb_name}"),
+            image: filename.to_string(),
+            description: get_description(filename, &config_clone),
         })
-        .collect();
+    };
+    let mut files: Vec<GalleryItem> = if low_memory {
+        image_entries.iter().filter_map(item_of).collect()
+    } else {
+        image_entries.par_iter().filter_map(item_of).collect()
+    };
 
     let ord = config.ord.unwrap_or_default();
 
@@ -238,7 +257,7 @@ fn get_description(filename: &str, config: &GalleryConfig) -> Option<String> {
     None
 }
 
-fn is_image_file(path: &Path) -> bool {
+pub fn is_image_file(path: &Path) -> bool {
     let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
         return false;
     };
@@ -249,7 +268,12 @@ fn is_image_file(path: &Path) -> bool {
     )
 }
 
-fn generate_thumbnail(image_path: &Path, thumbnails_dir: &Path, size: u32) -> Option<String> {
+fn generate_thumbnail(
+    image_path: &Path,
+    thumbnails_dir: &Path,
+    size: u32,
+    low_memory: bool,
+) -> Option<String> {
     let filename = image_path.file_name().and_then(|n| n.to_str())?;
 
     let thumb_path = thumbnails_dir.join(filename);
@@ -258,7 +282,7 @@ fn generate_thumbnail(image_path: &Path, thumbnails_dir: &Path, size: u32) -> Op
         return Some(filename.to_string());
     }
 
-    match create_thumbnail(image_path, &thumb_path, size) {
+    match create_thumbnail(image_path, &thumb_path, size, low_memory) {
         Ok(()) => {
             info!("Created thumbnail: {}", thumb_path.display());
             Some(filename.to_string())
@@ -274,8 +298,19 @@ fn generate_thumbnail(image_path: &Path, thumbnails_dir: &Path, size: u32) -> Op
     }
 }
 
-fn create_thumbnail(input_path: &Path, output_path: &Path, size: u32) -> Result<(), ImageError> {
-    let img = image::open(input_path)?;
+fn create_thumbnail(
+    input_path: &Path,
+    output_path: &Path,
+    size: u32,
+    low_memory: bool,
+) -> Result<(), ImageError> {
+    let mut reader = ImageReader::open(input_path)?;
+    if low_memory {
+        let mut limits = Limits::default();
+        limits.max_alloc = Some(LOW_MEMORY_MAX_ALLOC);
+        reader.limits(limits);
+    }
+    let img = reader.decode()?;
     let thumbnail = img.resize(size, size, FilterType::Nearest);
     thumbnail.save(output_path)?;
     Ok(())
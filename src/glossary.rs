@@ -0,0 +1,147 @@
+use crate::re;
+use log::error;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Elements whose text is never turned into a glossary link, `dt` are the terms
+/// of the definition lists, defined right there
+const SKIP_ELEMENTS: [&str; 13] = [
+    "a", "abbr", "code", "pre", "script", "style", "h1", "h2", "h3", "h4", "h5", "h6", "dt",
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GlossaryTerm {
+    pub term: String,
+    pub slug: String,
+    pub definition: String,
+}
+
+/// Load the glossary `term: definition` mapping sorted alphabetically,
+/// returns an empty list if the file doesn't exist
+pub fn load_glossary(path: &Path) -> Vec<GlossaryTerm> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to read glossary {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    match serde_yaml::from_str::<HashMap<String, String>>(&text) {
+        Ok(entries) => {
+            let mut terms: Vec<GlossaryTerm> = entries
+                .into_iter()
+                .map(|(term, definition)| GlossaryTerm {
+                    slug: slug::slugify(&term),
+                    definition: definition.trim().to_string(),
+                    term,
+                })
+                .collect();
+            terms.sort_by_key(|t| t.term.to_lowercase());
+            terms
+        }
+        Err(e) => {
+            error!("Failed to parse glossary {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Link the first occurrence of each term to its entry on the glossary page,
+/// the definition is exposed as a tooltip through `<abbr title>`.
+/// Text inside links, code, headings and existing abbreviations is left untouched.
+pub fn link_glossary_terms(html: &str, terms: &[GlossaryTerm], glossary_slug: &str) -> String {
+    let matchers: Vec<(&GlossaryTerm, Regex)> = terms
+        .iter()
+        .filter_map(|term| {
+            RegexBuilder::new(&format!(r"\b{}\b", regex::escape(&term.term)))
+                .case_insensitive(true)
+                .build()
+                .ok()
+                .map(|matcher| (term, matcher))
+        })
+        .collect();
+    let tag_re = Regex::new(re::MATCH_HTML_TAGS).expect("HTML tags regex should compile");
+
+    let mut linked = vec![false; matchers.len()];
+    let mut skip_depth = 0usize;
+    let mut result = String::with_capacity(html.len());
+    let mut last = 0;
+
+    for tag in tag_re.find_iter(html) {
+        let text = &html[last..tag.start()];
+        if skip_depth == 0 {
+            result.push_str(&link_text(text, &matchers, &mut linked, glossary_slug));
+        } else {
+            result.push_str(text);
+        }
+        let tag_text = tag.as_str();
+        let name = tag_text
+            .trim_start_matches('<')
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if SKIP_ELEMENTS.contains(&name.as_str()) {
+            if tag_text.starts_with("</") {
+                skip_depth = skip_depth.saturating_sub(1);
+            } else {
+                skip_depth += 1;
+            }
+        }
+        result.push_str(tag_text);
+        last = tag.end();
+    }
+    let text = &html[last..];
+    if skip_depth == 0 {
+        result.push_str(&link_text(text, &matchers, &mut linked, glossary_slug));
+    } else {
+        result.push_str(text);
+    }
+    result
+}
+
+fn link_text(
+    text: &str,
+    matchers: &[(&GlossaryTerm, Regex)],
+    linked: &mut [bool],
+    glossary_slug: &str,
+) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    loop {
+        // earliest match wins, longer terms win on ties e.g: `Static Site` over `Site`
+        let next = matchers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !linked[*index])
+            .filter_map(|(index, (term, matcher))| matcher.find(rest).map(|m| (index, term, m)))
+            .min_by_key(|(_, _, m)| (m.start(), usize::MAX - m.len()));
+        let Some((index, term, found)) = next else {
+            break;
+        };
+        linked[index] = true;
+        result.push_str(&rest[..found.start()]);
+        let _ = write!(
+            result,
+            r#"<a class="glossary-term" href="{glossary_slug}.html#{}"><abbr title="{}">{}</abbr></a>"#,
+            term.slug,
+            term.definition.replace('"', "&quot;"),
+            found.as_str()
+        );
+        rest = &rest[found.end()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+#[path = "tests/glossary.rs"]
+mod tests;
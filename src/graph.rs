@@ -0,0 +1,136 @@
+use crate::content::Content;
+use crate::site::Data;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Damping factor of the centrality, the chance of following a link
+/// instead of jumping to a random content
+const DAMPING: f64 = 0.85;
+const ITERATIONS: usize = 50;
+
+/// Position of a content on the internal link graph, exposed as `content.metrics`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ContentMetrics {
+    /// Number of contents linking to this content
+    pub in_degree: usize,
+    /// Number of contents this content links to
+    pub out_degree: usize,
+    /// PageRank of the content, the scores of all the contents add up to 1
+    pub centrality: f64,
+}
+
+/// Metrics of the posts and pages keyed by slug, drafts are left out of the
+/// graph and the links to missing content or to the content itself are ignored
+pub fn compute_metrics<'a>(
+    contents: impl Iterator<Item = &'a Content>,
+) -> HashMap<String, ContentMetrics> {
+    let contents: Vec<&Content> = contents
+        .filter(|content| content.stream.as_deref() != Some("draft"))
+        .collect();
+    let index: HashMap<&str, usize> = contents
+        .iter()
+        .enumerate()
+        .map(|(i, content)| (content.slug.as_str(), i))
+        .collect();
+    let links: Vec<Vec<usize>> = contents
+        .iter()
+        .enumerate()
+        .map(|(i, content)| {
+            let mut targets: Vec<usize> = content
+                .links_to
+                .iter()
+                .flatten()
+                .filter_map(|link| index.get(link.split('#').next().unwrap_or_default()))
+                .copied()
+                .filter(|&target| target != i)
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            targets
+        })
+        .collect();
+    let mut in_degree = vec![0; contents.len()];
+    for &target in links.iter().flatten() {
+        in_degree[target] += 1;
+    }
+    let centrality = page_rank(&links);
+    contents
+        .iter()
+        .enumerate()
+        .map(|(i, content)| {
+            (
+                content.slug.clone(),
+                ContentMetrics {
+                    in_degree: in_degree[i],
+                    out_degree: links[i].len(),
+                    centrality: centrality[i],
+                },
+            )
+        })
+        .collect()
+}
+
+/// PageRank of each node of `links` (the targets of each node), the rank of
+/// the nodes without links is shared by all the nodes
+#[allow(clippy::cast_precision_loss)]
+fn page_rank(links: &[Vec<usize>]) -> Vec<f64> {
+    if links.is_empty() {
+        return Vec::new();
+    }
+    let size = links.len() as f64;
+    let mut ranks = vec![1.0 / size; links.len()];
+    for _ in 0..ITERATIONS {
+        let dangling: f64 = links
+            .iter()
+            .zip(&ranks)
+            .filter(|(targets, _)| targets.is_empty())
+            .map(|(_, rank)| rank)
+            .sum();
+        let mut next = vec![(1.0 - DAMPING + DAMPING * dangling) / size; links.len()];
+        for (targets, rank) in links.iter().zip(&ranks) {
+            for &target in targets {
+                next[target] += DAMPING * rank / targets.len() as f64;
+            }
+        }
+        ranks = next;
+    }
+    ranks
+}
+
+/// Render the metrics as a plain text table for the terminal, the most central first
+pub fn format_table(metrics: &HashMap<String, ContentMetrics>) -> String {
+    let mut rows: Vec<(&String, &ContentMetrics)> = metrics.iter().collect();
+    rows.sort_by(|a, b| {
+        b.1.centrality
+            .total_cmp(&a.1.centrality)
+            .then_with(|| a.0.cmp(b.0))
+    });
+    let slug_width = rows
+        .iter()
+        .map(|(slug, _)| slug.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut table = format!("{:<slug_width$}  In  Out  Centrality\n", "Slug");
+    for (slug, metric) in rows {
+        let _ = writeln!(
+            table,
+            "{slug:<slug_width$}  {:>2}  {:>3}  {:.4}",
+            metric.in_degree, metric.out_degree, metric.centrality
+        );
+    }
+    table
+}
+
+/// Set `metrics` on the posts and pages
+pub fn apply_metrics(site_data: &mut Data) {
+    let mut metrics = compute_metrics(site_data.posts.iter().chain(&site_data.pages));
+    for content in site_data.posts.iter_mut().chain(&mut site_data.pages) {
+        content.metrics = metrics.remove(&content.slug).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/graph.rs"]
+mod tests;
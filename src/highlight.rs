@@ -0,0 +1,216 @@
+use crate::config::SyntaxHighlightConfig;
+use crate::re;
+use log::{error, warn};
+use regex::Regex;
+use std::sync::LazyLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Highlighted spans get classes like `hl-keyword` so the theme css
+/// doesn't clash with the classes of the theme templates
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Options of the fence info string after the language, e.g:
+/// ```` ```rust,linenos,linenostart=10,hl_lines=1,3-5 ````
+/// `hl_lines` are counted from the first line of the block
+#[derive(Debug, PartialEq)]
+struct FenceOptions {
+    linenos: bool,
+    linenostart: usize,
+    hl_lines: Vec<(usize, usize)>,
+}
+
+impl Default for FenceOptions {
+    fn default() -> Self {
+        FenceOptions {
+            linenos: false,
+            linenostart: 1,
+            hl_lines: Vec::new(),
+        }
+    }
+}
+
+impl FenceOptions {
+    fn splits_lines(&self) -> bool {
+        self.linenos || !self.hl_lines.is_empty()
+    }
+
+    fn is_highlighted(&self, line: usize) -> bool {
+        self.hl_lines
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&line))
+    }
+}
+
+/// Split the info string in the language and its options, ranges following
+/// `hl_lines` are also highlighted, e.g: `hl_lines=1,3-5`
+fn parse_info(info: &str) -> (&str, FenceOptions) {
+    let mut parts = info.split(',');
+    let lang = parts.next().unwrap_or_default();
+    let mut options = FenceOptions::default();
+    let mut in_hl_lines = false;
+    for part in parts.map(str::trim) {
+        if in_hl_lines {
+            if let Some(range) = parse_line_range(part) {
+                options.hl_lines.push(range);
+                continue;
+            }
+        }
+        in_hl_lines = false;
+        let (key, value) = part.split_once('=').unwrap_or((part, ""));
+        match key {
+            "linenos" => options.linenos = true,
+            "linenostart" => match value.parse() {
+                Ok(start) => options.linenostart = start,
+                Err(_) => warn!("Invalid linenostart '{value}' in code block `{info}`"),
+            },
+            "hl_lines" => match parse_line_range(value) {
+                Some(range) => {
+                    options.hl_lines.push(range);
+                    in_hl_lines = true;
+                }
+                None => warn!("Invalid hl_lines '{value}' in code block `{info}`"),
+            },
+            _ => warn!("Unknown option '{part}' in code block `{info}`"),
+        }
+    }
+    (lang, options)
+}
+
+/// `3` or `3-5`, `None` for anything else
+fn parse_line_range(range: &str) -> Option<(usize, usize)> {
+    let (start, end) = range.split_once('-').unwrap_or((range, range));
+    let start = start.trim().parse().ok()?;
+    let end = end.trim().parse().ok()?;
+    (start <= end).then_some((start, end))
+}
+
+/// Highlight the fenced code blocks of the html with css classes and wrap
+/// their lines when the info string asks for line numbers or highlighted lines,
+/// fences with a language unknown to syntect are kept as regular code blocks
+pub fn highlight_code(html: &str, config: &SyntaxHighlightConfig) -> String {
+    let re =
+        Regex::new(re::CAPTURE_INFO_AND_CODE_FROM_PRE).expect("Code fence regex should compile");
+    re.replace_all(html, |caps: &regex::Captures| {
+        let info = &caps[1];
+        let (lang, options) = parse_info(info);
+        let highlighted = if config.enabled {
+            highlight_lines(lang, &caps[2])
+        } else {
+            None
+        };
+        if highlighted.is_none() && !options.splits_lines() && lang == info {
+            return caps[0].to_string();
+        }
+
+        let mut pre_classes = Vec::new();
+        let mut code_class = format!("language-{lang}");
+        if highlighted.is_some() {
+            pre_classes.push("hl-code");
+        } else if options.splits_lines() {
+            // highlight.js rewrites the block from its text, dropping the line spans
+            code_class.push_str(" nohighlight");
+        }
+        if options.linenos {
+            pre_classes.push("linenos");
+        }
+        let code = highlighted.unwrap_or_else(|| caps[2].to_string());
+        let code = if options.splits_lines() {
+            split_lines(&code, &options)
+        } else {
+            code
+        };
+        let pre_open = if pre_classes.is_empty() {
+            "<pre>".to_string()
+        } else {
+            format!(r#"<pre class="{}">"#, pre_classes.join(" "))
+        };
+        format!(r#"{pre_open}<code class="{code_class}">{code}</code></pre>"#)
+    })
+    .to_string()
+}
+
+fn highlight_lines(lang: &str, escaped_code: &str) -> Option<String> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(lang)?;
+    let source = crate::fences::unescape_html(escaped_code);
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, CLASS_STYLE);
+    for line in LinesWithEndings::from(&source) {
+        if let Err(e) = generator.parse_html_for_line_which_includes_newline(line) {
+            error!("Failed to highlight `{lang}` code block: {e}");
+            return None;
+        }
+    }
+    Some(generator.finalize())
+}
+
+/// Wrap each line of the code in a `line` span, with a `lineno` span when
+/// numbered and the `hl-line` class when highlighted.
+/// Highlight spans crossing lines (e.g: block comments) are closed at the end
+/// of the line and reopened on the next so every line span is balanced
+fn split_lines(code: &str, options: &FenceOptions) -> String {
+    let tag_re = Regex::new(r"<span[^>]*>|</span>").expect("Span regex should compile");
+    // The last newline of the block belongs to the last line, not a new one
+    let trailing_re = Regex::new(r"\n((?:</span>)*)$").expect("Trailing regex should compile");
+    let code = trailing_re.replace(code, "$1");
+
+    let mut open: Vec<&str> = Vec::new();
+    let mut output = String::new();
+    for (index, line) in code.split('\n').enumerate() {
+        if options.is_highlighted(index + 1) {
+            output.push_str(r#"<span class="line hl-line">"#);
+        } else {
+            output.push_str(r#"<span class="line">"#);
+        }
+        if options.linenos {
+            output.push_str(&format!(
+                r#"<span class="lineno">{}</span>"#,
+                options.linenostart + index
+            ));
+        }
+        output.push_str(&open.concat());
+        for tag in tag_re.find_iter(line) {
+            if tag.as_str() == "</span>" {
+                open.pop();
+            } else {
+                open.push(tag.as_str());
+            }
+        }
+        output.push_str(line);
+        output.push_str(&"</span>".repeat(open.len()));
+        output.push_str("</span>\n");
+    }
+    output
+}
+
+/// Stylesheet of the configured `theme`, the `dark_theme` rules are wrapped
+/// in a `prefers-color-scheme: dark` media query
+pub fn theme_css(config: &SyntaxHighlightConfig) -> Result<String, String> {
+    let themes = ThemeSet::load_defaults();
+    let css_for = |name: &str| {
+        let theme = themes.themes.get(name).ok_or_else(|| {
+            let available: Vec<&str> = themes.themes.keys().map(String::as_str).collect();
+            format!(
+                "Unknown syntax highlight theme '{name}', available: {}",
+                available.join(", ")
+            )
+        })?;
+        css_for_theme_with_class_style(theme, CLASS_STYLE).map_err(|e| e.to_string())
+    };
+    let mut css = css_for(&config.theme)?;
+    if let Some(dark_theme) = &config.dark_theme {
+        css.push_str(&format!(
+            "\n@media (prefers-color-scheme: dark) {{\n{}\n}}\n",
+            css_for(dark_theme)?
+        ));
+    }
+    Ok(css)
+}
+
+#[cfg(test)]
+#[path = "tests/highlight.rs"]
+mod tests;
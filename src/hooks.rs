@@ -0,0 +1,206 @@
+use crate::content::Content;
+use crate::errors::{ErrorKind, Failure};
+use crate::lint::lint_source;
+use crate::preview::{changed_files, is_previewed, staged_files};
+use crate::site::{collect_site_data, Data};
+use log::{info, warn};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Hooks installed by `--install-hooks`
+pub const HOOKS: [&str; 2] = ["pre-commit", "pre-push"];
+
+/// Marks the hooks written by marmite, so they can be replaced without `--force`
+const HOOK_MARKER: &str = "# Installed by marmite --install-hooks";
+
+/// Script of a git hook running `--hook-check` on the input folder, the path of the
+/// input folder is relative to the repository root so the repository can be moved
+pub fn hook_script(hook: &str, input_folder: &str, config: &str) -> String {
+    format!(
+        "#!/bin/sh\n{HOOK_MARKER}\nexec marmite \"$(git rev-parse --show-toplevel)/{input_folder}\" --config \"{config}\" --hook-check {hook}\n"
+    )
+}
+
+/// Handle the `--install-hooks` command, writes the pre-commit and pre-push hooks
+/// of the git repository holding the input folder, existing hooks not written by
+/// marmite are only replaced with `--force`
+pub fn install(input_folder: &Path, args: &Arc<crate::cli::Cli>) -> Result<(), String> {
+    let git = |git_args: &[&str]| -> Result<String, String> {
+        let output = Command::new("git")
+            .args(git_args)
+            .current_dir(input_folder)
+            .output()
+            .map_err(|e| format!("Failed to run git: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "{} is not in a git repository: {}",
+                input_folder.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let prefix = git(&["rev-parse", "--show-prefix"])?;
+    let hooks_path = PathBuf::from(git(&["rev-parse", "--git-path", "hooks"])?);
+    let hooks_path = if hooks_path.is_absolute() {
+        hooks_path
+    } else {
+        input_folder.join(hooks_path)
+    };
+    fs::create_dir_all(&hooks_path)
+        .map_err(|e| format!("Unable to create {}: {e}", hooks_path.display()))?;
+
+    let input_folder = prefix.trim_end_matches('/');
+    for hook in HOOKS {
+        let hook_path = hooks_path.join(hook);
+        let existing = fs::read_to_string(&hook_path).unwrap_or_default();
+        if !existing.is_empty() && !existing.contains(HOOK_MARKER) && !args.force {
+            warn!(
+                "{} already exists, use --force to replace it",
+                hook_path.display()
+            );
+            continue;
+        }
+        fs::write(&hook_path, hook_script(hook, input_folder, &args.config))
+            .map_err(|e| format!("Failed to write {}: {e}", hook_path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))
+                .map_err(|e| format!("Failed to make {} executable: {e}", hook_path.display()))?;
+        }
+        info!("Installed {}", hook_path.display());
+        println!("Installed the {hook} hook");
+    }
+    Ok(())
+}
+
+/// An issue found on a changed content, `line` is only known for lint issues,
+/// `kind` is `links` for broken links and `content` for the others
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookIssue {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+    pub kind: ErrorKind,
+}
+
+/// Issues of the changed content: lint rules, slugs used by another content
+/// and links to pages the site doesn't generate
+pub fn check_contents(site_data: &Data, changed: &HashSet<PathBuf>) -> Vec<HookIssue> {
+    let contents: Vec<&Content> = site_data.posts.iter().chain(&site_data.pages).collect();
+    let urls: HashSet<String> = site_data
+        .generated_urls
+        .get_all_urls()
+        .into_iter()
+        .map(|url| url.trim_start_matches('/').to_string())
+        .collect();
+
+    let mut issues = Vec::new();
+    for content in &contents {
+        let Some(path) = content
+            .source_path
+            .as_ref()
+            .filter(|path| is_previewed(Some(path.as_path()), changed))
+        else {
+            continue;
+        };
+        if let Ok(text) = fs::read_to_string(path) {
+            let (lint_issues, _) = lint_source(&text, &site_data.site.lint, false);
+            for issue in lint_issues {
+                issues.push(HookIssue {
+                    path: path.clone(),
+                    line: Some(issue.line),
+                    message: issue.message,
+                    kind: ErrorKind::Content,
+                });
+            }
+        }
+        for other in &contents {
+            if other.slug == content.slug && other.source_path != content.source_path {
+                issues.push(HookIssue {
+                    path: path.clone(),
+                    line: None,
+                    message: format!(
+                        "slug '{}' is also used by {}",
+                        content.slug,
+                        other
+                            .source_path
+                            .as_deref()
+                            .map_or(String::new(), |p| p.display().to_string())
+                    ),
+                    kind: ErrorKind::Content,
+                });
+            }
+        }
+        for link in content.links_to.iter().flatten() {
+            let page = link.split('#').next().unwrap_or_default();
+            if !page.is_empty() && !urls.contains(&format!("{page}.html")) {
+                issues.push(HookIssue {
+                    path: path.clone(),
+                    line: None,
+                    message: format!("broken link to {page}.html"),
+                    kind: ErrorKind::Links,
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Handle `--hook-check`, checks the content staged (`pre-commit`) or not pushed
+/// yet (`pre-push`), failing the hook when issues are found, with the `links`
+/// exit code when all of them are broken links
+pub fn run(
+    config_path: &Arc<PathBuf>,
+    input_folder: &Arc<PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+    hook: &str,
+) -> Result<(), Failure> {
+    let changed = if hook == "pre-push" {
+        changed_files(input_folder, "@{upstream}...HEAD").or_else(|e| {
+            warn!("{e}, checking the changes of the last commit");
+            changed_files(input_folder, "HEAD~1...HEAD")
+        })?
+    } else {
+        staged_files(input_folder)?
+    };
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    let site_data = collect_site_data(config_path, input_folder, args);
+    let issues = check_contents(&site_data, &changed);
+    for issue in &issues {
+        let display_path = issue
+            .path
+            .strip_prefix(input_folder.as_path())
+            .unwrap_or(&issue.path);
+        match issue.line {
+            Some(line) => println!("{}:{line}: {}", display_path.display(), issue.message),
+            None => println!("{}: {}", display_path.display(), issue.message),
+        }
+    }
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let kind = if issues.iter().all(|issue| issue.kind == ErrorKind::Links) {
+        ErrorKind::Links
+    } else {
+        ErrorKind::Content
+    };
+    Err(Failure::new(
+        kind,
+        format!(
+            "Found {} issues in the changed content, fix them or skip the hook with --no-verify",
+            issues.len()
+        ),
+    ))
+}
+
+#[cfg(test)]
+#[path = "tests/hooks.rs"]
+mod tests;
@@ -0,0 +1,41 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+use ureq::Agent;
+
+/// Time a request of the build may take, from the connection to the end of the body
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Agent shared by the requests made while building the site, e.g: `fetch_json`
+/// and the bookmarks, so a slow server fails its request instead of hanging the build
+static AGENT: LazyLock<Agent> = LazyLock::new(|| build_agent(TIMEOUT));
+
+fn build_agent(timeout: Duration) -> Agent {
+    Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .build()
+        .into()
+}
+
+/// Body of `url` as text, a body larger than `limit` bytes is an error
+pub fn get_text(url: &str, accept: &str, limit: u64) -> Result<String, String> {
+    get_with(&AGENT, url, accept, limit)
+}
+
+fn get_with(agent: &Agent, url: &str, accept: &str, limit: u64) -> Result<String, String> {
+    agent
+        .get(url)
+        .header("Accept", accept)
+        .call()
+        .and_then(|mut response| {
+            response
+                .body_mut()
+                .with_config()
+                .limit(limit)
+                .read_to_string()
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+#[path = "tests/http.rs"]
+mod tests;
@@ -0,0 +1,136 @@
+use crate::conditionals::fence_marker;
+use crate::re;
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Inline the markdown files of the `{{ include "snippets/warning.md" }}` lines,
+/// the directive must be on its own line and is ignored inside code blocks.
+/// The path is relative to the folder of the including file or, when not found
+/// there, to one of its parent folders up to the `root` content folder, so the
+/// posts of any folder can include `snippets/*.md` of the content folder.
+/// Absolute paths and `..` are rejected so no file out of `root` is published.
+/// Included files can include others, a file including itself, directly or not,
+/// is an error. Returns the markdown and the files included
+pub fn apply_includes(
+    markdown: &str,
+    path: &Path,
+    root: &Path,
+) -> Result<(String, Vec<PathBuf>), String> {
+    let mut included = Vec::new();
+    if !markdown.contains("{{") {
+        return Ok((markdown.to_string(), included));
+    }
+    let re = Regex::new(re::CAPTURE_INCLUDE_DIRECTIVE).expect("Include regex should compile");
+    let mut stack = vec![path.canonicalize().unwrap_or_else(|_| path.to_path_buf())];
+    let output = expand(markdown, path, root, &re, &mut stack, &mut included)?;
+    Ok((output, included))
+}
+
+fn expand(
+    markdown: &str,
+    path: &Path,
+    root: &Path,
+    re: &Regex,
+    stack: &mut Vec<PathBuf>,
+    included: &mut Vec<PathBuf>,
+) -> Result<String, String> {
+    let mut fence: Option<String> = None;
+    let mut output = String::with_capacity(markdown.len());
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if let Some(marker) = &fence {
+            if trimmed.starts_with(marker.as_str())
+                && trimmed
+                    .trim_start_matches(marker.chars().next().unwrap_or('`'))
+                    .is_empty()
+            {
+                fence = None;
+            }
+        } else if let Some(marker) = fence_marker(trimmed) {
+            fence = Some(marker);
+        } else if let Some(caps) = (indent_width(line) < 4)
+            .then(|| re.captures(trimmed))
+            .flatten()
+        {
+            let target = resolve(&caps[1], path, root)?.ok_or_else(|| {
+                format!(
+                    "Included file {} not found from {}",
+                    &caps[1],
+                    path.display()
+                )
+            })?;
+            let canonical = target.canonicalize().unwrap_or_else(|_| target.clone());
+            if stack.contains(&canonical) {
+                let chain: Vec<String> = stack
+                    .iter()
+                    .chain([&canonical])
+                    .map(|p| p.display().to_string())
+                    .collect();
+                return Err(format!("Include cycle: {}", chain.join(" -> ")));
+            }
+            let text = fs::read_to_string(&target)
+                .map_err(|e| format!("Failed to read included file {}: {e}", target.display()))?;
+            stack.push(canonical);
+            let text = expand(&text, &target, root, re, stack, included)?;
+            stack.pop();
+            if !included.contains(&target) {
+                included.push(target);
+            }
+            output.push_str(&text);
+            if line.ends_with('\n') && !text.ends_with('\n') {
+                output.push('\n');
+            }
+            continue;
+        }
+        output.push_str(line);
+    }
+    Ok(output)
+}
+
+/// Columns of the leading whitespace, a line indented by 4 or more is in an
+/// indented code block and its directive is kept as code
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .map_while(|c| match c {
+            ' ' => Some(1),
+            '\t' => Some(4),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Timestamp of the latest modified of the included files, a content is rendered
+/// again when one of its includes changed after the last build
+pub fn latest_modified(included: &[PathBuf]) -> Option<i64> {
+    included
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .filter_map(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .filter_map(|duration| i64::try_from(duration.as_secs()).ok())
+        .max()
+}
+
+/// The included file relative to the folder of `from` or to the nearest parent
+/// holding it, the search stops at `root`
+fn resolve(target: &str, from: &Path, root: &Path) -> Result<Option<PathBuf>, String> {
+    if !Path::new(target)
+        .components()
+        .all(|part| matches!(part, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!(
+            "Included file {target} must be relative to the content folder, without `..`"
+        ));
+    }
+    Ok(from.parent().and_then(|folder| {
+        folder
+            .ancestors()
+            .take_while(|folder| folder.starts_with(root))
+            .map(|folder| folder.join(target))
+            .find(|candidate| candidate.is_file())
+    }))
+}
+
+#[cfg(test)]
+#[path = "tests/includes.rs"]
+mod tests;
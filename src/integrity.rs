@@ -0,0 +1,179 @@
+use crate::config::IntegrityConfig;
+use crate::content::Content;
+use crate::errors::{ErrorKind, Failure};
+use crate::site::Data;
+use chrono::{DateTime, SecondsFormat, Utc};
+use log::{error, info};
+use rayon::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Content of the integrity manifest, `files` maps the path of every file of
+/// the site, relative to the output folder, to the hex sha256 of its content
+/// and `pages` maps each content page to the hash embedded in it
+#[derive(Debug, Serialize)]
+pub struct Manifest {
+    pub generated_at: String,
+    pub url: String,
+    pub algorithm: &'static str,
+    pub files: BTreeMap<String, String>,
+    pub pages: BTreeMap<String, String>,
+}
+
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hex sha256 of the file content
+pub fn file_sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Hex sha256 of the html of a content, embedded in its page as
+/// `<meta name="integrity" content="sha256:{hash}">`, the hash doesn't
+/// change with the theme so copies of the article can be checked too
+pub fn content_sha256(content: &Content) -> String {
+    to_hex(&Sha256::digest(content.html.as_bytes()))
+}
+
+/// The `{slug}.html` of every post and page with the hash of its content
+pub fn page_hashes(site_data: &Data) -> BTreeMap<String, String> {
+    site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .map(|content| (format!("{}.html", content.slug), content_sha256(content)))
+        .collect()
+}
+
+/// The manifest of the files of `output_path`, the manifest itself and the
+/// files named after it, e.g: the `integrity.json.minisig` signature, are skipped
+pub fn build_manifest(
+    output_path: &Path,
+    config: &IntegrityConfig,
+    url: &str,
+    now: DateTime<Utc>,
+) -> Manifest {
+    let manifest = config.manifest.trim_start_matches('/');
+    let signatures = format!("{manifest}.");
+    let paths: Vec<(String, PathBuf)> = WalkDir::new(output_path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative = entry
+                .path()
+                .strip_prefix(output_path)
+                .ok()?
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            (relative != manifest && !relative.starts_with(&signatures))
+                .then(|| (relative, entry.into_path()))
+        })
+        .collect();
+    let files = paths
+        .into_par_iter()
+        .filter_map(|(relative, path)| match file_sha256(&path) {
+            Ok(hash) => Some((relative, hash)),
+            Err(e) => {
+                error!("Failed to hash {}: {e}", path.display());
+                None
+            }
+        })
+        .collect();
+    Manifest {
+        generated_at: now.to_rfc3339_opts(SecondsFormat::Secs, true),
+        url: url.to_string(),
+        algorithm: "sha256",
+        files,
+        pages: BTreeMap::new(),
+    }
+}
+
+/// Write the integrity manifest of the generated site and sign it with the
+/// `sign_command`, runs after every other file of the build is written.
+/// A manifest that can't be written or signed fails the build
+pub fn write_integrity_manifest(site_data: &Data, output_path: &Path) -> Result<(), Failure> {
+    let config = &site_data.site.integrity;
+    let manifest_path = manifest_path(output_path, config)?;
+    let mut manifest = build_manifest(output_path, config, &site_data.site.url, Utc::now());
+    manifest.pages = page_hashes(site_data);
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize the integrity manifest: {e}"))?;
+    if let Some(parent) = manifest_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            Failure::new(
+                ErrorKind::Io,
+                format!("Unable to create {}: {e}", parent.display()),
+            )
+        })?;
+    }
+    fs::write(&manifest_path, json).map_err(|e| {
+        Failure::new(
+            ErrorKind::Io,
+            format!("Failed to write {}: {e}", manifest_path.display()),
+        )
+    })?;
+    info!(
+        "Generated {} with the hashes of {} files",
+        manifest_path.display(),
+        manifest.files.len()
+    );
+
+    if let Some(command) = &config.sign_command {
+        sign_manifest(command, &manifest_path).map_err(|e| {
+            format!(
+                "Failed to sign {} with `{command}`: {e}",
+                manifest_path.display()
+            )
+        })?;
+        info!("Signed {}", manifest_path.display());
+    }
+    Ok(())
+}
+
+/// Path of the manifest in the output folder, `..` is rejected so the
+/// manifest is never written out of the site
+fn manifest_path(output_path: &Path, config: &IntegrityConfig) -> Result<PathBuf, Failure> {
+    let manifest = Path::new(config.manifest.trim_start_matches('/'));
+    if manifest.as_os_str().is_empty()
+        || !manifest
+            .components()
+            .all(|part| matches!(part, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(Failure::new(
+            ErrorKind::Config,
+            format!(
+                "Invalid integrity manifest `{}`, use a path inside the output folder",
+                config.manifest
+            ),
+        ));
+    }
+    Ok(output_path.join(manifest))
+}
+
+/// Run the `sign_command` split on whitespace with `{manifest}` replaced by
+/// the path of the manifest, no shell is involved
+fn sign_manifest(command: &str, manifest_path: &Path) -> Result<(), String> {
+    let manifest_path = std::path::absolute(manifest_path).map_err(|e| e.to_string())?;
+    let manifest_path = manifest_path.to_string_lossy();
+    let args: Vec<String> = command
+        .split_whitespace()
+        .map(|part| part.replace("{manifest}", &manifest_path))
+        .collect();
+    let (program, args) = args.split_first().ok_or("Empty integrity sign_command")?;
+    crate::converters::run_program(program, args, "").map(|_| ())
+}
+
+#[cfg(test)]
+#[path = "tests/integrity.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+use crate::content::Content;
+use crate::re;
+use crate::site::Data;
+use log::{error, info};
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Data shown on the hover card of the internal links to a content
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LinkPreview {
+    pub title: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+}
+
+impl LinkPreview {
+    pub fn new(content: &Content) -> Self {
+        LinkPreview {
+            title: content.title.clone(),
+            description: content.description.clone(),
+            image: content
+                .card_image
+                .clone()
+                .or_else(|| content.banner_image.clone()),
+        }
+    }
+}
+
+/// Preview of every content keyed by slug, written to `link-previews.json`
+pub fn collect_previews<'a>(
+    contents: impl Iterator<Item = &'a Content>,
+) -> BTreeMap<String, LinkPreview> {
+    contents
+        .filter(|content| content.stream.as_deref() != Some("draft"))
+        .map(|content| (content.slug.clone(), LinkPreview::new(content)))
+        .collect()
+}
+
+/// Add `data-preview-title`, `data-preview-description` and `data-preview-image`
+/// to the links pointing to a content of `previews`, other links are kept as written
+pub fn add_preview_attributes(html: &str, previews: &BTreeMap<String, LinkPreview>) -> String {
+    let link_re =
+        Regex::new(re::CAPTURE_INTERNAL_LINK_TAG).expect("Internal link regex should compile");
+    link_re
+        .replace_all(html, |captures: &Captures| {
+            let (tag, page) = (&captures[1], &captures[2]);
+            let slug = page.trim_start_matches("./").trim_start_matches('/');
+            let preview = previews
+                .get(slug)
+                .filter(|_| !page.contains("://") && !tag.contains("data-preview-"));
+            let Some(preview) = preview else {
+                return captures[0].to_string();
+            };
+            let mut tag = format!(
+                "{tag} data-preview-title=\"{}\"",
+                escape_attribute(&preview.title)
+            );
+            if let Some(description) = &preview.description {
+                tag.push_str(&format!(
+                    " data-preview-description=\"{}\"",
+                    escape_attribute(description)
+                ));
+            }
+            if let Some(image) = &preview.image {
+                tag.push_str(&format!(
+                    " data-preview-image=\"{}\"",
+                    escape_attribute(image)
+                ));
+            }
+            format!("{tag}>")
+        })
+        .to_string()
+}
+
+/// Escape a value for a double quoted attribute, urls keep their `/`
+pub fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Add the preview attributes to the internal links of the posts and pages
+pub fn apply_link_previews(site_data: &mut Data) {
+    let previews = collect_previews(site_data.posts.iter().chain(&site_data.pages));
+    for content in site_data.posts.iter_mut().chain(&mut site_data.pages) {
+        content.html = add_preview_attributes(&content.html, &previews);
+    }
+}
+
+/// Write `link-previews.json` mapping the slugs to their preview
+pub fn write_link_previews(site_data: &Data, output_path: &Path) {
+    let previews = collect_previews(site_data.posts.iter().chain(&site_data.pages));
+    let previews_path = output_path.join("link-previews.json");
+    match serde_json::to_string_pretty(&previews) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&previews_path, json) {
+                error!("Failed to write {}: {e}", previews_path.display());
+            } else {
+                info!("Generated {}", previews_path.display());
+            }
+        }
+        Err(e) => error!("Failed to serialize link-previews.json: {e}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/link_previews.rs"]
+mod tests;
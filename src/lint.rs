@@ -0,0 +1,257 @@
+use crate::config::{LintConfig, TitleCase};
+use crate::frontmatter::{split_field, FrontmatterEditor};
+use crate::site::{get_content_folder, Data};
+use crate::spellcheck::collect_markdown_files;
+use log::info;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Words kept lowercase in title case unless first or last
+const MINOR_WORDS: [&str; 17] = [
+    "a", "an", "and", "as", "at", "but", "by", "for", "in", "nor", "of", "on", "or", "per", "the",
+    "to", "via",
+];
+
+/// A style issue and the line where it was found, `fixable` issues
+/// are rewritten by `--lint --fix`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    pub line: usize,
+    pub message: String,
+    pub fixable: bool,
+}
+
+/// Rewrite `text` in the given capitalization style, words with inner
+/// capitals (`GitHub`, `HTML`), proper nouns, inline code and URLs are kept as written
+pub fn recase(text: &str, style: TitleCase, proper_nouns: &[String]) -> String {
+    let words: Vec<&str> = text.split(' ').collect();
+    let last = words.iter().rposition(|w| !w.is_empty()).unwrap_or(0);
+    let mut after_colon = false;
+    words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let is_first = index == 0 || after_colon;
+            after_colon = word.ends_with(':');
+            if is_verbatim(word, proper_nouns) {
+                return (*word).to_string();
+            }
+            match style {
+                TitleCase::Title => {
+                    let lower = word.to_lowercase();
+                    let bare = lower.trim_matches(|c: char| !c.is_alphanumeric());
+                    if !is_first && index != last && MINOR_WORDS.contains(&bare) {
+                        lower
+                    } else {
+                        capitalize(word)
+                    }
+                }
+                TitleCase::Sentence if is_first => capitalize(word),
+                TitleCase::Sentence => word.to_lowercase(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Words that must never be recased
+fn is_verbatim(word: &str, proper_nouns: &[String]) -> bool {
+    let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+    proper_nouns.iter().any(|noun| noun == bare)
+        || word.contains('`')
+        || word.contains("://")
+        || word == "I"
+        || word.starts_with("I'")
+        || word
+            .chars()
+            .skip_while(|c| !c.is_alphabetic())
+            .skip(1)
+            .any(char::is_uppercase)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut done = false;
+    word.chars()
+        .map(|c| {
+            if !done && c.is_alphabetic() {
+                done = true;
+                c.to_uppercase().next().unwrap_or(c)
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Check a markdown source against the configured rules, when `fix` is set
+/// the title casing issues are rewritten and the new source is returned
+pub fn lint_source(text: &str, config: &LintConfig, fix: bool) -> (Vec<LintIssue>, String) {
+    let mut issues = Vec::new();
+    let mut output = Vec::new();
+    let mut in_frontmatter = text.starts_with("---");
+    let mut in_code_block = false;
+    let mut description = None;
+    let mut frontmatter_end = 0;
+    let mut fixed_title = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let mut line = line.to_string();
+        if in_frontmatter {
+            if index > 0 && line.trim() == "---" {
+                in_frontmatter = false;
+                frontmatter_end = number;
+            } else if let Some((key, _, value)) = split_field(&line) {
+                match key {
+                    "title" => fixed_title = check_casing(value, config, number, &mut issues),
+                    "description" => description = Some((number, value.chars().count())),
+                    _ => {}
+                }
+            }
+        } else if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block && line.starts_with('#') {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            let heading = line[level..].trim().to_string();
+            if level <= 6 && line[level..].starts_with(' ') {
+                if let Some(max) = config.max_heading_depth.filter(|max| level > *max) {
+                    issues.push(LintIssue {
+                        line: number,
+                        message: format!("heading level {level} is deeper than {max}"),
+                        fixable: false,
+                    });
+                }
+                if let Some(fixed) = check_casing(&heading, config, number, &mut issues) {
+                    if fix {
+                        line = format!("{} {fixed}", "#".repeat(level));
+                    }
+                }
+            }
+        }
+        output.push(line);
+    }
+
+    check_description(description, frontmatter_end, config, &mut issues);
+
+    let mut fixed = output.join("\n");
+    if text.ends_with('\n') {
+        fixed.push('\n');
+    }
+    if fix {
+        if let Some(title) = fixed_title {
+            if let Ok(mut editor) = FrontmatterEditor::parse(&fixed) {
+                editor.set("title", &title);
+                fixed = editor.to_source();
+            }
+        }
+        issues.retain(|issue| !issue.fixable);
+    }
+    (issues, fixed)
+}
+
+/// Push an issue and return the expected text when casing doesn't match the style
+fn check_casing(
+    text: &str,
+    config: &LintConfig,
+    line: usize,
+    issues: &mut Vec<LintIssue>,
+) -> Option<String> {
+    let style = config.title_case?;
+    let expected = recase(text, style, &config.proper_nouns);
+    if expected == text {
+        return None;
+    }
+    issues.push(LintIssue {
+        line,
+        message: match style {
+            TitleCase::Sentence => format!("expected `{expected}` (sentence case)"),
+            TitleCase::Title => format!("expected `{expected}` (title case)"),
+        },
+        fixable: true,
+    });
+    Some(expected)
+}
+
+fn check_description(
+    description: Option<(usize, usize)>,
+    frontmatter_end: usize,
+    config: &LintConfig,
+    issues: &mut Vec<LintIssue>,
+) {
+    if config.description_min_length.is_none() && config.description_max_length.is_none() {
+        return;
+    }
+    let Some((line, length)) = description else {
+        issues.push(LintIssue {
+            line: frontmatter_end.max(1),
+            message: "missing description".to_string(),
+            fixable: false,
+        });
+        return;
+    };
+    if let Some(min) = config.description_min_length.filter(|min| length < *min) {
+        issues.push(LintIssue {
+            line,
+            message: format!("description has {length} characters, minimum is {min}"),
+            fixable: false,
+        });
+    }
+    if let Some(max) = config.description_max_length.filter(|max| length > *max) {
+        issues.push(LintIssue {
+            line,
+            message: format!("description has {length} characters, maximum is {max}"),
+            fixable: false,
+        });
+    }
+}
+
+/// Handle the `--lint` command, prints `file:line: message` for each issue
+/// and fails when any is found, `--fix` rewrites the title casing in place
+pub fn run(
+    config_path: &Arc<PathBuf>,
+    input_folder: &Arc<PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) -> Result<(), String> {
+    let mut site_data = Data::from_file(config_path.as_path());
+    site_data.site.override_from_cli_args(args);
+    let config = &site_data.site.lint;
+    if *config == LintConfig::default() {
+        println!("No lint rules configured, add a `lint` section to the configuration file");
+        return Ok(());
+    }
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    let files = collect_markdown_files(&content_folder);
+
+    let mut total = 0;
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let (issues, fixed) = lint_source(&text, config, args.fix);
+        if args.fix && fixed != text {
+            fs::write(path, &fixed)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            info!("Fixed title casing in {}", path.display());
+        }
+        let display_path = path.strip_prefix(input_folder.as_path()).unwrap_or(path);
+        for issue in &issues {
+            println!(
+                "{}:{}: {}",
+                display_path.display(),
+                issue.line,
+                issue.message
+            );
+        }
+        total += issues.len();
+    }
+
+    if total > 0 {
+        return Err(format!("Found {total} style issues"));
+    }
+    println!("No style issues found in {} files", files.len());
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/lint.rs"]
+mod tests;
@@ -0,0 +1,206 @@
+use chrono::{Datelike, NaiveDateTime};
+
+/// Month and weekday names of a language, weekdays start on Monday
+struct Names {
+    months: [&'static str; 12],
+    months_short: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    weekdays_short: [&'static str; 7],
+}
+
+const PT: Names = Names {
+    months: [
+        "janeiro",
+        "fevereiro",
+        "março",
+        "abril",
+        "maio",
+        "junho",
+        "julho",
+        "agosto",
+        "setembro",
+        "outubro",
+        "novembro",
+        "dezembro",
+    ],
+    months_short: [
+        "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+    ],
+    weekdays: [
+        "segunda-feira",
+        "terça-feira",
+        "quarta-feira",
+        "quinta-feira",
+        "sexta-feira",
+        "sábado",
+        "domingo",
+    ],
+    weekdays_short: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+};
+
+const ES: Names = Names {
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    months_short: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+    ],
+    weekdays: [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ],
+    weekdays_short: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+};
+
+const FR: Names = Names {
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    months_short: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+        "déc.",
+    ],
+    weekdays: [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ],
+    weekdays_short: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+};
+
+const DE: Names = Names {
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    months_short: [
+        "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+        "Dez.",
+    ],
+    weekdays: [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ],
+    weekdays_short: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+};
+
+const IT: Names = Names {
+    months: [
+        "gennaio",
+        "febbraio",
+        "marzo",
+        "aprile",
+        "maggio",
+        "giugno",
+        "luglio",
+        "agosto",
+        "settembre",
+        "ottobre",
+        "novembre",
+        "dicembre",
+    ],
+    months_short: [
+        "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+    ],
+    weekdays: [
+        "lunedì",
+        "martedì",
+        "mercoledì",
+        "giovedì",
+        "venerdì",
+        "sabato",
+        "domenica",
+    ],
+    weekdays_short: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+};
+
+/// Language of a language tag e.g: `pt` for `pt-BR` and `pt_PT`
+pub fn base_language(lang: &str) -> &str {
+    lang.split(['-', '_']).next().unwrap_or(lang)
+}
+
+fn names(lang: &str) -> Option<&'static Names> {
+    match base_language(lang).to_lowercase().as_str() {
+        "pt" => Some(&PT),
+        "es" => Some(&ES),
+        "fr" => Some(&FR),
+        "de" => Some(&DE),
+        "it" => Some(&IT),
+        _ => None,
+    }
+}
+
+/// Format the date with the strftime `format` using the month and weekday names
+/// of `lang` for `%B`, `%b` (`%h`), `%A` and `%a`, languages without bundled names
+/// are formatted in English
+pub fn format_date(date: &NaiveDateTime, format: &str, lang: &str) -> String {
+    let Some(names) = names(lang) else {
+        return date.format(format).to_string();
+    };
+    let month = date.month0() as usize;
+    let weekday = date.weekday().num_days_from_monday() as usize;
+    let mut localized = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            localized.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('B') => localized.push_str(names.months[month]),
+            Some('b' | 'h') => localized.push_str(names.months_short[month]),
+            Some('A') => localized.push_str(names.weekdays[weekday]),
+            Some('a') => localized.push_str(names.weekdays_short[weekday]),
+            Some(other) => {
+                localized.push('%');
+                localized.push(other);
+            }
+            None => localized.push('%'),
+        }
+    }
+    date.format(&localized).to_string()
+}
+
+#[cfg(test)]
+#[path = "tests/locale.rs"]
+mod tests;
@@ -1,27 +1,71 @@
 use clap::Parser;
 use env_logger::{Builder, Env};
+use errors::{ErrorKind, Failure};
 use log::{error, info, warn, SetLoggerError};
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
 
+mod alt_text;
+mod badges;
+mod bookmarks;
+mod bundles;
+mod citations;
 mod cli;
+mod conditionals;
 mod config;
 mod content;
+mod converters;
+mod cv;
 mod embedded;
+mod errors;
 mod feed;
+mod fences;
+mod freshness;
+mod frontmatter;
 mod gallery;
+mod glossary;
+mod graph;
+mod highlight;
+mod hooks;
+mod http;
 mod image_provider;
+mod includes;
+mod integrity;
+mod link_previews;
+mod lint;
+mod locale;
+mod math;
+mod notebook;
+mod now;
+mod orphans;
 mod parser;
+mod photos;
+mod pipeline;
+mod preview;
+mod private;
+mod projects;
 mod re;
+mod render_cache;
+mod review;
+mod robots;
+mod schema;
+mod sections;
 mod server;
 mod shortcodes;
+mod sidecar;
 mod site;
+mod slug_history;
+mod spellcheck;
+mod tag_intersections;
 mod templates;
 mod tera_filter;
 mod tera_functions;
 mod theme_manager;
+mod tokens;
+mod uses;
+mod wellknown;
 
 fn setup_logging(verbose: u8, debug: bool) -> Result<(), SetLoggerError> {
     let env = Env::default().default_filter_or(match verbose {
@@ -46,6 +90,7 @@ fn determine_verbosity(args: &cli::Cli) -> u8 {
         && (args.watch
             || args.serve
             || args.start_theme.is_some()
+            || args.new_theme.is_some()
             || args.set_theme.is_some()
             || args.init_templates
             || args.generate_config
@@ -82,6 +127,7 @@ fn run_cli(args: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
     if let Err(e) = setup_logging(verbose, args.debug) {
         error!("Logger already initialized: {e:?}");
     }
+    config::configure_thread_pool(&config_path, &args);
 
     if args.init_site {
         site::initialize(&input_folder, &cloned_args);
@@ -107,6 +153,11 @@ fn run_cli(args: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if let Some(theme_name) = args.new_theme {
+        templates::new_theme(&input_folder, &theme_name);
+        return Ok(());
+    }
+
     if let Some(theme_source) = args.set_theme {
         theme_manager::set_theme(
             &input_folder,
@@ -130,6 +181,49 @@ fn run_cli(args: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if args.freshness {
+        site::show_freshness(&config_path, &input_folder, &cloned_args);
+        return Ok(());
+    }
+
+    if args.orphans {
+        site::show_orphans(&config_path, &input_folder, &cloned_args);
+        return Ok(());
+    }
+
+    if args.stats {
+        site::show_stats(&config_path, &input_folder, &cloned_args);
+        return Ok(());
+    }
+
+    if args.lint {
+        return lint::run(&config_path, &input_folder, &cloned_args)
+            .map_err(|e| Failure::new(ErrorKind::Content, e).into());
+    }
+
+    if args.install_hooks {
+        return hooks::install(&input_folder, &cloned_args).map_err(Into::into);
+    }
+
+    if let Some(hook) = &args.hook_check {
+        return hooks::run(&config_path, &input_folder, &cloned_args, hook).map_err(Into::into);
+    }
+
+    if args.alt_text {
+        return alt_text::run(&config_path, &input_folder, &cloned_args)
+            .map_err(|e| Failure::new(ErrorKind::Content, e).into());
+    }
+
+    if args.normalize_frontmatter {
+        return frontmatter::run(&config_path, &input_folder, &cloned_args)
+            .map_err(|e| Failure::new(ErrorKind::Content, e).into());
+    }
+
+    if args.spellcheck {
+        return spellcheck::run(&config_path, &input_folder, &cloned_args)
+            .map_err(|e| Failure::new(ErrorKind::Content, e).into());
+    }
+
     let output_folder = Arc::new(args.output_folder.unwrap_or(input_folder.join("site")));
     if let Err(e) = site::generate(
         &config_path,
@@ -140,13 +234,20 @@ fn run_cli(args: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
         bind_address,
         &cloned_args,
     ) {
-        error!("Failed to generate site: {e}");
-        std::process::exit(1);
+        errors::exit(
+            errors::kind_of(e.as_ref()),
+            &format!("Failed to generate site: {e}"),
+        );
     }
 
     if serve && !watch {
         info!("Starting built-in HTTP server...");
-        server::start(bind_address, &Arc::clone(&output_folder), None);
+        server::start(
+            bind_address,
+            &Arc::clone(&output_folder),
+            None,
+            cloned_args.editor.as_deref(),
+        );
     }
 
     Ok(())
@@ -154,9 +255,9 @@ fn run_cli(args: cli::Cli) -> Result<(), Box<dyn std::error::Error>> {
 
 fn main() {
     let args = cli::Cli::parse();
+    errors::set_format(&args.error_format);
     if let Err(e) = run_cli(args) {
-        error!("{e}");
-        std::process::exit(1);
+        errors::exit(errors::kind_of(e.as_ref()), &e.to_string());
     }
 }
 
@@ -240,6 +341,9 @@ fn handle_shortcodes_command(
                     | "series"
                     | "card"
                     | "gallery"
+                    | "video"
+                    | "audio"
+                    | "csv"
             ) {
                 let param_example = if pattern.contains(r"<!--") {
                     format!("  <!-- .{name} param=value -->")
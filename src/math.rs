@@ -0,0 +1,79 @@
+use crate::config::{MathMode, MathOptions};
+use crate::re;
+use log::{debug, error};
+use regex::Regex;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Replace the math spans of the `math_dollars` extension, e.g:
+/// `<span data-math-style="inline">x^2</span>`, according to the math `mode`.
+/// `client` wraps the TeX in `\(...\)` or `\[...\]`, the default delimiters of
+/// KaTeX auto-render and MathJax, `server` replaces it with the output of the
+/// `inline_command` or `display_command`, falling back to `client` on failure
+pub fn render_math(html: &str, options: &MathOptions) -> String {
+    if options.mode == MathMode::None {
+        return html.to_string();
+    }
+    let re = Regex::new(re::CAPTURE_MATH_SPAN).expect("Math span regex should compile");
+    let cache_dir = crate::converters::render_cache_dir("math");
+    re.replace_all(html, |caps: &regex::Captures| {
+        let display = &caps[1] == "display";
+        let tex = &caps[2];
+        if options.mode == MathMode::Server {
+            let command = if display {
+                &options.display_command
+            } else {
+                &options.inline_command
+            };
+            let source = crate::fences::unescape_html(tex);
+            match render_tex(command, &source, cache_dir.as_deref()) {
+                Ok(rendered) => return rendered,
+                Err(e) => error!("Failed to render math `{source}` with `{command}`: {e}"),
+            }
+        }
+        if display {
+            format!(r#"<span class="math math-display">\[{tex}\]</span>"#)
+        } else {
+            format!(r#"<span class="math math-inline">\({tex}\)</span>"#)
+        }
+    })
+    .to_string()
+}
+
+/// Rendered formulas are cached in `.marmite/renders/math/` by the hash of the
+/// command and the TeX source so a rebuild doesn't spawn the renderer for every
+/// formula again
+fn render_tex(command: &str, source: &str, cache_dir: Option<&Path>) -> Result<String, String> {
+    let mut hasher = DefaultHasher::new();
+    command.hash(&mut hasher);
+    source.hash(&mut hasher);
+    let cache_path = cache_dir.map(|dir| dir.join(format!("{:016x}.html", hasher.finish())));
+    if let Some(cache_path) = &cache_path {
+        if let Ok(rendered) = fs::read_to_string(cache_path) {
+            debug!("Using cached math render {}", cache_path.display());
+            return Ok(rendered);
+        }
+    }
+
+    let rendered = crate::converters::run_with_stdin(command, source)?
+        .trim()
+        .to_string();
+    if rendered.is_empty() {
+        return Err("Renderer output is empty".to_string());
+    }
+
+    if let (Some(cache_dir), Some(cache_path)) = (cache_dir, cache_path) {
+        if let Err(e) =
+            fs::create_dir_all(cache_dir).and_then(|()| fs::write(&cache_path, &rendered))
+        {
+            debug!("Failed to cache math render {}: {e}", cache_path.display());
+        }
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+#[path = "tests/math.rs"]
+mod tests;
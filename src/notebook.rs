@@ -0,0 +1,117 @@
+use serde_json::Value;
+use std::fmt::Write as _;
+
+/// Convert a Jupyter notebook (nbformat 4) to markdown so it can go through
+/// the same content pipeline as `.md` files.
+/// - The first `raw` cell is used as the frontmatter
+/// - Markdown cells are kept as is
+/// - Code cells become fenced code blocks using the kernel language
+/// - Outputs are rendered as text blocks, inline images or raw html
+pub fn notebook_to_markdown(notebook: &str) -> Result<String, String> {
+    let notebook: Value =
+        serde_json::from_str(notebook).map_err(|e| format!("Invalid notebook: {e}"))?;
+    let cells = notebook
+        .get("cells")
+        .and_then(Value::as_array)
+        .ok_or("Invalid notebook: missing `cells`")?;
+    let language = notebook
+        .pointer("/metadata/language_info/name")
+        .or_else(|| notebook.pointer("/metadata/kernelspec/language"))
+        .and_then(Value::as_str)
+        .unwrap_or("python");
+
+    let mut markdown = String::new();
+    for cell in cells {
+        let source = get_text(cell.get("source"));
+        match cell.get("cell_type").and_then(Value::as_str) {
+            // Only a leading raw cell is treated as frontmatter
+            Some("raw") if markdown.is_empty() => {
+                let frontmatter = source.trim();
+                if frontmatter.starts_with("---") {
+                    let _ = write!(markdown, "{frontmatter}\n\n");
+                } else {
+                    let _ = write!(markdown, "---\n{frontmatter}\n---\n\n");
+                }
+            }
+            Some("markdown") => {
+                let _ = write!(markdown, "{}\n\n", source.trim_end());
+            }
+            Some("code") => {
+                if !source.trim().is_empty() {
+                    let _ = write!(markdown, "```{language}\n{}\n```\n\n", source.trim_end());
+                }
+                for output in cell
+                    .get("outputs")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    markdown.push_str(&output_to_markdown(output));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(markdown)
+}
+
+fn output_to_markdown(output: &Value) -> String {
+    match output.get("output_type").and_then(Value::as_str) {
+        Some("stream") => text_block(&get_text(output.get("text"))),
+        Some("error") => {
+            let ename = output
+                .get("ename")
+                .and_then(Value::as_str)
+                .unwrap_or("Error");
+            let evalue = output.get("evalue").and_then(Value::as_str).unwrap_or("");
+            text_block(&format!("{ename}: {evalue}"))
+        }
+        Some("execute_result" | "display_data") => {
+            let Some(data) = output.get("data") else {
+                return String::new();
+            };
+            if let Some(png) = data.get("image/png") {
+                let png: String = get_text(Some(png)).split_whitespace().collect();
+                format!("![output](data:image/png;base64,{png})\n\n")
+            } else if let Some(jpeg) = data.get("image/jpeg") {
+                let jpeg: String = get_text(Some(jpeg)).split_whitespace().collect();
+                format!("![output](data:image/jpeg;base64,{jpeg})\n\n")
+            } else if let Some(svg) = data.get("image/svg+xml") {
+                format!(
+                    "<div class=\"notebook-output\">{}</div>\n\n",
+                    get_text(Some(svg))
+                )
+            } else if let Some(html) = data.get("text/html") {
+                format!(
+                    "<div class=\"notebook-output\">{}</div>\n\n",
+                    get_text(Some(html))
+                )
+            } else if let Some(md) = data.get("text/markdown") {
+                format!("{}\n\n", get_text(Some(md)).trim_end())
+            } else {
+                text_block(&get_text(data.get("text/plain")))
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+fn text_block(text: &str) -> String {
+    if text.trim().is_empty() {
+        return String::new();
+    }
+    format!("```text\n{}\n```\n\n", text.trim_end())
+}
+
+/// Notebook text fields can be either a string or a list of lines
+fn get_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/notebook.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+use crate::config::ParserOptions;
+use crate::content::try_to_parse_date;
+use crate::feed::ActivityItem;
+use chrono::NaiveDateTime;
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Characters of the status text used as title of its feed item
+const TITLE_LENGTH: usize = 80;
+
+/// A short timestamped update, listed on the `/now` page and in `status.rss`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Status {
+    pub date: NaiveDateTime,
+    /// The markdown source of the update
+    pub text: String,
+    pub html: String,
+    /// Anchor of the update on the now page e.g: `status-20250101103000`
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+struct StatusEntry {
+    date: String,
+    text: String,
+}
+
+/// Load the `- {date, text}` list of status updates latest first,
+/// returns an empty list if the file doesn't exist, entries with an
+/// invalid date are skipped
+pub fn load_statuses(path: &Path, parser_options: &ParserOptions) -> Vec<Status> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to read statuses {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    let entries = match serde_yaml::from_str::<Vec<StatusEntry>>(&text) {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Failed to parse statuses {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    let mut statuses: Vec<Status> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let Ok(date) = try_to_parse_date(&entry.date) else {
+                error!(
+                    "Invalid status date '{}' in {}, skipping",
+                    entry.date,
+                    path.display()
+                );
+                return None;
+            };
+            let text = entry.text.trim().to_string();
+            Some(Status {
+                id: format!("status-{}", date.format("%Y%m%d%H%M%S")),
+                html: crate::parser::get_html_with_options(&text, parser_options),
+                date,
+                text,
+            })
+        })
+        .collect();
+    statuses.sort_by_key(|status| std::cmp::Reverse(status.date));
+    statuses
+}
+
+/// Feed items of the statuses linking to the `{now_slug}.html` page,
+/// the guid of each item is the anchor of the status on the page
+pub fn status_items(statuses: &[Status], now_slug: &str) -> Vec<ActivityItem> {
+    statuses
+        .iter()
+        .map(|status| {
+            let first_line = status.text.lines().next().unwrap_or_default();
+            let mut title: String = first_line.chars().take(TITLE_LENGTH).collect();
+            if title.len() < status.text.trim_end().len() {
+                title.push('…');
+            }
+            ActivityItem {
+                title,
+                link: format!("{now_slug}.html"),
+                source: format!("{now_slug}.html"),
+                target: status.id.clone(),
+                description: status.html.clone(),
+                date: status.date,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/now.rs"]
+mod tests;
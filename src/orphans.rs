@@ -0,0 +1,94 @@
+use crate::content::Content;
+use crate::site::Data;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// A content no listing, menu, fragment or other content links to,
+/// only reachable by typing its url
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Orphan {
+    pub title: String,
+    pub slug: String,
+    pub source_path: Option<String>,
+}
+
+/// Slug of a menu or fragment link to a content of the site, e.g:
+/// `./about.html`, `/about.html` and `https://example.com/about.html` are `about`
+pub fn link_slug(link: &str, site_url: &str) -> String {
+    let link = link.split('#').next().unwrap_or_default();
+    let link = if site_url.is_empty() {
+        link
+    } else {
+        link.trim_start_matches(site_url.trim_end_matches('/'))
+    };
+    link.trim_start_matches("./")
+        .trim_start_matches('/')
+        .trim_end_matches(".html")
+        .to_string()
+}
+
+/// Whether the content is listed on a group page: posts are listed on their
+/// stream and archive pages, tagged content on the tag pages and so on,
+/// the `pages.html` listing of every page doesn't count
+fn is_listed(content: &Content) -> bool {
+    content.date.is_some()
+        || !content.tags.is_empty()
+        || content.series.is_some()
+        || !content.taxonomies.is_empty()
+        || content.section.is_some()
+}
+
+/// Posts and pages not listed on a group page, not in the menu, not linked
+/// from `fragment_links` (the slugs linked by the fragments) and without
+/// backlinks, sorted by slug
+pub fn collect_orphans(site_data: &Data, fragment_links: &HashSet<String>) -> Vec<Orphan> {
+    let site = &site_data.site;
+    let menu_links: HashSet<String> = site
+        .menu
+        .iter()
+        .flatten()
+        .map(|(_, url)| link_slug(url, &site.url))
+        .collect();
+    let mut orphans: Vec<Orphan> = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter(|content| content.stream.as_deref() != Some("draft"))
+        .filter(|content| {
+            !is_listed(content)
+                && content.back_links.is_empty()
+                && !menu_links.contains(&content.slug)
+                && !fragment_links.contains(&content.slug)
+        })
+        .map(|content| Orphan {
+            title: content.title.clone(),
+            slug: content.slug.clone(),
+            source_path: content
+                .source_path
+                .as_ref()
+                .map(|path| path.display().to_string()),
+        })
+        .collect();
+    orphans.sort_by(|a, b| a.slug.cmp(&b.slug));
+    orphans
+}
+
+/// Render the report as a plain text table for the terminal
+pub fn format_table(orphans: &[Orphan]) -> String {
+    let slug_width = orphans
+        .iter()
+        .map(|orphan| orphan.slug.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut table = format!("{:<slug_width$}  Title\n", "Slug");
+    for orphan in orphans {
+        let _ = writeln!(table, "{:<slug_width$}  {}", orphan.slug, orphan.title);
+    }
+    table
+}
+
+#[cfg(test)]
+#[path = "tests/orphans.rs"]
+mod tests;
@@ -1,10 +1,13 @@
-use crate::config::ParserOptions;
+use crate::config::{AnchorPosition, HeadingAnchorsConfig, MathMode, ParserOptions};
+use crate::content::Content;
 use crate::re;
 use crate::site::Data;
 use comrak::{markdown_to_html, options::BrokenLinkReference, Options, ResolvedReference};
 use frontmatter_gen::{detect_format, extract_raw_frontmatter, parse, Frontmatter};
-use log::warn;
+use log::{debug, warn};
 use regex::Regex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
 
 use std::fs;
@@ -51,6 +54,7 @@ fn warn_broken_link(link_ref: BrokenLinkReference) -> Option<ResolvedReference>
         || original.starts_with('!') // Callouts
         || original.starts_with('#') // anchors
         ||original.starts_with('^') // footnotes
+        || original.starts_with('@') // citations
         || original.starts_with('/') // absolute links
         || (original.len() == 1 && original.chars().next().is_some_and(|c| !c.is_ascii_digit())) // task checkboxes
         || original.is_empty(); // empty links
@@ -61,19 +65,10 @@ fn warn_broken_link(link_ref: BrokenLinkReference) -> Option<ResolvedReference>
 }
 
 pub fn get_table_of_contents_from_html(html: &str) -> String {
-    let re = Regex::new(re::CAPTURE_LEVEL_ANCHOR_TEXT_FROM_H_TAG)
-        .expect("Table of contents regex should compile");
     let mut toc = String::new();
     let mut last_level = 0;
 
-    for cap in re.captures_iter(html) {
-        let level = cap.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
-        let title = cap.get(3).map_or("", |m| m.as_str());
-        let slug = cap.get(2).map_or_else(
-            || format!("#{}", slug::slugify(title)),
-            |m| m.as_str().to_string(),
-        );
-
+    for (level, slug, title) in get_headings(html) {
         match level.cmp(&last_level) {
             std::cmp::Ordering::Greater => {
                 for _ in last_level..level {
@@ -99,24 +94,150 @@ pub fn get_table_of_contents_from_html(html: &str) -> String {
     toc
 }
 
+/// A heading of the table of contents, `id` is the id of the heading
+/// so `#{id}` links to it, `children` are the headings of deeper levels below it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TocEntry {
+    pub level: usize,
+    pub id: String,
+    pub title: String,
+    pub children: Vec<TocEntry>,
+}
+
+/// Nested table of contents of the html headings, a heading is a child of the
+/// previous heading of a lower level, headings without one are at the top level
+pub fn get_toc_entries_from_html(html: &str) -> Vec<TocEntry> {
+    let mut entries: Vec<TocEntry> = Vec::new();
+    for (level, slug, title) in get_headings(html) {
+        let entry = TocEntry {
+            level,
+            id: slug.trim_start_matches('#').to_string(),
+            title: title.to_string(),
+            children: Vec::new(),
+        };
+        let mut siblings = &mut entries;
+        while siblings.last().is_some_and(|last| last.level < level) {
+            siblings = &mut siblings
+                .last_mut()
+                .expect("Checked by is_some_and above")
+                .children;
+        }
+        siblings.push(entry);
+    }
+    entries
+}
+
+/// `(level, #anchor, title)` of the html headings, the anchor is the one of the
+/// `a.anchor` permalink or the id of the heading, the slug of the title when there is none
+fn get_headings(html: &str) -> Vec<(usize, String, String)> {
+    let re = Regex::new(re::CAPTURE_HEADING_ELEMENT).expect("Heading regex should compile");
+    let anchor_re =
+        Regex::new(re::CAPTURE_HREF_FROM_HEADING_ANCHOR).expect("Anchor regex should compile");
+    let id_re = Regex::new(re::CAPTURE_ID_ATTRIBUTE).expect("Id regex should compile");
+    re.captures_iter(html)
+        .map(|cap| {
+            let level = cap[1].parse().unwrap_or(0);
+            let attributes = cap.get(2).map_or("", |m| m.as_str());
+            let inner = &cap[3];
+            let title = anchor_re.replace_all(inner, "").trim().to_string();
+            let slug = anchor_re
+                .captures(inner)
+                .map(|anchor| anchor[1].to_string())
+                .or_else(|| id_re.captures(attributes).map(|id| format!("#{}", &id[1])))
+                .unwrap_or_else(|| format!("#{}", slug::slugify(&title)));
+            (level, slug, title)
+        })
+        .collect()
+}
+
+/// Give every heading a stable id, the `slug::slugify` of its text, the same rule
+/// `fix_internal_links` applies to the fragment of links like `post.md#My Section`.
+/// Ids written on the heading are kept and repeated ids get a `-1`, `-2`.. suffix.
+/// With `link` the id is held by an injected `a.anchor` permalink, like the markdown
+/// renderer writes it, otherwise the id is set on the heading itself
+pub fn heading_anchors(html: &str, config: &HeadingAnchorsConfig) -> String {
+    let re = Regex::new(re::CAPTURE_HEADING_ELEMENT).expect("Heading regex should compile");
+    let anchor_re =
+        Regex::new(re::CAPTURE_HREF_FROM_HEADING_ANCHOR).expect("Anchor regex should compile");
+    let id_re = Regex::new(re::CAPTURE_ID_ATTRIBUTE).expect("Id regex should compile");
+    let tags_re = Regex::new(re::MATCH_HTML_TAGS).expect("HTML tags regex should compile");
+    let mut seen: HashSet<String> = HashSet::new();
+    re.replace_all(html, |caps: &regex::Captures| {
+        let level = &caps[1];
+        let attributes = caps.get(2).map_or("", |m| m.as_str());
+        let inner = anchor_re.replace_all(&caps[3], "");
+        let written_id = id_re.captures(attributes).map(|id| id[1].to_string());
+        let id = if let Some(id) = &written_id {
+            seen.insert(id.clone());
+            id.clone()
+        } else {
+            let text = crate::fences::unescape_html(&tags_re.replace_all(&inner, ""));
+            let slug = slug::slugify(text);
+            if slug.is_empty() {
+                return format!("<h{level}{attributes}>{inner}</h{level}>");
+            }
+            let mut id = slug.clone();
+            let mut count = 0;
+            while !seen.insert(id.clone()) {
+                count += 1;
+                id = format!("{slug}-{count}");
+            }
+            id
+        };
+
+        if !config.link {
+            return if written_id.is_some() {
+                format!("<h{level}{attributes}>{inner}</h{level}>")
+            } else {
+                format!(r#"<h{level} id="{id}"{attributes}>{inner}</h{level}>"#)
+            };
+        }
+        let id_attribute = if written_id.is_some() {
+            String::new()
+        } else {
+            format!(r#" id="{id}""#)
+        };
+        let anchor = format!(
+            r##"<a href="#{id}" aria-hidden="true" class="anchor"{id_attribute}>{}</a>"##,
+            config.symbol
+        );
+        match config.position {
+            AnchorPosition::Before => format!("<h{level}{attributes}>{anchor}{inner}</h{level}>"),
+            AnchorPosition::After => {
+                format!("<h{level}{attributes}>{inner} {anchor}</h{level}>")
+            }
+        }
+    })
+    .to_string()
+}
+
 /// Convert markdown to html using comrak
 #[allow(dead_code)]
 pub fn get_html(markdown: &str) -> String {
     get_html_with_options(markdown, &ParserOptions::default())
 }
 
-/// Convert markdown to html using comrak with configurable options
+/// Convert markdown to html using comrak with configurable options, reusing the
+/// html of a previous `--watch` build for the same markdown and options
 pub fn get_html_with_options(markdown: &str, parser_options: &ParserOptions) -> String {
+    crate::render_cache::cached(markdown, parser_options, || {
+        render_html(markdown, parser_options)
+    })
+}
+
+fn render_html(markdown: &str, parser_options: &ParserOptions) -> String {
     let mut options = Options::default();
 
     // Apply configurable render options
     options.render.figure_with_caption = parser_options.render.figure_with_caption;
     options.render.ignore_empty_links = parser_options.render.ignore_empty_links;
     options.render.r#unsafe = parser_options.render.unsafe_;
+    options.render.tasklist_classes = parser_options.render.tasklist_classes;
 
     // Apply configurable parse options
     options.parse.broken_link_callback = Some(Arc::new(warn_broken_link)); // Not configurable
     options.parse.relaxed_tasklist_matching = parser_options.parse.relaxed_tasklist_matching;
+    options.parse.smart = parser_options.parse.smart;
 
     // Apply configurable extension options
     options.extension.alerts = parser_options.extension.alerts;
@@ -124,7 +245,7 @@ pub fn get_html_with_options(markdown: &str, parser_options: &ParserOptions) ->
     options.extension.description_lists = parser_options.extension.description_lists;
     options.extension.footnotes = parser_options.extension.footnotes;
     options.extension.greentext = parser_options.extension.greentext;
-    options.extension.header_ids = Some(String::new()); // Not configurable
+    options.extension.header_ids = Some(String::new()); // Rewritten by `heading_anchors`
                                                         // options.extension.image_url_rewriter = TODO: implement this to point to a resized image
     options.extension.multiline_block_quotes = parser_options.extension.multiline_block_quotes;
     options.extension.tagfilter = parser_options.extension.tagfilter;
@@ -138,8 +259,108 @@ pub fn get_html_with_options(markdown: &str, parser_options: &ParserOptions) ->
         parser_options.extension.wikilinks_title_before_pipe;
     options.extension.wikilinks_title_after_pipe =
         parser_options.extension.wikilinks_title_after_pipe;
+    options.extension.math_dollars = parser_options.math.mode != MathMode::None;
+
+    let mut html = markdown_to_html(markdown, &options);
+    if parser_options.extension.tasklist && parser_options.render.tasklist_interactive {
+        html = interactive_tasklist(&html);
+    }
+    let html = crate::math::render_math(&html, &parser_options.math);
+    let html = process_color_scheme_images(&html);
+    let html = process_media_images(&html);
+    fix_internal_links(&html)
+}
+
+/// Task checkboxes without `disabled`, numbered by `data-task` in the order
+/// of the page so `marmite.js` can keep the ticked ones on the browser
+fn interactive_tasklist(html: &str) -> String {
+    let re = Regex::new(re::CAPTURE_TASK_CHECKBOX).expect("Task checkbox regex should compile");
+    let mut index = 0;
+    re.replace_all(html, |caps: &regex::Captures| {
+        let checkbox = format!(
+            r#"{}<input type="checkbox"{} data-task="{index}" />"#,
+            &caps[1], &caps[2]
+        );
+        index += 1;
+        checkbox
+    })
+    .to_string()
+}
+
+/// Done and total items of the task lists of the html, e.g: `(1, 3)` for a
+/// list with one of three items ticked
+pub fn task_counts(html: &str) -> (usize, usize) {
+    if !html.contains("type=\"checkbox\"") {
+        return (0, 0);
+    }
+    let re = Regex::new(re::CAPTURE_TASK_CHECKBOX).expect("Task checkbox regex should compile");
+    re.captures_iter(html).fold((0, 0), |(done, total), caps| {
+        (done + usize::from(caps[2].contains("checked")), total + 1)
+    })
+}
+
+/// Footnote definitions of the markdown e.g: `[^1]: Source` with their indented
+/// continuation lines, appended to a summary so its references resolve
+pub fn footnote_definitions(markdown: &str) -> String {
+    let definition_re = Regex::new(re::MATCH_FOOTNOTE_DEFINITION)
+        .expect("Footnote definition regex should compile");
+    let mut definitions = String::new();
+    let mut in_definition = false;
+    for line in markdown.lines() {
+        if definition_re.is_match(line) {
+            in_definition = true;
+        } else if !line.trim().is_empty() && !line.starts_with([' ', '\t']) {
+            in_definition = false;
+        }
+        if in_definition {
+            definitions.push_str(line);
+            definitions.push('\n');
+        }
+    }
+    definitions
+}
+
+/// Remove the footnote references and the footnotes section of the html,
+/// summaries are listed away from the content so their footnote links would be broken
+pub fn strip_footnotes(html: &str) -> String {
+    let re = Regex::new(re::MATCH_FOOTNOTE_REF_OR_SECTION).expect("Footnote regex should compile");
+    re.replace_all(html, "").trim_end().to_string()
+}
 
-    fix_internal_links(&markdown_to_html(markdown, &options))
+/// Images pointing to video or audio files e.g: `![My clip](media/clip.mp4)`
+/// are rendered as `<video>` or `<audio>` elements with native controls,
+/// the alt text is kept as `aria-label`.
+pub fn process_media_images(html: &str) -> String {
+    let re = Regex::new(re::CAPTURE_MEDIA_SRC_FROM_IMG).expect("Media img regex should compile");
+    let alt_re = Regex::new(re::CAPTURE_ALT_FROM_IMG).expect("Alt regex should compile");
+    re.replace_all(html, |caps: &regex::Captures| {
+        let src = &caps[1];
+        let tag = if matches!(
+            caps[2].to_lowercase().as_str(),
+            "mp3" | "ogg" | "wav" | "m4a" | "flac"
+        ) {
+            "audio"
+        } else {
+            "video"
+        };
+        let label = alt_re
+            .captures(&caps[0])
+            .filter(|alt| !alt[1].is_empty())
+            .map(|alt| format!(r#" aria-label="{}""#, &alt[1]))
+            .unwrap_or_default();
+        format!(r#"<{tag} src="{src}" controls preload="metadata"{label}></{tag}>"#)
+    })
+    .to_string()
+}
+
+/// Images using the `#light` or `#dark` fragment convention e.g: `![alt](shot.png#dark)`
+/// get the fragment removed and a `color-scheme-{light|dark}` class so the
+/// stylesheet shows only the variant matching the current color scheme.
+pub fn process_color_scheme_images(html: &str) -> String {
+    let re =
+        Regex::new(re::CAPTURE_COLOR_SCHEME_IMG).expect("Color scheme img regex should compile");
+    re.replace_all(html, r#"<img${1}src="${2}" class="color-scheme-${3}"${4}>"#)
+        .to_string()
 }
 
 /// Takes the html content, finds all the internal links and
@@ -180,15 +401,19 @@ pub fn fix_internal_links(html: &str) -> String {
         }
 
         let new_href = if let Ok(parsed) = Url::parse(&format!("m://m/{href}")) {
+            // `[[Page Title]]` wikilinks have the spaces encoded
+            let path = urlencoding::decode(parsed.path())
+                .map_or_else(|_| parsed.path().to_string(), std::borrow::Cow::into_owned);
             let path = slug::slugify(
-                parsed
-                    .path()
-                    .trim_start_matches('/')
+                path.trim_start_matches('/')
                     .trim_end_matches(".md")
                     .trim_end_matches(".html"),
             );
             let fragment = match parsed.fragment() {
-                Some(f) => slug::slugify(f),
+                Some(f) => slug::slugify(
+                    urlencoding::decode(f)
+                        .map_or_else(|_| f.to_string(), std::borrow::Cow::into_owned),
+                ),
                 None => String::new(),
             };
 
@@ -227,6 +452,68 @@ fn decode_html_entities(text: &str) -> String {
         .replace("&#x27;", "'")
 }
 
+/// Targets of the wikilinks, the slugified title and source file name of every
+/// content mapped to its slug, slugs take precedence over the others
+pub fn wikilink_targets(contents: &[Content]) -> HashMap<String, String> {
+    let mut targets = HashMap::new();
+    for content in contents {
+        targets.insert(slug::slugify(&content.title), content.slug.clone());
+    }
+    for content in contents {
+        if let Some(stem) = content
+            .source_path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .and_then(|stem| stem.to_str())
+        {
+            targets.insert(slug::slugify(stem), content.slug.clone());
+        }
+    }
+    for content in contents {
+        targets.insert(content.slug.clone(), content.slug.clone());
+    }
+    targets
+}
+
+/// Point the wikilinks of the html, e.g: `[[Page Title]]`, `[[file name]]` or
+/// `[[slug#Heading]]`, to the slug of the content they name. The href was already
+/// slugified by `fix_internal_links`, the links not matching any content keep it,
+/// e.g: `[[tags]]` linking to `tags.html`.
+/// Returns the html and the pages resolved with the slug each one points to
+pub fn resolve_wikilinks(
+    html: &str,
+    targets: &HashMap<String, String>,
+) -> (String, Vec<(String, String)>) {
+    let re =
+        Regex::new(re::CAPTURE_WIKILINK_HREF_AND_TITLE).expect("Wikilink regex should compile");
+    let mut links = Vec::new();
+    let html = re
+        .replace_all(html, |caps: &regex::Captures| {
+            let link = &caps[0];
+            let (page, fragment) = caps[1].split_once('#').unwrap_or((&caps[1], ""));
+            let page = page.trim_end_matches(".html");
+            if link.contains("data-wikilink-resolved") || page.is_empty() {
+                return link.to_string();
+            }
+            let Some(slug) = targets.get(page) else {
+                debug!("Wikilink to `{page}` doesn't match any content");
+                return link.to_string();
+            };
+            let href = if fragment.is_empty() {
+                format!("{slug}.html")
+            } else {
+                format!("{slug}.html#{fragment}")
+            };
+            links.push((page.to_string(), slug.clone()));
+            format!(
+                r#"<a href="{href}" data-wikilink="true" data-wikilink-resolved>{}</a>"#,
+                &caps[2]
+            )
+        })
+        .to_string();
+    (html, links)
+}
+
 /// Find content by title in site data (case-insensitive)
 /// Returns the slug of the matching content if found
 fn find_content_by_title(title: &str, site_data: &Data) -> Option<String> {
@@ -258,6 +545,9 @@ pub fn fix_wikilinks(html: &str, site_data: &Data) -> String {
 
     re.replace_all(html, |caps: &regex::Captures| {
         let original_link = caps.get(0).map_or("", |m| m.as_str());
+        if original_link.contains("data-wikilink-resolved") {
+            return original_link.to_string();
+        }
         let original_href = caps.get(1).map_or("", |m| m.as_str());
         let link_title = caps.get(2).map_or("", |m| m.as_str());
 
@@ -0,0 +1,346 @@
+use crate::config::{Marmite, PhotosConfig};
+use crate::content::{Content, ContentBuilder};
+use crate::gallery::is_image_file;
+use crate::link_previews::escape_attribute;
+use chrono::NaiveDateTime;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use walkdir::WalkDir;
+
+const TAG_IMAGE_DESCRIPTION: u16 = 0x010E;
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TYPE_ASCII: u16 = 2;
+const TYPE_LONG: u16 = 4;
+/// Bytes read looking for the EXIF of a photo with `low_memory`, the `APP1`
+/// segment of a JPEG is at most 64 KiB and comes before the image data
+const LOW_MEMORY_EXIF_BYTES: u64 = 128 * 1024;
+
+/// Metadata read from the EXIF of a JPEG or TIFF image
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Exif {
+    /// When the photo was taken, else when the file was last changed by the camera
+    pub date: Option<NaiveDateTime>,
+    /// Make and model e.g: `FUJIFILM X-T3`
+    pub camera: Option<String>,
+    /// The image description
+    pub caption: Option<String>,
+}
+
+/// A photo of the `photos` stream, exposed as `content.photos` and on the photo wall
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Photo {
+    /// Url of the image e.g: `media/photos/2024/beach.jpg`
+    pub image: String,
+    pub title: String,
+    pub date: Option<NaiveDateTime>,
+    pub camera: Option<String>,
+    pub caption: Option<String>,
+    /// Folder of the photo relative to the photos folder, e.g: `2024/italy`
+    pub album: Option<String>,
+    /// Slug of the content showing the photo
+    pub slug: String,
+}
+
+/// Byte order aware reader of a TIFF structure, the EXIF payload of a JPEG
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl Tiff<'_> {
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// The ASCII and LONG entries of the IFD at `offset` keyed by tag
+    fn entries(&self, offset: usize) -> BTreeMap<u16, Entry> {
+        let mut entries = BTreeMap::new();
+        let Some(count) = self.u16(offset) else {
+            return entries;
+        };
+        for index in 0..usize::from(count) {
+            let entry = offset + 2 + index * 12;
+            let (Some(tag), Some(kind), Some(count)) =
+                (self.u16(entry), self.u16(entry + 2), self.u32(entry + 4))
+            else {
+                break;
+            };
+            let value = match kind {
+                TYPE_ASCII => {
+                    let count = count as usize;
+                    let start = if count <= 4 {
+                        entry + 8
+                    } else {
+                        self.u32(entry + 8).unwrap_or_default() as usize
+                    };
+                    let Some(bytes) = self.data.get(start..start + count) else {
+                        continue;
+                    };
+                    let text = String::from_utf8_lossy(bytes);
+                    Entry::Text(text.trim_matches(['\0', ' ']).trim().to_string())
+                }
+                TYPE_LONG => Entry::Number(self.u32(entry + 8).unwrap_or_default()),
+                _ => continue,
+            };
+            entries.insert(tag, value);
+        }
+        entries
+    }
+}
+
+enum Entry {
+    Text(String),
+    Number(u32),
+}
+
+fn text(entries: &BTreeMap<u16, Entry>, tag: u16) -> Option<String> {
+    match entries.get(&tag) {
+        Some(Entry::Text(text)) if !text.is_empty() => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// TIFF structure of a JPEG `APP1` EXIF segment, or the image itself for TIFF files
+fn tiff_data(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+        return Some(bytes);
+    }
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut offset = 2;
+    while let [0xFF, marker, high, low, ..] = *bytes.get(offset..)? {
+        let length = usize::from(u16::from_be_bytes([high, low]));
+        // start of scan, the metadata segments are all before the image data
+        if marker == 0xDA || length < 2 {
+            return None;
+        }
+        let segment = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return Some(&segment[6..]);
+        }
+        offset += 2 + length;
+    }
+    None
+}
+
+/// Read the date, camera and caption of a JPEG or TIFF image,
+/// `None` when the image has no EXIF
+pub fn read_exif(bytes: &[u8]) -> Option<Exif> {
+    let data = tiff_data(bytes)?;
+    let tiff = Tiff {
+        data,
+        little_endian: data.starts_with(b"II"),
+    };
+    if tiff.u16(2)? != 42 {
+        return None;
+    }
+    let ifd0 = tiff.entries(tiff.u32(4)? as usize);
+    let exif_ifd = match ifd0.get(&TAG_EXIF_IFD) {
+        Some(Entry::Number(offset)) => tiff.entries(*offset as usize),
+        _ => BTreeMap::new(),
+    };
+    let date = text(&exif_ifd, TAG_DATE_TIME_ORIGINAL)
+        .or_else(|| text(&ifd0, TAG_DATE_TIME))
+        .and_then(|date| NaiveDateTime::parse_from_str(&date, "%Y:%m:%d %H:%M:%S").ok());
+    let camera = match (text(&ifd0, TAG_MAKE), text(&ifd0, TAG_MODEL)) {
+        (Some(make), Some(model)) if !model.starts_with(&make) => Some(format!("{make} {model}")),
+        (_, Some(model)) => Some(model),
+        (make, None) => make,
+    };
+    Some(Exif {
+        date,
+        camera,
+        caption: text(&ifd0, TAG_IMAGE_DESCRIPTION),
+    })
+}
+
+/// Photos of the `photos.folder` inside the media folder, the date falls back to
+/// the modification time of the file and the title to the file name,
+/// with `albums` the photos of a subfolder share the slug of the album
+pub fn collect_photos(media_dir: &Path, site: &Marmite) -> Vec<Photo> {
+    let config = &site.photos;
+    let photos_dir = media_dir.join(&config.folder);
+    if !photos_dir.is_dir() {
+        warn!("Photos folder does not exist: {}", photos_dir.display());
+        return Vec::new();
+    }
+    let mut photos: Vec<Photo> = WalkDir::new(&photos_dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file() && is_image_file(entry.path()))
+        .filter(|entry| {
+            // gallery thumbnails are never photos of the stream
+            !entry
+                .path()
+                .components()
+                .any(|c| c.as_os_str() == "thumbnails")
+        })
+        .map(|entry| photo(entry.path(), &photos_dir, site))
+        .collect();
+    photos.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.image.cmp(&b.image)));
+    info!("Found {} photos in {}", photos.len(), photos_dir.display());
+    photos
+}
+
+/// The bytes of the photo holding the EXIF, only the first
+/// `LOW_MEMORY_EXIF_BYTES` with `low_memory`
+fn read_head(path: &Path, low_memory: bool) -> Option<Vec<u8>> {
+    if !low_memory {
+        return fs::read(path).ok();
+    }
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(LOW_MEMORY_EXIF_BYTES).read_to_end(&mut bytes))
+        .ok()?;
+    Some(bytes)
+}
+
+fn photo(path: &Path, photos_dir: &Path, site: &Marmite) -> Photo {
+    let config = &site.photos;
+    let exif = read_head(path, site.low_memory)
+        .and_then(|bytes| read_exif(&bytes))
+        .unwrap_or_default();
+    let date = exif.date.or_else(|| {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+        Some(chrono::DateTime::<chrono::Utc>::from(modified).naive_utc())
+    });
+    let relative = path.strip_prefix(photos_dir).unwrap_or(path);
+    let album = relative
+        .parent()
+        .map(|parent| parent.to_string_lossy().replace('\\', "/"))
+        .filter(|album| !album.is_empty());
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let mut slug = match album.as_ref().filter(|_| config.albums) {
+        Some(album) => slug::slugify(format!("{} {album}", config.stream)),
+        None => slug::slugify(format!(
+            "{} {} {stem}",
+            config.stream,
+            album.as_deref().unwrap_or_default()
+        )),
+    };
+    // `{stream}-wall.html` is the photo wall
+    let wall = format!("{}-wall", config.stream);
+    if slug == wall {
+        warn!(
+            "Photo {} would replace the photo wall {wall}.html, using the slug '{wall}-photo'",
+            path.display()
+        );
+        slug = format!("{wall}-photo");
+    }
+    Photo {
+        image: format!(
+            "{}/{}/{}",
+            site.media_path,
+            config.folder,
+            relative.to_string_lossy().replace('\\', "/")
+        ),
+        title: exif
+            .caption
+            .clone()
+            .unwrap_or_else(|| stem.replace(['-', '_'], " ")),
+        date,
+        camera: exif.camera,
+        caption: exif.caption,
+        album,
+        slug,
+    }
+}
+
+fn figure(photo: &Photo) -> String {
+    let caption = [photo.caption.as_deref(), photo.camera.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(tera::escape_html)
+        .collect::<Vec<_>>()
+        .join(" · ");
+    format!(
+        "<figure class=\"photo\"><img src=\"{}\" alt=\"{}\" loading=\"lazy\">{}</figure>",
+        escape_attribute(&photo.image),
+        tera::escape_html(&photo.title),
+        if caption.is_empty() {
+            String::new()
+        } else {
+            format!("<figcaption>{caption}</figcaption>")
+        }
+    )
+}
+
+/// One content per photo, or per album with `albums`, on the `photos.stream`,
+/// dated by the latest photo of the content
+pub fn photo_contents(photos: &[Photo], config: &PhotosConfig) -> Vec<Content> {
+    let mut by_slug: BTreeMap<&str, Vec<&Photo>> = BTreeMap::new();
+    for photo in photos {
+        by_slug.entry(&photo.slug).or_default().push(photo);
+    }
+    by_slug
+        .into_iter()
+        .map(|(slug, photos)| {
+            let first = photos[0];
+            let title = match (&first.album, config.albums) {
+                (Some(album), true) => album.rsplit('/').next().unwrap_or(album).to_string(),
+                _ => first.title.clone(),
+            };
+            let mut content = ContentBuilder::new()
+                .title(title)
+                .slug(slug.to_string())
+                .stream(config.stream.clone())
+                .html(photos.iter().copied().map(figure).collect())
+                .card_image(first.image.clone())
+                .photos(photos.iter().copied().cloned().collect());
+            if let Some(date) = photos.iter().filter_map(|photo| photo.date).max() {
+                content = content.date(date);
+            }
+            if let Some(caption) = first.caption.clone().filter(|_| photos.len() == 1) {
+                content = content.description(caption);
+            }
+            content.build()
+        })
+        .collect()
+}
+
+/// Point the photos back to the content showing them, the slug of a photo
+/// content can change after `photo_contents` when duplicate slugs are resolved
+pub fn update_photo_slugs(photos: &mut [Photo], contents: &mut [Content]) {
+    let mut slugs = HashMap::new();
+    for content in contents.iter_mut() {
+        for photo in &mut content.photos {
+            photo.slug.clone_from(&content.slug);
+            slugs.insert(photo.image.clone(), content.slug.clone());
+        }
+    }
+    for photo in photos {
+        if let Some(slug) = slugs.get(&photo.image) {
+            photo.slug.clone_from(slug);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/photos.rs"]
+mod tests;
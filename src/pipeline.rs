@@ -0,0 +1,144 @@
+use crate::config::{ExternalLinksConfig, Marmite, PipelineStep};
+use crate::re;
+use log::{error, warn};
+use regex::{Captures, Regex};
+use std::fmt::Write as _;
+use url::Url;
+
+/// Steps available to the `html_pipeline`, besides `command`
+pub const BUILTIN_STEPS: [&str; 4] = [
+    "external_links",
+    "lazy_images",
+    "wrap_tables",
+    "heading_anchors",
+];
+
+/// Run the enabled steps of the `html_pipeline` in the configured order,
+/// a `command` step pipes the html through an external program and keeps
+/// the html unchanged when it fails, unknown steps are skipped.
+/// The `external_links` run last when enabled outside of the pipeline
+pub fn run_pipeline(html: &str, site: &Marmite) -> String {
+    let mut html = html.to_string();
+    for step in site.html_pipeline.iter().filter(|step| step.enabled) {
+        html = match step.step.as_str() {
+            "external_links" => external_links(&html, &site.url, &site.external_links),
+            "lazy_images" => lazy_images(&html),
+            "wrap_tables" => wrap_tables(&html),
+            "heading_anchors" => crate::parser::heading_anchors(&html, &site.heading_anchors),
+            "command" => run_command(&html, step),
+            name => {
+                warn!(
+                    "Unknown html_pipeline step `{name}`, expected `command` or one of {}",
+                    BUILTIN_STEPS.join(", ")
+                );
+                html
+            }
+        };
+    }
+    if site.external_links.enabled {
+        html = external_links(&html, &site.url, &site.external_links);
+    }
+    html
+}
+
+fn run_command(html: &str, step: &PipelineStep) -> String {
+    let Some(command) = step.command.as_deref().filter(|c| !c.trim().is_empty()) else {
+        warn!("html_pipeline `command` step without a `command`, skipped");
+        return html.to_string();
+    };
+    match crate::converters::run_with_stdin(command, html) {
+        Ok(output) => output,
+        Err(e) => {
+            error!("html_pipeline command `{command}` failed: {e}");
+            html.to_string()
+        }
+    }
+}
+
+/// Host of the site `url`, which may be set without the protocol
+fn site_host(site_url: &str) -> Option<String> {
+    let site_url = if site_url.contains("://") {
+        site_url.to_string()
+    } else {
+        format!("http://{site_url}")
+    };
+    Url::parse(&site_url)
+        .ok()?
+        .host_str()
+        .map(str::to_lowercase)
+}
+
+fn is_external(href: &str, host: Option<&str>) -> bool {
+    let href = if href.starts_with("//") {
+        format!("http:{href}")
+    } else {
+        href.to_string()
+    };
+    if !href.starts_with("http://") && !href.starts_with("https://") {
+        return false;
+    }
+    Url::parse(&href)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .is_some_and(|link_host| host != Some(link_host.as_str()))
+}
+
+/// Links pointing off-site get the `target` and `rel` of the config, by default
+/// opening in a new tab with `rel="noopener noreferrer"`, attributes already
+/// written on the link are kept
+pub fn external_links(html: &str, site_url: &str, config: &ExternalLinksConfig) -> String {
+    let tag_re = Regex::new(re::MATCH_A_OPEN_TAG).expect("Link tag regex should compile");
+    let href_re = Regex::new(re::CAPTURE_HREF_ATTRIBUTE).expect("Href regex should compile");
+    let host = site_host(site_url);
+    let mut rel: Vec<&str> = config.rel.split_whitespace().collect();
+    if config.nofollow && !rel.contains(&"nofollow") {
+        rel.push("nofollow");
+    }
+    let rel = rel.join(" ");
+    tag_re
+        .replace_all(html, |caps: &Captures| {
+            let tag = &caps[0];
+            let external = href_re
+                .captures(tag)
+                .is_some_and(|href| is_external(&href[1], host.as_deref()));
+            if !external {
+                return tag.to_string();
+            }
+            let mut attributes = String::new();
+            if !config.target.is_empty() && !tag.contains(" target=") {
+                let _ = write!(attributes, r#" target="{}""#, config.target);
+            }
+            if !rel.is_empty() && !tag.contains(" rel=") {
+                let _ = write!(attributes, r#" rel="{rel}""#);
+            }
+            format!("{}{attributes}>", &tag[..tag.len() - 1])
+        })
+        .to_string()
+}
+
+/// Images without a `loading` attribute are loaded when scrolled into view
+pub fn lazy_images(html: &str) -> String {
+    let re = Regex::new(re::MATCH_IMG_TAG).expect("Img tag regex should compile");
+    re.replace_all(html, |caps: &Captures| {
+        let tag = &caps[0];
+        if tag.contains(" loading=") {
+            return tag.to_string();
+        }
+        let (tag, close) = tag
+            .strip_suffix("/>")
+            .map_or_else(|| (&tag[..tag.len() - 1], ">"), |tag| (tag, "/>"));
+        format!(r#"{} loading="lazy"{close}"#, tag.trim_end())
+    })
+    .to_string()
+}
+
+/// Tables are wrapped in a `div.table-wrapper` scrolling horizontally on small screens
+pub fn wrap_tables(html: &str) -> String {
+    let re = Regex::new(re::MATCH_TABLE_ELEMENT).expect("Table regex should compile");
+    re.replace_all(html, r#"<div class="table-wrapper">${0}</div>"#)
+        .to_string()
+}
+
+#[cfg(test)]
+#[path = "tests/pipeline.rs"]
+mod tests;
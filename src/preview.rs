@@ -0,0 +1,107 @@
+use crate::site::Data;
+use log::{error, info, warn};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files changed on the `git diff` of `range` (e.g: `origin/main...HEAD`),
+/// canonicalized so they can be compared to the content sources,
+/// deleted files are left out as there is nothing to preview
+pub fn changed_files(input_folder: &Path, range: &str) -> Result<HashSet<PathBuf>, String> {
+    git_diff_files(input_folder, range)
+}
+
+/// Files staged for the next commit, canonicalized like `changed_files`
+pub fn staged_files(input_folder: &Path) -> Result<HashSet<PathBuf>, String> {
+    git_diff_files(input_folder, "--cached")
+}
+
+fn git_diff_files(input_folder: &Path, arg: &str) -> Result<HashSet<PathBuf>, String> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--relative", arg])
+        .current_dir(input_folder)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git diff {arg} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|file| fs::canonicalize(input_folder.join(file)).ok())
+        .collect())
+}
+
+/// Sources of the content affected by the changed files, the files that are not
+/// content (templates, config, fragments) are reported as not previewed
+pub fn affected_sources(site_data: &Data, changed: &HashSet<PathBuf>) -> HashSet<PathBuf> {
+    let sources: HashSet<PathBuf> = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter_map(|content| content.source_path.as_ref())
+        .filter_map(|path| fs::canonicalize(path).ok())
+        .filter(|path| changed.contains(path))
+        .collect();
+    for file in changed.difference(&sources) {
+        warn!(
+            "{} changed but is not a content source, pages depending on it are not previewed",
+            file.display()
+        );
+    }
+    sources
+}
+
+/// URLs of the previewed content, sorted
+pub fn preview_urls(site_data: &Data) -> Vec<String> {
+    let Some(sources) = &site_data.preview_sources else {
+        return Vec::new();
+    };
+    let mut urls: Vec<String> = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter(|content| is_previewed(content.source_path.as_deref(), sources))
+        .map(|content| format!("{}.html", content.slug))
+        .collect();
+    urls.sort();
+    urls
+}
+
+pub fn is_previewed(source_path: Option<&Path>, sources: &HashSet<PathBuf>) -> bool {
+    source_path
+        .and_then(|path| fs::canonicalize(path).ok())
+        .is_some_and(|path| sources.contains(&path))
+}
+
+/// Write `preview.json` listing the previewed URLs and print them
+pub fn write_preview_manifest(site_data: &Data, range: &str, output_path: &Path) {
+    let urls = preview_urls(site_data);
+    let base_url = site_data.site.url.trim_end_matches('/');
+    for url in &urls {
+        if base_url.is_empty() {
+            println!("{url}");
+        } else {
+            println!("{base_url}/{url}");
+        }
+    }
+    let manifest = serde_json::json!({ "range": range, "urls": urls });
+    let manifest_path = output_path.join("preview.json");
+    match serde_json::to_string_pretty(&manifest) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&manifest_path, json) {
+                error!("Failed to write {}: {e}", manifest_path.display());
+            } else {
+                info!("Generated preview of {} pages", urls.len());
+            }
+        }
+        Err(e) => error!("Failed to serialize preview.json: {e}"),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/preview.rs"]
+mod tests;
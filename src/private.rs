@@ -0,0 +1,64 @@
+use crate::config::Marmite;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extension of the content encrypted with `age` e.g: `private/plans.md.age`
+pub const PRIVATE_EXTENSION: &str = "age";
+
+/// Returns true if `path` is an encrypted content inside the `private_folder`
+pub fn is_private_source(path: &Path, content_dir: &Path, site: &Marmite) -> bool {
+    path.extension().is_some_and(|ext| ext == PRIVATE_EXTENSION)
+        && path.starts_with(content_dir.join(&site.private_folder))
+}
+
+/// Path of the content without the encryption extension, used for the slug
+/// and the date e.g: `private/2024-01-01-plans.md.age` -> `private/2024-01-01-plans.md`
+pub fn plain_path(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// Decrypt `path` with the `age` identity file, the decrypted content
+/// is kept in memory only and never written to disk. Only identity files are
+/// supported, `age` reads passphrases from the terminal and would block the build
+pub fn decrypt(path: &Path, identity: &Path) -> Result<String, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    if is_passphrase_encrypted(&data) {
+        return Err(format!(
+            "{} is encrypted with a passphrase, only identity files are supported, re-encrypt it with `age -r <recipient>`",
+            path.display()
+        ));
+    }
+    let output = Command::new("age")
+        .arg("--decrypt")
+        .arg("--identity")
+        .arg(identity)
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run age to decrypt {}: {e}", path.display()))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to decrypt {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|e| format!("Decrypted {} is not UTF-8: {e}", path.display()))
+}
+
+/// Returns true if the header of a binary `age` file has a `scrypt` stanza,
+/// the one written by `age --passphrase`
+fn is_passphrase_encrypted(data: &[u8]) -> bool {
+    let Some(header) = data.strip_prefix(b"age-encryption.org/v1\n") else {
+        return false;
+    };
+    header
+        .split(|byte| *byte == b'\n')
+        .take_while(|line| !line.starts_with(b"---"))
+        .any(|line| line.starts_with(b"-> scrypt "))
+}
+
+#[cfg(test)]
+#[path = "tests/private.rs"]
+mod tests;
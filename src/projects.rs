@@ -0,0 +1,216 @@
+use crate::config::ProjectsConfig;
+use chrono::{Duration, NaiveDateTime};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Sidecar file keeping the metadata fetched for each repository
+pub const PROJECTS_CACHE: &str = "projects.json";
+
+/// A repository of the portfolio, the description, stars and language are
+/// read from the forge API and kept in `.marmite/projects.json`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Project {
+    /// The repository as written on `projects.repos`
+    pub repo: String,
+    pub forge: String,
+    pub owner: String,
+    pub name: String,
+    #[serde(default)]
+    pub slug: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub stars: u64,
+    #[serde(default)]
+    pub forks: u64,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub fetched_at: Option<NaiveDateTime>,
+}
+
+/// Forges with a public repository API
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Forge {
+    GitHub,
+    Codeberg,
+}
+
+impl Forge {
+    fn name(self) -> &'static str {
+        match self {
+            Forge::GitHub => "github",
+            Forge::Codeberg => "codeberg",
+        }
+    }
+
+    fn host(self) -> &'static str {
+        match self {
+            Forge::GitHub => "github.com",
+            Forge::Codeberg => "codeberg.org",
+        }
+    }
+
+    fn api_url(self, owner: &str, name: &str) -> String {
+        match self {
+            Forge::GitHub => format!("https://api.github.com/repos/{owner}/{name}"),
+            Forge::Codeberg => format!("https://codeberg.org/api/v1/repos/{owner}/{name}"),
+        }
+    }
+}
+
+/// Parse `github:owner/name`, `codeberg:owner/name` or the repository url,
+/// repositories without a forge prefix are looked up on GitHub
+fn parse_repo(repo: &str) -> Option<(Forge, String, String)> {
+    let repo = repo.trim().trim_end_matches('/').trim_end_matches(".git");
+    let path = repo
+        .strip_prefix("https://")
+        .or_else(|| repo.strip_prefix("http://"))
+        .unwrap_or(repo);
+    let (forge, path) = if let Some(path) = path.strip_prefix("github:") {
+        (Forge::GitHub, path)
+    } else if let Some(path) = path.strip_prefix("codeberg:") {
+        (Forge::Codeberg, path)
+    } else if let Some(path) = path.strip_prefix("github.com/") {
+        (Forge::GitHub, path)
+    } else if let Some(path) = path.strip_prefix("codeberg.org/") {
+        (Forge::Codeberg, path)
+    } else {
+        (Forge::GitHub, path)
+    };
+    let (owner, name) = path.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') || owner.contains(':') {
+        return None;
+    }
+    Some((forge, owner.to_string(), name.to_string()))
+}
+
+/// The project known only from its repository, used until the metadata is fetched
+fn stub_project(repo: &str, forge: Forge, owner: &str, name: &str) -> Project {
+    Project {
+        repo: repo.to_string(),
+        forge: forge.name().to_string(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+        url: format!("https://{}/{owner}/{name}", forge.host()),
+        ..Default::default()
+    }
+}
+
+/// Fill the project with the repository JSON of the GitHub or Forgejo API,
+/// both name the fields the same except the stars and the homepage
+fn apply_metadata(project: &mut Project, forge: Forge, metadata: &Value) {
+    let text = |key: &str| {
+        metadata[key]
+            .as_str()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(String::from)
+    };
+    let (stars, homepage) = match forge {
+        Forge::GitHub => ("stargazers_count", "homepage"),
+        Forge::Codeberg => ("stars_count", "website"),
+    };
+    project.description = text("description");
+    project.homepage = text(homepage);
+    project.language = text("language");
+    project.stars = metadata[stars].as_u64().unwrap_or_default();
+    project.forks = metadata["forks_count"].as_u64().unwrap_or_default();
+    project.topics = metadata["topics"]
+        .as_array()
+        .map(|topics| {
+            topics
+                .iter()
+                .filter_map(|topic| topic.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(url) = text("html_url") {
+        project.url = url;
+    }
+}
+
+fn fetch_metadata(forge: Forge, owner: &str, name: &str) -> Result<Value, String> {
+    let json = ureq::get(forge.api_url(owner, name))
+        .header("Accept", "application/json")
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+/// Projects of `projects.repos` in the configured order, the repositories missing
+/// from the `cache` or cached longer than `refresh_hours` are fetched when `fetch`
+/// is set, a failed fetch keeps the cached metadata so builds work offline
+pub fn load_projects(
+    config: &ProjectsConfig,
+    cache: &mut BTreeMap<String, Project>,
+    fetch: bool,
+    now: NaiveDateTime,
+) -> Vec<Project> {
+    let refresh = Duration::try_hours(config.refresh_hours);
+    if refresh.is_none() {
+        warn!(
+            "projects.refresh_hours {} is out of range, the cached projects are not refreshed",
+            config.refresh_hours
+        );
+    }
+    let mut projects: Vec<Project> = Vec::new();
+    for repo in &config.repos {
+        let Some((forge, owner, name)) = parse_repo(repo) else {
+            error!("Invalid project repository '{repo}', use `github:owner/name` or its url");
+            continue;
+        };
+        let is_stale = cache
+            .get(repo)
+            .and_then(|project| project.fetched_at)
+            .is_none_or(|fetched_at| {
+                refresh
+                    .and_then(|refresh| fetched_at.checked_add_signed(refresh))
+                    .is_some_and(|expires| expires <= now)
+            });
+        if fetch && is_stale {
+            match fetch_metadata(forge, &owner, &name) {
+                Ok(metadata) => {
+                    info!("Fetched project metadata of {repo}");
+                    let mut project = stub_project(repo, forge, &owner, &name);
+                    apply_metadata(&mut project, forge, &metadata);
+                    project.fetched_at = Some(now);
+                    cache.insert(repo.clone(), project);
+                }
+                Err(e) => warn!("Failed to fetch project {repo}: {e}"),
+            }
+        }
+        let project = cache
+            .get(repo)
+            .cloned()
+            .unwrap_or_else(|| stub_project(repo, forge, &owner, &name));
+        projects.push(project);
+    }
+    assign_slugs(&mut projects);
+    projects
+}
+
+/// Slug of the project page from the repository name, prefixed with
+/// the owner when two repositories have the same name
+fn assign_slugs(projects: &mut [Project]) {
+    let mut names: HashMap<String, usize> = HashMap::new();
+    for project in projects.iter() {
+        *names.entry(slug::slugify(&project.name)).or_default() += 1;
+    }
+    for project in projects.iter_mut() {
+        let slug = slug::slugify(&project.name);
+        project.slug = if names[&slug] > 1 {
+            slug::slugify(format!("{}-{}", project.owner, project.name))
+        } else {
+            slug
+        };
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/projects.rs"]
+mod tests;
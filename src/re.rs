@@ -15,11 +15,12 @@ pub const MATCH_HTML_TAGS: &str = r"<[^>]*>";
 /// Used for converting markdown links to HTML links
 pub const CAPTURE_SLUG_ANCHOR_FROM_HREF: &str = r#"href=['\"]([^'\"]+)\.html(#[^'\"]+)?['\"]"#;
 
-/// Matches HTML heading tags (h1-h6) with optional anchor links inside
-/// Captures: 1) heading level, 2) optional anchor href, 3) heading content
+/// Matches the permalink of a heading, an `a.anchor` before or after its text
+/// or an empty link
+/// Captures: 1) the anchor href
 /// Used for extracting table of contents from HTML
-pub const CAPTURE_LEVEL_ANCHOR_TEXT_FROM_H_TAG: &str =
-    r#"<h([1-6])[^>]*>(?:<a[^>]*href=['\"]([^'\"]+)['\"][^>]*></a>)?(.*?)</h[1-6]>"#;
+pub const CAPTURE_HREF_FROM_HEADING_ANCHOR: &str =
+    r#"(?s)\s*<a[^>]*href=['\"]([^'\"]+)['\"](?:[^>]*class="anchor"[^>]*>.*?|[^>]*>)</a>\s*"#;
 
 /// Matches anchor tags in HTML
 /// Captures: 1) href attribute value, 2) link text
@@ -32,12 +33,189 @@ pub const CAPTURE_LINK_AND_TEXT_FROM_A_TAG: &str =
 /// Used for extracting image URLs from HTML content
 pub const CAPTURE_SRC_FROM_IMG_HTMLTAG: &str = r#"<img[^>]*src=['\"]([^'\"]+)['\"]"#;
 
+/// Matches img tags whose src ends with a `#light` or `#dark` fragment
+/// Captures: 1) attributes before src, 2) src without fragment, 3) scheme, 4) remaining attributes
+/// Used for switching images with the user's color scheme
+pub const CAPTURE_COLOR_SCHEME_IMG: &str =
+    r##"<img([^>]*?)src=['\"]([^'\"#]+)#(light|dark)['\"]([^>]*)>"##;
+
+/// Matches img tags pointing to video or audio files
+/// Captures: 1) the src attribute value, 2) the file extension
+/// Used for rendering `![alt](clip.mp4)` as video/audio elements
+pub const CAPTURE_MEDIA_SRC_FROM_IMG: &str =
+    r#"(?i)<img[^>]*src=['\"]([^'\"]+\.(mp4|webm|ogv|mov|mp3|ogg|wav|m4a|flac))['\"][^>]*>"#;
+
+/// Matches src and href attributes
+/// Captures: 1) the attribute up to the opening quote, 2) the url
+/// Used for rewriting media urls when slug normalization rules are enabled
+pub const CAPTURE_SRC_OR_HREF_VALUE: &str = r#"((?:src|href)=['\"])([^'\"]+)"#;
+
+/// Matches the alt attribute of an img tag
+/// Captures: 1) the alt text
+pub const CAPTURE_ALT_FROM_IMG: &str = r#"alt=['\"]([^'\"]*)['\"]"#;
+
+/// Matches markdown images with an empty alt text e.g: `![](photo.jpg "title")`
+/// Captures: 1) the link target including the optional title, 2) the image src
+/// Used for finding and filling missing alt text
+pub const CAPTURE_MARKDOWN_IMAGE_WITHOUT_ALT: &str = r"!\[\s*\]\((\s*<?([^)\s>]+)>?[^)]*)\)";
+
+/// Matches img tags including their attributes
+pub const MATCH_IMG_HTMLTAG: &str = r"<img\b[^>]*>";
+
+/// Matches code blocks rendered from fenced code with a language
+/// Captures: 1) the fence language, 2) the html escaped code
+/// Used for replacing fences like `dot` and `plantuml` with rendered SVGs
+pub const CAPTURE_LANGUAGE_AND_CODE_FROM_PRE: &str =
+    r#"(?s)<pre><code class="language-([\w+-]+)">(.*?)</code></pre>"#;
+
+/// Matches code blocks rendered from fenced code with an info string
+/// Captures: 1) the info string, e.g: `rust,linenos,hl_lines=3-5`, 2) the html escaped code
+/// Used for syntax highlighting and line numbers of code blocks
+pub const CAPTURE_INFO_AND_CODE_FROM_PRE: &str =
+    r#"(?s)<pre><code class="language-([^"]+)">(.*?)</code></pre>"#;
+
+/// Matches the math rendered by the `math_dollars` extension
+/// Captures: 1) `inline` or `display`, 2) the html escaped TeX
+pub const CAPTURE_MATH_SPAN: &str = r#"(?s)<span data-math-style="(inline|display)">(.*?)</span>"#;
+
+/// Matches the elements whose text is kept as written, e.g: code samples of templates
+pub const MATCH_CODE_OR_SCRIPT_ELEMENT: &str =
+    r"(?is)<pre\b.*?</pre>|<code\b.*?</code>|<script\b.*?</script>";
+
+/// Matches build-time tokens on the content body e.g: `{{ site.url }}`
+/// Captures: 1) `site`, `build` or `content`, 2) the dotted path of the value
+pub const CAPTURE_BUILD_TOKEN: &str = r"\{\{\s*(site|build|content)\.([\w.]+)\s*\}\}";
+
+/// Matches a line opening a profile conditional block e.g: `<!-- if profile == "preview" -->`
+/// Captures: 1) `==` or `!=`, 2) the profile name
+pub const CAPTURE_PROFILE_CONDITION: &str =
+    r#"^<!--\s*if\s+profile\s*(==|!=)\s*["']([^"']*)["']\s*-->$"#;
+
+/// Matches a line switching to the other branch of a profile conditional block
+pub const MATCH_ELSE_MARKER: &str = r"^<!--\s*else\s*-->$";
+
+/// Matches a line closing a profile conditional block
+pub const MATCH_ENDIF_MARKER: &str = r"^<!--\s*endif\s*-->$";
+
+/// Matches the checkbox of a task list item e.g: `<li><input type="checkbox" checked="" disabled="" />`
+/// Captures: 1) the opening `<li>` tag, 2) the attributes before `disabled`
+pub const CAPTURE_TASK_CHECKBOX: &str =
+    r#"(<li\b[^>]*>)<input type="checkbox"([^>]*?)(?: disabled="")? />"#;
+
+/// Matches a line inlining another markdown file e.g: `{{ include "snippets/warning.md" }}`
+/// Captures: 1) the path of the included file
+pub const CAPTURE_INCLUDE_DIRECTIVE: &str = r#"^\{\{\s*include\s+["']([^"']+)["']\s*\}\}$"#;
+
+/// Matches the opening tag of a link
+pub const MATCH_A_OPEN_TAG: &str = r"(?i)<a\s[^>]*>";
+
+/// Matches the href attribute of a tag
+/// Captures: 1) the href value
+pub const CAPTURE_HREF_ATTRIBUTE: &str = r#"\shref="([^"]*)""#;
+
+/// Matches an img tag
+pub const MATCH_IMG_TAG: &str = r"(?i)<img\b[^>]*>";
+
+/// Matches a table element with its content
+pub const MATCH_TABLE_ELEMENT: &str = r"(?is)<table\b.*?</table>";
+
+/// Matches a heading element
+/// Captures: 1) heading level, 2) the attributes, 3) heading content
+pub const CAPTURE_HEADING_ELEMENT: &str = r"(?is)<h([1-6])(\s[^>]*)?>(.*?)</h[1-6]>";
+
+/// Matches the id attribute of a tag
+/// Captures: 1) the id value
+pub const CAPTURE_ID_ATTRIBUTE: &str = r#"(?:^|\s)id="([^"]*)""#;
+
+/// Matches a line starting a footnote definition e.g: `[^note]: The source`
+pub const MATCH_FOOTNOTE_DEFINITION: &str = r"^\[\^[^\]\s]+\]:";
+
+/// Matches the footnote references and the footnotes section rendered by comrak
+pub const MATCH_FOOTNOTE_REF_OR_SECTION: &str =
+    r#"(?s)<sup class="footnote-ref">.*?</sup>|<section class="footnotes"[^>]*>.*?</section>"#;
+
+/// Matches the first level heading of an HTML document
+/// Captures: 1) the heading inner html
+/// Used for extracting the title of `.html` content
+pub const CAPTURE_H1_TEXT: &str = r"(?s)<h1[^>]*>(.*?)</h1>";
+
+/// Matches pandoc style citations like `[@key]` or `[@key1; @key2]`
+/// Captures: 1) the citation keys separated by `;`
+/// Used for replacing citations with links to the bibliography
+pub const CAPTURE_CITATION_KEYS: &str = r"\[(@[\w:.-]+(?:\s*;\s*@[\w:.-]+)*)\]";
+
+/// Matches `<pre>` blocks including their content
+/// Used for skipping code blocks when counting words
+pub const MATCH_PRE_BLOCKS: &str = r"(?s)<pre[^>]*>.*?</pre>";
+
+/// Matches opening `<a>` tags linking to a `.html` page
+/// Captures: 1) the tag without the closing `>`, 2) the page path without `.html`
+/// Used for adding the link preview attributes
+pub const CAPTURE_INTERNAL_LINK_TAG: &str =
+    r##"(<a\s[^>]*?href=['"]([^'"#]+)\.html(?:#[^'"]*)?['"][^>]*?)>"##;
+
+/// Matches the `<!-- more -->` marker on its own line
+/// Used for splitting the summary from the rest of the content
+pub const MATCH_MORE_MARKER: &str = r"(?m)^[ \t]*<!--\s*more\s*-->[ \t]*$";
+
+/// Matches `<p>` paragraphs including their content
+/// Used for taking the first paragraphs as the content summary
+pub const MATCH_PARAGRAPHS: &str = r"(?s)<p[\s>].*?</p>";
+
+/// Matches `<meta>` and `<link>` tags
+/// Captures: 1) the tag name, 2) the attributes
+/// Used for reading the description and favicon of bookmarked pages
+pub const CAPTURE_META_OR_LINK_TAG: &str = r"(?is)<(meta|link)\s([^>]*)>";
+
+/// Matches the attributes of an HTML tag
+/// Captures: 1) the name, 2) the double quoted value, 3) the single quoted value
+pub const CAPTURE_HTML_ATTRIBUTES: &str = r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#;
+
+/// Matches the `<title>` of a page
+/// Captures: 1) the title text
+pub const CAPTURE_TITLE_TAG: &str = r"(?is)<title[^>]*>(.*?)</title>";
+
+/// Matches HTML entities like `&amp;` or `&#39;`
+pub const MATCH_HTML_ENTITIES: &str = r"&#?\w+;";
+
+/// Matches `<pre>` and `<code>` blocks including their content
+/// Used for skipping code when processing citations
+pub const MATCH_CODE_BLOCKS: &str = r"(?s)<pre[^>]*>.*?</pre>|<code[^>]*>.*?</code>";
+
+/// Matches markdown and HTML fragments that are not prose
+/// inline code, link targets, tags, URLs, template expressions, wikilinks,
+/// citations and reference definitions
+/// Used for skipping non prose text when spell checking
+pub const MATCH_SPELLCHECK_SKIP: &str = r"`[^`]*`|\]\([^)]*\)|<[^>]*>|https?://\S+|\{\{.*?\}\}|\{%.*?%\}|\[\[[^\]]*\]\]|\[@[^\]]*\]|^\s*\[[^\]]+\]:.*$";
+
+/// Matches a word made of letters, allowing inner apostrophes e.g: `don't`
+/// Used for splitting prose into words when spell checking
+pub const MATCH_WORD: &str = r"\p{L}+(?:['’]\p{L}+)*";
+
 /// Matches wikilink anchor tags with data-wikilink attribute
 /// Captures: 1) href attribute value, 2) link text content
 /// Used for fixing Obsidian wikilinks to use proper slugs instead of filename-based hrefs
 pub const CAPTURE_WIKILINK_HREF_AND_TITLE: &str =
     r#"<a[^>]*href=['\"]([^'\"]+)['\"][^>]*data-wikilink=['\"]true['\"][^>]*>(.*?)</a>"#;
 
+/// Captures the inside of Tera expressions and statements
+/// Captures: 1) `{{ expression }}` body, 2) `{% statement %}` body
+/// Used by the theme check to find variables and referenced templates
+pub const CAPTURE_TERA_TAGS: &str = r"(?s)\{\{-?(.*?)-?\}\}|\{%-?(.*?)-?%\}";
+
+/// Matches Tera comments `{# ... #}`
+/// Used by the theme check to skip commented out template code
+pub const MATCH_TERA_COMMENTS: &str = r"(?s)\{#.*?#\}";
+
+/// Captures string literals inside a Tera expression or statement
+/// Captures: 1) double quoted, 2) single quoted, 3) backtick quoted text
+/// Used by the theme check to find referenced templates and ignore literal text
+pub const CAPTURE_TERA_STRINGS: &str = r#""([^"]*)"|'([^']*)'|`([^`]*)`"#;
+
+/// Matches dotted identifiers in a Tera expression, e.g. `content.title`
+/// Used by the theme check to find context variables and their fields
+pub const MATCH_TERA_IDENTIFIER: &str = r"[A-Za-z_][A-Za-z0-9_]*(?:\.[A-Za-z_][A-Za-z0-9_]*)*";
+
 // === Shortcode Patterns ===
 
 /// Default pattern for HTML comment-style shortcodes
@@ -0,0 +1,105 @@
+use log::debug;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+
+/// Markdown rendered by the previous builds of `--watch` and `--serve`, a change
+/// to the templates or to the config doesn't render the unchanged markdown again
+static RENDER_CACHE: LazyLock<Mutex<RenderCache>> =
+    LazyLock::new(|| Mutex::new(RenderCache::default()));
+
+#[derive(Debug, Default)]
+pub struct RenderCache {
+    enabled: bool,
+    build: u64,
+    hits: usize,
+    /// Rendered html by the hash of the markdown and the parser options,
+    /// with the build that last used it
+    entries: HashMap<u64, (String, u64)>,
+}
+
+impl RenderCache {
+    /// Start a new build, the entries not used by the previous build are dropped
+    pub fn start_build(&mut self) {
+        if self.build > 0 {
+            debug!(
+                "Reused {} of {} rendered markdown bodies",
+                self.hits,
+                self.entries.len()
+            );
+        }
+        let previous = self.build;
+        self.entries.retain(|_, (_, build)| *build >= previous);
+        self.build += 1;
+        self.hits = 0;
+    }
+
+    pub fn get(&mut self, key: u64) -> Option<String> {
+        let build = self.build;
+        let (html, used) = self.entries.get_mut(&key)?;
+        *used = build;
+        self.hits += 1;
+        Some(html.clone())
+    }
+
+    pub fn insert(&mut self, key: u64, html: String) {
+        self.entries.insert(key, (html, self.build));
+    }
+}
+
+/// Keep the rendered markdown in memory between the builds of this process
+pub fn enable() {
+    if let Ok(mut cache) = RENDER_CACHE.lock() {
+        cache.enabled = true;
+    }
+}
+
+/// Called at the start of every build
+pub fn start_build() {
+    if let Ok(mut cache) = RENDER_CACHE.lock() {
+        if cache.enabled {
+            cache.start_build();
+        }
+    }
+}
+
+pub fn cache_key(markdown: &str, options: &impl std::fmt::Debug) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    markdown.hash(&mut hasher);
+    format!("{options:?}").hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The html of `render` for this markdown and options, reused from a previous
+/// build when the cache is enabled. The markdown is rendered without holding
+/// the lock so the contents are still rendered in parallel
+pub fn cached(
+    markdown: &str,
+    options: &impl std::fmt::Debug,
+    render: impl FnOnce() -> String,
+) -> String {
+    let key = {
+        let Ok(mut cache) = RENDER_CACHE.lock() else {
+            return render();
+        };
+        if !cache.enabled {
+            drop(cache);
+            return render();
+        }
+        let key = cache_key(markdown, options);
+        if let Some(html) = cache.get(key) {
+            return html;
+        }
+        key
+    };
+    let html = render();
+    if let Ok(mut cache) = RENDER_CACHE.lock() {
+        cache.insert(key, html.clone());
+    }
+    html
+}
+
+#[cfg(test)]
+#[path = "tests/render_cache.rs"]
+mod tests;
@@ -0,0 +1,92 @@
+use crate::content::Content;
+use chrono::Datelike;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A post of the year and the number of posts and pages linking to it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LinkedPost {
+    pub title: String,
+    pub slug: String,
+    pub inbound_links: usize,
+}
+
+/// Stats of the posts published in a year, rendered on `review-{year}.html`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct YearReview {
+    pub year: i32,
+    pub posts: usize,
+    pub words: usize,
+    /// `(tag, posts)` of the most used tags, the most used first
+    pub top_tags: Vec<(String, usize)>,
+    /// Posts of the year with inbound links, the most linked first
+    pub most_linked: Vec<LinkedPost>,
+}
+
+/// Review of every year with published posts, the latest first, the current
+/// year is only reviewed with `include_current` as it is not over yet
+pub fn collect_reviews(
+    posts: &[Content],
+    top: usize,
+    current_year: i32,
+    include_current: bool,
+) -> Vec<YearReview> {
+    let mut years: BTreeMap<i32, Vec<&Content>> = BTreeMap::new();
+    for post in posts {
+        if post.stream.as_deref() == Some("draft") {
+            continue;
+        }
+        let Some(date) = post.date else {
+            continue;
+        };
+        if date.year() < current_year || (include_current && date.year() == current_year) {
+            years.entry(date.year()).or_default().push(post);
+        }
+    }
+    years
+        .into_iter()
+        .rev()
+        .map(|(year, posts)| review_year(year, &posts, top))
+        .collect()
+}
+
+fn review_year(year: i32, posts: &[&Content], top: usize) -> YearReview {
+    let mut tags: HashMap<&str, usize> = HashMap::new();
+    for tag in posts.iter().flat_map(|post| &post.tags) {
+        *tags.entry(tag.as_str()).or_default() += 1;
+    }
+    let mut top_tags: Vec<(String, usize)> = tags
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect();
+    top_tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    top_tags.truncate(top);
+
+    let mut most_linked: Vec<LinkedPost> = posts
+        .iter()
+        .filter(|post| !post.back_links.is_empty())
+        .map(|post| LinkedPost {
+            title: post.title.clone(),
+            slug: post.slug.clone(),
+            inbound_links: post.back_links.len(),
+        })
+        .collect();
+    most_linked.sort_by(|a, b| {
+        b.inbound_links
+            .cmp(&a.inbound_links)
+            .then(a.title.cmp(&b.title))
+    });
+    most_linked.truncate(top);
+
+    YearReview {
+        year,
+        posts: posts.len(),
+        words: posts.iter().map(|post| post.word_count).sum(),
+        top_tags,
+        most_linked,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/review.rs"]
+mod tests;
@@ -0,0 +1,83 @@
+use crate::content::Content;
+use crate::site::Data;
+use log::{error, info};
+use std::fs;
+use std::path::Path;
+
+/// User agents of the crawlers collecting content to train AI models
+pub const AI_CRAWLERS: [&str; 10] = [
+    "GPTBot",
+    "ChatGPT-User",
+    "CCBot",
+    "Google-Extended",
+    "anthropic-ai",
+    "ClaudeBot",
+    "PerplexityBot",
+    "Bytespider",
+    "Applebot-Extended",
+    "meta-externalagent",
+];
+
+/// First line of the rules appended to `robots.txt`, everything after it is
+/// replaced on every build
+const RULES_MARKER: &str = "# Generated by marmite for `noai: true` content";
+
+/// `robots.txt` rules disallowing the `noai` content to the AI crawlers,
+/// `base_path` is the path of `site.url` e.g: `/blog` for `https://example.com/blog`
+pub fn noai_rules<'a>(contents: impl Iterator<Item = &'a Content>, base_path: &str) -> String {
+    let mut slugs: Vec<&str> = contents
+        .filter(|content| content.noai)
+        .map(|content| content.slug.as_str())
+        .collect();
+    if slugs.is_empty() {
+        return String::new();
+    }
+    slugs.sort_unstable();
+    let mut rules = format!("{RULES_MARKER}\n");
+    for crawler in AI_CRAWLERS {
+        rules.push_str(&format!("User-agent: {crawler}\n"));
+    }
+    for slug in slugs {
+        rules.push_str(&format!("Disallow: {base_path}/{slug}.html\n"));
+    }
+    rules
+}
+
+/// Append the `noai` rules to the `robots.txt` of the output folder, creating
+/// it when the site has none, rules from a previous build are replaced
+pub fn write_robots_rules(site_data: &Data, output_path: &Path) {
+    let base_path = url::Url::parse(&site_data.site.url)
+        .map(|url| url.path().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+    let rules = noai_rules(
+        site_data
+            .posts
+            .iter()
+            .chain(&site_data.pages)
+            .chain(&site_data.expired),
+        &base_path,
+    );
+    let robots_path = output_path.join("robots.txt");
+    let existing = fs::read_to_string(&robots_path).unwrap_or_default();
+    let existing = existing
+        .split_once(RULES_MARKER)
+        .map_or(existing.as_str(), |(before, _)| before)
+        .trim_end();
+    if rules.is_empty() && !robots_path.exists() {
+        return;
+    }
+    let robots = match (existing.is_empty(), rules.is_empty()) {
+        (true, _) => rules,
+        (false, true) => format!("{existing}\n"),
+        (false, false) => format!("{existing}\n\n{rules}"),
+    };
+    if let Err(e) = fs::write(&robots_path, robots) {
+        error!("Failed to write {}: {e}", robots_path.display());
+    } else {
+        info!("Generated robots.txt rules for noai content");
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/robots.rs"]
+mod tests;
@@ -0,0 +1,149 @@
+use crate::config::{FrontmatterSchema, Marmite};
+use crate::content::{apply_frontmatter_aliases, get_terms, try_to_parse_date};
+use crate::parser::parse_front_matter;
+use crate::site::{ARCHIVE_INTROS_FOLDER, SNIPPETS_FOLDER, TAG_LANDINGS_FOLDER};
+use crate::spellcheck::collect_markdown_files;
+use frontmatter_gen::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Frontmatter keys holding a date, any other format fails the build
+const DATE_KEYS: [&str; 4] = ["date", "updated", "last_modified", "expires"];
+
+/// A frontmatter value breaking the `frontmatter_schema` and the line of its key,
+/// missing keys are reported on the first line
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Line of each top level key of a YAML (`key:`) or TOML (`key =`) frontmatter
+fn key_lines(text: &str) -> HashMap<String, usize> {
+    let mut lines = text.lines().enumerate().skip_while(|(_, l)| l.is_empty());
+    let Some(delimiter) = lines
+        .next()
+        .map(|(_, line)| line.trim())
+        .filter(|line| *line == "---" || *line == "+++")
+    else {
+        return HashMap::new();
+    };
+    lines
+        .take_while(|(_, line)| line.trim() != delimiter)
+        .filter(|(_, line)| !line.starts_with([' ', '\t', '#', '-']))
+        .filter_map(|(index, line)| {
+            let key = line.split([':', '=']).next()?.trim().trim_matches('"');
+            (!key.is_empty()).then(|| (key.to_string(), index + 1))
+        })
+        .collect()
+}
+
+/// Check the frontmatter of a source: the dates must be parseable, the `required`
+/// keys present and the keys of `allowed` must only have the listed values,
+/// keys renamed by `frontmatter_aliases` are checked with their canonical name
+pub fn validate_source(
+    text: &str,
+    schema: &FrontmatterSchema,
+    aliases: &HashMap<String, String>,
+) -> Vec<SchemaViolation> {
+    let mut frontmatter = match parse_front_matter(text) {
+        Ok((frontmatter, _)) => frontmatter,
+        Err(e) => {
+            return vec![SchemaViolation {
+                line: 1,
+                message: format!("invalid frontmatter: {e}"),
+            }]
+        }
+    };
+    apply_frontmatter_aliases(&mut frontmatter, aliases);
+    let mut lines = key_lines(text);
+    for (alias, canonical) in aliases {
+        if let Some(line) = lines.get(alias).copied() {
+            lines.entry(canonical.clone()).or_insert(line);
+        }
+    }
+    let line_of = |key: &str| lines.get(key).copied().unwrap_or(1);
+
+    let mut violations = Vec::new();
+    for key in DATE_KEYS {
+        let Some(Value::String(input)) = frontmatter.get(key) else {
+            continue;
+        };
+        if try_to_parse_date(input).is_err() {
+            violations.push(SchemaViolation {
+                line: line_of(key),
+                message: format!(
+                    "invalid `{key}` '{input}', expected YYYY-MM-DD, YYYY-MM-DD HH:MM or YYYY-MM-DD HH:MM:SS"
+                ),
+            });
+        }
+    }
+    for key in &schema.required {
+        if frontmatter
+            .get(key)
+            .is_none_or(|value| matches!(value, Value::Null))
+        {
+            violations.push(SchemaViolation {
+                line: 1,
+                message: format!("missing required `{key}`"),
+            });
+        }
+    }
+    let mut allowed: Vec<(&String, &Vec<String>)> = schema.allowed.iter().collect();
+    allowed.sort();
+    for (key, values) in allowed {
+        for value in get_terms(&frontmatter, key) {
+            if !values.contains(&value) {
+                violations.push(SchemaViolation {
+                    line: line_of(key),
+                    message: format!(
+                        "`{key}` '{value}' is not allowed, expected one of: {}",
+                        values.join(", ")
+                    ),
+                });
+            }
+        }
+    }
+    violations.sort_by_key(|violation| violation.line);
+    violations
+}
+
+/// Validate the markdown sources of the content folder, fragments, the
+/// `tags` and `archive` intros and the `snippets` are skipped, returns `file:line: message`
+/// for every violation with the path relative to `input_folder`
+pub fn validate_content(content_folder: &Path, input_folder: &Path, site: &Marmite) -> Vec<String> {
+    let skipped = [
+        content_folder.join(TAG_LANDINGS_FOLDER),
+        content_folder.join(ARCHIVE_INTROS_FOLDER),
+        content_folder.join(SNIPPETS_FOLDER),
+    ];
+    let mut errors = Vec::new();
+    for path in collect_markdown_files(content_folder) {
+        let is_fragment = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('_'));
+        if is_fragment || skipped.iter().any(|folder| path.starts_with(folder)) {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let display_path = path.strip_prefix(input_folder).unwrap_or(&path);
+        for violation in validate_source(&text, &site.frontmatter_schema, &site.frontmatter_aliases)
+        {
+            errors.push(format!(
+                "{}:{}: {}",
+                display_path.display(),
+                violation.line,
+                violation.message
+            ));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+#[path = "tests/schema.rs"]
+mod tests;
@@ -0,0 +1,98 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// A folder of the content folder, listed on `section-{slug}.html` with
+/// `enable_sections`, e.g: `content/docs/install/` is the `docs/install` section
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Section {
+    pub path: String,
+    pub name: String,
+    pub slug: String,
+    /// Path of the parent section, `None` for the top level sections
+    pub parent: Option<String>,
+    /// `(name, url)` of the subsections, sorted by path
+    pub children: Vec<(String, String)>,
+}
+
+/// Section of a content source, the folder relative to the content folder
+/// with `/` separators, `None` for the content at the root of the content folder
+pub fn section_of(source_path: &Path, content_dir: &Path) -> Option<String> {
+    let folder = source_path.parent()?.strip_prefix(content_dir).ok()?;
+    let segments: Vec<String> = folder
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("/"))
+    }
+}
+
+/// Slug of the section page, e.g: `docs/install` -> `section-docs-install`
+pub fn section_slug(path: &str) -> String {
+    let segments: Vec<String> = path.split('/').map(slug::slugify).collect();
+    format!("section-{}", segments.join("-"))
+}
+
+/// Display name of the section from its folder name, e.g: `getting_started` -> `Getting started`
+pub fn section_name(path: &str) -> String {
+    let folder = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .replace(['-', '_'], " ");
+    let mut chars = folder.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// `(name, url)` of the section and its ancestors, from the top level section down
+pub fn breadcrumbs(path: &str) -> Vec<(String, String)> {
+    let segments: Vec<&str> = path.split('/').collect();
+    (1..=segments.len())
+        .map(|depth| {
+            let ancestor = segments[..depth].join("/");
+            (
+                section_name(&ancestor),
+                format!("{}.html", section_slug(&ancestor)),
+            )
+        })
+        .collect()
+}
+
+/// Every section holding content and their ancestors, sorted by path
+pub fn collect_sections<'a>(paths: impl Iterator<Item = &'a String>) -> Vec<Section> {
+    let mut all_paths = BTreeSet::new();
+    for path in paths {
+        let segments: Vec<&str> = path.split('/').collect();
+        for depth in 1..=segments.len() {
+            all_paths.insert(segments[..depth].join("/"));
+        }
+    }
+    let mut children: BTreeMap<&str, Vec<(String, String)>> = BTreeMap::new();
+    for path in &all_paths {
+        if let Some((parent, _)) = path.rsplit_once('/') {
+            children
+                .entry(parent)
+                .or_default()
+                .push((section_name(path), format!("{}.html", section_slug(path))));
+        }
+    }
+    all_paths
+        .iter()
+        .map(|path| Section {
+            path: path.clone(),
+            name: section_name(path),
+            slug: section_slug(path),
+            parent: path.rsplit_once('/').map(|(parent, _)| parent.to_string()),
+            children: children.remove(path.as_str()).unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/sections.rs"]
+mod tests;
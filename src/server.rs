@@ -1,8 +1,11 @@
 use chrono::Utc;
 use log::{error, info, warn};
 use serde_json::json;
+use std::collections::HashMap;
 use std::io::{Cursor, ErrorKind};
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use std::process::Command;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
@@ -17,6 +20,60 @@ use urlencoding::decode;
 const FALLBACK_BIND_ADDRESS: &str = "0.0.0.0:0";
 const LIVE_RELOAD_SCRIPT_PATH: &str = "__marmite__/livereload.js";
 const LIVE_RELOAD_WS_PATH: &str = "/__marmite__/livereload";
+const PREVIEW_SCRIPT_PATH: &str = "__marmite__/preview.js";
+const EDITOR_OPEN_PATH: &str = "/__marmite__/open";
+/// Shows the source of the current page (from `sources.json`) with a link
+/// to open it in the editor, pressing `e` follows the same link.
+/// With `--editor` the file is opened through the server and alt+click
+/// on a heading opens the source at the heading line.
+const PREVIEW_SCRIPT: &str = r#"(() => {
+    const page = decodeURIComponent(window.location.pathname).replace(/^\//, "") || "index.html";
+    fetch("/sources.json", { cache: "no-store" })
+        .then((response) => (response.ok ? response.json() : null))
+        .then((sources) => {
+            const entry = sources && sources.files[page];
+            if (!entry) {
+                return;
+            }
+            const editorUrl = sources.editor_url.replace("{file}", entry.file);
+            const open = (line) => {
+                if (!sources.editor) {
+                    window.location.href = editorUrl;
+                    return;
+                }
+                const query = `page=${encodeURIComponent(page)}&line=${line}`;
+                fetch(`/__marmite__/open?${query}`, { method: "POST" });
+            };
+            const badge = document.createElement("a");
+            badge.href = editorUrl;
+            badge.addEventListener("click", (event) => {
+                event.preventDefault();
+                open(1);
+            });
+            badge.textContent = `\u270E ${entry.source}`;
+            badge.title = `Open in editor (press e), template: ${entry.template}`;
+            badge.style.cssText = "position:fixed;bottom:1rem;right:1rem;z-index:9999;padding:.25rem .5rem;font:12px monospace;background:#222;color:#fff;border-radius:4px;opacity:.8;text-decoration:none;";
+            document.body.appendChild(badge);
+            document.addEventListener("keydown", (event) => {
+                const target = event.target;
+                if (event.key !== "e" || event.ctrlKey || event.metaKey || event.altKey) {
+                    return;
+                }
+                if (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName)) {
+                    return;
+                }
+                open(1);
+            });
+            document.addEventListener("click", (event) => {
+                const heading = event.altKey && event.target.closest("h1[id],h2[id],h3[id],h4[id],h5[id],h6[id]");
+                if (heading && entry.headings[heading.id]) {
+                    event.preventDefault();
+                    open(entry.headings[heading.id]);
+                }
+            });
+        })
+        .catch(() => {});
+})();"#;
 const LIVE_RELOAD_SCRIPT: &str = r#"(() => {
     const isHttps = window.location.protocol === "https:";
     const hostPart = window.location.hostname.includes(":") ? `[${window.location.hostname}]` : window.location.hostname;
@@ -49,7 +106,12 @@ const LIVE_RELOAD_SCRIPT: &str = r#"(() => {
     connect();
 })();"#;
 
-pub fn start(bind_address: &str, output_folder: &Arc<PathBuf>, live_reload: Option<&LiveReload>) {
+pub fn start(
+    bind_address: &str,
+    output_folder: &Arc<PathBuf>,
+    live_reload: Option<&LiveReload>,
+    editor: Option<&str>,
+) {
     let server = match Server::http(bind_address) {
         Ok(server) => server,
         Err(e) => {
@@ -84,14 +146,18 @@ pub fn start(bind_address: &str, output_folder: &Arc<PathBuf>, live_reload: Opti
                 }
             }
 
-            let response =
-                match handle_request(&request, output_folder.as_path(), live_reload.is_some()) {
-                    Ok(response) => response,
-                    Err(err) => {
-                        error!("Error handling request: {err:?}");
-                        Response::from_string("Internal Server Error").with_status_code(500)
-                    }
-                };
+            let response = match handle_request(
+                &request,
+                output_folder.as_path(),
+                live_reload.is_some(),
+                editor,
+            ) {
+                Ok(response) => response,
+                Err(err) => {
+                    error!("Error handling request: {err:?}");
+                    Response::from_string("Internal Server Error").with_status_code(500)
+                }
+            };
 
             if let Err(err) = request.respond(response) {
                 error!("Error sending response: {err:?}");
@@ -116,14 +182,18 @@ pub fn start(bind_address: &str, output_folder: &Arc<PathBuf>, live_reload: Opti
             }
         }
 
-        let response =
-            match handle_request(&request, output_folder.as_path(), live_reload.is_some()) {
-                Ok(response) => response,
-                Err(err) => {
-                    error!("Error handling request: {err:?}");
-                    Response::from_string("Internal Server Error").with_status_code(500)
-                }
-            };
+        let response = match handle_request(
+            &request,
+            output_folder.as_path(),
+            live_reload.is_some(),
+            editor,
+        ) {
+            Ok(response) => response,
+            Err(err) => {
+                error!("Error handling request: {err:?}");
+                Response::from_string("Internal Server Error").with_status_code(500)
+            }
+        };
 
         if let Err(err) = request.respond(response) {
             error!("Failed to send response: {err:?}");
@@ -136,6 +206,7 @@ fn handle_request(
     request: &tiny_http::Request,
     output_folder: &Path,
     live_reload_enabled: bool,
+    editor: Option<&str>,
 ) -> Result<Response<Cursor<Vec<u8>>>, String> {
     let decoded_url = match decode(request.url()) {
         Ok(decoded) => decoded.into_owned(),
@@ -156,13 +227,57 @@ fn handle_request(
         return Ok(response);
     }
 
+    if live_reload_enabled && decoded_url == format!("/{PREVIEW_SCRIPT_PATH}") {
+        let mut response = Response::from_string(PREVIEW_SCRIPT);
+        let js_header = Header::from_bytes("Content-Type", "application/javascript")
+            .map_err(|()| "invalid preview header".to_string())?;
+        response.add_header(js_header);
+        return Ok(response);
+    }
+
+    // the query is decoded value by value, an encoded `&` or `=` is part of the value
+    if let Some(query) = editor_query(request.url()) {
+        let Some(editor) = editor else {
+            return Ok(
+                Response::from_string("Editor not configured, use --editor").with_status_code(404)
+            );
+        };
+        if request.method() != &Method::Post {
+            return Ok(Response::from_string("Method Not Allowed").with_status_code(405));
+        }
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv(name))
+                .map(|header| header.value.as_str())
+        };
+        if !is_local_request(request.remote_addr(), header("Host"), header("Origin")) {
+            warn!(
+                "Refused to open the editor for a request from {:?}",
+                request.remote_addr()
+            );
+            return Ok(Response::from_string("Forbidden").with_status_code(403));
+        }
+        return Ok(match open_in_editor(editor, output_folder, query) {
+            Ok(file) => {
+                info!("Opened {file} in the editor");
+                Response::from_string(file)
+            }
+            Err(err) => {
+                warn!("Failed to open the editor: {err}");
+                Response::from_string(err).with_status_code(400)
+            }
+        });
+    }
+
     let request_path = match decoded_url.as_str() {
         "/" => "index.html",
         url => &url[1..], // Remove the leading '/'
     };
 
     let file_path = output_folder.join(request_path);
-    let error_path = output_folder.join("404.html");
+    let error_path = nearest_error_page(output_folder, request_path);
 
     if file_path.is_file() {
         match File::open(&file_path) {
@@ -173,8 +288,10 @@ fn handle_request(
                     let original_buffer = buffer.clone();
                     if let Ok(mut html) = String::from_utf8(buffer) {
                         if !html.contains(LIVE_RELOAD_SCRIPT_PATH) {
-                            let snippet =
-                                format!("\n<script src=\"/{LIVE_RELOAD_SCRIPT_PATH}\"></script>\n");
+                            let snippet = format!(
+                                "\n<script src=\"/{LIVE_RELOAD_SCRIPT_PATH}\"></script>\n\
+                                <script src=\"/{PREVIEW_SCRIPT_PATH}\"></script>\n"
+                            );
                             if let Some(pos) = html.rfind("</body>") {
                                 html.insert_str(pos, &snippet);
                             } else {
@@ -221,6 +338,103 @@ fn handle_request(
     }
 }
 
+/// Open the source of `page` (looked up in `sources.json`) with the editor command,
+/// only files listed in the source map can be opened. Returns the opened file.
+fn open_in_editor(editor: &str, output_folder: &Path, query: &str) -> Result<String, String> {
+    let params = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| decode(value).map(|value| (key, value.into_owned())))
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Invalid query: {e}"))?;
+    let page = params.get("page").ok_or("Missing `page` parameter")?;
+    let line = params
+        .get("line")
+        .and_then(|line| line.parse::<usize>().ok())
+        .unwrap_or(1);
+
+    let sources = std::fs::read_to_string(output_folder.join("sources.json"))
+        .map_err(|e| format!("Failed to read sources.json: {e}"))?;
+    let sources: serde_json::Value = serde_json::from_str(&sources).map_err(|e| e.to_string())?;
+    let file = sources["files"][page.as_str()]["file"]
+        .as_str()
+        .ok_or_else(|| format!("No source found for {page}"))?
+        .to_string();
+
+    let command = editor_command(editor, &file, line);
+    let (program, args) = command.split_first().ok_or("Empty editor command")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| format!("Failed to run `{editor}`: {e}"))?;
+    // reap the process without blocking the server
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(file)
+}
+
+/// The raw query of a request to exactly `EDITOR_OPEN_PATH`, empty without `?`
+fn editor_query(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix(EDITOR_OPEN_PATH)?;
+    if rest.is_empty() {
+        Some(rest)
+    } else {
+        rest.strip_prefix('?')
+    }
+}
+
+/// The editor is opened only for a browser on this machine showing the site:
+/// the peer is on loopback, the `Host` is a loopback name and the `Origin`, when
+/// sent, is that same host, so neither the LAN nor other sites can open files
+fn is_local_request(peer: Option<&SocketAddr>, host: Option<&str>, origin: Option<&str>) -> bool {
+    let Some(host) = host.filter(|host| is_loopback_host(host)) else {
+        return false;
+    };
+    peer.is_some_and(|peer| peer.ip().to_canonical().is_loopback())
+        && origin.is_none_or(|origin| {
+            origin
+                .strip_prefix("http://")
+                .or_else(|| origin.strip_prefix("https://"))
+                .is_some_and(|origin_host| origin_host.eq_ignore_ascii_case(host))
+        })
+}
+
+/// `localhost` or a loopback address, with or without the port
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.to_canonical().is_loopback())
+}
+
+/// Split the editor command and replace `{file}` and `{line}` on each argument
+/// so paths with spaces are passed as a single argument
+fn editor_command(editor: &str, file: &str, line: usize) -> Vec<String> {
+    editor
+        .split_whitespace()
+        .map(|part| {
+            part.replace("{file}", file)
+                .replace("{line}", &line.to_string())
+        })
+        .collect()
+}
+
+/// The `{stream}-404.html` of the stream a missing page belongs to, e.g.
+/// `docs-404.html` for a missing `docs-page.html`, falling back to the site `404.html`
+fn nearest_error_page(output_folder: &Path, request_path: &str) -> PathBuf {
+    let name = request_path.trim_end_matches(".html");
+    name.match_indices('-')
+        .rev()
+        .map(|(index, _)| output_folder.join(format!("{}-404.html", &name[..index])))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| output_folder.join("404.html"))
+}
+
 fn render_not_found(error_path: &PathBuf) -> Result<Response<Cursor<Vec<u8>>>, String> {
     match File::open(error_path) {
         Ok(mut file) => {
@@ -0,0 +1,52 @@
+use log::error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder inside the input folder holding the json files kept between builds,
+/// e.g: slug history and content ids, meant to be committed with the content
+pub const SIDECAR_FOLDER: &str = ".marmite";
+
+pub fn sidecar_path(input_folder: &Path, name: &str) -> PathBuf {
+    input_folder.join(SIDECAR_FOLDER).join(name)
+}
+
+/// Load a sidecar json file, returns the default value if the file doesn't exist
+pub fn load_sidecar<T: DeserializeOwned + Default>(path: &Path) -> T {
+    if !path.exists() {
+        return T::default();
+    }
+    match fs::read_to_string(path).map(|json| serde_json::from_str(&json)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => {
+            error!("Failed to parse {}: {e}", path.display());
+            T::default()
+        }
+        Err(e) => {
+            error!("Failed to read {}: {e}", path.display());
+            T::default()
+        }
+    }
+}
+
+pub fn save_sidecar<T: Serialize>(path: &Path, value: &T) {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            error!("Unable to create {}: {e}", parent.display());
+            return;
+        }
+    }
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => {
+            if let Err(e) = fs::write(path, json) {
+                error!("Failed to write {}: {e}", path.display());
+            }
+        }
+        Err(e) => error!("Failed to serialize {}: {e}", path.display()),
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sidecar.rs"]
+mod tests;
@@ -1,26 +1,44 @@
-use crate::config::{Author, Marmite};
-use crate::content::{check_for_duplicate_slugs, Content, ContentBuilder, GroupedContent, Kind};
+use crate::bookmarks::{Bookmark, BOOKMARKS_CACHE};
+use crate::config::{Author, ContactFormConfig, FeedGuid, Marmite, SlugConfig};
+use crate::content::{
+    check_for_duplicate_slugs, compare_weight, new_content_id, normalize_url_path,
+    resolve_duplicate_slugs, AuthorActivity, Content, ContentBuilder, GroupedContent, Kind,
+};
 use crate::embedded::{generate_static, Templates, EMBEDDED_TERA};
+use crate::errors::{self, ErrorKind};
 use crate::gallery::Gallery;
+use crate::glossary::GlossaryTerm;
+use crate::now::Status;
 use crate::parser::fix_wikilinks;
+use crate::photos::Photo;
+use crate::projects::{Project, PROJECTS_CACHE};
+use crate::sections::{breadcrumbs, collect_sections, section_of};
 use crate::shortcodes::ShortcodeProcessor;
+use crate::sidecar::{load_sidecar, save_sidecar, sidecar_path};
+use crate::slug_history::{alias_redirects, update_slug_history, write_redirects, SlugHistory};
+use crate::tag_intersections::TagIntersection;
 use crate::tera_functions::{
-    DisplayName, GetDataBySlug, GetGallery, GetPosts, Group, SourceLink, UrlFor,
+    DisplayName, FetchJson, GetDataBySlug, GetGallery, GetPosts, Group, LoadCsv, SourceLink,
+    UrlFor, FETCH_JSON_CACHE,
 };
+use crate::theme_manager::check_theme;
+use crate::uses::UsesCategory;
 use crate::{re, server, tera_filter};
 use chrono::Datelike;
 use core::str;
 use fs_extra::dir::{copy as dircopy, CopyOptions};
 use glob::glob;
 use hotwatch::{Event, EventKind, Hotwatch};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 use std::vec;
-use std::{fs, process, sync::Arc, sync::Mutex};
+use std::{fs, sync::Arc, sync::Mutex};
 use tera::{Context, Tera};
 use tera::{Function, Value};
 use walkdir::WalkDir;
@@ -50,11 +68,41 @@ pub struct Data {
     pub author: GroupedContent,
     pub stream: GroupedContent,
     pub series: GroupedContent,
+    /// Content of every folder of the content folder, keyed by section path
+    pub section: GroupedContent,
+    /// Custom taxonomies declared in `taxonomies` config, keyed by taxonomy name
+    pub taxonomies: HashMap<String, GroupedContent>,
     pub latest_timestamp: Option<i64>,
     pub config_path: String,
     pub force_render: bool,
     pub generated_urls: UrlCollection,
     pub galleries: HashMap<String, Gallery>,
+    pub glossary: Vec<GlossaryTerm>,
+    /// Status updates of the `now.file`, the latest first
+    pub statuses: Vec<Status>,
+    /// Categories of the uses page from the `uses.file`
+    pub uses: Vec<UsesCategory>,
+    /// JSON Resume of the cv page from the `cv.file`
+    pub resume: Option<Value>,
+    /// Repositories of the portfolio page from `projects.repos`
+    pub projects: Vec<Project>,
+    /// Markdown intros of the archive pages from `archive/{year}.md`, keyed by year
+    pub archive_intros: HashMap<String, String>,
+    /// Content past its `expires` date, rendered with `render_expired` but never listed
+    pub expired: Vec<Content>,
+    /// Photos of the `photos` stream for the photo wall, the latest first
+    pub photos: Vec<Photo>,
+    /// Content uuids kept in `.marmite/content-ids.json`, keyed by the source
+    /// path relative to the content folder
+    #[serde(skip)]
+    pub content_ids: BTreeMap<String, String>,
+    /// Link metadata kept in `.marmite/bookmarks.json`, keyed by the bookmarked url
+    #[serde(skip)]
+    pub bookmarks_cache: BTreeMap<String, Bookmark>,
+    /// Canonical sources of the content rendered by `--only-changed`, when set
+    /// only these pages are rendered
+    #[serde(skip)]
+    pub preview_sources: Option<HashSet<PathBuf>>,
 }
 
 impl Data {
@@ -62,11 +110,24 @@ impl Data {
         let site: Marmite = match serde_yaml::from_str::<Marmite>(config_content) {
             Ok(site) => site,
             Err(e) => {
-                error!("Failed to parse config YAML: {e:?}");
-                process::exit(1);
+                errors::exit(
+                    ErrorKind::Config,
+                    &format!("Failed to parse config YAML: {e:?}"),
+                );
             }
         };
 
+        for name in site.taxonomies.keys() {
+            if crate::config::BUILTIN_TAXONOMIES.contains(&name.as_str()) {
+                warn!("Taxonomy '{name}' clashes with a builtin group and will be ignored");
+            }
+        }
+        let taxonomies = site
+            .taxonomy_names()
+            .into_iter()
+            .map(|name| (name.clone(), GroupedContent::new(Kind::Taxonomy(name))))
+            .collect();
+
         Data {
             site,
             posts: Vec::new(),
@@ -76,11 +137,24 @@ impl Data {
             author: GroupedContent::new(Kind::Author),
             stream: GroupedContent::new(Kind::Stream),
             series: GroupedContent::new(Kind::Series),
+            section: GroupedContent::new(Kind::Section),
+            taxonomies,
             latest_timestamp: None,
             config_path: config_path.to_string_lossy().to_string(),
             force_render: false,
             generated_urls: UrlCollection::default(),
             galleries: HashMap::new(),
+            glossary: Vec::new(),
+            statuses: Vec::new(),
+            uses: Vec::new(),
+            resume: None,
+            projects: Vec::new(),
+            archive_intros: HashMap::new(),
+            expired: Vec::new(),
+            photos: Vec::new(),
+            content_ids: BTreeMap::new(),
+            bookmarks_cache: BTreeMap::new(),
+            preview_sources: None,
         }
     }
 
@@ -103,19 +177,58 @@ impl Data {
     }
 
     pub fn sort_all(&mut self) {
-        self.posts.sort_by(|a, b| b.date.cmp(&a.date));
-        self.pages.sort_by(|a, b| b.title.cmp(&a.title));
+        self.posts
+            .sort_by(|a, b| b.date.cmp(&a.date).then_with(|| compare_weight(a, b)));
+        self.pages
+            .sort_by(|a, b| compare_weight(a, b).then_with(|| b.title.cmp(&a.title)));
         self.tag.sort_all();
         self.archive.sort_all();
         self.author.sort_all();
         self.stream.sort_all();
         self.series.sort_all();
+        self.section.sort_all();
+        for taxonomy in self.taxonomies.values_mut() {
+            taxonomy.sort_all();
+        }
+    }
+
+    /// Reviews of the past years for the `year_review` pages
+    pub fn year_reviews(&self) -> Vec<crate::review::YearReview> {
+        let config = &self.site.year_review;
+        crate::review::collect_reviews(
+            &self.posts,
+            config.top,
+            chrono::Local::now().year(),
+            config.include_current,
+        )
+    }
+
+    /// Bookmarked posts and pages for the `bookmarks.html` link board
+    pub fn bookmarks(&self) -> Vec<Content> {
+        crate::bookmarks::collect_bookmarks(self.posts.iter().chain(&self.pages))
+    }
+
+    /// The uses page is generated from the `uses.file` unless a content has its slug
+    pub fn has_uses_page(&self) -> bool {
+        !self.uses.is_empty()
+            && !self
+                .posts
+                .iter()
+                .chain(&self.pages)
+                .any(|content| content.slug == self.site.uses.slug)
     }
 
     /// takes content then classifies the content
     /// into posts, pages, tags, authors, archive, stream
     /// and adds the content to the respective fields in self
     pub fn push_content(&mut self, content: Content) {
+        // sections list both posts and pages
+        if let Some(section) = &content.section {
+            self.section
+                .entry(section.clone())
+                .or_default()
+                .push(content.clone());
+        }
         if let Some(date) = content.date {
             self.posts.push(content.clone());
             // tags
@@ -162,6 +275,18 @@ impl Data {
                     .or_default()
                     .push(content.clone());
             }
+
+            // custom taxonomies by term slug
+            for (name, terms) in &content.taxonomies {
+                if let Some(taxonomy) = self.taxonomies.get_mut(name) {
+                    for term in terms {
+                        taxonomy
+                            .entry(slug::slugify(term))
+                            .or_default()
+                            .push(content.clone());
+                    }
+                }
+            }
         } else {
             self.pages.push(content);
         }
@@ -242,6 +367,31 @@ impl Data {
             self.generated_urls.add_url("tags", "tags.html".to_string());
         }
 
+        // Add tag intersection pages and pagination
+        for intersection in self
+            .site
+            .tag_intersections
+            .iter()
+            .filter_map(|spec| TagIntersection::parse(spec))
+        {
+            let content_count = intersection.contents(&self.posts).len();
+            if content_count == 0 {
+                continue;
+            }
+            let filename = intersection.filename();
+            self.generated_urls
+                .add_url("tags", format!("{filename}.html"));
+            self.generated_urls
+                .add_url("pagination", format!("{filename}-1.html"));
+            if content_count > self.site.pagination {
+                let total_pages = content_count.div_ceil(self.site.pagination);
+                for page_num in 2..=total_pages {
+                    self.generated_urls
+                        .add_url("pagination", format!("{filename}-{page_num}.html"));
+                }
+            }
+        }
+
         // Add author pages and pagination
         for author in self.author.iter() {
             let author_slug = format!("author-{}.html", slug::slugify(author.0));
@@ -358,6 +508,39 @@ impl Data {
                 .add_url("archives", "archive.html".to_string());
         }
 
+        // Add custom taxonomy pages and pagination
+        for (name, taxonomy) in &self.taxonomies {
+            for (term, contents) in taxonomy.iter() {
+                self.generated_urls
+                    .add_url("taxonomies", format!("{name}-{term}.html"));
+                self.generated_urls
+                    .add_url("feeds", format!("{name}-{term}.rss"));
+                self.generated_urls
+                    .add_url("pagination", format!("{name}-{term}-1.html"));
+                let total_pages = contents.len().div_ceil(self.site.pagination);
+                for page_num in 2..=total_pages {
+                    self.generated_urls
+                        .add_url("pagination", format!("{name}-{term}-{page_num}.html"));
+                }
+            }
+            if !taxonomy.map.is_empty() {
+                self.generated_urls
+                    .add_url("taxonomies", format!("{name}.html"));
+            }
+        }
+
+        // Add section pages and pagination
+        for section in collect_sections(self.section.map.keys()) {
+            let contents = self.section.map.get(&section.path).map_or(0, Vec::len);
+            self.generated_urls
+                .add_url("sections", format!("{}.html", section.slug));
+            let total_pages = contents.div_ceil(self.site.pagination);
+            for page_num in 1..=total_pages {
+                self.generated_urls
+                    .add_url("pagination", format!("{}-{page_num}.html", section.slug));
+            }
+        }
+
         // Add main index pagination
         let posts_count = self.posts.len();
         if posts_count > self.site.pagination {
@@ -448,6 +631,97 @@ impl Data {
             }
         }
 
+        // Add activity feed
+        if self.site.activity_feed {
+            self.generated_urls
+                .add_url("feeds", "activity.rss".to_string());
+        }
+
+        // Add status updates feed
+        if !self.statuses.is_empty() {
+            self.generated_urls
+                .add_url("feeds", "status.rss".to_string());
+        }
+
+        // Add contact page
+        if let Some(contact_form) = &self.site.contact_form {
+            if contact_form.action().is_some() {
+                self.generated_urls
+                    .add_url("pages", format!("{}.html", contact_form.slug));
+            }
+        }
+
+        // Add freshness report page
+        if self.site.freshness.page {
+            self.generated_urls
+                .add_url("pages", "meta/freshness/index.html".to_string());
+        }
+
+        // Add year in review pages
+        if self.site.year_review.enabled {
+            for review in self.year_reviews() {
+                self.generated_urls
+                    .add_url("pages", format!("review-{}.html", review.year));
+            }
+        }
+
+        // Add glossary page
+        if !self.glossary.is_empty() {
+            self.generated_urls
+                .add_url("pages", format!("{}.html", self.site.glossary.slug));
+        }
+
+        // Add uses page
+        if self.has_uses_page() {
+            self.generated_urls
+                .add_url("pages", format!("{}.html", self.site.uses.slug));
+        }
+
+        // Add cv page and its PDF
+        if self.resume.is_some() {
+            self.generated_urls
+                .add_url("pages", format!("{}/index.html", self.site.cv.slug));
+            if let Some(pdf) = crate::cv::pdf_url(&self.site.cv) {
+                self.generated_urls.add_url("misc", pdf);
+            }
+        }
+
+        // Add badges
+        if self.site.badges.enabled {
+            for (filename, _) in crate::badges::collect_badges(self, &self.site.badges) {
+                self.generated_urls
+                    .add_url("misc", format!("{}/{filename}", self.site.badges.path));
+            }
+        }
+
+        // Add portfolio page and the page of each project
+        if !self.projects.is_empty() {
+            let slug = &self.site.projects.slug;
+            self.generated_urls.add_url("pages", format!("{slug}.html"));
+            for project in &self.projects {
+                self.generated_urls
+                    .add_url("pages", format!("{slug}/{}.html", project.slug));
+            }
+        }
+
+        // Add photo wall page
+        if !self.photos.is_empty() {
+            self.generated_urls
+                .add_url("pages", format!("{}-wall.html", self.site.photos.stream));
+        }
+
+        // Add bookmarks link board and its feeds
+        if !self.bookmarks().is_empty() {
+            self.generated_urls
+                .add_url("pages", "bookmarks.html".to_string());
+            self.generated_urls
+                .add_url("feeds", "bookmarks.rss".to_string());
+            if self.site.json_feed {
+                self.generated_urls
+                    .add_url("feeds", "bookmarks.json".to_string());
+            }
+        }
+
         // Add file mappings if they exist
         for mapping in &self.site.file_mapping {
             let destination = if mapping.dest.starts_with('/') {
@@ -469,6 +743,9 @@ struct BuildInfo {
     timestamp: i64,
     elapsed_time: f64,
     config: Marmite,
+    /// The private content was decrypted, the key itself is not part of `config`
+    #[serde(default)]
+    private: bool,
 }
 
 impl BuildInfo {
@@ -499,9 +776,14 @@ pub fn generate(
         None
     };
 
+    if watch {
+        crate::render_cache::enable();
+    }
+
     let rebuild = {
         move || -> Result<(), Box<dyn std::error::Error>> {
             let start_time = std::time::Instant::now();
+            crate::render_cache::start_build();
             let site_data = Arc::new(Mutex::new(Data::from_file(
                 moved_config_path.clone().as_path(),
             )));
@@ -529,8 +811,38 @@ pub fn generate(
                 site_data.force_render = true;
             }
 
+            let violations = crate::schema::validate_content(
+                &content_folder,
+                &moved_input_folder,
+                &site_data.site,
+            );
+            if !violations.is_empty() {
+                for violation in &violations {
+                    error!("{violation}");
+                }
+                return Err(errors::Failure::new(
+                    ErrorKind::Content,
+                    format!("Found {} frontmatter errors", violations.len()),
+                )
+                .into());
+            }
+
             let fragments = collect_content_fragments(&content_folder);
-            collect_content(&content_folder, &mut site_data, &fragments);
+            let content_ids_path = sidecar_path(&moved_input_folder, "content-ids.json");
+            site_data.content_ids = load_sidecar(&content_ids_path);
+            let previous_content_ids = site_data.content_ids.clone();
+            let bookmarks_cache_path = sidecar_path(&moved_input_folder, BOOKMARKS_CACHE);
+            site_data.bookmarks_cache = load_sidecar(&bookmarks_cache_path);
+            let previous_bookmarks_cache = site_data.bookmarks_cache.clone();
+            collect_content(&content_folder, &mut site_data, &fragments)?;
+            // previews never write the sidecar files kept with the content
+            let preview = moved_cli_args.only_changed.as_deref();
+            if site_data.content_ids != previous_content_ids && preview.is_none() {
+                save_sidecar(&content_ids_path, &site_data.content_ids);
+            }
+            if site_data.bookmarks_cache != previous_bookmarks_cache && preview.is_none() {
+                save_sidecar(&bookmarks_cache_path, &site_data.bookmarks_cache);
+            }
 
             // Process galleries
             let media_path = content_folder.join(&site_data.site.media_path);
@@ -539,19 +851,85 @@ pub fn generate(
                 &site_data.site.gallery_path,
                 site_data.site.gallery_create_thumbnails,
                 site_data.site.gallery_thumb_size,
+                site_data.site.low_memory,
             );
 
+            site_data.uses =
+                crate::uses::load_uses(&moved_input_folder.join(&site_data.site.uses.file));
+            site_data.resume =
+                crate::cv::load_resume(&moved_input_folder.join(&site_data.site.cv.file));
+            if !site_data.site.projects.repos.is_empty() {
+                let projects_cache_path = sidecar_path(&moved_input_folder, PROJECTS_CACHE);
+                let mut projects_cache = load_sidecar(&projects_cache_path);
+                let previous_projects_cache = projects_cache.clone();
+                site_data.projects = crate::projects::load_projects(
+                    &site_data.site.projects,
+                    &mut projects_cache,
+                    site_data.site.projects.fetch,
+                    chrono::Local::now().naive_local(),
+                );
+                if projects_cache != previous_projects_cache && preview.is_none() {
+                    save_sidecar(&projects_cache_path, &projects_cache);
+                }
+            }
+
             site_data.sort_all();
             detect_slug_collision(&site_data); // Detect slug collision and warn user
+
+            // Track the slugs of every source to redirect the ones changed by a title edit
+            let slug_history_path = sidecar_path(&moved_input_folder, "slug-history.json");
+            let previous_slug_history: SlugHistory = load_sidecar(&slug_history_path);
+            let mut slug_history = previous_slug_history.clone();
+            let mut redirects = update_slug_history(
+                &mut slug_history,
+                &content_sources(&site_data, &content_folder),
+            );
+            if slug_history != previous_slug_history && preview.is_none() {
+                save_sidecar(&slug_history_path, &slug_history);
+            }
+
             collect_back_links(&mut site_data);
+            crate::graph::apply_metrics(&mut site_data);
+            if site_data.site.backlinks_section {
+                append_backlinks_sections(&mut site_data);
+            }
             set_next_and_previous_links(&mut site_data);
+            if site_data.site.link_previews {
+                crate::link_previews::apply_link_previews(&mut site_data);
+            }
             site_data.collect_all_urls();
+            redirects.extend(alias_redirects(
+                site_data
+                    .posts
+                    .iter()
+                    .chain(&site_data.pages)
+                    .chain(&site_data.expired),
+                &site_data.generated_urls.get_all_urls(),
+            ));
+
+            if let Some(range) = preview {
+                let changed = crate::preview::changed_files(&moved_input_folder, range)?;
+                site_data.preview_sources =
+                    Some(crate::preview::affected_sources(&site_data, &changed));
+            }
+
+            let theme_path = site_data
+                .site
+                .theme
+                .as_ref()
+                .map(|theme| moved_input_folder.join(theme));
+            let templates_path = site_data.site.get_templates_path(&moved_input_folder);
+            for issue in check_theme(theme_path.as_deref(), &templates_path) {
+                warn!("Theme check: {issue}");
+            }
 
             let site_path = site_data.site.site_path.clone();
             let output_path = moved_output_folder.join(site_path);
             if let Err(e) = fs::create_dir_all(&output_path) {
-                error!("Unable to create output directory: {e:?}");
-                process::exit(1);
+                errors::exit(
+                    ErrorKind::Io,
+                    &format!("Unable to create output directory: {e:?}"),
+                );
             }
 
             [
@@ -575,8 +953,10 @@ pub fn generate(
                         latest_build_info.as_ref(),
                         shortcode_processor.as_ref(),
                     ) {
-                        error!("Failed to render templates: {e:?}");
-                        process::exit(1);
+                        errors::exit(
+                            ErrorKind::Template,
+                            &format!("Failed to render templates: {e:?}"),
+                        );
                     }
                 }
                 "handle_static_artifacts" => {
@@ -588,18 +968,33 @@ pub fn generate(
                     );
                 }
                 "generate_search_index" => {
-                    if site_data.site.enable_search {
+                    if site_data.site.enable_search && preview.is_none() {
                         generate_search_index(&site_data, &moved_output_folder);
                     }
                 }
                 "copy_markdown_sources" => {
-                    if site_data.site.publish_md {
+                    if site_data.site.publish_md && preview.is_none() {
                         copy_markdown_sources(&site_data, &content_folder, &output_path);
                     }
                 }
                 _ => {}
             });
 
+            if let Some(range) = preview {
+                crate::preview::write_preview_manifest(&site_data, range, &output_path);
+                return Ok(());
+            }
+
+            write_redirects(&redirects, &output_path);
+            crate::robots::write_robots_rules(&site_data, &output_path);
+            crate::wellknown::write_wellknown_files(&site_data, &output_path);
+            if site_data.site.link_previews {
+                crate::link_previews::write_link_previews(&site_data, &output_path);
+            }
+            if site_data.site.badges.enabled {
+                crate::badges::write_badges(&site_data, &output_path);
+            }
+
             // Generate sitemap after all templates are rendered
             let (tera, _) = initialize_tera(&moved_input_folder, &site_data);
             generate_sitemap(&site_data, &tera, &output_path);
@@ -609,8 +1004,26 @@ pub fn generate(
                 generate_urls_json(&site_data, &output_path);
             }
 
+            // Source map is only useful (and only safe to expose) on the local preview
+            if serve {
+                generate_sources_json(
+                    &site_data,
+                    &moved_input_folder,
+                    &tera,
+                    &output_path,
+                    moved_cli_args.editor.is_some(),
+                );
+            }
+
             let end_time = start_time.elapsed().as_secs_f64();
             write_build_info(&output_path, &site_data, end_time);
+            // the manifest hashes every file written above, so it is always the last
+            if site_data.site.integrity.enabled {
+                if let Err(e) = crate::integrity::write_integrity_manifest(&site_data, &output_path)
+                {
+                    errors::exit(e.kind, &e.message);
+                }
+            }
             debug!("Site generated in {end_time:.2}s");
             info!("Site generated at: {}/", moved_output_folder.display());
             Ok(())
@@ -635,9 +1048,13 @@ pub fn generate(
         let watch_result = hotwatch.watch(watch_folder, move |event: Event| match event.kind {
             EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
                 for ev in &event.paths {
+                    // the sidecar folder holds files written by the build itself
                     if !ev.starts_with(
                         fs::canonicalize(out_folder.clone()).unwrap_or_else(|_| out_folder.clone()),
-                    ) {
+                    ) && !ev
+                        .components()
+                        .any(|c| c.as_os_str() == crate::sidecar::SIDECAR_FOLDER)
+                    {
                         info!("Change detected. Rebuilding site...");
                         if let Err(e) = rebuild() {
                             error!("Failed to rebuild site: {e}");
@@ -663,6 +1080,7 @@ pub fn generate(
                 bind_address,
                 &Arc::clone(output_folder),
                 live_reload.as_ref(),
+                cli_args.editor.as_deref(),
             );
         } else {
             loop {
@@ -778,6 +1196,43 @@ fn collect_back_links(site_data: &mut std::sync::MutexGuard<'_, Data>) {
     _collect_back_links(&mut site_data.pages, &other_contents);
 }
 
+/// Append the rendered backlinks section to the html of the posts and pages
+fn append_backlinks_sections(site_data: &mut Data) {
+    let title = site_data.site.backlinks_title.clone();
+    for content in site_data.posts.iter_mut().chain(&mut site_data.pages) {
+        content
+            .html
+            .push_str(&render_backlinks_section(content, &title));
+    }
+}
+
+/// `<section class="backlinks">` listing the content linking to `content`,
+/// empty when nothing links to it
+fn render_backlinks_section(content: &Content, title: &str) -> String {
+    let items: Vec<String> = content
+        .back_links
+        .iter()
+        .filter(|link| link.stream.as_deref() != Some("draft"))
+        .map(|link| {
+            format!(
+                "<li><a href=\"{}.html\">{}</a></li>",
+                link.slug,
+                link.title
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+            )
+        })
+        .collect();
+    if items.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\n<section class=\"backlinks\">\n<h2>{title}</h2>\n<ul>\n{}\n</ul>\n</section>\n",
+        items.join("\n")
+    )
+}
+
 #[allow(clippy::needless_range_loop)]
 fn _collect_back_links(contents: &mut [Content], other_contents: &[Content]) {
     for content in contents.iter_mut() {
@@ -811,29 +1266,22 @@ fn set_next_and_previous_links(site_data: &mut std::sync::MutexGuard<'_, Data>)
 
     // Sort series posts chronologically (oldest to newest)
     for posts in series_posts.values_mut() {
-        posts.sort_by(|a, b| a.date.cmp(&b.date));
+        posts.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| compare_weight(a, b)));
     }
 
     // Set next/previous for posts in series
     for posts in series_posts.values() {
+        let neighbour = |index: usize| posts.get(index).map(|post| Box::new(post.clone()));
         for i in 0..posts.len() {
             let current_slug = &posts[i].slug;
 
-            let previous = if i > 0 {
-                Some(Box::new(posts[i - 1].clone()))
-            } else {
-                None
-            };
-
-            let next = if i < posts.len() - 1 {
-                Some(Box::new(posts[i + 1].clone()))
-            } else {
-                None
-            };
-
             if let Some(content) = site_data.posts.iter_mut().find(|c| c.slug == *current_slug) {
-                content.previous = previous;
-                content.next = next;
+                content.series_index = Some(i + 1);
+                content.series_total = Some(posts.len());
+                content.series_prev = i.checked_sub(1).and_then(neighbour);
+                content.series_next = neighbour(i + 1);
+                content.previous = i.checked_sub(1).and_then(neighbour);
+                content.next = neighbour(i + 1);
             }
         }
     }
@@ -876,12 +1324,200 @@ fn set_next_and_previous_links(site_data: &mut std::sync::MutexGuard<'_, Data>)
     }
 }
 
+/// Folder of the content folder holding the tag landing files, e.g: `tags/rust.md`
+pub const TAG_LANDINGS_FOLDER: &str = "tags";
+
+/// Folder of the content folder holding the archive intros, e.g: `archive/2024.md`
+pub const ARCHIVE_INTROS_FOLDER: &str = "archive";
+
+/// Folder of the content folder holding the markdown inlined by `{{ include "snippets/*.md" }}`
+pub const SNIPPETS_FOLDER: &str = "snippets";
+
+/// Read the `archive/{year}.md` intros shown on top of the archive page of the year,
+/// the frontmatter is ignored
+fn load_archive_intros(content_dir: &Path) -> HashMap<String, String> {
+    let Ok(entries) = fs::read_dir(content_dir.join(ARCHIVE_INTROS_FOLDER)) else {
+        return HashMap::new();
+    };
+    let mut intros = HashMap::new();
+    for path in entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+    {
+        let Some(year) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Failed to read archive intro {}: {e}", path.display());
+                continue;
+            }
+        };
+        match crate::parser::parse_front_matter(&text) {
+            Ok((_, body)) if !body.trim().is_empty() => {
+                intros.insert(year.to_string(), body.trim().to_string());
+            }
+            Ok(_) => {}
+            Err(e) => error!("Failed to parse archive intro {}: {e}", path.display()),
+        }
+    }
+    intros
+}
+
+/// Files of the tag landings or archive intros `folder` that are not one of its
+/// `*.md` files, these are read by neither and are never built as pages
+fn is_stray_intro_file(path: &Path, folder: &Path) -> bool {
+    path.is_file()
+        && !(path.parent() == Some(folder) && path.extension().is_some_and(|ext| ext == "md"))
+}
+
+/// Render the markdown of the tag and archive intros with the site parser options
+fn render_intro(markdown: &str, site: &Marmite) -> String {
+    let default_parser_options = crate::config::ParserOptions::default();
+    let parser_options = site
+        .markdown_parser
+        .as_ref()
+        .unwrap_or(&default_parser_options);
+    crate::parser::get_html_with_options(markdown, parser_options)
+}
+
+/// Read the `tags/{tag}.md` landing files into the `tags` configuration, the
+/// frontmatter sets the tag `description`, `card_image` and `banner_image` and
+/// the body is the `intro` shown on top of the tag page
+fn load_tag_landings(content_dir: &Path, site: &mut Marmite) {
+    let Ok(entries) = fs::read_dir(content_dir.join(TAG_LANDINGS_FOLDER)) else {
+        return;
+    };
+    let paths = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"));
+    for path in paths {
+        let Some(tag_slug) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(slug::slugify)
+        else {
+            continue;
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                error!("Failed to read tag landing {}: {e}", path.display());
+                continue;
+            }
+        };
+        let (frontmatter, body) = match crate::parser::parse_front_matter(&text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                error!("Failed to parse tag landing {}: {e}", path.display());
+                continue;
+            }
+        };
+        // merge with the `tags` configuration of the same tag
+        let key = site
+            .tags
+            .keys()
+            .find(|tag| slug::slugify(tag) == tag_slug)
+            .cloned()
+            .unwrap_or(tag_slug);
+        let tag_config = site.tags.entry(key).or_default();
+        for (field, value) in [
+            ("description", &mut tag_config.description),
+            ("card_image", &mut tag_config.card_image),
+            ("banner_image", &mut tag_config.banner_image),
+        ] {
+            if let Some(field_value) = frontmatter.get(field).and_then(|v| v.as_str()) {
+                *value = Some(field_value.to_string());
+            }
+        }
+        if !body.trim().is_empty() {
+            tag_config.intro = Some(body.trim().to_string());
+        }
+    }
+}
+
+/// Add the profiles of the `authors_file` found in the content folder or in the
+/// folder of the configuration file to `authors`, the configured authors are kept,
+/// then fill the `username` and missing `name` of every author
+fn load_authors_file(site: &mut Marmite, folders: &[&Path]) {
+    let authors_path = folders
+        .iter()
+        .map(|folder| folder.join(&site.authors_file))
+        .find(|path| path.is_file());
+    if let Some(authors_path) = authors_path {
+        let profiles = fs::read_to_string(&authors_path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                serde_yaml::from_str::<HashMap<String, Author>>(&text).map_err(|e| e.to_string())
+            });
+        match profiles {
+            Ok(profiles) => {
+                for (username, profile) in profiles {
+                    site.authors.entry(username).or_insert(profile);
+                }
+            }
+            Err(e) => error!("Failed to load authors {}: {e}", authors_path.display()),
+        }
+    }
+    for (username, author) in &mut site.authors {
+        author.username.clone_from(username);
+        if author.name.is_empty() {
+            author.name.clone_from(username);
+        }
+    }
+}
+
+/// Point the wikilinks of the contents to the slugs they name and record them
+/// on `links_to` in place of the unresolved page, so the backlinks cover them
+/// as any other internal link
+fn resolve_wikilinks(contents: &mut [Content]) {
+    if !contents
+        .iter()
+        .any(|content| content.html.contains("data-wikilink"))
+    {
+        return;
+    }
+    let targets = crate::parser::wikilink_targets(contents);
+    for content in contents.iter_mut() {
+        if !content.html.contains("data-wikilink") {
+            continue;
+        }
+        let (html, links) = crate::parser::resolve_wikilinks(&content.html, &targets);
+        content.html = html;
+        if let Some(summary) = &content.summary_html {
+            content.summary_html = Some(crate::parser::resolve_wikilinks(summary, &targets).0);
+        }
+        if !links.is_empty() {
+            let links_to = content.links_to.get_or_insert_with(Vec::new);
+            for (page, slug) in links {
+                links_to.retain(|link| *link != page);
+                if !links_to.contains(&slug) {
+                    links_to.push(slug);
+                }
+            }
+        }
+    }
+}
+
 #[allow(clippy::cast_possible_wrap)]
 fn collect_content(
     content_dir: &std::path::PathBuf,
     site_data: &mut Data,
     fragments: &HashMap<String, String>,
-) {
+) -> Result<(), errors::Failure> {
+    let config_dir = Path::new(&site_data.config_path)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    load_authors_file(&mut site_data.site, &[content_dir, &config_dir]);
+    load_tag_landings(content_dir, &mut site_data.site);
+    site_data.archive_intros = load_archive_intros(content_dir);
+    let tag_landings_dir = content_dir.join(TAG_LANDINGS_FOLDER);
+    let archive_intros_dir = content_dir.join(ARCHIVE_INTROS_FOLDER);
+    let snippets_dir = content_dir.join(SNIPPETS_FOLDER);
     let contents = WalkDir::new(content_dir)
         .into_iter()
         .filter_map(Result::ok)
@@ -897,8 +1533,38 @@ fn collect_content(
                         .to_str()
                         .unwrap_or_else(|| panic!("Could not get file name {e:?}")),
                 );
-            let file_extension = e.path().extension().and_then(|ext| ext.to_str());
-            e.path().is_file() && file_extension == Some("md") && !file_name.starts_with('_')
+            for (folder, kind) in [
+                (&tag_landings_dir, "tag landings"),
+                (&archive_intros_dir, "archive intros"),
+            ] {
+                if e.path().starts_with(folder) {
+                    if is_stray_intro_file(e.path(), folder) {
+                        warn!(
+                            "Skipping {}: {} only holds {kind} (`*.md` files), it is not built as a page",
+                            e.path().display(),
+                            folder.display()
+                        );
+                    }
+                    return false;
+                }
+            }
+            if e.path().starts_with(&snippets_dir) {
+                return false;
+            }
+            if crate::private::is_private_source(e.path(), content_dir, &site_data.site) {
+                if site_data.site.private_key.is_none() {
+                    info!("Skipping private content without --private-key: {file_name}");
+                    return false;
+                }
+                return e.path().is_file();
+            }
+            e.path().is_file()
+                && crate::converters::is_content_source(
+                    e.path(),
+                    &site_data.site.input_converters,
+                    &site_data.site.content_extensions,
+                )
+                && !file_name.starts_with('_')
         })
         .map(|entry| {
             // let modified_time = entry.metadata().unwrap().modified().unwrap();
@@ -932,19 +1598,147 @@ fn collect_content(
                 Some(fragments),
                 &site_data.site,
                 modified_time,
+                Some(content_dir.as_path()),
             )
         })
         .collect::<Vec<_>>();
+    let now = chrono::Local::now().naive_local();
+    let bibliography =
+        crate::citations::load_bibliography(&content_dir.join(&site_data.site.bibliography));
+    site_data.glossary =
+        crate::glossary::load_glossary(&content_dir.join(&site_data.site.glossary.file));
+    let default_parser_options = crate::config::ParserOptions::default();
+    site_data.statuses = crate::now::load_statuses(
+        &content_dir.join(&site_data.site.now.file),
+        site_data
+            .site
+            .markdown_parser
+            .as_ref()
+            .unwrap_or(&default_parser_options),
+    );
+    let token_values = if site_data.site.enable_tokens {
+        let site = serde_json::to_value(&site_data.site).unwrap_or_default();
+        Some((site, crate::tokens::build_values(&site_data.site, now)))
+    } else {
+        None
+    };
+    let mut collected = Vec::new();
+    let mut invalid = 0;
     for content in contents {
         match content {
-            Ok(content) => {
-                site_data.push_content(content);
+            Ok(mut content) => {
+                if content.draft && !site_data.site.drafts {
+                    info!("Skipping draft: {}", content.slug);
+                    continue;
+                }
+                if !site_data.site.future && content.date.is_some_and(|date| date > now) {
+                    info!("Skipping future post: {}", content.slug);
+                    continue;
+                }
+                if let Some(html) = crate::citations::apply_citations(&content.html, &bibliography)
+                {
+                    content.html = html;
+                }
+                if !site_data.glossary.is_empty() && content.glossary != Some(false) {
+                    content.html = crate::glossary::link_glossary_terms(
+                        &content.html,
+                        &site_data.glossary,
+                        &site_data.site.glossary.slug,
+                    );
+                }
+                if content.uuid.is_none() && site_data.site.feed_guid == FeedGuid::Uuid {
+                    if let Some(source) = content
+                        .source_path
+                        .as_ref()
+                        .and_then(|path| path.strip_prefix(content_dir).ok())
+                    {
+                        let source = source.to_string_lossy().replace('\\', "/");
+                        let uuid = site_data
+                            .content_ids
+                            .entry(source.clone())
+                            .or_insert_with(|| new_content_id(&source));
+                        content.uuid = Some(uuid.clone());
+                    }
+                }
+                if let Some((site, build)) = &token_values {
+                    crate::tokens::apply_tokens(&mut content, site, build);
+                }
+                if content.expires.is_some_and(|expires| expires <= now) {
+                    if site_data.site.render_expired {
+                        info!("Expired content rendered as noindex: {}", content.slug);
+                        content.expired = true;
+                        site_data.expired.push(content);
+                    } else {
+                        info!("Skipping expired content: {}", content.slug);
+                    }
+                    continue;
+                }
+                if site_data.site.enable_sections {
+                    content.section = content
+                        .source_path
+                        .as_deref()
+                        .and_then(|path| section_of(path, content_dir));
+                    if let Some(section) = &content.section {
+                        content.breadcrumbs = breadcrumbs(section);
+                    }
+                }
+                crate::bookmarks::apply_metadata(
+                    &mut content,
+                    &mut site_data.bookmarks_cache,
+                    site_data.site.fetch_bookmarks,
+                );
+                collected.push(content);
+            }
+            Err(e) if e.kind == ErrorKind::Content => {
+                error!("Invalid content: {e}");
+                invalid += 1;
             }
             Err(e) => {
-                error!("Failed to process content: {e:?}");
+                error!("Failed to process content: {e}");
             }
         }
     }
+    if invalid > 0 {
+        return Err(errors::Failure::new(
+            ErrorKind::Content,
+            format!("Found {invalid} invalid content files"),
+        ));
+    }
+    if site_data.site.photos.enabled {
+        let media_dir = content_dir.join(&site_data.site.media_path);
+        site_data.photos = crate::photos::collect_photos(&media_dir, &site_data.site);
+        collected.extend(crate::photos::photo_contents(
+            &site_data.photos,
+            &site_data.site.photos,
+        ));
+    }
+    resolve_duplicate_slugs(&mut collected, site_data.site.slug.duplicates);
+    if !site_data.photos.is_empty() {
+        crate::photos::update_photo_slugs(&mut site_data.photos, &mut collected);
+    }
+    resolve_wikilinks(&mut collected);
+    for content in collected {
+        site_data.push_content(content);
+    }
+    Ok(())
+}
+
+/// `(source, slug)` of every post and page, the source is relative to the content folder
+fn content_sources(site_data: &Data, content_dir: &Path) -> Vec<(String, String)> {
+    site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter_map(|content| {
+            let source_path = content.source_path.as_ref()?;
+            let source = source_path
+                .strip_prefix(content_dir)
+                .unwrap_or(source_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            Some((source, content.slug.clone()))
+        })
+        .collect()
 }
 
 fn detect_slug_collision(site_data: &Data) {
@@ -958,7 +1752,8 @@ fn detect_slug_collision(site_data: &Data) {
         error!(
             "Duplicate slug found: '{duplicate}' \
             - try setting `title` or `slug` as a unique text, \
-            or leave both empty so filename will be assumed. \
+            or leave both empty so filename will be assumed, \
+            or set `slug.duplicates` to `suffix` or `date` to rename the duplicates. \
             - The latest content rendered will overwrite the previous one."
         );
     }
@@ -1018,10 +1813,28 @@ fn initialize_tera(input_folder: &Path, site_data: &Data) -> (Tera, Option<Short
             site_data: site_data.clone(),
         },
     );
-    tera.register_filter(
-        "default_date_format",
+    tera.register_function(
+        "load_csv",
+        LoadCsv {
+            input_folder: input_folder.to_path_buf(),
+            content_folder: get_content_folder(&site_data.site, input_folder),
+        },
+    );
+    tera.register_function(
+        "fetch_json",
+        FetchJson {
+            cache_folder: sidecar_path(input_folder, FETCH_JSON_CACHE),
+            offline: site_data.site.fetch_json.offline,
+            ttl: site_data.site.fetch_json.ttl,
+            fetched: Mutex::default(),
+        },
+    );
+    tera.register_filter(
+        "default_date_format",
         tera_filter::DefaultDateFormat {
             date_format: site_data.site.default_date_format.to_string(),
+            language: site_data.site.language.clone(),
+            date_formats: site_data.site.date_formats.clone(),
         },
     );
     tera.register_filter("remove_draft", tera_filter::RemoveDraft);
@@ -1042,8 +1855,10 @@ fn initialize_tera(input_folder: &Path, site_data: &Data) -> (Tera, Option<Short
         } else {
             Templates::get(template_name).map_or_else(
                 || {
-                    error!("Failed to load template: {template_name}");
-                    process::exit(1);
+                    errors::exit(
+                        ErrorKind::Template,
+                        &format!("Failed to load template: {template_name}"),
+                    );
                 },
                 |template| {
                     let template_str =
@@ -1128,11 +1943,41 @@ fn render_templates(
     global_context.insert("site_data", &site_data);
     global_context.insert("site", &site_data.site);
     global_context.insert("menu", &site_data.site.menu);
+    global_context.insert("head_meta", &site_data.site.verification.meta_tags());
     global_context.insert("language", &site_data.site.language);
+    global_context.insert("statuses", &site_data.statuses);
+    global_context.insert(
+        "now_page",
+        &site_data
+            .pages
+            .iter()
+            .chain(&site_data.posts)
+            .find(|content| content.slug == site_data.site.now.slug),
+    );
     debug!("Global Context site: {:?}", &site_data.site);
     debug!("Site data galleries count: {}", site_data.galleries.len());
+    if site_data.site.build_metadata {
+        global_context.insert(
+            "build_metadata",
+            &build_metadata_comment(None, input_folder, &chrono::Local::now().to_rfc3339()),
+        );
+    }
     collect_global_fragments(content_dir, &mut global_context, tera, &site_data.site);
 
+    // `--only-changed` previews render only the content pages
+    if site_data.preview_sources.is_some() {
+        return handle_content_pages(
+            &site_data,
+            &global_context,
+            tera,
+            output_dir,
+            content_dir,
+            input_folder,
+            latest_build_info,
+            shortcode_processor,
+        );
+    }
+
     handle_stream_pages(&site_data, &global_context, tera, output_dir)?;
     handle_series_pages(&site_data, &global_context, tera, output_dir)?;
     // If site_data.stream.map does not contain the index stream
@@ -1157,6 +2002,53 @@ fn render_templates(
 
     // Check and guarantees that page 404 was generated even if _404.md is removed
     handle_404(content_dir, &global_context, tera, output_dir)?;
+    handle_stream_error_pages(&site_data, content_dir, &global_context, tera, output_dir)?;
+
+    if let Some(contact_form) = &site_data.site.contact_form {
+        handle_contact_page(contact_form, &global_context, tera, output_dir)?;
+    }
+
+    if site_data.site.activity_feed {
+        handle_activity_feed(&site_data, input_folder, tera, output_dir)?;
+    }
+
+    if !site_data.statuses.is_empty() {
+        handle_status_feed(&site_data, tera, output_dir)?;
+    }
+
+    if !site_data.glossary.is_empty() {
+        handle_glossary_page(&site_data, &global_context, tera, output_dir)?;
+    }
+
+    if site_data.has_uses_page() {
+        handle_uses_page(&site_data, &global_context, tera, output_dir)?;
+    }
+
+    if let Some(resume) = &site_data.resume {
+        handle_cv_page(resume, &site_data, &global_context, tera, output_dir)?;
+    }
+
+    if !site_data.projects.is_empty() {
+        handle_projects_pages(&site_data, &global_context, tera, output_dir)?;
+    }
+
+    if site_data.site.freshness.page {
+        handle_freshness_page(&site_data, &global_context, tera, output_dir)?;
+    }
+
+    if site_data.site.year_review.enabled {
+        handle_year_review_pages(&site_data, &global_context, tera, output_dir)?;
+    }
+
+    if site_data.site.orphans_page {
+        handle_orphans_page(&site_data, content_dir, &global_context, tera, output_dir)?;
+    }
+
+    handle_bookmarks_page(&site_data, &global_context, tera, output_dir)?;
+
+    if !site_data.photos.is_empty() {
+        handle_photo_wall_page(&site_data, &global_context, tera, output_dir)?;
+    }
 
     // Render individual content-slug.html from content.html template
     // content is rendered as last step so it gives the user the ability to
@@ -1181,31 +2073,45 @@ fn handle_group_pages(
     tera: &Tera,
     output_dir: &Path,
 ) -> Result<(), String> {
-    ["tags", "archives", "authors", "streams", "series"]
-        .par_iter()
-        .map(|step| -> Result<(), String> {
-            match *step {
-                "tags" => {
-                    handle_tag_pages(output_dir, site_data, global_context, tera)?;
-                }
-                "archives" => {
-                    handle_archive_pages(output_dir, site_data, global_context, tera)?;
-                }
-                "authors" => {
-                    handle_author_pages(output_dir, site_data, global_context, tera)?;
-                }
-                "streams" => {
-                    handle_stream_list_page(output_dir, site_data, global_context, tera)?;
-                }
-                "series" => {
-                    handle_series_list_page(output_dir, site_data, global_context, tera)?;
-                }
-                _ => {}
+    [
+        "tags",
+        "archives",
+        "authors",
+        "streams",
+        "series",
+        "taxonomies",
+        "sections",
+    ]
+    .par_iter()
+    .map(|step| -> Result<(), String> {
+        match *step {
+            "tags" => {
+                handle_tag_pages(output_dir, site_data, global_context, tera)?;
             }
-            Ok(())
-        })
-        .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
-        .unwrap_or(Ok(()))
+            "archives" => {
+                handle_archive_pages(output_dir, site_data, global_context, tera)?;
+            }
+            "authors" => {
+                handle_author_pages(output_dir, site_data, global_context, tera)?;
+            }
+            "streams" => {
+                handle_stream_list_page(output_dir, site_data, global_context, tera)?;
+            }
+            "series" => {
+                handle_series_list_page(output_dir, site_data, global_context, tera)?;
+            }
+            "taxonomies" => {
+                handle_taxonomy_pages(output_dir, site_data, global_context, tera)?;
+            }
+            "sections" => {
+                handle_section_pages(output_dir, site_data, global_context, tera)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    })
+    .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
+    .unwrap_or(Ok(()))
 }
 
 /// Assuming every item on `site_data.posts` is a Content and has a stream field
@@ -1243,7 +2149,7 @@ fn handle_stream_pages(
                 } else if !a.pinned && b.pinned {
                     std::cmp::Ordering::Greater
                 } else {
-                    b.date.cmp(&a.date)
+                    b.date.cmp(&a.date).then_with(|| compare_weight(a, b))
                 }
             });
 
@@ -1265,6 +2171,7 @@ fn handle_stream_pages(
                     output_dir,
                     &stream_slug,
                     &site_data.site,
+                    tera,
                 )?;
 
                 if site_data.site.json_feed {
@@ -1317,7 +2224,13 @@ fn handle_series_pages(
             )?;
 
             // Generate RSS feed for series
-            crate::feed::generate_rss(series_contents, output_dir, &series_slug, &site_data.site)?;
+            crate::feed::generate_rss(
+                series_contents,
+                output_dir,
+                &series_slug,
+                &site_data.site,
+                tera,
+            )?;
 
             if site_data.site.json_feed {
                 crate::feed::generate_json(
@@ -1410,18 +2323,8 @@ fn handle_author_pages(
         .collect::<Vec<_>>()
         .par_iter()
         .map(|(username, _)| -> Result<(), String> {
-            let default_author = Author {
-                name: (*username).to_string(),
-                bio: None,
-                avatar: Some("static/avatar-placeholder.png".to_string()),
-                links: None,
-            };
             let mut author_context = global_context.clone();
-            let author = if let Some(author) = site_data.site.authors.get(*username) {
-                author
-            } else {
-                &default_author
-            };
+            let author = site_data.site.author_profile(username);
             author_context.insert("author", &author);
 
             let author_slug = slug::slugify(username);
@@ -1440,10 +2343,15 @@ fn handle_author_pages(
                 } else if !a.pinned && b.pinned {
                     std::cmp::Ordering::Greater
                 } else {
-                    b.date.cmp(&a.date)
+                    b.date.cmp(&a.date).then_with(|| compare_weight(a, b))
                 }
             });
 
+            author_context.insert(
+                "author_activity",
+                &AuthorActivity::from_contents(username, &author_posts),
+            );
+
             let filename = format!("author-{}", &author_slug);
             handle_list_page(
                 &author_context,
@@ -1461,6 +2369,7 @@ fn handle_author_pages(
                 output_dir,
                 &filename.clone(),
                 &site_data.site,
+                tera,
             )?;
 
             if site_data.site.json_feed {
@@ -1611,13 +2520,23 @@ fn handle_static_artifacts(
     content_dir: &std::path::Path,
 ) {
     let static_source = site_data.site.get_static_path(input_folder);
+
+    // The embedded `base.html` links to the embedded assets, write them first so a
+    // site without `base.html` is styled even when its static folder only has a few files
+    let templates_path = site_data.site.get_templates_path(input_folder);
+    if !static_source.is_dir() || !templates_path.join("base.html").exists() {
+        generate_static(&output_folder.join(site_data.site.static_path.clone()));
+    }
+
     if static_source.is_dir() {
         let mut options = CopyOptions::new();
         options.overwrite = true; // Overwrite files if they already exist
 
         if let Err(e) = dircopy(&static_source, &**output_folder, &options) {
-            error!("Failed to copy static directory: {e:?}");
-            process::exit(1);
+            errors::exit(
+                ErrorKind::Io,
+                &format!("Failed to copy static directory: {e:?}"),
+            );
         }
 
         info!(
@@ -1625,8 +2544,6 @@ fn handle_static_artifacts(
             &static_source.display(),
             &output_folder.display()
         );
-    } else {
-        generate_static(&output_folder.join(site_data.site.static_path.clone()));
     }
 
     // Copy extra static folders if present
@@ -1642,8 +2559,10 @@ fn handle_static_artifacts(
                         options.overwrite = true;
 
                         if let Err(e) = dircopy(&source_folder, &**output_folder, &options) {
-                            error!("Failed to copy extra static folders directory: {e:?}");
-                            process::exit(1);
+                            errors::exit(
+                                ErrorKind::Io,
+                                &format!("Failed to copy extra static folders directory: {e:?}"),
+                            );
                         }
 
                         info!(
@@ -1664,8 +2583,10 @@ fn handle_static_artifacts(
         options.overwrite = true; // Overwrite files if they already exist
 
         if let Err(e) = dircopy(&media_source, &**output_folder, &options) {
-            error!("Failed to copy media directory: {e:?}");
-            process::exit(1);
+            errors::exit(
+                ErrorKind::Io,
+                &format!("Failed to copy media directory: {e:?}"),
+            );
         }
 
         info!(
@@ -1673,6 +2594,36 @@ fn handle_static_artifacts(
             &media_source.display(),
             &output_folder.display()
         );
+
+        if site_data.site.slug.normalizes_urls() {
+            normalize_media_files(
+                &output_folder.join(&site_data.site.media_path),
+                &site_data.site.slug,
+            );
+        }
+    }
+
+    // Copy the files of the page bundles next to their pages
+    let bundles_output = output_folder.join(&site_data.site.site_path);
+    for content in site_data.posts.iter().chain(&site_data.pages) {
+        crate::bundles::copy_bundle_assets(content, &bundles_output, content_dir, &site_data.site);
+    }
+
+    // Write the stylesheet of the server side syntax highlighting
+    if site_data.site.syntax_highlight.enabled {
+        match crate::highlight::theme_css(&site_data.site.syntax_highlight) {
+            Ok(css) => {
+                let css_path = output_folder
+                    .join(&site_data.site.static_path)
+                    .join("syntax.css");
+                if let Err(e) = fs::create_dir_all(css_path.parent().unwrap_or(output_folder))
+                    .and_then(|()| fs::write(&css_path, css))
+                {
+                    error!("Failed to write {}: {e}", css_path.display());
+                }
+            }
+            Err(e) => error!("Failed to generate syntax highlight css: {e}"),
+        }
     }
 
     // Handle file mappings
@@ -1809,7 +2760,16 @@ fn copy_markdown_sources(site_data: &Data, content_folder: &Path, output_path: &
                     }
                 }
 
-                if let Err(e) = fs::copy(source_path, &dest_path) {
+                // the blocks of other profiles are dropped from the source as from
+                // the page, sources that aren't text, e.g: `.age`, are copied as is
+                let copied = match fs::read_to_string(source_path) {
+                    Ok(text) => fs::write(
+                        &dest_path,
+                        crate::conditionals::apply_conditionals(&text, &site_data.site.profile),
+                    ),
+                    Err(_) => fs::copy(source_path, &dest_path).map(|_| ()),
+                };
+                if let Err(e) = copied {
                     error!(
                         "Failed to copy markdown source {}: {e:?}",
                         source_path.display()
@@ -1834,6 +2794,7 @@ fn write_build_info(
         timestamp: chrono::Utc::now().timestamp(),
         elapsed_time: end_time,
         config: site_data.site.clone(),
+        private: site_data.site.private_key.is_some(),
     };
 
     let build_info_path = output_path.join("marmite.json");
@@ -1850,6 +2811,42 @@ fn write_build_info(
     }
 }
 
+/// Rename the copied media files following the slug normalization rules,
+/// the urls in the content are rewritten by `normalize_media_urls`
+fn normalize_media_files(media_folder: &Path, config: &SlugConfig) {
+    let files: Vec<std::path::PathBuf> = WalkDir::new(media_folder)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.path().is_file())
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    for file in files {
+        let Some(relative) = file
+            .strip_prefix(media_folder)
+            .ok()
+            .and_then(|p| p.to_str())
+            .map(|p| p.replace('\\', "/"))
+        else {
+            continue;
+        };
+        let normalized = normalize_url_path(&relative, config);
+        if normalized == relative {
+            continue;
+        }
+        let target = media_folder.join(&normalized);
+        if let Some(parent) = target.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Failed to create {}: {e:?}", parent.display());
+                continue;
+            }
+        }
+        match fs::rename(&file, &target) {
+            Ok(()) => warn!("URL of media file changed by slug rules: {relative} -> {normalized}"),
+            Err(e) => error!("Failed to rename {}: {e:?}", file.display()),
+        }
+    }
+}
+
 fn generate_sitemap(site_data: &Data, tera: &Tera, output_path: &Path) {
     if !site_data.site.build_sitemap {
         return;
@@ -1881,10 +2878,19 @@ fn generate_sitemap(site_data: &Data, tera: &Tera, output_path: &Path) {
         }
     };
 
-    // Get all URLs from the shared collection and apply URL generation
+    // Get all URLs from the shared collection and apply URL generation,
+    // content marked with `noindex: true` is left out
+    let noindex_urls: HashSet<String> = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter(|content| content.noindex)
+        .map(|content| format!("{}.html", content.slug))
+        .collect();
     let all_raw_urls = site_data.generated_urls.get_all_urls();
     let sitemap_urls: Vec<String> = all_raw_urls
         .iter()
+        .filter(|url| !noindex_urls.contains(url.strip_prefix('/').unwrap_or(url)))
         .map(|url| {
             // Remove leading slash if present for consistent path handling
             let path = url.strip_prefix('/').unwrap_or(url);
@@ -2200,6 +3206,101 @@ fn create_urls_json(site_data: &Data) -> serde_json::Value {
     serde_json::Value::Object(output)
 }
 
+/// Output file to markdown source and template mapping, stored in `sources.json`
+#[derive(Serialize, Debug, PartialEq)]
+pub struct SourceMapEntry {
+    /// Source path relative to the input folder
+    pub source: String,
+    /// Absolute source path, used to open the file in the editor
+    pub file: String,
+    pub template: String,
+    /// Heading anchor to source line, used to open the editor at a heading
+    pub headings: std::collections::BTreeMap<String, usize>,
+}
+
+/// Map the anchors generated for markdown headings to their line numbers
+fn heading_lines(source: &str) -> std::collections::BTreeMap<String, usize> {
+    let mut headings = std::collections::BTreeMap::new();
+    let mut in_code_block = false;
+    for (index, line) in source.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || !line.starts_with('#') {
+            continue;
+        }
+        let text = line.trim_start_matches('#');
+        if !text.starts_with(' ') {
+            continue;
+        }
+        // same rule as `parser::heading_anchors`
+        let anchor = slug::slugify(text.trim());
+        headings.entry(anchor).or_insert(index + 1);
+    }
+    headings
+}
+
+fn create_sources_json(
+    site_data: &Data,
+    input_folder: &Path,
+    tera: &Tera,
+    editor: bool,
+) -> serde_json::Value {
+    let input_folder =
+        fs::canonicalize(input_folder).unwrap_or_else(|_| input_folder.to_path_buf());
+    let files: std::collections::BTreeMap<String, SourceMapEntry> = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .filter_map(|content| {
+            let source_path = content.source_path.as_ref()?;
+            let file = fs::canonicalize(source_path).unwrap_or_else(|_| source_path.clone());
+            let source = file.strip_prefix(&input_folder).unwrap_or(&file);
+            Some((
+                format!("{}.html", content.slug),
+                SourceMapEntry {
+                    source: source.display().to_string(),
+                    file: file.display().to_string(),
+                    template: resolved_template(content, tera).to_string(),
+                    headings: fs::read_to_string(&file)
+                        .map(|source| heading_lines(&source))
+                        .unwrap_or_default(),
+                },
+            ))
+        })
+        .collect();
+    serde_json::json!({
+        "editor_url": site_data.site.editor_url,
+        "editor": editor,
+        "files": files,
+    })
+}
+
+fn generate_sources_json(
+    site_data: &Data,
+    input_folder: &Path,
+    tera: &Tera,
+    output_path: &Path,
+    editor: bool,
+) {
+    let json = create_sources_json(site_data, input_folder, tera, editor);
+    let sources_file = output_path.join("sources.json");
+    match serde_json::to_string_pretty(&json) {
+        Ok(json_string) => {
+            if let Err(e) = fs::write(&sources_file, json_string) {
+                error!("Failed to write sources.json: {e:?}");
+            } else {
+                info!("Generated sources.json");
+            }
+        }
+        Err(e) => {
+            error!("Failed to serialize sources to JSON: {e}");
+        }
+    }
+}
+
 fn generate_urls_json(site_data: &Data, output_path: &Path) {
     if !site_data.site.publish_urls_json {
         return;
@@ -2371,17 +3472,34 @@ fn handle_content_pages(
         .posts
         .iter()
         .chain(&site_data.pages)
+        .chain(&site_data.expired)
         .collect::<Vec<_>>()
         .par_iter()
         .filter(|content| {
-            // render only if force_render or content is newer than the latest timestamp
-            force_render || content.modified_time.unwrap_or(i64::MAX) > last_build
+            if let Some(sources) = &site_data.preview_sources {
+                return crate::preview::is_previewed(content.source_path.as_deref(), sources);
+            }
+            // render only if force_render or content is newer than the latest timestamp,
+            // scheduled posts that became due or content that expired after the latest
+            // build are rendered too
+            let after_last_build = |date: chrono::NaiveDateTime| {
+                date.and_local_timezone(chrono::Local)
+                    .earliest()
+                    .is_some_and(|date| date.timestamp() > last_build)
+            };
+            force_render
+                || content.modified_time.unwrap_or(i64::MAX) > last_build
+                || content.date.is_some_and(after_last_build)
+                || (content.expired && content.expires.is_some_and(after_last_build))
         })
         .map(|content| -> Result<(), String> {
             let mut content_context = global_context.clone();
             content_context.insert("title", &content.title);
             content_context.insert("content", &content);
             content_context.insert("current_page", &format!("{}.html", &content.slug));
+            if let Some(lang) = &content.lang {
+                content_context.insert("language", lang);
+            }
             debug!(
                 "{} context: {:?}",
                 &content.slug,
@@ -2395,8 +3513,23 @@ fn handle_content_pages(
                 content_context.remove("comments");
             }
 
+            if site_data.site.integrity.enabled {
+                content_context.insert("content_hash", &crate::integrity::content_sha256(content));
+            }
+
+            if site_data.site.build_metadata {
+                content_context.insert(
+                    "build_metadata",
+                    &build_metadata_comment(
+                        Some(content),
+                        input_folder,
+                        &chrono::Local::now().to_rfc3339(),
+                    ),
+                );
+            }
+
             render_html_with_shortcodes(
-                "content.html",
+                content_template(content, tera),
                 &format!("{}.html", &content.slug),
                 tera,
                 &content_context,
@@ -2409,6 +3542,60 @@ fn handle_content_pages(
         .unwrap_or(Ok(()))
 }
 
+/// Template of the content, `template` from frontmatter when it exists
+/// else `content.html`
+fn resolved_template<'a>(content: &'a Content, tera: &Tera) -> &'a str {
+    match content.template.as_deref() {
+        Some(template) if tera.get_template_names().any(|name| name == template) => template,
+        _ => "content.html",
+    }
+}
+
+/// The `resolved_template` of the content, logging the frontmatter
+/// templates missing from the templates folder
+fn content_template<'a>(content: &'a Content, tera: &Tera) -> &'a str {
+    let resolved = resolved_template(content, tera);
+    if let Some(template) = content
+        .template
+        .as_deref()
+        .filter(|template| *template != resolved)
+    {
+        error!(
+            "Template '{template}' set on {} not found on the templates folder, \
+            rendering it with content.html",
+            content
+                .source_path
+                .as_deref()
+                .map_or(content.slug.clone(), |path| path.display().to_string())
+        );
+    }
+    resolved
+}
+
+/// HTML comment appended to every rendered page when `build_metadata` is enabled
+/// e.g: `<!-- marmite 0.2.6 | source: content/post.md | hash: 9f2c... | built: 2025-... -->`
+fn build_metadata_comment(
+    content: Option<&Content>,
+    input_folder: &Path,
+    built_at: &str,
+) -> String {
+    let mut fields = vec![format!("marmite {}", env!("CARGO_PKG_VERSION"))];
+    if let Some(source_path) = content.and_then(|c| c.source_path.as_ref()) {
+        let display_path = source_path
+            .strip_prefix(input_folder)
+            .unwrap_or(source_path);
+        fields.push(format!("source: {}", display_path.display()));
+        if let Ok(source) = fs::read(source_path) {
+            let mut hasher = DefaultHasher::new();
+            source.hash(&mut hasher);
+            fields.push(format!("hash: {:016x}", hasher.finish()));
+        }
+    }
+    fields.push(format!("built: {built_at}"));
+    // `--` is not allowed inside HTML comments
+    format!("<!-- {} -->", fields.join(" | ").replace("--", "- -"))
+}
+
 #[allow(clippy::cast_possible_wrap)]
 fn should_force_render(
     input_folder: &Path,
@@ -2473,9 +3660,14 @@ fn should_force_render(
             true
         });
 
-    let config_modified = latest_build_info
-        .as_ref()
-        .is_none_or(|info| info.config != site_data.site);
+    // the private key is skipped when saving the config, only its presence is compared
+    let config_modified = latest_build_info.as_ref().is_none_or(|info| {
+        let site = Marmite {
+            private_key: None,
+            ..site_data.site.clone()
+        };
+        info.config != site || info.private != site_data.site.private_key.is_some()
+    });
 
     templates_modified || fragments_modified || config_modified
 }
@@ -2496,94 +3688,522 @@ fn handle_404(
         .build();
     if input_404_path.exists() {
         let custom_content =
-            Content::from_markdown(&input_404_path, None, &Marmite::default(), None)?;
+            Content::from_markdown(&input_404_path, None, &Marmite::default(), None, None)
+                .map_err(|e| e.message)?;
         content.html.clone_from(&custom_content.html);
         content.title.clone_from(&custom_content.title);
     }
     context.insert("title", &content.title);
     context.insert("content", &content);
     context.insert("current_page", "404.html");
-    render_html("content.html", "404.html", tera, &context, output_dir)?;
+    render_html(
+        "custom_404.html,content.html",
+        "404.html",
+        tera,
+        &context,
+        output_dir,
+    )?;
     Ok(())
 }
 
-fn handle_tag_pages(
-    output_dir: &Path,
+/// Render `{stream}-404.html` and `{stream}-410.html` for every stream having a
+/// `custom_{stream}_404.html` or `custom_{stream}_410.html` template, the text comes
+/// from `_{stream}_404.md` (or `_{stream}_410.md`) falling back to `_404.md`.
+fn handle_stream_error_pages(
     site_data: &Data,
+    content_dir: &Path,
     global_context: &Context,
     tera: &Tera,
+    output_dir: &Path,
 ) -> Result<(), String> {
-    site_data
-        .tag
-        .iter()
-        // IMPORTANT: Filter to only process slugified keys (for backward compatibility,
-        // we store under both original and slugified keys, but only generate pages for slugified ones)
-        .filter(|(key, _)| slug::slugify(key) == **key)
-        .collect::<Vec<_>>()
-        .par_iter()
-        .map(|(tag_slug, tagged_contents)| -> Result<(), String> {
-            // tag_slug is the slugified version from the HashMap key
-            // we need to find the original tag name from the content
-            // We look in the unfiltered content first to ensure we find the original tag
-            let original_tag = tagged_contents
-                .iter()
-                .find_map(|content| {
-                    content
-                        .tags
-                        .iter()
-                        .find(|t| slug::slugify(t) == tag_slug.as_str())
-                        .cloned()
-                })
-                .unwrap_or_else(|| (*tag_slug).to_string());
-
-            debug!("Tag slug: '{tag_slug}' -> Original tag: '{original_tag}'");
-
-            let filename = format!("tag-{tag_slug}");
-            // Filter out draft content
-            let filtered_contents: Vec<Content> = tagged_contents
-                .iter()
-                .filter(|content| content.stream.as_deref() != Some("draft"))
-                .cloned()
-                .collect();
-            handle_list_page(
-                global_context,
-                &site_data
-                    .site
-                    .tags_content_title
-                    .replace("$tag", &original_tag),
-                &filtered_contents,
-                site_data,
-                tera,
-                output_dir,
-                &filename,
-            )?;
-            // Render tag-{tag}.rss for each stream
-            crate::feed::generate_rss(
-                &filtered_contents,
-                output_dir,
-                &filename.clone(),
-                &site_data.site,
-            )?;
+    let error_pages = [
+        (404, "Page not found", "Page not found :/"),
+        (
+            410,
+            "Page gone",
+            "This page was removed and is no longer available.",
+        ),
+    ];
+    for stream in site_data.stream.map.keys() {
+        let stream_slug = slug::slugify(stream);
+        for (code, title, html) in error_pages {
+            let template = format!("custom_{stream}_{code}.html");
+            if tera.get_template(&template).is_err() {
+                continue;
+            }
 
-            if site_data.site.json_feed {
-                crate::feed::generate_json(
-                    &filtered_contents,
-                    output_dir,
-                    &filename,
-                    &site_data.site,
-                )?;
+            let filename = format!("{stream_slug}-{code}.html");
+            let mut content = ContentBuilder::default()
+                .html(html.to_string())
+                .title(title.to_string())
+                .slug(format!("{stream_slug}-{code}"))
+                .stream(stream.clone())
+                .build();
+            let source = [
+                content_dir.join(format!("_{stream}_{code}.md")),
+                content_dir.join(format!("_{code}.md")),
+            ]
+            .into_iter()
+            .find(|path| path.exists());
+            if let Some(source) = source {
+                let custom_content =
+                    Content::from_markdown(&source, None, &Marmite::default(), None, None)
+                        .map_err(|e| e.message)?;
+                content.html.clone_from(&custom_content.html);
+                content.title.clone_from(&custom_content.title);
             }
-            Ok(())
-        })
-        .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
-        .unwrap_or(Ok(()))?;
 
-    // Render tags.html group page
-    let mut tag_list_context = global_context.clone();
-    tag_list_context.insert("title", &site_data.site.tags_title);
-    tag_list_context.insert("current_page", "tags.html");
-    tag_list_context.insert("kind", "tag");
-    render_html(
+            let mut context = global_context.clone();
+            context.insert("title", &content.title);
+            context.insert("content", &content);
+            context.insert("current_page", &filename);
+            context.insert("stream", stream);
+            render_html(&template, &filename, tera, &context, output_dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generate `activity.rss` with backlinks between site contents and
+/// the webmentions received, loaded from `webmentions_file` if it exists.
+fn handle_activity_feed(
+    site_data: &Data,
+    input_folder: &Path,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let all_content = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .cloned()
+        .collect::<Vec<Content>>();
+    let mut items = crate::feed::collect_backlink_activity(&all_content);
+    items.extend(crate::feed::load_webmentions(
+        &input_folder.join(&site_data.site.webmentions_file),
+    ));
+    crate::feed::generate_activity_rss(&items, output_dir, "activity", &site_data.site, tera)
+}
+
+/// Generate `status.rss` with the status updates linking to the now page
+fn handle_status_feed(site_data: &Data, tera: &Tera, output_dir: &Path) -> Result<(), String> {
+    let now = &site_data.site.now;
+    let items = crate::now::status_items(&site_data.statuses, &now.slug);
+    let title = format!("{} - {}", site_data.site.name, now.title);
+    crate::feed::generate_items_rss(&items, output_dir, "status", &title, &site_data.site, tera)
+}
+
+/// Render the contact page from `contact.html` template
+/// the form posts to the configured endpoint (or formspree form id)
+fn handle_contact_page(
+    contact_form: &ContactFormConfig,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let Some(form_action) = contact_form.action() else {
+        error!("Contact form requires either `endpoint` or `formspree` to be set, skipping page");
+        return Ok(());
+    };
+    let filename = format!("{}.html", contact_form.slug);
+    let mut context = global_context.clone();
+    context.insert("title", &contact_form.title);
+    context.insert("contact_form", contact_form);
+    context.insert("form_action", &form_action);
+    context.insert("current_page", &filename);
+    render_html(
+        &format!("custom_{}.html,contact.html", contact_form.slug),
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render the glossary page from `glossary.html` template
+/// each term is anchored by its slug so posts can link to `{slug}.html#{term}`
+fn handle_glossary_page(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let glossary = &site_data.site.glossary;
+    let filename = format!("{}.html", glossary.slug);
+    let mut context = global_context.clone();
+    context.insert("title", &glossary.title);
+    context.insert("terms", &site_data.glossary);
+    context.insert("current_page", &filename);
+    render_html(
+        &format!("custom_{}.html,glossary.html", glossary.slug),
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render the uses page from `uses.html` template with the categories of the `uses.file`
+fn handle_uses_page(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let uses = &site_data.site.uses;
+    let filename = format!("{}.html", uses.slug);
+    let mut context = global_context.clone();
+    context.insert("title", &uses.title);
+    context.insert("uses", &site_data.uses);
+    context.insert("current_page", &filename);
+    render_html(
+        &format!("custom_{}.html,uses.html", uses.slug),
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render `{slug}/index.html` from `cv.html` template with the JSON Resume,
+/// then print it to PDF when `cv.pdf_command` is set
+fn handle_cv_page(
+    resume: &Value,
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let cv = &site_data.site.cv;
+    let filename = format!("{}/index.html", cv.slug);
+    let pdf = crate::cv::pdf_url(cv);
+    fs::create_dir_all(output_dir.join(&cv.slug)).map_err(|e| e.to_string())?;
+    let mut context = global_context.clone();
+    context.insert("title", &cv.title);
+    context.insert("resume", resume);
+    context.insert("pdf", &pdf);
+    context.insert("current_page", &filename);
+    render_html(
+        &format!("custom_{}.html,cv.html", cv.slug),
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )?;
+
+    if let (Some(command), Some(pdf)) = (&cv.pdf_command, pdf) {
+        let output = output_dir.join(&pdf);
+        match crate::cv::export_pdf(command, &output_dir.join(&filename), &output) {
+            Ok(()) => info!("Generated {}", output.display()),
+            Err(e) => error!("Failed to export {pdf}: {e}"),
+        }
+    }
+    Ok(())
+}
+
+/// Render the `{slug}.html` portfolio from `projects.html` template and
+/// the `{slug}/{project}.html` page of each project from `project.html`
+fn handle_projects_pages(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let projects = &site_data.site.projects;
+    let filename = format!("{}.html", projects.slug);
+    let mut context = global_context.clone();
+    context.insert("title", &projects.title);
+    context.insert("projects", &site_data.projects);
+    context.insert("current_page", &filename);
+    render_html(
+        &format!("custom_{}.html,projects.html", projects.slug),
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )?;
+
+    fs::create_dir_all(output_dir.join(&projects.slug)).map_err(|e| e.to_string())?;
+    for project in &site_data.projects {
+        let filename = format!("{}/{}.html", projects.slug, project.slug);
+        let mut context = global_context.clone();
+        context.insert("title", &project.name);
+        context.insert("project", project);
+        context.insert("current_page", &filename);
+        render_html(
+            "custom_project.html,project.html",
+            &filename,
+            tera,
+            &context,
+            output_dir,
+        )?;
+    }
+    Ok(())
+}
+
+/// Render the `meta/freshness/index.html` report from `freshness.html` template
+fn handle_freshness_page(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let months = site_data.site.freshness.months;
+    let stale = crate::freshness::collect_stale(
+        &site_data.posts,
+        months,
+        chrono::Local::now().naive_local(),
+    );
+    fs::create_dir_all(output_dir.join("meta/freshness")).map_err(|e| e.to_string())?;
+    let filename = "meta/freshness/index.html";
+    let mut context = global_context.clone();
+    context.insert("title", "Content freshness");
+    context.insert("months", &months);
+    context.insert("stale", &stale);
+    context.insert("current_page", filename);
+    render_html(
+        "custom_freshness.html,freshness.html",
+        filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render the hidden `orphans/index.html` report from `orphans.html` template
+fn handle_orphans_page(
+    site_data: &Data,
+    content_dir: &Path,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let orphans =
+        crate::orphans::collect_orphans(site_data, &fragment_links(content_dir, &site_data.site));
+    fs::create_dir_all(output_dir.join("orphans")).map_err(|e| e.to_string())?;
+    let filename = "orphans/index.html";
+    let mut context = global_context.clone();
+    context.insert("title", "Orphan content");
+    context.insert("orphans", &orphans);
+    context.insert("current_page", filename);
+    render_html(
+        "custom_orphans.html,orphans.html",
+        filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render the `bookmarks.html` link board and the `bookmarks.rss` feed
+/// when any content has a `bookmark`
+fn handle_bookmarks_page(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let bookmarks = site_data.bookmarks();
+    if bookmarks.is_empty() {
+        return Ok(());
+    }
+    let filename = "bookmarks.html";
+    let mut context = global_context.clone();
+    context.insert("title", &site_data.site.bookmarks_title);
+    context.insert("bookmarks", &bookmarks);
+    context.insert("current_page", filename);
+    render_html(
+        "custom_bookmarks.html,bookmarks.html",
+        filename,
+        tera,
+        &context,
+        output_dir,
+    )?;
+    crate::feed::generate_rss(&bookmarks, output_dir, "bookmarks", &site_data.site, tera)?;
+    if site_data.site.json_feed {
+        crate::feed::generate_json(&bookmarks, output_dir, "bookmarks", &site_data.site)?;
+    }
+    Ok(())
+}
+
+/// Render `{stream}-wall.html` from `photos.html` template with every photo
+/// of the `photos` stream, the latest first
+fn handle_photo_wall_page(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    let filename = format!("{}-wall.html", site_data.site.photos.stream);
+    let mut context = global_context.clone();
+    context.insert("title", &site_data.site.photos.title);
+    context.insert("photos", &site_data.photos);
+    context.insert("current_page", &filename);
+    render_html(
+        "custom_photos.html,photos.html",
+        &filename,
+        tera,
+        &context,
+        output_dir,
+    )
+}
+
+/// Render `review-{year}.html` from `year_review.html` template for every
+/// past year, the `archive/{year}.md` intro is shown when it exists
+fn handle_year_review_pages(
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+    output_dir: &Path,
+) -> Result<(), String> {
+    for review in site_data.year_reviews() {
+        let filename = format!("review-{}.html", review.year);
+        let mut context = global_context.clone();
+        context.insert("title", &format!("{} in review", review.year));
+        context.insert("review", &review);
+        context.insert("current_page", &filename);
+        if let Some(intro) = site_data.archive_intros.get(&review.year.to_string()) {
+            context.insert("archive_intro", &render_intro(intro, &site_data.site));
+        }
+        render_html(
+            "custom_year_review.html,year_review.html",
+            &filename,
+            tera,
+            &context,
+            output_dir,
+        )?;
+    }
+    Ok(())
+}
+
+/// Render `tag-{a}+{b}.html` for each combination of `tag_intersections`
+/// with the posts tagged with all of its tags, the template gets `tag_intersection`
+fn handle_tag_intersection_pages(
+    output_dir: &Path,
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+) -> Result<(), String> {
+    for intersection in crate::tag_intersections::parse_all(&site_data.site.tag_intersections) {
+        let contents = intersection.contents(&site_data.posts);
+        if contents.is_empty() {
+            warn!(
+                "No posts are tagged with all of '{}', skipping its page",
+                intersection.title()
+            );
+            continue;
+        }
+        let mut context = global_context.clone();
+        context.insert("tag_intersection", &intersection);
+        handle_list_page(
+            &context,
+            &site_data
+                .site
+                .tags_content_title
+                .replace("$tag", &intersection.title()),
+            &contents,
+            site_data,
+            tera,
+            output_dir,
+            &intersection.filename(),
+        )?;
+    }
+    Ok(())
+}
+
+fn handle_tag_pages(
+    output_dir: &Path,
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+) -> Result<(), String> {
+    site_data
+        .tag
+        .iter()
+        // IMPORTANT: Filter to only process slugified keys (for backward compatibility,
+        // we store under both original and slugified keys, but only generate pages for slugified ones)
+        .filter(|(key, _)| slug::slugify(key) == **key)
+        .collect::<Vec<_>>()
+        .par_iter()
+        .map(|(tag_slug, tagged_contents)| -> Result<(), String> {
+            // tag_slug is the slugified version from the HashMap key
+            // we need to find the original tag name from the content
+            // We look in the unfiltered content first to ensure we find the original tag
+            let original_tag = tagged_contents
+                .iter()
+                .find_map(|content| {
+                    content
+                        .tags
+                        .iter()
+                        .find(|t| slug::slugify(t) == tag_slug.as_str())
+                        .cloned()
+                })
+                .unwrap_or_else(|| (*tag_slug).to_string());
+
+            debug!("Tag slug: '{tag_slug}' -> Original tag: '{original_tag}'");
+
+            let filename = format!("tag-{tag_slug}");
+            let mut tag_context = global_context.clone();
+            if let Some(tag_config) = site_data
+                .site
+                .tags
+                .iter()
+                .find(|(tag, _)| slug::slugify(tag) == tag_slug.as_str())
+                .map(|(_, tag_config)| tag_config)
+            {
+                tag_context.insert("tag", tag_config);
+                if let Some(intro) = &tag_config.intro {
+                    tag_context.insert("tag_intro", &render_intro(intro, &site_data.site));
+                }
+            }
+            // Filter out draft content
+            let filtered_contents: Vec<Content> = tagged_contents
+                .iter()
+                .filter(|content| content.stream.as_deref() != Some("draft"))
+                .cloned()
+                .collect();
+            handle_list_page(
+                &tag_context,
+                &site_data
+                    .site
+                    .tags_content_title
+                    .replace("$tag", &original_tag),
+                &filtered_contents,
+                site_data,
+                tera,
+                output_dir,
+                &filename,
+            )?;
+            // Render tag-{tag}.rss for each stream
+            crate::feed::generate_rss(
+                &filtered_contents,
+                output_dir,
+                &filename.clone(),
+                &site_data.site,
+                tera,
+            )?;
+
+            if site_data.site.json_feed {
+                crate::feed::generate_json(
+                    &filtered_contents,
+                    output_dir,
+                    &filename,
+                    &site_data.site,
+                )?;
+            }
+            Ok(())
+        })
+        .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
+        .unwrap_or(Ok(()))?;
+
+    handle_tag_intersection_pages(output_dir, site_data, global_context, tera)?;
+
+    // Render tags.html group page
+    let mut tag_list_context = global_context.clone();
+    tag_list_context.insert("title", &site_data.site.tags_title);
+    tag_list_context.insert("current_page", "tags.html");
+    tag_list_context.insert("kind", "tag");
+    render_html(
         "group.html",
         "tags.html",
         tera,
@@ -2593,6 +4213,128 @@ fn handle_tag_pages(
     Ok(())
 }
 
+/// Render `{taxonomy}-{term}.html` list pages with its feeds and the
+/// `{taxonomy}.html` group page for every custom taxonomy
+fn handle_taxonomy_pages(
+    output_dir: &Path,
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+) -> Result<(), String> {
+    for (name, taxonomy) in &site_data.taxonomies {
+        let config = site_data
+            .site
+            .taxonomies
+            .get(name)
+            .cloned()
+            .unwrap_or_default();
+        taxonomy
+            .iter()
+            .collect::<Vec<_>>()
+            .par_iter()
+            .map(|(term_slug, term_contents)| -> Result<(), String> {
+                let original_term = term_contents
+                    .iter()
+                    .find_map(|content| {
+                        content.taxonomies.get(name).and_then(|terms| {
+                            terms
+                                .iter()
+                                .find(|t| slug::slugify(t) == term_slug.as_str())
+                                .cloned()
+                        })
+                    })
+                    .unwrap_or_else(|| (*term_slug).to_string());
+                let filename = format!("{name}-{term_slug}");
+                let filtered_contents: Vec<Content> = term_contents
+                    .iter()
+                    .filter(|content| content.stream.as_deref() != Some("draft"))
+                    .cloned()
+                    .collect();
+                handle_list_page(
+                    global_context,
+                    &config.content_title(&original_term),
+                    &filtered_contents,
+                    site_data,
+                    tera,
+                    output_dir,
+                    &filename,
+                )?;
+                crate::feed::generate_rss(
+                    &filtered_contents,
+                    output_dir,
+                    &filename,
+                    &site_data.site,
+                    tera,
+                )?;
+                if site_data.site.json_feed {
+                    crate::feed::generate_json(
+                        &filtered_contents,
+                        output_dir,
+                        &filename,
+                        &site_data.site,
+                    )?;
+                }
+                Ok(())
+            })
+            .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
+            .unwrap_or(Ok(()))?;
+
+        if taxonomy.map.is_empty() {
+            continue;
+        }
+        let mut group_context = global_context.clone();
+        group_context.insert("title", &config.title(name));
+        group_context.insert("current_page", &format!("{name}.html"));
+        group_context.insert("kind", name);
+        render_html(
+            &format!("custom_{name}.html,group.html"),
+            &format!("{name}.html"),
+            tera,
+            &group_context,
+            output_dir,
+        )?;
+    }
+    Ok(())
+}
+
+/// Render a `section-{slug}.html` list page for every folder of the content folder,
+/// the folders holding only subfolders are rendered with an empty list
+fn handle_section_pages(
+    output_dir: &Path,
+    site_data: &Data,
+    global_context: &Context,
+    tera: &Tera,
+) -> Result<(), String> {
+    collect_sections(site_data.section.map.keys())
+        .par_iter()
+        .map(|section| -> Result<(), String> {
+            // Filter out draft content
+            let section_contents: Vec<Content> = site_data
+                .section
+                .map
+                .get(&section.path)
+                .into_iter()
+                .flatten()
+                .filter(|content| content.stream.as_deref() != Some("draft"))
+                .cloned()
+                .collect();
+            let mut section_context = global_context.clone();
+            section_context.insert("section", section);
+            section_context.insert("breadcrumbs", &breadcrumbs(&section.path));
+            handle_list_page(
+                &section_context,
+                &section.name,
+                &section_contents,
+                site_data,
+                tera,
+                output_dir,
+                &section.slug,
+            )
+        })
+        .reduce_with(|r1, r2| if r1.is_err() { r1 } else { r2 })
+        .unwrap_or(Ok(()))
+}
+
 fn handle_archive_pages(
     output_dir: &Path,
     site_data: &Data,
@@ -2606,6 +4348,10 @@ fn handle_archive_pages(
         .par_iter()
         .map(|(year, archive_contents)| -> Result<(), String> {
             let filename = format!("archive-{year}");
+            let mut archive_context = global_context.clone();
+            if let Some(intro) = site_data.archive_intros.get(year.as_str()) {
+                archive_context.insert("archive_intro", &render_intro(intro, &site_data.site));
+            }
             // Filter out draft content
             let filtered_contents: Vec<Content> = archive_contents
                 .iter()
@@ -2613,7 +4359,7 @@ fn handle_archive_pages(
                 .cloned()
                 .collect();
             handle_list_page(
-                global_context,
+                &archive_context,
                 &site_data.site.archives_content_title.replace("$year", year),
                 &filtered_contents,
                 site_data,
@@ -2627,6 +4373,7 @@ fn handle_archive_pages(
                 output_dir,
                 &filename.clone(),
                 &site_data.site,
+                tera,
             )?;
 
             if site_data.site.json_feed {
@@ -2749,6 +4496,12 @@ fn render_html_with_shortcodes(
         rendered = fix_wikilinks(&rendered, data);
     }
 
+    // Build metadata comment is only in the context when `build_metadata` is enabled
+    if let Some(comment) = context.get("build_metadata").and_then(Value::as_str) {
+        rendered.push('\n');
+        rendered.push_str(comment);
+    }
+
     let output_file = output_dir.join(filename);
     fs::write(&output_file, rendered).map_err(|e| e.to_string())?;
     info!("Generated {}", &output_file.display());
@@ -2763,77 +4516,94 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
     let media_folder = content_folder.join("media");
 
     if let Err(e) = fs::create_dir_all(input_folder) {
-        error!("Failed to create input folder: {e:?}");
-        process::exit(1);
-    }
-    if input_folder
-        .read_dir()
-        .map_err(|e| {
-            error!("Failed to read input folder: {e}");
-            process::exit(1);
-        })
-        .unwrap_or_else(|()| std::process::exit(1))
-        .next()
-        .is_some()
-    {
-        error!("Input folder is not empty: {}", input_folder.display());
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create input folder: {e:?}"),
+        );
+    }
+    let mut entries = match input_folder.read_dir() {
+        Ok(entries) => entries,
+        Err(e) => errors::exit(ErrorKind::Io, &format!("Failed to read input folder: {e}")),
+    };
+    if entries.next().is_some() {
+        errors::exit(
+            ErrorKind::Config,
+            &format!("Input folder is not empty: {}", input_folder.display()),
+        );
     }
     crate::config::generate(input_folder, cli_args);
     if let Err(e) = fs::create_dir(&content_folder) {
-        error!("Failed to create 'content' folder: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content' folder: {e:?}"),
+        );
     }
     if let Err(e) = fs::create_dir(&media_folder) {
-        error!("Failed to create 'content/media' folder: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/media' folder: {e:?}"),
+        );
     }
     // create input_folder/custom.css with `/* Custom CSS */` content
     if let Err(e) = fs::write(input_folder.join("custom.css"), "/* Custom CSS */") {
-        error!("Failed to create 'custom.css' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'custom.css' file: {e:?}"),
+        );
     }
     // create input_folder/custom.js with `// Custom JS` content
     if let Err(e) = fs::write(input_folder.join("custom.js"), "// Custom JS") {
-        error!("Failed to create 'custom.js' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'custom.js' file: {e:?}"),
+        );
     }
     // create content/_404.md with `# Not Found` content
     if let Err(e) = fs::write(content_folder.join("_404.md"), "# Not Found") {
-        error!("Failed to create 'content/_404.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_404.md' file: {e:?}"),
+        );
     }
     // create content/_references.md with `[marmite]: https://github.com/rochacbruno/marmite` content
     if let Err(e) = fs::write(
         content_folder.join("_references.md"),
         "[github]: https://github.com/rochacbruno/marmite",
     ) {
-        error!("Failed to create 'content/_references.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_references.md' file: {e:?}"),
+        );
     }
     // create content/_markdown_header.md with `<!-- Content Injected to every content markdown header -->` content
     if let Err(e) = fs::write(
         content_folder.join("_markdown_header.md"),
         "<!-- Content Injected to every content markdown header -->",
     ) {
-        error!("Failed to create 'content/_markdown_header.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_markdown_header.md' file: {e:?}"),
+        );
     }
     // create content/_markdown_footer.md with `<!-- Content Injected to every content markdown footer -->` content
     if let Err(e) = fs::write(
         content_folder.join("_markdown_footer.md"),
         "<!-- Content Injected to every content markdown footer -->",
     ) {
-        error!("Failed to create 'content/_markdown_footer.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_markdown_footer.md' file: {e:?}"),
+        );
     }
     // create content/_announce.md with `Give us a &star; on [github]` content
     if let Err(e) = fs::write(
         content_folder.join("_announce.md"),
         "Give us a &star; on [github]",
     ) {
-        error!("Failed to create 'content/_announce.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_announce.md' file: {e:?}"),
+        );
     }
     // create content/_sidebar.md with `<!-- Sidebar content -->` content
     let side_bar_content = "
@@ -2849,8 +4619,10 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
     {% endfor %}
     ";
     if let Err(e) = fs::write(content_folder.join("_sidebar.example.md"), side_bar_content) {
-        error!("Failed to create 'content/_sidebar.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_sidebar.md' file: {e:?}"),
+        );
     }
     // create content/_comments.md with `<!-- Comments -->` content
     if let Err(e) = fs::write(
@@ -2868,8 +4640,10 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
         </script>\n\
         ",
     ) {
-        error!("Failed to create 'content/_comments.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_comments.md' file: {e:?}"),
+        );
     }
     // create content/_hero.md with `<!-- Hero content -->` content
     if let Err(e) = fs::write(
@@ -2881,8 +4655,10 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
         remove the file to disable the hero section.\n\
         ",
     ) {
-        error!("Failed to create 'content/_hero.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/_hero.md' file: {e:?}"),
+        );
     }
     // create content/about.md with `# About` content
     if let Err(e) = fs::write(
@@ -2892,8 +4668,10 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
         Hi, edit `about.md` to change this content.
         ",
     ) {
-        error!("Failed to create 'content/about.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/about.md' file: {e:?}"),
+        );
     }
     // create content/{now}-welcome.md with `# Welcome to Marmite` content
     let now = chrono::Local::now();
@@ -2917,19 +4695,137 @@ pub fn initialize(input_folder: &Arc<std::path::PathBuf>, cli_args: &Arc<crate::
         read more on [marmite documentation](https://marmite.blog)\n\n\
         ",
     ) {
-        error!("Failed to create 'content/{now}-welcome.md' file: {e:?}");
-        process::exit(1);
+        errors::exit(
+            ErrorKind::Io,
+            &format!("Failed to create 'content/{now}-welcome.md' file: {e:?}"),
+        );
     }
     info!("Site initialized in {}", input_folder.display());
 }
 
-/// Show all site URLs in JSON format
-#[allow(clippy::too_many_lines)]
-pub fn show_urls(
+/// Show the posts not updated in `freshness.months` as a table
+pub fn show_freshness(
+    config_path: &Arc<std::path::PathBuf>,
+    input_folder: &Arc<std::path::PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) {
+    let mut site_data = Data::from_file(config_path.as_path());
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    site_data.site.override_from_cli_args(args);
+
+    let fragments = collect_content_fragments(&content_folder);
+    if let Err(e) = collect_content(&content_folder, &mut site_data, &fragments) {
+        errors::exit(e.kind, &e.message);
+    }
+    let other_contents = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .cloned()
+        .collect::<Vec<Content>>();
+    _collect_back_links(&mut site_data.posts, &other_contents);
+
+    let months = site_data.site.freshness.months;
+    let stale = crate::freshness::collect_stale(
+        &site_data.posts,
+        months,
+        chrono::Local::now().naive_local(),
+    );
+    if stale.is_empty() {
+        println!("All posts were updated in the last {months} months");
+    } else {
+        println!("Posts not updated in the last {months} months:\n");
+        print!("{}", crate::freshness::format_table(&stale));
+    }
+}
+
+/// Slugs linked by the fragments shown on every page (`_announce.md`, `_header.md`,
+/// `_hero.md`, `_sidebar.md` and `_footer.md`)
+fn fragment_links(content_dir: &Path, site: &Marmite) -> HashSet<String> {
+    let default_parser_options = crate::config::ParserOptions::default();
+    let parser_options = site
+        .markdown_parser
+        .as_ref()
+        .unwrap_or(&default_parser_options);
+    ["announce", "header", "hero", "sidebar", "footer"]
+        .iter()
+        .filter_map(|fragment| fs::read_to_string(content_dir.join(format!("_{fragment}.md"))).ok())
+        .filter_map(|fragment| {
+            crate::parser::get_links_to(&crate::parser::get_html_with_options(
+                &fragment,
+                parser_options,
+            ))
+        })
+        .flatten()
+        .map(|link| crate::orphans::link_slug(&link, &site.url))
+        .collect()
+}
+
+/// Show the content only reachable by typing its url as a table
+pub fn show_orphans(
+    config_path: &Arc<std::path::PathBuf>,
+    input_folder: &Arc<std::path::PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) {
+    let mut site_data = Data::from_file(config_path.as_path());
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    site_data.site.override_from_cli_args(args);
+
+    let fragments = collect_content_fragments(&content_folder);
+    if let Err(e) = collect_content(&content_folder, &mut site_data, &fragments) {
+        errors::exit(e.kind, &e.message);
+    }
+    let other_contents = site_data
+        .posts
+        .iter()
+        .chain(&site_data.pages)
+        .cloned()
+        .collect::<Vec<Content>>();
+    _collect_back_links(&mut site_data.posts, &other_contents);
+    _collect_back_links(&mut site_data.pages, &other_contents);
+
+    let orphans = crate::orphans::collect_orphans(
+        &site_data,
+        &fragment_links(&content_folder, &site_data.site),
+    );
+    if orphans.is_empty() {
+        println!("No orphan content, everything is reachable");
+    } else {
+        println!("Content not listed, in the menu or linked from other content:\n");
+        print!("{}", crate::orphans::format_table(&orphans));
+    }
+}
+
+/// Show the internal link graph metrics of the posts and pages as a table
+pub fn show_stats(
     config_path: &Arc<std::path::PathBuf>,
     input_folder: &Arc<std::path::PathBuf>,
     args: &Arc<crate::cli::Cli>,
 ) {
+    let mut site_data = Data::from_file(config_path.as_path());
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    site_data.site.override_from_cli_args(args);
+
+    let fragments = collect_content_fragments(&content_folder);
+    if let Err(e) = collect_content(&content_folder, &mut site_data, &fragments) {
+        errors::exit(e.kind, &e.message);
+    }
+
+    let metrics = crate::graph::compute_metrics(site_data.posts.iter().chain(&site_data.pages));
+    if metrics.is_empty() {
+        println!("No content found");
+    } else {
+        print!("{}", crate::graph::format_table(&metrics));
+    }
+}
+
+/// Load the config and collect the content and the generated URLs without rendering,
+/// used by the commands inspecting the site
+pub fn collect_site_data(
+    config_path: &Arc<std::path::PathBuf>,
+    input_folder: &Arc<std::path::PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) -> Data {
     // Load site data from config
     let mut site_data = Data::from_file(config_path.as_path());
     let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
@@ -2939,11 +4835,32 @@ pub fn show_urls(
 
     // Collect content fragments and process content
     let fragments = collect_content_fragments(&content_folder);
-    collect_content(&content_folder, &mut site_data, &fragments);
+    if let Err(e) = collect_content(&content_folder, &mut site_data, &fragments) {
+        errors::exit(e.kind, &e.message);
+    }
+    site_data.uses = crate::uses::load_uses(&input_folder.join(&site_data.site.uses.file));
+    site_data.resume = crate::cv::load_resume(&input_folder.join(&site_data.site.cv.file));
+    // inspecting the site never fetches, the cached metadata is enough for the urls
+    site_data.projects = crate::projects::load_projects(
+        &site_data.site.projects,
+        &mut load_sidecar(&sidecar_path(input_folder, PROJECTS_CACHE)),
+        false,
+        chrono::Local::now().naive_local(),
+    );
     site_data.sort_all();
 
     // Collect all URLs including pagination, feeds, and file mappings
     site_data.collect_all_urls();
+    site_data
+}
+
+/// Show all site URLs in JSON format
+pub fn show_urls(
+    config_path: &Arc<std::path::PathBuf>,
+    input_folder: &Arc<std::path::PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) {
+    let site_data = collect_site_data(config_path, input_folder, args);
 
     // Generate JSON using the shared function
     let json = create_urls_json(&site_data);
@@ -0,0 +1,139 @@
+use crate::content::Content;
+use log::{error, info, warn};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Slugs used by every source file, keyed by the source path relative to the
+/// input folder, oldest first, the last slug of each list is the current one
+pub type SlugHistory = BTreeMap<String, Vec<String>>;
+
+/// Record the current `(source, slug)` of every content, warns about the slugs
+/// changed since the previous build and returns the `(old_slug, new_slug)` redirects
+/// for every previous slug that is not used by a content anymore
+pub fn update_slug_history(
+    history: &mut SlugHistory,
+    sources: &[(String, String)],
+) -> Vec<(String, String)> {
+    let current_slugs: HashSet<&str> = sources.iter().map(|(_, slug)| slug.as_str()).collect();
+    let mut redirects = Vec::new();
+    for (source, slug) in sources {
+        let slugs = history.entry(source.clone()).or_default();
+        if let Some(old_slug) = slugs.last().filter(|old_slug| *old_slug != slug) {
+            warn!(
+                "Slug of {source} changed from '{old_slug}' to '{slug}', {old_slug}.html will redirect to {slug}.html"
+            );
+        }
+        slugs.retain(|old_slug| old_slug != slug);
+        slugs.push(slug.clone());
+        for old_slug in &slugs[..slugs.len() - 1] {
+            if !current_slugs.contains(old_slug.as_str()) {
+                redirects.push((old_slug.clone(), slug.clone()));
+            }
+        }
+    }
+    redirects
+}
+
+/// The `(old_slug, slug)` redirects of the `aliases` frontmatter of every content,
+/// `/old/post.html` and `/old/post` redirect from `old/post.html` while `/old/post/`
+/// redirects from `old/post/index.html`, aliases used by some content or by
+/// one of the `generated_urls` (tag pages, feeds, pagination...) are skipped
+pub fn alias_redirects<'a>(
+    contents: impl Iterator<Item = &'a Content> + Clone,
+    generated_urls: &[String],
+) -> Vec<(String, String)> {
+    let current_slugs: HashSet<&str> = contents
+        .clone()
+        .map(|content| content.slug.as_str())
+        .collect();
+    let generated_urls: HashSet<&str> = generated_urls.iter().map(String::as_str).collect();
+    let mut redirects = Vec::new();
+    for content in contents {
+        for alias in &content.aliases {
+            let Some(old_slug) = alias_slug(alias) else {
+                warn!("Invalid alias '{alias}' of {}, skipping", content.slug);
+                continue;
+            };
+            if current_slugs.contains(old_slug.as_str()) {
+                warn!(
+                    "Alias '{alias}' of {} is the url of a content, skipping",
+                    content.slug
+                );
+                continue;
+            }
+            if generated_urls.contains(format!("{old_slug}.html").as_str()) {
+                warn!(
+                    "Alias '{alias}' of {} is the url of a generated page, skipping",
+                    content.slug
+                );
+                continue;
+            }
+            redirects.push((old_slug, content.slug.clone()));
+        }
+    }
+    redirects
+}
+
+/// Path of the redirect page of an alias without the `.html` extension,
+/// `None` for the site root and aliases leaving the output folder
+fn alias_slug(alias: &str) -> Option<String> {
+    let alias = alias
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    if alias.is_empty() {
+        return None;
+    }
+    let slug = if alias.ends_with('/') {
+        format!("{alias}index")
+    } else {
+        alias.trim_end_matches(".html").to_string()
+    };
+    if slug
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return None;
+    }
+    Some(slug)
+}
+
+/// Write a `{old_slug}.html` page redirecting to `{new_slug}.html` for every redirect
+pub fn write_redirects(redirects: &[(String, String)], output_path: &Path) {
+    for (old_slug, new_slug) in redirects {
+        let prefix = "../".repeat(old_slug.matches('/').count());
+        let target = format!("{prefix}{new_slug}.html");
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Redirecting to {target}</title>
+<link rel="canonical" href="{target}">
+<meta http-equiv="refresh" content="0; url={target}">
+<meta name="robots" content="noindex">
+</head>
+<body><a href="{target}">{target}</a></body>
+</html>
+"#
+        );
+        let redirect_path = output_path.join(format!("{old_slug}.html"));
+        if let Some(parent) = redirect_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                error!("Unable to create {}: {e}", parent.display());
+                continue;
+            }
+        }
+        if let Err(e) = fs::write(&redirect_path, html) {
+            error!("Failed to write {}: {e}", redirect_path.display());
+        } else {
+            info!("Generated redirect {old_slug}.html -> {new_slug}.html");
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/slug_history.rs"]
+mod tests;
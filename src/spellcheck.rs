@@ -0,0 +1,220 @@
+use crate::embedded::Dictionaries;
+use crate::re;
+use crate::site::{get_content_folder, Data};
+use log::{debug, info};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// Language word lists searched on the system, `{lang}` is the site language
+const SYSTEM_DICTIONARIES: [&str; 4] = [
+    "/usr/share/hunspell/{lang}_*.dic",
+    "/usr/share/myspell/{lang}_*.dic",
+    "/usr/share/myspell/dicts/{lang}_*.dic",
+    "/Library/Spelling/{lang}_*.dic",
+];
+
+/// Words accepted by the spell checker, acronyms are matched case sensitively
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    pub words: HashSet<String>,
+    pub acronyms: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Add words from a word list (one per line) or a hunspell `.dic` file,
+    /// `#` comments, the hunspell word count and affix flags are ignored
+    pub fn add_words(&mut self, text: &str) {
+        for line in text.lines() {
+            let word = line.split('/').next().unwrap_or_default().trim();
+            if word.is_empty() || word.starts_with('#') || word.chars().all(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+            if is_acronym(word) {
+                self.acronyms.insert(word.to_string());
+            }
+            self.words.insert(word.to_lowercase());
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        if is_acronym(word) {
+            return self.acronyms.contains(word);
+        }
+        if let Some(acronym) = word.strip_suffix('s').filter(|w| is_acronym(w)) {
+            return self.acronyms.contains(acronym);
+        }
+        let lower = word.to_lowercase();
+        if self.words.contains(&lower) {
+            return true;
+        }
+        // Word lists without inflections (e.g: hunspell without affixes)
+        ["'s", "s", "es", "ed", "d", "ing", "ly"]
+            .iter()
+            .filter_map(|suffix| lower.strip_suffix(suffix))
+            .any(|stem| stem.len() > 1 && self.words.contains(stem))
+    }
+}
+
+fn is_acronym(word: &str) -> bool {
+    word.chars().count() > 1 && word.chars().all(|c| c.is_uppercase() || c.is_ascii_digit())
+}
+
+/// A misspelled word and the line where it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub line: usize,
+    pub word: String,
+}
+
+/// Check the `title` and `description` frontmatter fields and the body text
+/// of a markdown source, code blocks, inline code, links targets, HTML tags
+/// and template expressions are skipped
+pub fn check_source(text: &str, dictionary: &Dictionary) -> Vec<Finding> {
+    let skip_re = Regex::new(re::MATCH_SPELLCHECK_SKIP).expect("Spellcheck regex should compile");
+    let word_re = Regex::new(re::MATCH_WORD).expect("Word regex should compile");
+
+    let mut findings = Vec::new();
+    let mut in_frontmatter = text.starts_with("---");
+    let mut in_code_block = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        let to_check = if in_frontmatter {
+            if index > 0 && trimmed == "---" {
+                in_frontmatter = false;
+            }
+            match trimmed.split_once(':') {
+                Some(("title" | "description", value)) => value,
+                _ => continue,
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        } else if in_code_block || line.starts_with("    ") || line.starts_with('\t') {
+            continue;
+        } else {
+            line
+        };
+
+        let cleaned = skip_re.replace_all(to_check, " ");
+        for word in word_re.find_iter(&cleaned) {
+            let word = word.as_str();
+            if word.chars().count() > 1 && !dictionary.contains(word) {
+                findings.push(Finding {
+                    line: index + 1,
+                    word: word.to_string(),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Load the bundled dictionaries, the system dictionary for the site language
+/// and the project word lists from `input_folder/dictionaries`
+pub fn load_dictionary(input_folder: &Path, language: &str) -> Result<Dictionary, String> {
+    let mut dictionary = Dictionary::default();
+    for name in Dictionaries::iter() {
+        if let Some(file) = Dictionaries::get(name.as_ref()) {
+            dictionary.add_words(&String::from_utf8_lossy(&file.data));
+        }
+    }
+    let bundled_size = dictionary.words.len();
+
+    let mut paths: Vec<PathBuf> = SYSTEM_DICTIONARIES
+        .iter()
+        .filter_map(|pattern| glob::glob(&pattern.replace("{lang}", language)).ok())
+        .flat_map(|entries| entries.filter_map(Result::ok))
+        .collect();
+    if language == "en" {
+        paths.push(PathBuf::from("/usr/share/dict/words"));
+    }
+    let project_dictionaries = input_folder.join("dictionaries");
+    if project_dictionaries.is_dir() {
+        paths.extend(
+            WalkDir::new(&project_dictionaries)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|e| e.path().is_file())
+                .map(|e| e.path().to_path_buf()),
+        );
+    }
+
+    for path in paths.iter().filter(|p| p.exists()) {
+        debug!("Loading dictionary {}", path.display());
+        let text = fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        dictionary.add_words(&String::from_utf8_lossy(&text));
+    }
+
+    if dictionary.words.len() == bundled_size {
+        return Err(format!(
+            "No dictionary found for language `{language}`, \
+            install a hunspell dictionary or add a word list to {}",
+            project_dictionaries.display()
+        ));
+    }
+    Ok(dictionary)
+}
+
+/// Handle the `--spellcheck` command, prints `file:line: word` for each
+/// unknown word and fails when any is found so it can be used on CI
+pub fn run(
+    config_path: &Arc<PathBuf>,
+    input_folder: &Arc<PathBuf>,
+    args: &Arc<crate::cli::Cli>,
+) -> Result<(), String> {
+    let mut site_data = Data::from_file(config_path.as_path());
+    site_data.site.override_from_cli_args(args);
+    let content_folder = get_content_folder(&site_data.site, input_folder.as_path());
+    let dictionary = load_dictionary(input_folder, &site_data.site.language)?;
+
+    let files = collect_markdown_files(&content_folder);
+
+    let mut total = 0;
+    for path in &files {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let findings = check_source(&text, &dictionary);
+        let display_path = path.strip_prefix(input_folder.as_path()).unwrap_or(path);
+        for finding in &findings {
+            println!(
+                "{}:{}: {}",
+                display_path.display(),
+                finding.line,
+                finding.word
+            );
+        }
+        total += findings.len();
+    }
+
+    info!("Checked {} files", files.len());
+    if total > 0 {
+        return Err(format!(
+            "Found {total} unknown words, add them to {}/dictionaries/words.txt if correct",
+            input_folder.display()
+        ));
+    }
+    println!("No spelling issues found in {} files", files.len());
+    Ok(())
+}
+
+/// Markdown sources of the content folder sorted by path
+pub fn collect_markdown_files(content_folder: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(content_folder)
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(walkdir::DirEntry::into_path)
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+#[path = "tests/spellcheck.rs"]
+mod tests;
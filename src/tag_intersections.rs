@@ -0,0 +1,76 @@
+use crate::content::Content;
+use log::warn;
+use serde::Serialize;
+
+/// A combination of tags from `tag_intersections` e.g: `rust+async`, rendered
+/// on `tag-rust+async.html` with the posts tagged with all of them
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TagIntersection {
+    /// Tags as written on the config
+    pub tags: Vec<String>,
+    /// Slugs of the tags joined by `+` e.g: `rust+async`
+    pub slug: String,
+}
+
+impl TagIntersection {
+    /// Parse `rust+async`, `None` unless the combination has at least 2 tags
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in spec.split('+').map(str::trim).filter(|tag| !tag.is_empty()) {
+            if !tags.iter().any(|t| slug::slugify(t) == slug::slugify(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        if tags.len() < 2 {
+            return None;
+        }
+        let slug = tags.iter().map(slug::slugify).collect::<Vec<_>>().join("+");
+        Some(TagIntersection { tags, slug })
+    }
+
+    /// Page of the combination without extension e.g: `tag-rust+async`
+    pub fn filename(&self) -> String {
+        format!("tag-{}", self.slug)
+    }
+
+    /// Tags joined for the page title e.g: `rust + async`
+    pub fn title(&self) -> String {
+        self.tags.join(" + ")
+    }
+
+    /// Posts tagged with all the tags of the combination, drafts excluded
+    pub fn contents(&self, posts: &[Content]) -> Vec<Content> {
+        posts
+            .iter()
+            .filter(|post| post.stream.as_deref() != Some("draft"))
+            .filter(|post| {
+                self.tags.iter().all(|tag| {
+                    post.tags
+                        .iter()
+                        .any(|post_tag| slug::slugify(post_tag) == slug::slugify(tag))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Valid combinations of `tag_intersections`, the ones with less than 2 tags are ignored
+pub fn parse_all(specs: &[String]) -> Vec<TagIntersection> {
+    specs
+        .iter()
+        .filter_map(|spec| {
+            let intersection = TagIntersection::parse(spec);
+            if intersection.is_none() {
+                warn!(
+                    "Ignoring tag intersection '{spec}', it needs at least 2 tags e.g: rust+async"
+                );
+            }
+            intersection
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/tag_intersections.rs"]
+mod tests;
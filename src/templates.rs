@@ -1,8 +1,9 @@
 #![allow(clippy::module_name_repetitions)]
-use crate::embedded::{generate_static, Templates, ThemeTemplate};
+use crate::embedded::{generate_static, Templates, ThemeMinimal, ThemeTemplate};
 use log::{error, info};
+use rust_embed::RustEmbed;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Creates the `templates/` folder and writes embedded templates to it.
 pub fn initialize_templates(input_folder: &Path) {
@@ -47,10 +48,40 @@ pub fn initialize_theme_assets(input_folder: &Path) {
 
 /// Creates a new theme with templates and static assets from the embedded theme template
 pub fn initialize_theme(input_folder: &Path, theme_name: &str) {
+    if write_embedded_theme::<ThemeTemplate>(input_folder, theme_name).is_some() {
+        info!("To use this theme, add 'theme: {theme_name}' to your marmite.yaml config file",);
+    }
+}
+
+/// Creates a minimal theme meant as a starting point for theme authors,
+/// every template uses the documented context and falls back to the
+/// embedded templates for anything the theme does not define.
+pub fn new_theme(input_folder: &Path, theme_name: &str) {
+    let Some(theme_path) = write_embedded_theme::<ThemeMinimal>(input_folder, theme_name) else {
+        return;
+    };
+
+    // Fill in the theme name on the metadata files
+    for file_name in ["theme.json", "README.md"] {
+        let file_path = theme_path.join(file_name);
+        if let Ok(text) = fs::read_to_string(&file_path) {
+            if let Err(e) = fs::write(&file_path, text.replace("THEME_NAME", theme_name)) {
+                error!("Failed to update {}: {e:?}", file_path.display());
+            }
+        }
+    }
+
+    info!("Edit the templates in {}/templates", theme_path.display());
+    info!("To use this theme, add 'theme: {theme_name}' to your marmite.yaml config file",);
+}
+
+/// Writes every file of the embedded theme `E` to `input_folder/theme_name`,
+/// returns the theme path or `None` when the theme could not be created.
+fn write_embedded_theme<E: RustEmbed>(input_folder: &Path, theme_name: &str) -> Option<PathBuf> {
     // Validate theme name
     if theme_name.is_empty() || theme_name.contains('/') || theme_name.contains('\\') {
         error!("Invalid theme name: '{theme_name}'");
-        return;
+        return None;
     }
 
     let theme_path = input_folder.join(theme_name);
@@ -58,7 +89,7 @@ pub fn initialize_theme(input_folder: &Path, theme_name: &str) {
     // Check if theme directory already exists
     if theme_path.exists() {
         error!("Theme directory already exists: {}", theme_path.display());
-        return;
+        return None;
     }
 
     // Create theme directory structure
@@ -67,17 +98,16 @@ pub fn initialize_theme(input_folder: &Path, theme_name: &str) {
 
     if let Err(e) = fs::create_dir_all(&templates_path) {
         error!("Failed to create theme templates directory: {e:?}");
-        return;
+        return None;
     }
 
     if let Err(e) = fs::create_dir_all(&static_path) {
         error!("Failed to create theme static directory: {e:?}");
-        return;
+        return None;
     }
 
-    // Copy theme template files from embedded ThemeTemplate
-    for name in ThemeTemplate::iter() {
-        if let Some(file) = ThemeTemplate::get(name.as_ref()) {
+    for name in E::iter() {
+        if let Some(file) = E::get(name.as_ref()) {
             let file_path = Path::new(name.as_ref());
             let dest_path = theme_path.join(file_path);
 
@@ -108,7 +138,7 @@ pub fn initialize_theme(input_folder: &Path, theme_name: &str) {
         theme_name,
         theme_path.display()
     );
-    info!("To use this theme, add 'theme: {theme_name}' to your marmite.yaml config file",);
+    Some(theme_path)
 }
 
 #[cfg(test)]
@@ -2,22 +2,44 @@ use std::str::FromStr;
 
 use tera::{to_value, Filter, Value};
 
+/// Format dates with `default_date_format`, `lang` (e.g: `default_date_format(lang=content.lang)`)
+/// localizes the month and weekday names and picks the format of `date_formats`
+/// when the language is not the site language
 pub struct DefaultDateFormat {
     pub date_format: String,
+    pub language: String,
+    pub date_formats: std::collections::HashMap<String, String>,
+}
+
+impl DefaultDateFormat {
+    fn format_for(&self, lang: &str) -> &str {
+        if lang == self.language {
+            return &self.date_format;
+        }
+        self.date_formats
+            .get(lang)
+            .or_else(|| self.date_formats.get(crate::locale::base_language(lang)))
+            .unwrap_or(&self.date_format)
+    }
 }
 
 impl Filter for DefaultDateFormat {
     fn filter(
         &self,
         value: &tera::Value,
-        _: &std::collections::HashMap<String, tera::Value>,
+        args: &std::collections::HashMap<String, tera::Value>,
     ) -> tera::Result<tera::Value> {
         let date_str = value
             .as_str()
             .ok_or(tera::Error::msg("Missing date string"))?;
         let date = chrono::NaiveDateTime::from_str(date_str)
             .map_err(|e| tera::Error::msg(e.to_string()))?;
-        let formatted_date = date.format(self.date_format.as_str()).to_string();
+        let lang = args
+            .get("lang")
+            .and_then(Value::as_str)
+            .filter(|lang| !lang.is_empty())
+            .unwrap_or(&self.language);
+        let formatted_date = crate::locale::format_date(&date, self.format_for(lang), lang);
 
         to_value(formatted_date).map_err(tera::Error::from)
     }
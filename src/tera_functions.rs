@@ -1,10 +1,16 @@
+use chrono::Utc;
 use indexmap::IndexMap;
-use serde::Serialize;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use tera::{to_value, Function, Result as TeraResult, Value};
 use url::Url;
 
 use crate::content::Content;
+use crate::sidecar::{load_sidecar, save_sidecar};
 use crate::site::Data;
 
 #[derive(Serialize)]
@@ -41,11 +47,17 @@ impl Function for UrlFor {
             path = format!("/{path}");
         }
 
+        // `origin` replaces the site url, e.g: the same page on a mirror
+        let base_url = args
+            .get("origin")
+            .and_then(Value::as_str)
+            .unwrap_or(&self.base_url);
+
         // Trim trailing slashes from base_url if it's not empty
-        let base_url = if self.base_url.is_empty() {
+        let base_url = if base_url.is_empty() {
             String::new()
         } else {
-            self.base_url.trim_end_matches('/').to_string()
+            base_url.trim_end_matches('/').to_string()
         };
 
         // Parse the base_url to extract the path part if not empty
@@ -111,7 +123,11 @@ impl Function for Group {
             "author" => &self.site_data.author,
             "stream" => &self.site_data.stream,
             "series" => &self.site_data.series,
-            _ => return Err(tera::Error::msg("Invalid `kind` argument")),
+            taxonomy => self
+                .site_data
+                .taxonomies
+                .get(taxonomy)
+                .ok_or_else(|| tera::Error::msg("Invalid `kind` argument"))?,
         };
 
         // Convert to vector for sorting
@@ -560,6 +576,235 @@ impl Function for GetGallery {
     }
 }
 
+/// Tera function to load a CSV or TSV file as `{headers: [], rows: [[]]}`
+/// the file is searched on `data/`, then on the content folder and then on the input folder,
+/// absolute paths and `..` are refused so only files of the site can be loaded.
+/// Optional `sort` (column name) and `order` (asc|desc) sort the rows at build time.
+pub struct LoadCsv {
+    pub input_folder: PathBuf,
+    pub content_folder: PathBuf,
+}
+
+impl Function for LoadCsv {
+    fn call(&self, args: &HashMap<String, Value>) -> TeraResult<Value> {
+        let file = args
+            .get("file")
+            .and_then(Value::as_str)
+            .ok_or_else(|| tera::Error::msg("Missing `file` argument"))?;
+        if !Path::new(file)
+            .components()
+            .all(|part| matches!(part, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(tera::Error::msg(format!(
+                "Data file `{file}` must be relative to the site folder, without `..`"
+            )));
+        }
+
+        let path = [
+            self.input_folder.join("data").join(file),
+            self.content_folder.join(file),
+            self.input_folder.join(file),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| tera::Error::msg(format!("Data file `{file}` not found")))?;
+
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| tera::Error::msg(format!("Failed to read `{file}`: {e}")))?;
+
+        let delimiter = match args.get("delimiter").and_then(Value::as_str) {
+            Some(delimiter) if !delimiter.is_empty() => {
+                if delimiter == "\\t" || delimiter == "tab" {
+                    '\t'
+                } else {
+                    delimiter.chars().next().unwrap_or(',')
+                }
+            }
+            _ if path.extension().is_some_and(|ext| ext == "tsv") => '\t',
+            _ => ',',
+        };
+
+        let mut rows = parse_delimited(&text, delimiter);
+        if rows.is_empty() {
+            return to_value(CsvTable::default()).map_err(tera::Error::from);
+        }
+        let headers = rows.remove(0);
+
+        if let Some(sort) = args.get("sort").and_then(Value::as_str) {
+            if let Some(column) = headers.iter().position(|header| header == sort) {
+                rows.sort_by(|a, b| {
+                    let a = a.get(column).map_or("", String::as_str);
+                    let b = b.get(column).map_or("", String::as_str);
+                    match (a.parse::<f64>(), b.parse::<f64>()) {
+                        (Ok(a), Ok(b)) => a.total_cmp(&b),
+                        _ => a.cmp(b),
+                    }
+                });
+                if args.get("order").and_then(Value::as_str) == Some("desc") {
+                    rows.reverse();
+                }
+            }
+        }
+
+        to_value(CsvTable { headers, rows }).map_err(tera::Error::from)
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Parse delimited text supporting quoted fields with `""` escapes
+/// and delimiters or line breaks inside quotes
+fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            if ch == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(ch);
+            }
+        } else if ch == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if ch == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if ch == '\n' || ch == '\r' {
+            if ch == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            row.push(std::mem::take(&mut field));
+            if row.iter().any(|f| !f.is_empty()) {
+                rows.push(std::mem::take(&mut row));
+            } else {
+                row.clear();
+            }
+        } else {
+            field.push(ch);
+        }
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Sidecar folder keeping the responses of `fetch_json`, one file per url
+pub const FETCH_JSON_CACHE: &str = "fetch";
+
+/// Largest response `fetch_json` reads
+const FETCH_JSON_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A response of `fetch_json` kept in the cache folder
+#[derive(Serialize, Deserialize, Default)]
+struct FetchedJson {
+    url: String,
+    fetched_at: i64,
+    data: Value,
+}
+
+/// Tera template function fetching the JSON of `url` at build time, e.g: the latest
+/// release of a project `fetch_json(url="https://api.github.com/repos/o/n/releases/latest")`.
+/// Responses are cached in `cache_folder` for `ttl` seconds, a failed fetch or the
+/// `offline` config use the cached response whatever its age and `default` is
+/// returned when there is none. Each url is fetched once per build
+pub struct FetchJson {
+    pub cache_folder: PathBuf,
+    pub offline: bool,
+    pub ttl: i64,
+    pub fetched: Mutex<HashMap<String, FetchedOnce>>,
+}
+
+/// The response of an url loaded by the first render asking for it, the
+/// renders asking for other urls don't wait for it
+pub type FetchedOnce = Arc<OnceLock<Option<Value>>>;
+
+impl FetchJson {
+    fn cache_path(&self, url: &str) -> PathBuf {
+        let hash: String = Sha256::digest(url.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.cache_folder.join(format!("{hash}.json"))
+    }
+
+    fn load(&self, url: &str, ttl: i64) -> Option<Value> {
+        let path = self.cache_path(url);
+        let cached: Option<FetchedJson> = path
+            .exists()
+            .then(|| load_sidecar::<FetchedJson>(&path))
+            .filter(|cached| cached.url == url);
+        let now = Utc::now().timestamp();
+        if self.offline
+            || cached
+                .as_ref()
+                .is_some_and(|cached| cached.fetched_at.saturating_add(ttl) > now)
+        {
+            return cached.map(|cached| cached.data);
+        }
+        match fetch_url_json(url) {
+            Ok(data) => {
+                info!("Fetched {url}");
+                let fetched = FetchedJson {
+                    url: url.to_string(),
+                    fetched_at: now,
+                    data,
+                };
+                save_sidecar(&path, &fetched);
+                Some(fetched.data)
+            }
+            Err(e) => {
+                warn!("Failed to fetch {url}: {e}");
+                cached.map(|cached| cached.data)
+            }
+        }
+    }
+}
+
+impl Function for FetchJson {
+    fn call(&self, args: &HashMap<String, Value>) -> TeraResult<Value> {
+        let url = args
+            .get("url")
+            .and_then(Value::as_str)
+            .ok_or_else(|| tera::Error::msg("Missing `url` argument"))?;
+        let ttl = args.get("ttl").and_then(Value::as_i64).unwrap_or(self.ttl);
+
+        let once = self
+            .fetched
+            .lock()
+            .map_err(|_| tera::Error::msg("fetch_json cache is poisoned"))?
+            .entry(url.to_string())
+            .or_default()
+            .clone();
+        once.get_or_init(|| self.load(url, ttl))
+            .clone()
+            .or_else(|| args.get("default").cloned())
+            .ok_or_else(|| {
+                tera::Error::msg(format!(
+                    "Failed to fetch `{url}`, there is no cached response or `default`"
+                ))
+            })
+    }
+}
+
+fn fetch_url_json(url: &str) -> Result<Value, String> {
+    let json = crate::http::get_text(url, "application/json", FETCH_JSON_MAX_BYTES)?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 #[path = "tests/tera_functions.rs"]
 mod tests;
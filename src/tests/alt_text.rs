@@ -0,0 +1,46 @@
+use super::*;
+
+const BODY: &str = r#"# Trip
+
+![](media/beach.jpg "The beach")
+![Sunset](media/sunset.jpg)
+<img src="media/boat.png" alt="">
+<img alt="Harbour" src="media/harbour.png">
+
+```markdown
+![](media/example.png)
+```
+"#;
+
+#[test]
+fn test_find_missing_alt() {
+    let missing = find_missing_alt(BODY);
+    assert_eq!(
+        missing,
+        vec![
+            MissingAlt {
+                line: 3,
+                src: "media/beach.jpg".to_string()
+            },
+            MissingAlt {
+                line: 5,
+                src: "media/boat.png".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_apply_alt_text() {
+    let suggestions = HashMap::from([
+        ("media/beach.jpg".to_string(), "A sandy [beach]".to_string()),
+        ("media/boat.png".to_string(), "A \"red\" boat".to_string()),
+        ("media/example.png".to_string(), "Never written".to_string()),
+    ]);
+    let fixed = apply_alt_text(BODY, &suggestions);
+    assert!(fixed.contains(r#"![A sandy \[beach\]](media/beach.jpg "The beach")"#));
+    assert!(fixed.contains(r#"<img src="media/boat.png" alt="A &quot;red&quot; boat">"#));
+    assert!(fixed.contains("<img alt=\"Harbour\" src=\"media/harbour.png\">"));
+    assert!(fixed.contains("![](media/example.png)"));
+    assert!(find_missing_alt(&fixed).is_empty());
+}
@@ -0,0 +1,48 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+use tempfile::TempDir;
+
+#[test]
+fn test_badge_svg() {
+    let svg = badge_svg("posts", "12", "#007ec6");
+    // 5 chars label, 2 chars value
+    assert!(svg.contains(r#"width="69" height="20""#));
+    assert!(svg.contains("<title>posts: 12</title>"));
+    assert!(svg.contains(r##"fill="#007ec6""##));
+
+    let svg = badge_svg("site", "<R&D>", "#007ec6");
+    assert!(svg.contains("&lt;R&amp;D&gt;"));
+}
+
+#[test]
+fn test_write_badges() {
+    let output = TempDir::new().unwrap();
+    let mut site_data = Data::new("badges:\n  enabled: true\n", Path::new("marmite.yaml"));
+    let date = |day| {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+    site_data
+        .posts
+        .push(ContentBuilder::new().date(date(1)).updated(date(9)).build());
+    site_data
+        .posts
+        .push(ContentBuilder::new().date(date(5)).build());
+    site_data.posts.push(
+        ContentBuilder::new()
+            .date(date(20))
+            .stream("draft".to_string())
+            .build(),
+    );
+
+    write_badges(&site_data, output.path());
+    let badges = output.path().join("badges");
+    let posts = fs::read_to_string(badges.join("posts.svg")).unwrap();
+    assert!(posts.contains("<title>posts: 2</title>"));
+    let updated = fs::read_to_string(badges.join("updated.svg")).unwrap();
+    assert!(updated.contains("<title>updated: 2025-03-09</title>"));
+    assert!(badges.join("rss.svg").is_file());
+}
@@ -0,0 +1,121 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+use frontmatter_gen::Value;
+
+const URL: &str = "https://example.com/posts/rust.html";
+
+fn bookmark_content(slug: &str, date: u32) -> Content {
+    ContentBuilder::new()
+        .title(URL.to_string())
+        .slug(slug.to_string())
+        .date(
+            NaiveDate::from_ymd_opt(2025, 1, date)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .bookmark(Bookmark {
+            url: URL.to_string(),
+            ..Default::default()
+        })
+        .build()
+}
+
+#[test]
+fn test_get_bookmark() {
+    let mut frontmatter = Frontmatter::new();
+    assert_eq!(get_bookmark(&frontmatter), None);
+
+    frontmatter.insert("bookmark".to_string(), Value::String(format!(" {URL} ")));
+    let bookmark = get_bookmark(&frontmatter).unwrap();
+    assert_eq!(bookmark.url, URL);
+    assert_eq!(bookmark.title, None);
+}
+
+#[test]
+fn test_parse_metadata() {
+    let html = r#"<html><head>
+        <title>
+            Rust  posts
+        </title>
+        <meta name="description" content="All about Rust">
+        <meta property='og:description' content='Open graph description'>
+        <link rel="shortcut icon" href="../icon.png">
+    </head></html>"#;
+    let bookmark = parse_metadata(html, URL);
+    assert_eq!(bookmark.url, URL);
+    assert_eq!(bookmark.title.as_deref(), Some("Rust posts"));
+    assert_eq!(bookmark.description.as_deref(), Some("All about Rust"));
+    assert_eq!(
+        bookmark.favicon.as_deref(),
+        Some("https://example.com/icon.png")
+    );
+}
+
+#[test]
+fn test_parse_metadata_prefers_open_graph_title_and_defaults_favicon() {
+    let html = r#"<title>Page</title><meta content="Shared title" property="og:title">"#;
+    let bookmark = parse_metadata(html, URL);
+    assert_eq!(bookmark.title.as_deref(), Some("Shared title"));
+    assert_eq!(bookmark.description, None);
+    assert_eq!(
+        bookmark.favicon.as_deref(),
+        Some("https://example.com/favicon.ico")
+    );
+}
+
+#[test]
+fn test_apply_metadata_from_cache() {
+    let mut cache = BTreeMap::new();
+    cache.insert(
+        URL.to_string(),
+        Bookmark {
+            url: URL.to_string(),
+            title: Some("Rust posts".to_string()),
+            description: Some("All about Rust".to_string()),
+            favicon: None,
+        },
+    );
+    let mut content = bookmark_content("rust", 1);
+    apply_metadata(&mut content, &mut cache, false);
+    assert_eq!(content.title, "Rust posts");
+    assert_eq!(content.description.as_deref(), Some("All about Rust"));
+    assert_eq!(
+        content.bookmark.unwrap().title.as_deref(),
+        Some("Rust posts")
+    );
+
+    // the frontmatter title wins over the fetched title
+    let mut content = bookmark_content("rust", 1);
+    content.title = "My title".to_string();
+    apply_metadata(&mut content, &mut cache, false);
+    assert_eq!(content.title, "My title");
+}
+
+#[test]
+fn test_apply_metadata_without_cache_keeps_url() {
+    let mut cache = BTreeMap::new();
+    let mut content = bookmark_content("rust", 1);
+    apply_metadata(&mut content, &mut cache, false);
+    assert_eq!(content.title, URL);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_collect_bookmarks() {
+    let mut draft = bookmark_content("draft", 3);
+    draft.stream = Some("draft".to_string());
+    let plain = ContentBuilder::new().slug("plain".to_string()).build();
+    let contents = [
+        bookmark_content("old", 1),
+        plain,
+        draft,
+        bookmark_content("new", 2),
+    ];
+    let slugs: Vec<String> = collect_bookmarks(contents.iter())
+        .into_iter()
+        .map(|content| content.slug)
+        .collect();
+    assert_eq!(slugs, vec!["new", "old"]);
+}
@@ -0,0 +1,124 @@
+use super::*;
+use crate::content::ContentBuilder;
+use tempfile::TempDir;
+
+fn write_bundle(dir: &TempDir) -> PathBuf {
+    let folder = dir.path().join("content/2024-03-01-trip-to-rome");
+    fs::create_dir_all(folder.join("images")).unwrap();
+    fs::write(
+        folder.join("index.md"),
+        "# Trip to Rome\n\n![Colosseum](images/colosseum.jpg)\n\n[Itinerary](./itinerary.pdf) and [home](index.html)\n",
+    )
+    .unwrap();
+    fs::write(folder.join("images/colosseum.jpg"), b"jpg").unwrap();
+    fs::write(folder.join("itinerary.pdf"), b"pdf").unwrap();
+    folder
+}
+
+#[test]
+fn test_bundle_folder() {
+    let content_folder = Path::new("site/content");
+    assert_eq!(
+        bundle_folder(Path::new("site/content/my-post/index.md"), content_folder),
+        Some(PathBuf::from("site/content/my-post"))
+    );
+    assert_eq!(
+        bundle_folder(
+            Path::new("site/content/my-post/index.md.age"),
+            content_folder
+        ),
+        Some(PathBuf::from("site/content/my-post"))
+    );
+    assert_eq!(
+        bundle_folder(Path::new("site/content/index.md"), content_folder),
+        None
+    );
+    assert_eq!(
+        bundle_folder(Path::new("site/content/my-post/notes.md"), content_folder),
+        None
+    );
+    assert_eq!(
+        bundle_name_path(
+            Path::new("content/2024-03-01-trip"),
+            Path::new("content/2024-03-01-trip/index.md")
+        ),
+        PathBuf::from("content/2024-03-01-trip.md")
+    );
+}
+
+#[test]
+fn test_rewrite_links() {
+    let dir = TempDir::new().unwrap();
+    let folder = write_bundle(&dir);
+    let html = r#"<img src="images/colosseum.jpg"><a href="./itinerary.pdf#page=2">a</a><a href="missing.pdf">b</a><a href="https://example.com/itinerary.pdf">c</a><a href="../itinerary.pdf">d</a>"#;
+    assert_eq!(
+        rewrite_links(html, &folder, "trip-to-rome"),
+        r#"<img src="trip-to-rome/images/colosseum.jpg"><a href="trip-to-rome/itinerary.pdf#page=2">a</a><a href="missing.pdf">b</a><a href="https://example.com/itinerary.pdf">c</a><a href="../itinerary.pdf">d</a>"#
+    );
+}
+
+#[test]
+fn test_from_markdown_bundle() {
+    let dir = TempDir::new().unwrap();
+    let folder = write_bundle(&dir);
+    let content = Content::from_markdown(
+        &folder.join("index.md"),
+        None,
+        &Marmite::new(),
+        None,
+        Some(dir.path().join("content").as_path()),
+    )
+    .unwrap();
+    assert_eq!(content.slug, "trip-to-rome");
+    assert!(content.date.is_some());
+    assert!(content
+        .html
+        .contains(r#"src="trip-to-rome/images/colosseum.jpg""#));
+    assert!(content
+        .html
+        .contains(r#"href="trip-to-rome/itinerary.pdf""#));
+    assert!(content.html.contains(r#"href="index.html""#));
+    assert_eq!(
+        content.card_image.as_deref(),
+        Some("trip-to-rome/images/colosseum.jpg")
+    );
+}
+
+#[test]
+fn test_copy_bundle_assets() {
+    let dir = TempDir::new().unwrap();
+    let folder = write_bundle(&dir);
+    let output = dir.path().join("site");
+    let content = ContentBuilder::new()
+        .slug("trip-to-rome".to_string())
+        .source_path(folder.join("index.md"))
+        .build();
+    copy_bundle_assets(
+        &content,
+        &output,
+        &dir.path().join("content"),
+        &Marmite::new(),
+    );
+    assert!(output.join("trip-to-rome/images/colosseum.jpg").is_file());
+    assert!(output.join("trip-to-rome/itinerary.pdf").is_file());
+    assert!(!output.join("trip-to-rome/index.md").exists());
+}
+
+#[test]
+fn test_flat_layout_root_index_is_not_a_bundle() {
+    // without a `content/` folder the input folder holds the content
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("my-blog");
+    fs::create_dir_all(input.join("templates")).unwrap();
+    fs::write(input.join("index.md"), "# Home\n").unwrap();
+    fs::write(input.join("marmite.yaml"), "name: My Blog\n").unwrap();
+    assert_eq!(bundle_folder(&input.join("index.md"), &input), None);
+
+    let output = input.join("site");
+    let content = ContentBuilder::new()
+        .slug("my-blog".to_string())
+        .source_path(input.join("index.md"))
+        .build();
+    copy_bundle_assets(&content, &output, &input, &Marmite::new());
+    assert!(!output.exists());
+}
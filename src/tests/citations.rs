@@ -0,0 +1,76 @@
+use super::*;
+
+const BIBTEX: &str = r#"
+@comment{ generated by a reference manager }
+@article{knuth1984,
+  author = {Donald E. Knuth},
+  title = {Literate {Programming}},
+  journal = "The Computer Journal",
+  year = 1984,
+  doi = {10.1093/comjnl/27.2.97},
+}
+
+@book{sicp,
+  author = {Harold Abelson and Gerald Jay Sussman},
+  title = {Structure and Interpretation of Computer Programs},
+  publisher = {MIT Press},
+  year = {1996},
+  url = {https://mitpress.mit.edu/sicp}
+}
+"#;
+
+#[test]
+fn test_parse_bibtex() {
+    let entries = parse_bibtex(BIBTEX);
+    assert_eq!(entries.len(), 2);
+
+    let knuth = &entries["knuth1984"];
+    assert_eq!(knuth.kind, "article");
+    assert_eq!(knuth.fields["author"], "Donald E. Knuth");
+    assert_eq!(knuth.fields["title"], "Literate Programming");
+    assert_eq!(knuth.fields["journal"], "The Computer Journal");
+    assert_eq!(knuth.fields["year"], "1984");
+
+    let sicp = &entries["sicp"];
+    assert_eq!(sicp.kind, "book");
+    assert_eq!(sicp.fields["url"], "https://mitpress.mit.edu/sicp");
+}
+
+#[test]
+fn test_format_entry() {
+    let entries = parse_bibtex(BIBTEX);
+    assert_eq!(
+        format_entry(&entries["knuth1984"]),
+        r#"Donald E. Knuth (1984). <em>Literate Programming</em>. The Computer Journal. <a href="https://doi.org/10.1093/comjnl/27.2.97">https://doi.org/10.1093/comjnl/27.2.97</a>"#
+    );
+    assert!(format_entry(&entries["sicp"])
+        .starts_with("Harold Abelson, Gerald Jay Sussman (1996). <em>Structure"));
+}
+
+#[test]
+fn test_apply_citations() {
+    let bibliography = parse_bibtex(BIBTEX);
+    let html = "<p>First [@sicp] then [@knuth1984; @sicp].</p>\n<pre><code>[@sicp]</code></pre>";
+    let result = apply_citations(html, &bibliography).unwrap();
+
+    assert!(
+        result.contains(r##"First <sup class="citation">[<a href="#ref-sicp">1</a>]</sup> then"##)
+    );
+    assert!(result.contains(
+        r##"<sup class="citation">[<a href="#ref-knuth1984">2</a>, <a href="#ref-sicp">1</a>]</sup>"##
+    ));
+    assert!(result.contains("<pre><code>[@sicp]</code></pre>"));
+    assert!(result.contains(r#"<section class="bibliography">"#));
+
+    let sicp_pos = result.find(r#"<li id="ref-sicp">"#).unwrap();
+    let knuth_pos = result.find(r#"<li id="ref-knuth1984">"#).unwrap();
+    assert!(sicp_pos < knuth_pos);
+}
+
+#[test]
+fn test_apply_citations_without_known_keys() {
+    let bibliography = parse_bibtex(BIBTEX);
+    assert_eq!(apply_citations("<p>No citations</p>", &bibliography), None);
+    assert_eq!(apply_citations("<p>[@missing]</p>", &bibliography), None);
+    assert_eq!(apply_citations("<p>[@sicp]</p>", &HashMap::new()), None);
+}
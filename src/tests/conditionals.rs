@@ -0,0 +1,50 @@
+use super::*;
+
+const MARKDOWN: &str = r#"Intro
+<!-- if profile == "preview" -->
+Preview note
+<!-- else -->
+Published note
+<!-- endif -->
+<!-- if profile != 'production' -->
+Not in production
+<!-- if profile == "draft" -->
+Only drafts
+<!-- endif -->
+<!-- endif -->
+Outro
+"#;
+
+#[test]
+fn test_apply_conditionals() {
+    assert_eq!(
+        apply_conditionals(MARKDOWN, "production"),
+        "Intro\nPublished note\nOutro\n"
+    );
+    assert_eq!(
+        apply_conditionals(MARKDOWN, "preview"),
+        "Intro\nPreview note\nNot in production\nOutro\n"
+    );
+    assert_eq!(
+        apply_conditionals(MARKDOWN, "draft"),
+        "Intro\nPublished note\nNot in production\nOnly drafts\nOutro\n"
+    );
+}
+
+#[test]
+fn test_apply_conditionals_skips_code_fences() {
+    let markdown =
+        "````markdown\n<!-- if profile == \"preview\" -->\n```\nkept\n```\n<!-- endif -->\n````\n";
+    assert_eq!(apply_conditionals(markdown, "production"), markdown);
+}
+
+#[test]
+fn test_apply_conditionals_unbalanced() {
+    assert_eq!(
+        apply_conditionals(
+            "a\n<!-- endif -->\nb\n<!-- if profile == \"x\" -->\nc\n",
+            "y"
+        ),
+        "a\nb\n"
+    );
+}
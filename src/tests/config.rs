@@ -0,0 +1,109 @@
+use super::*;
+
+#[test]
+fn test_contact_form_defaults() {
+    let config: Marmite =
+        serde_yaml::from_str("contact_form:\n  formspree: abc123\n").expect("valid config");
+    let contact_form = config.contact_form.expect("contact form should be set");
+    assert_eq!(contact_form.title, "Contact");
+    assert_eq!(contact_form.slug, "contact");
+    assert_eq!(contact_form.honeypot, "_gotcha");
+    let names: Vec<&str> = contact_form
+        .fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["name", "email", "message"]);
+    assert_eq!(contact_form.fields[2].type_, "textarea");
+}
+
+#[test]
+fn test_contact_form_action_from_formspree() {
+    let config: Marmite =
+        serde_yaml::from_str("contact_form:\n  formspree: abc123\n").expect("valid config");
+    assert_eq!(
+        config.contact_form.unwrap().action(),
+        Some("https://formspree.io/f/abc123".to_string())
+    );
+}
+
+#[test]
+fn test_contact_form_endpoint_takes_precedence() {
+    let config: Marmite = serde_yaml::from_str(
+        "contact_form:\n  formspree: abc123\n  endpoint: https://example.com/submit\n",
+    )
+    .expect("valid config");
+    assert_eq!(
+        config.contact_form.unwrap().action(),
+        Some("https://example.com/submit".to_string())
+    );
+}
+
+#[test]
+fn test_contact_form_without_backend_has_no_action() {
+    let config: Marmite =
+        serde_yaml::from_str("contact_form:\n  title: Talk to me\n").expect("valid config");
+    assert_eq!(config.contact_form.unwrap().action(), None);
+}
+
+#[test]
+fn test_verification_meta_tags() {
+    let config: Marmite = serde_yaml::from_str(
+        "verification:\n  google: g123\n  yandex: ''\n  meta:\n    p:domain_verify: abc\n",
+    )
+    .expect("valid config");
+    assert_eq!(
+        config.verification.meta_tags(),
+        vec![
+            ("google-site-verification".to_string(), "g123".to_string()),
+            ("p:domain_verify".to_string(), "abc".to_string()),
+        ]
+    );
+    assert!(Marmite::new().verification.meta_tags().is_empty());
+}
+
+#[test]
+fn test_input_converters_are_opt_in() {
+    let config: Marmite = serde_yaml::from_str("name: Site\n").expect("valid config");
+    assert!(config.input_converters.is_empty());
+    assert!(Marmite::default().input_converters.is_empty());
+
+    let config: Marmite = serde_yaml::from_str("input_converters:\n  rst: pandoc -f rst -t gfm\n")
+        .expect("valid config");
+    assert_eq!(
+        config.input_converters.get("rst").map(String::as_str),
+        Some("pandoc -f rst -t gfm")
+    );
+}
+
+#[test]
+fn test_thread_count_with_low_memory() {
+    assert_eq!(thread_count(0, false), 0);
+    assert_eq!(thread_count(0, true), LOW_MEMORY_JOBS);
+    assert_eq!(thread_count(4, true), 4);
+    assert_eq!(thread_count(4, false), 4);
+}
+
+#[test]
+fn test_tokens_are_opt_in() {
+    let config: Marmite = serde_yaml::from_str("name: Site\n").expect("valid config");
+    assert!(!config.enable_tokens);
+    assert!(!Marmite::default().enable_tokens);
+}
+
+#[test]
+fn test_pool_threads() {
+    assert_eq!(pool_threads("", None, None), 0);
+    assert_eq!(pool_threads("jobs: 3\n", None, None), 3);
+    assert_eq!(pool_threads("jobs: 3\n", Some(1), None), 1);
+    assert_eq!(
+        pool_threads("low_memory: true\n", None, None),
+        LOW_MEMORY_JOBS
+    );
+    assert_eq!(pool_threads("low_memory: true\n", Some(4), None), 4);
+    assert_eq!(
+        pool_threads("name: Site\n", None, Some(true)),
+        LOW_MEMORY_JOBS
+    );
+    assert_eq!(pool_threads("[not, a, mapping]", None, None), 0);
+}
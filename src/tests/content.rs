@@ -118,6 +118,31 @@ fn test_get_description_from_empty_frontmatter() {
     assert_eq!(description, None);
 }
 
+#[test]
+fn test_apply_frontmatter_aliases() {
+    let mut frontmatter = Frontmatter::new();
+    frontmatter.insert(
+        "categories".to_string(),
+        Value::String("rust, python".to_string()),
+    );
+    frontmatter.insert("summary".to_string(), Value::String("Imported".to_string()));
+    frontmatter.insert(
+        "description".to_string(),
+        Value::String("Original".to_string()),
+    );
+
+    let mut aliases = HashMap::new();
+    aliases.insert("categories".to_string(), "tags".to_string());
+    aliases.insert("summary".to_string(), "description".to_string());
+    apply_frontmatter_aliases(&mut frontmatter, &aliases);
+
+    assert_eq!(get_tags(&frontmatter), vec!["rust", "python"]);
+    assert_eq!(
+        get_description(&frontmatter),
+        Some("\"Original\"".to_string())
+    );
+}
+
 #[test]
 fn test_get_slug_from_frontmatter() {
     let mut frontmatter = Frontmatter::new();
@@ -138,6 +163,108 @@ fn test_get_slug_from_title() {
     assert_eq!(slug, "test-title");
 }
 
+#[test]
+fn test_get_slug_with_config_from_title() {
+    let mut frontmatter = Frontmatter::new();
+    frontmatter.insert(
+        "title".to_string(),
+        Value::String("The Art of Writing a Static Site Generator".to_string()),
+    );
+    let path = Path::new("2024-01-01-myfile.md");
+    let config = SlugConfig {
+        max_length: Some(20),
+        remove_stop_words: true,
+        ..Default::default()
+    };
+
+    let slug = get_slug_with_config(&frontmatter, path, &config);
+    assert_eq!(slug, "art-writing-static");
+
+    // explicit slug is kept as is
+    frontmatter.insert(
+        "slug".to_string(),
+        Value::String("the-explicit-slug-is-kept".to_string()),
+    );
+    let slug = get_slug_with_config(&frontmatter, path, &config);
+    assert_eq!(slug, "the-explicit-slug-is-kept");
+}
+
+#[test]
+fn test_slugify_with_config() {
+    let config = SlugConfig {
+        max_length: Some(5),
+        remove_stop_words: true,
+        stop_words: vec!["foo".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(slugify_with_config("Foo", &config), "foo");
+    assert_eq!(
+        slugify_with_config("foo supercalifragilistic", &config),
+        "super"
+    );
+    assert_eq!(
+        slugify_with_config("The Title", &SlugConfig::default()),
+        "the-title"
+    );
+}
+
+#[test]
+fn test_normalize_url_path() {
+    let config = SlugConfig {
+        lowercase: true,
+        collapse_dashes: true,
+        normalize_unicode: true,
+        ..Default::default()
+    };
+    assert_eq!(normalize_url_path("News-My--Post", &config), "news-my-post");
+    // decomposed "e" + combining acute becomes the composed character
+    assert_eq!(
+        normalize_url_path("Cafe\u{301}/Photo---1.PNG", &config),
+        "caf\u{e9}/photo-1.png"
+    );
+    assert_eq!(
+        normalize_url_path("Keep--As-Is", &SlugConfig::default()),
+        "Keep--As-Is"
+    );
+
+    let frontmatter = Frontmatter::new();
+    let path = Path::new("2024-01-01-My--File.md");
+    assert_eq!(get_slug_with_config(&frontmatter, path, &config), "my-file");
+}
+
+#[test]
+fn test_normalize_media_urls() {
+    let config = SlugConfig {
+        lowercase: true,
+        ..Default::default()
+    };
+    let html = r#"<img src="media/Photo.JPG"><a href="./media/Docs/Manual.PDF">x</a><a href="About.html">y</a>"#;
+    assert_eq!(
+        normalize_media_urls(html, "media", &config),
+        r#"<img src="media/photo.jpg"><a href="./media/docs/manual.pdf">x</a><a href="About.html">y</a>"#
+    );
+    assert_eq!(
+        normalize_media_urls(html, "media", &SlugConfig::default()),
+        html
+    );
+}
+
+#[test]
+fn test_from_markdown_with_reserved_slug() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("tags.md");
+    fs::write(&path, "# Tags\n\nMy tags page").unwrap();
+
+    let mut site = Marmite::new();
+    site.slug.reserved = vec!["tags".to_string()];
+
+    let error = Content::from_markdown(&path, None, &site, None, None).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Content);
+    assert!(error.message.contains("reserved"));
+}
+
 #[test]
 fn test_get_slug_from_filename() {
     let frontmatter = Frontmatter::new();
@@ -238,7 +365,7 @@ fn test_get_date_from_frontmatter() {
     );
     let path = Path::new("myfile.md");
 
-    let date = get_date(&frontmatter, path).unwrap();
+    let date = get_date(&frontmatter, path).unwrap().unwrap();
     assert_eq!(
         date,
         NaiveDate::from_ymd_opt(2024, 1, 1)
@@ -254,7 +381,7 @@ fn test_get_date_from_frontmatter_without_time() {
     frontmatter.insert("date".to_string(), Value::String("2024-01-01".to_string()));
     let path = Path::new("myfile.md");
 
-    let date = get_date(&frontmatter, path).unwrap();
+    let date = get_date(&frontmatter, path).unwrap().unwrap();
     assert_eq!(
         date,
         NaiveDate::from_ymd_opt(2024, 1, 1)
@@ -264,12 +391,149 @@ fn test_get_date_from_frontmatter_without_time() {
     );
 }
 
+#[test]
+fn test_get_updated_from_frontmatter() {
+    let path = Path::new("2024-01-01-myfile.md");
+    assert_eq!(get_updated(&Frontmatter::new(), path), None);
+
+    let mut frontmatter = Frontmatter::new();
+    frontmatter.insert(
+        "last_modified".to_string(),
+        Value::String("2024-02-01 10:30".to_string()),
+    );
+    assert_eq!(
+        get_updated(&frontmatter, path),
+        NaiveDate::from_ymd_opt(2024, 2, 1)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+    );
+
+    // `updated` wins over the `last_modified` alias
+    frontmatter.insert(
+        "updated".to_string(),
+        Value::String("2024-03-01".to_string()),
+    );
+    assert_eq!(
+        get_updated(&frontmatter, path),
+        NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+    );
+}
+
+#[test]
+fn test_get_weight_from_frontmatter() {
+    assert_eq!(get_weight(&Frontmatter::new()), None);
+
+    let mut frontmatter = Frontmatter::new();
+    frontmatter.insert("order".to_string(), Value::String("-1".to_string()));
+    assert_eq!(get_weight(&frontmatter), Some(-1));
+
+    // `weight` wins over the `order` alias
+    frontmatter.insert("weight".to_string(), Value::Number(10.0));
+    assert_eq!(get_weight(&frontmatter), Some(10));
+
+    frontmatter.insert("weight".to_string(), Value::String("first".to_string()));
+    assert_eq!(get_weight(&frontmatter), None);
+}
+
+#[test]
+fn test_get_extra_from_frontmatter() {
+    let (frontmatter, _) = parse_front_matter(
+        "---\ntitle: Title\ntags: rust\ngithub_repo: rochacbruno/marmite\nvideo_url: https://youtu.be/x\nplatform: linux\nsummary: Short\nextra:\n  math: true\n  video_url: https://vimeo.com/x\n---\n# Title\n",
+    )
+    .unwrap();
+    let mut site = Marmite::new();
+    site.taxonomies.insert(
+        "platform".to_string(),
+        crate::config::TaxonomyConfig::default(),
+    );
+    site.frontmatter_aliases
+        .insert("summary".to_string(), "description".to_string());
+
+    let extra = get_extra(&frontmatter, &site);
+    let mut keys: Vec<&String> = extra.keys().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["github_repo", "math", "video_url"]);
+    assert_eq!(
+        extra.get("github_repo").and_then(Value::as_str),
+        Some("rochacbruno/marmite")
+    );
+    // keys of the `extra` object win over the top level keys
+    assert_eq!(
+        extra.get("video_url").and_then(Value::as_str),
+        Some("https://vimeo.com/x")
+    );
+    assert_eq!(extra.get("math").and_then(Value::as_bool), Some(true));
+}
+
+#[test]
+fn test_split_summary_and_first_paragraphs() {
+    assert_eq!(
+        split_summary("Intro **text**\n\n<!-- more -->\n\nThe rest"),
+        Some("Intro **text**")
+    );
+    assert_eq!(split_summary("No marker <!-- more --> inline"), None);
+    assert_eq!(split_summary("Intro\n  <!--more-->\nRest"), Some("Intro"));
+
+    let html = "<h2>Title</h2>\n<p>One</p>\n<p class=\"x\">Two</p>\n<pre>code</pre>\n<p>Three</p>";
+    assert_eq!(first_paragraphs(html, 0), None);
+    assert_eq!(
+        first_paragraphs(html, 2),
+        Some("<p>One</p>\n<p class=\"x\">Two</p>".to_string())
+    );
+    assert_eq!(first_paragraphs("<pre>code</pre>", 2), None);
+}
+
+#[test]
+fn test_get_content_with_more_marker() {
+    let path = Path::new("test_get_content_with_more_marker.md");
+    fs::write(
+        path,
+        "# Title\n\nThe summary.\n\n<!-- more -->\n\nThe rest of the post.\n",
+    )
+    .unwrap();
+    let mut site = Marmite::default();
+    let content = Content::from_markdown(path, None, &site, None, None).unwrap();
+    assert_eq!(
+        content.summary_html.as_deref(),
+        Some("<p>The summary.</p>\n")
+    );
+    assert!(content.html.contains("The rest of the post."));
+
+    fs::write(path, "# Title\n\nFirst.\n\nSecond.\n\nThird.\n").unwrap();
+    let content = Content::from_markdown(path, None, &site, None, None).unwrap();
+    assert!(content.summary_html.is_none());
+    site.summary_paragraphs = 2;
+    let content = Content::from_markdown(path, None, &site, None, None).unwrap();
+    assert_eq!(
+        content.summary_html.as_deref(),
+        Some("<p>First.</p>\n<p>Second.</p>")
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_count_words_and_reading_time() {
+    let html = "<h1>Hello world</h1><p>Rust &amp; Marmite</p><pre><code>let x = 1;</code></pre>";
+    assert_eq!(count_words(html, true), (4, 0));
+    assert_eq!(count_words(html, false), (7, 0));
+    // CJK is counted by character, punctuation is not a word
+    assert_eq!(count_words("<p>静的サイト generator 。</p>", true), (1, 5));
+
+    let config = crate::config::ReadingTimeConfig::default();
+    assert_eq!(reading_time(0, 0, &config), 1);
+    assert_eq!(reading_time(200, 0, &config), 1);
+    assert_eq!(reading_time(201, 0, &config), 2);
+    assert_eq!(reading_time(300, 500, &config), 3);
+}
+
 #[test]
 fn test_get_date_from_filename() {
     let frontmatter = Frontmatter::new();
     let path = Path::new("2024-01-01-myfile.md");
 
-    let date = get_date(&frontmatter, path).unwrap();
+    let date = get_date(&frontmatter, path).unwrap().unwrap();
     assert_eq!(
         date,
         NaiveDate::from_ymd_opt(2024, 1, 1)
@@ -279,12 +543,34 @@ fn test_get_date_from_filename() {
     );
 }
 
+#[test]
+fn test_get_date_invalid() {
+    let mut frontmatter = Frontmatter::new();
+    frontmatter.insert("date".to_string(), Value::String("not-a-date".to_string()));
+    let path = Path::new("2024-01-01-myfile.md");
+
+    let error = get_date(&frontmatter, path).unwrap_err();
+    assert!(error.contains("not-a-date"));
+}
+
+#[test]
+fn test_from_markdown_invalid_date_in_markdown_extension() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("post.markdown");
+    fs::write(&path, "---\ndate: not-a-date\n---\n# Post\n\nHello").unwrap();
+
+    let error = Content::from_markdown(&path, None, &Marmite::new(), None, None).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Content);
+}
+
 #[test]
 fn test_get_date_no_date() {
     let frontmatter = Frontmatter::new();
     let path = Path::new("myfile.md");
 
-    let date = get_date(&frontmatter, path);
+    let date = get_date(&frontmatter, path).unwrap();
     assert!(date.is_none());
 }
 
@@ -395,6 +681,68 @@ fn test_check_for_duplicate_slugs_empty_list() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_resolve_duplicate_slugs() {
+    let content = |slug: &str, date: Option<&str>, path: &str| {
+        let mut builder = ContentBuilder::new()
+            .slug(slug.to_string())
+            .source_path(std::path::PathBuf::from(path));
+        if let Some(date) = date {
+            builder = builder.date(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            );
+        }
+        builder.build()
+    };
+    let contents = || {
+        vec![
+            content("hello", Some("2024-03-01"), "b.md"),
+            content("hello", Some("2024-01-01"), "a.md"),
+            content("hello-1", None, "hello-1.md"),
+            content("hello", None, "c.md"),
+            content("other", None, "other.md"),
+        ]
+    };
+    let slugs = |contents: &[Content]| -> Vec<String> {
+        contents
+            .iter()
+            .map(|content| content.slug.clone())
+            .collect()
+    };
+
+    let mut errors = contents();
+    resolve_duplicate_slugs(&mut errors, DuplicateSlugs::Error);
+    assert_eq!(
+        slugs(&errors),
+        vec!["hello", "hello", "hello-1", "hello", "other"]
+    );
+
+    // pages (no date) sort first, `hello-1` is taken so the suffix skips it
+    let mut suffixed = contents();
+    resolve_duplicate_slugs(&mut suffixed, DuplicateSlugs::Suffix);
+    assert_eq!(
+        slugs(&suffixed),
+        vec!["hello-3", "hello-2", "hello-1", "hello", "other"]
+    );
+
+    let mut dated = contents();
+    resolve_duplicate_slugs(&mut dated, DuplicateSlugs::Date);
+    assert_eq!(
+        slugs(&dated),
+        vec![
+            "2024-03-01-hello",
+            "2024-01-01-hello",
+            "hello-1",
+            "hello",
+            "other"
+        ]
+    );
+    assert!(check_for_duplicate_slugs(&dated.iter().collect()).is_ok());
+}
+
 #[test]
 fn test_extract_date_from_filename_valid_date() {
     let path = Path::new("2024-01-01-myfile.md");
@@ -584,7 +932,7 @@ date: "2023-01-01"
 This is a test content.
 "#;
     fs::write(path, content).unwrap();
-    let result = Content::from_markdown(path, None, &Marmite::default(), None).unwrap();
+    let result = Content::from_markdown(path, None, &Marmite::default(), None, None).unwrap();
     assert_eq!(result.title, "Test Title");
     assert_eq!(result.description, Some("\"Test Description\"".to_string()));
     assert_eq!(result.slug, "test-title");
@@ -610,7 +958,7 @@ extra: "extra content"
 This is a test content.
 "#;
     fs::write(path, content).unwrap();
-    let result = Content::from_markdown(path, None, &Marmite::default(), None);
+    let result = Content::from_markdown(path, None, &Marmite::default(), None, None);
     assert!(result.is_err());
     fs::remove_file(path).unwrap();
 }
@@ -623,13 +971,13 @@ fn test_get_content_without_frontmatter() {
 This is a test content.
 ";
     fs::write(path, content).unwrap();
-    let result = Content::from_markdown(path, None, &Marmite::default(), None).unwrap();
+    let result = Content::from_markdown(path, None, &Marmite::default(), None, None).unwrap();
     assert_eq!(result.title, "Test Content".to_string());
     assert_eq!(result.description, None);
     assert_eq!(result.slug, "test_get_content_without_frontmatter");
     assert!(result.tags.is_empty());
     assert!(result.date.is_none());
-    assert!(result.extra.is_none());
+    assert!(result.extra.is_empty());
     assert_eq!(result.html, "<p>This is a test content.</p>\n");
     fs::remove_file(path).unwrap();
 }
@@ -639,7 +987,223 @@ fn test_get_content_with_empty_file() {
     let path = Path::new("test_get_content_with_empty_file.md");
     let content = "";
     fs::write(path, content).unwrap();
-    let result = Content::from_markdown(path, None, &Marmite::default(), None).unwrap();
+    let result = Content::from_markdown(path, None, &Marmite::default(), None, None).unwrap();
     assert_eq!(result.slug, "test_get_content_with_empty_file".to_string());
     fs::remove_file(path).unwrap();
 }
+
+#[test]
+fn test_apply_og_defaults_from_stream_and_tags() {
+    let site: Marmite = serde_yaml::from_str(
+        "streams:\n  news:\n    display_name: News\n    card_image: media/news.png\n\
+         tags:\n  rust:\n    description: All about rust\n    banner_image: media/rust.png\n",
+    )
+    .unwrap();
+    let mut content = ContentBuilder::new()
+        .stream("news".to_string())
+        .tags(vec!["Rust".to_string()])
+        .build();
+
+    apply_og_defaults(&mut content, &site);
+
+    assert_eq!(content.card_image, Some("media/news.png".to_string()));
+    assert_eq!(content.banner_image, Some("media/rust.png".to_string()));
+    assert_eq!(content.description, Some("All about rust".to_string()));
+}
+
+#[test]
+fn test_apply_og_defaults_keeps_content_values() {
+    let site: Marmite = serde_yaml::from_str(
+        "tags:\n  rust:\n    description: All about rust\n    card_image: media/rust.png\n",
+    )
+    .unwrap();
+    let mut content = ContentBuilder::new()
+        .description("Own description".to_string())
+        .card_image("media/own.png".to_string())
+        .tags(vec!["rust".to_string()])
+        .build();
+
+    apply_og_defaults(&mut content, &site);
+
+    assert_eq!(content.card_image, Some("media/own.png".to_string()));
+    assert_eq!(content.description, Some("Own description".to_string()));
+}
+
+#[test]
+fn test_author_activity_from_contents() {
+    let date = |d: &str| {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    };
+    let contents = vec![
+        ContentBuilder::new()
+            .slug("first".to_string())
+            .date(date("2024-01-01"))
+            .authors(vec!["alice".to_string()])
+            .tags(vec!["rust".to_string()])
+            .build(),
+        ContentBuilder::new()
+            .slug("second".to_string())
+            .date(date("2024-02-01"))
+            .authors(vec!["bob".to_string(), "alice".to_string()])
+            .tags(vec!["rust".to_string(), "python".to_string()])
+            .build(),
+        ContentBuilder::new()
+            .slug("draft".to_string())
+            .date(date("2024-03-01"))
+            .authors(vec!["alice".to_string()])
+            .tags(vec!["draft-tag".to_string()])
+            .stream("draft".to_string())
+            .build(),
+        ContentBuilder::new()
+            .slug("other".to_string())
+            .authors(vec!["bob".to_string()])
+            .tags(vec!["go".to_string()])
+            .build(),
+    ];
+
+    let activity = AuthorActivity::from_contents("alice", &contents);
+    assert_eq!(activity.authored.len(), 1);
+    assert_eq!(activity.authored[0].slug, "first");
+    assert_eq!(activity.co_authored.len(), 1);
+    assert_eq!(activity.co_authored[0].slug, "second");
+    assert_eq!(
+        activity.tags,
+        vec![("rust".to_string(), 2), ("python".to_string(), 1)]
+    );
+}
+
+#[test]
+fn test_get_authors_with_structured_authors() {
+    let (frontmatter, _) = parse_front_matter(
+        "---\nauthors:\n  - name: alice\n    role: editor\n  - bob\n  - name: carol\n---\n# Title\n",
+    )
+    .unwrap();
+
+    let authors = get_authors(&frontmatter, Some("default".to_string()));
+    assert_eq!(authors, vec!["alice", "bob", "carol"]);
+
+    let roles = get_author_roles(&frontmatter);
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles.get("alice"), Some(&"editor".to_string()));
+}
+
+#[test]
+fn test_get_authors_skips_blank_and_repeated_names() {
+    let (frontmatter, _) =
+        parse_front_matter("---\nauthors: alice, , bob, alice\n---\n# Title\n").unwrap();
+    let authors = get_authors(&frontmatter, Some("default".to_string()));
+    assert_eq!(authors, vec!["alice", "bob"]);
+}
+
+#[test]
+fn test_byline_from_profiles() {
+    let profile = |name: &str| Author {
+        username: name.to_lowercase(),
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(Byline::from_profiles(&[]), None);
+    assert_eq!(Byline::from_profiles(&[profile("Alice")]), None);
+
+    let byline = Byline::from_profiles(&[profile("Alice"), profile("Bob")]).unwrap();
+    assert_eq!(byline.author.username, "alice");
+    assert_eq!(byline.written_with, vec![profile("Bob")]);
+    assert_eq!(byline.names, "Alice and Bob");
+
+    let byline =
+        Byline::from_profiles(&[profile("Alice"), profile("Bob"), profile("Carol")]).unwrap();
+    assert_eq!(byline.written_with.len(), 2);
+    assert_eq!(byline.names, "Alice, Bob and Carol");
+}
+
+#[test]
+fn test_share_links() {
+    let mut site = Marmite::new();
+    assert_eq!(ShareLinks::new("Hello World", "hello-world", &site), None);
+
+    site.url = "https://example.com/blog/".to_string();
+    let links = ShareLinks::new("Hello World", "hello-world", &site).unwrap();
+    assert_eq!(links.url, "https://example.com/blog/hello-world.html");
+    assert_eq!(
+        links.bluesky,
+        "https://bsky.app/intent/compose?text=Hello%20World%20https%3A%2F%2Fexample.com%2Fblog%2Fhello-world.html"
+    );
+    assert!(links
+        .mastodon
+        .starts_with("https://toot.kytta.dev/?text=Hello%20World"));
+    assert_eq!(
+        links.email,
+        "mailto:?subject=Hello%20World&body=https%3A%2F%2Fexample.com%2Fblog%2Fhello-world.html"
+    );
+}
+
+#[test]
+fn test_from_markdown_with_html_content() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("2024-05-01-landing.html");
+    fs::write(
+        &path,
+        "---\ntags: html, custom\n---\n<h1 class=\"big\">My <em>Landing</em></h1>\n<section># not markdown</section>",
+    )
+    .unwrap();
+
+    let content = Content::from_markdown(&path, None, &Marmite::new(), None, None).unwrap();
+    assert_eq!(content.title, "My Landing");
+    assert_eq!(content.html, "<section># not markdown</section>");
+    assert_eq!(content.tags, vec!["html", "custom"]);
+    assert!(content.date.is_some());
+}
+
+#[test]
+fn test_from_markdown_with_canonical_url() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("2024-05-01-syndicated.md");
+    fs::write(
+        &path,
+        "---\ncanonical_url: \" https://dev.to/me/syndicated \"\n---\n# Syndicated\n",
+    )
+    .unwrap();
+
+    let content = Content::from_markdown(&path, None, &Marmite::new(), None, None).unwrap();
+    assert_eq!(
+        content.canonical_url.as_deref(),
+        Some("https://dev.to/me/syndicated")
+    );
+    assert!(!content.extra.contains_key("canonical_url"));
+}
+
+#[test]
+fn test_get_html_title_fallback_to_filename() {
+    let frontmatter = Frontmatter::new();
+    let (title, html) = get_html_title(
+        &frontmatter,
+        "<p>No heading</p>",
+        Path::new("2024-01-01-my-page.html"),
+    );
+    assert_eq!(title, "my-page");
+    assert_eq!(html, "<p>No heading</p>");
+}
+
+#[test]
+fn test_new_content_id() {
+    let id = new_content_id("2024-01-01-hello.md");
+    let parts: Vec<&str> = id.split('-').collect();
+    assert_eq!(
+        parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+        vec![8, 4, 4, 4, 12]
+    );
+    assert!(parts[2].starts_with('4'));
+    assert!(matches!(
+        parts[3].chars().next(),
+        Some('8' | '9' | 'a' | 'b')
+    ));
+    assert_ne!(id, new_content_id("2024-01-01-other.md"));
+}
@@ -0,0 +1,242 @@
+use super::*;
+use std::path::Path;
+
+#[test]
+fn test_split_frontmatter() {
+    let (frontmatter, body) = split_frontmatter("---\ntitle: Hello\n---\nTitle\n=====\n");
+    assert_eq!(frontmatter, "---\ntitle: Hello\n---");
+    assert_eq!(body, "Title\n=====\n");
+
+    let (frontmatter, body) = split_frontmatter("Title\n=====\n");
+    assert_eq!(frontmatter, "");
+    assert_eq!(body, "Title\n=====\n");
+}
+
+#[test]
+fn test_is_content_source() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let html_content = temp_dir.path().join("page.html");
+    let html_other = temp_dir.path().join("other.html");
+    std::fs::write(&html_content, "---\ntitle: Page\n---\n<p>Hello</p>").unwrap();
+    std::fs::write(&html_other, "<p>Not content</p>").unwrap();
+
+    let extensions = ContentExtensionsConfig::default();
+    let mut converters = HashMap::new();
+    converters.insert("rst".to_string(), "pandoc -f rst -t gfm".to_string());
+    assert!(is_content_source(
+        Path::new("post.md"),
+        &converters,
+        &extensions
+    ));
+    assert!(is_content_source(
+        Path::new("post.ipynb"),
+        &converters,
+        &extensions
+    ));
+    assert!(is_content_source(
+        Path::new("post.rst"),
+        &converters,
+        &extensions
+    ));
+    assert!(!is_content_source(
+        Path::new("post.adoc"),
+        &converters,
+        &extensions
+    ));
+    assert!(!is_content_source(
+        Path::new("README"),
+        &converters,
+        &extensions
+    ));
+    assert!(is_content_source(&html_content, &converters, &extensions));
+    assert!(!is_content_source(&html_other, &converters, &extensions));
+}
+
+#[test]
+fn test_source_to_markdown_with_converter() {
+    let extensions = ContentExtensionsConfig::default();
+    let mut converters = HashMap::new();
+    converters.insert("rst".to_string(), "cat".to_string());
+
+    let markdown = source_to_markdown(
+        Path::new("post.rst"),
+        "---\ntitle: Hello\n---\nSome *text*\n".to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(markdown, "---\ntitle: Hello\n---\n\nSome *text*\n");
+
+    // Markdown files are not converted
+    let markdown = source_to_markdown(
+        Path::new("post.md"),
+        "# Hello".to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(markdown, "# Hello");
+}
+
+#[test]
+fn test_source_to_markdown_with_missing_converter() {
+    let extensions = ContentExtensionsConfig::default();
+    let mut converters = HashMap::new();
+    converters.insert(
+        "adoc".to_string(),
+        "marmite-converter-that-does-not-exist".to_string(),
+    );
+    let result = source_to_markdown(
+        Path::new("post.adoc"),
+        "= Hello".to_string(),
+        &converters,
+        &extensions,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_is_content_source_with_extensions() {
+    let converters = HashMap::new();
+    let mut extensions = ContentExtensionsConfig::default();
+    assert!(is_content_source(
+        Path::new("post.markdown"),
+        &converters,
+        &extensions
+    ));
+    assert!(is_content_source(
+        Path::new("post.mdx"),
+        &converters,
+        &extensions
+    ));
+    assert!(!is_content_source(
+        Path::new("notes.txt"),
+        &converters,
+        &extensions
+    ));
+
+    extensions.text.push("txt".to_string());
+    assert!(is_content_source(
+        Path::new("notes.txt"),
+        &converters,
+        &extensions
+    ));
+}
+
+#[test]
+fn test_source_to_markdown_with_markdown_extensions() {
+    let converters = HashMap::new();
+    let extensions = ContentExtensionsConfig::default();
+    let markdown = source_to_markdown(
+        Path::new("post.markdown"),
+        "# Hello\n\nimport is kept\n".to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(markdown, "# Hello\n\nimport is kept\n");
+
+    // mdx imports and exports are dropped, except inside code blocks
+    let markdown = source_to_markdown(
+        Path::new("post.mdx"),
+        "import Chart from './chart'\nexport const meta = {}\n# Hello\n```js\nimport x from 'y'\n```"
+            .to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(markdown, "# Hello\n```js\nimport x from 'y'\n```");
+}
+
+#[test]
+fn test_source_to_markdown_with_text_extensions() {
+    let converters = HashMap::new();
+    let extensions = ContentExtensionsConfig {
+        text: vec!["txt".to_string()],
+        ..ContentExtensionsConfig::default()
+    };
+
+    // the first line is the title
+    let markdown = source_to_markdown(
+        Path::new("notes.txt"),
+        "My Notes\n  indented\n*not emphasis*\n".to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(
+        markdown,
+        "# My Notes\n\n```text\n  indented\n*not emphasis*\n```\n"
+    );
+
+    // the frontmatter is kept and the fence outgrows the backticks of the text
+    let markdown = source_to_markdown(
+        Path::new("notes.txt"),
+        "---\ntitle: Notes\n---\nuse ```code```\n".to_string(),
+        &converters,
+        &extensions,
+    )
+    .unwrap();
+    assert_eq!(
+        markdown,
+        "---\ntitle: Notes\n---\n\n````text\nuse ```code```\n````\n"
+    );
+}
+
+fn limits() -> CommandLimits {
+    CommandLimits::new(&ExternalCommandsConfig::default(), Path::new(""))
+}
+
+#[test]
+fn test_run_limited_timeout() {
+    let limits = CommandLimits {
+        timeout: Some(Duration::from_millis(200)),
+        ..limits()
+    };
+    let started = Instant::now();
+    let result = run_limited("sleep", &["5"], "", &limits);
+    assert_eq!(result, Err("Timed out after 200ms".to_string()));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_run_limited_max_output() {
+    let limits = CommandLimits {
+        max_output: Some(1024),
+        ..limits()
+    };
+    let result = run_limited("yes", &["marmite"], "", &limits);
+    assert_eq!(result, Err("Output larger than 1024 bytes".to_string()));
+    assert_eq!(
+        run_limited("cat", &[] as &[&str], "marmite", &limits),
+        Ok("marmite".to_string())
+    );
+}
+
+#[test]
+fn test_run_limited_env_and_working_dir() {
+    let folder = tempfile::TempDir::new().unwrap();
+    let config = ExternalCommandsConfig {
+        working_dir: Some("assets".to_string()),
+        env: vec!["PATH".to_string()],
+        ..ExternalCommandsConfig::default()
+    };
+    fs::create_dir(folder.path().join("assets")).unwrap();
+    let limits = CommandLimits::new(&config, folder.path());
+    assert_eq!(limits.working_dir, Some(folder.path().join("assets")));
+    assert_eq!(
+        limits.cache_folder,
+        Some(folder.path().join(".marmite/renders"))
+    );
+    assert_eq!(limits().cache_folder, None);
+
+    let env = run_limited("env", &[] as &[&str], "", &limits).unwrap();
+    assert!(env.lines().all(|line| line.starts_with("PATH=")), "{env}");
+    let pwd = run_limited("pwd", &["-P"], "", &limits).unwrap();
+    assert_eq!(
+        PathBuf::from(pwd.trim()),
+        folder.path().join("assets").canonicalize().unwrap()
+    );
+}
@@ -0,0 +1,72 @@
+use super::*;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+#[test]
+fn test_load_resume() {
+    let dir = TempDir::new().unwrap();
+    let json = dir.path().join("resume.json");
+    fs::write(
+        &json,
+        r#"{"basics": {"name": "Ada Lovelace", "label": "Programmer"}, "work": [{"name": "Analytical Engine", "startDate": "1842-01"}]}"#,
+    )
+    .unwrap();
+    let resume = load_resume(&json).unwrap();
+    assert_eq!(resume["basics"]["name"], "Ada Lovelace");
+    assert_eq!(resume["work"][0]["startDate"], "1842-01");
+
+    let yaml = dir.path().join("resume.yaml");
+    fs::write(
+        &yaml,
+        "basics:\n  name: Ada Lovelace\nskills:\n  - name: Math\n",
+    )
+    .unwrap();
+    let resume = load_resume(&yaml).unwrap();
+    assert_eq!(resume["skills"][0]["name"], "Math");
+}
+
+#[test]
+fn test_load_resume_missing_or_invalid() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(load_resume(&dir.path().join("resume.json")), None);
+
+    let path = dir.path().join("list.json");
+    fs::write(&path, "[1, 2]").unwrap();
+    assert_eq!(load_resume(&path), None);
+
+    fs::write(&path, "{not json").unwrap();
+    assert_eq!(load_resume(&path), None);
+}
+
+#[test]
+fn test_pdf_args_and_url() {
+    let args = pdf_args(
+        "chromium --headless --print-to-pdf={output} {input}",
+        Path::new("site/cv/index.html"),
+        Path::new("site/cv/cv.pdf"),
+    );
+    assert_eq!(
+        args,
+        vec![
+            "chromium",
+            "--headless",
+            "--print-to-pdf=site/cv/cv.pdf",
+            "site/cv/index.html"
+        ]
+    );
+
+    let mut config = CvConfig::default();
+    assert_eq!(pdf_url(&config), None);
+    config.pdf_command = Some("weasyprint {input} {output}".to_string());
+    assert_eq!(pdf_url(&config).as_deref(), Some("cv/cv.pdf"));
+}
+
+#[test]
+fn test_export_pdf_with_missing_program() {
+    let result = export_pdf(
+        "marmite-missing-pdf-program {input} {output}",
+        &PathBuf::from("index.html"),
+        &PathBuf::from("cv.pdf"),
+    );
+    assert!(result.is_err());
+}
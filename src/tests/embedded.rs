@@ -38,3 +38,28 @@ fn test_embedded_static_initialization() {
     let static_files = &*EMBEDDED_STATIC;
     assert!(!static_files.is_empty());
 }
+
+#[test]
+fn test_embedded_assets_are_complete() {
+    // a bare binary builds and themes a site with only these files
+    for template in [
+        "base.html",
+        "content.html",
+        "list.html",
+        "group.html",
+        "rss.xml",
+    ] {
+        assert!(Templates::get(template).is_some(), "missing {template}");
+    }
+    let static_names: Vec<&str> = EMBEDDED_STATIC
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    for file in ["marmite.css", "marmite.js", "pico.min.css", "favicon.ico"] {
+        assert!(static_names.contains(&file), "missing {file}");
+    }
+    assert!(ThemeTemplate::iter().next().is_some());
+    assert!(ThemeMinimal::iter().next().is_some());
+    assert!(!EMBEDDED_SHORTCODES.is_empty());
+    assert!(Dictionaries::iter().next().is_some());
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn test_exit_codes_are_distinct() {
+    let kinds = [
+        ErrorKind::Other,
+        ErrorKind::Config,
+        ErrorKind::Content,
+        ErrorKind::Template,
+        ErrorKind::Io,
+        ErrorKind::Links,
+    ];
+    let mut codes: Vec<i32> = kinds.iter().map(|kind| kind.code()).collect();
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), kinds.len());
+    // clap exits with 2 on invalid arguments
+    assert!(!codes.contains(&2));
+    assert!(!codes.contains(&0));
+}
+
+#[test]
+fn test_kind_of() {
+    let failure: Box<dyn Error> = Box::new(Failure::new(ErrorKind::Content, "Found 2 errors"));
+    assert_eq!(kind_of(failure.as_ref()), ErrorKind::Content);
+    let io: Box<dyn Error> = Box::new(std::io::Error::other("disk full"));
+    assert_eq!(kind_of(io.as_ref()), ErrorKind::Io);
+    let other: Box<dyn Error> = "Input folder does not exist".into();
+    assert_eq!(kind_of(other.as_ref()), ErrorKind::Other);
+}
+
+#[test]
+fn test_json_report() {
+    let report: serde_json::Value =
+        serde_json::from_str(&json_report(ErrorKind::Template, "Failed \"base.html\"")).unwrap();
+    assert_eq!(report["error"], "template");
+    assert_eq!(report["code"], 5);
+    assert_eq!(report["message"], "Failed \"base.html\"");
+}
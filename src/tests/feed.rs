@@ -1,5 +1,6 @@
 use super::*;
 use crate::content::ContentBuilder;
+use crate::embedded::EMBEDDED_TERA;
 use std::path::PathBuf;
 
 fn setup_test_environment() -> (Vec<Content>, PathBuf, Marmite) {
@@ -65,7 +66,7 @@ fn test_generate_rss() {
     let (contents, output_path, config) = setup_test_environment();
     let filename = "test_rss";
 
-    let result = generate_rss(&contents, &output_path, filename, &config);
+    let result = generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA);
     assert!(result.is_ok());
 
     // Check that the RSS file was created
@@ -87,6 +88,89 @@ fn test_generate_rss() {
     assert_eq!(item.description(), content.description.as_deref());
 }
 
+#[test]
+fn test_generate_feeds_with_updated_date() {
+    let (mut contents, output_path, config) = setup_test_environment();
+    contents[0].updated = Some(
+        chrono::NaiveDateTime::parse_from_str("2021-02-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+    );
+    let filename = "test_feed_updated";
+
+    generate_json(&contents, &output_path, filename, &config).unwrap();
+    let feed_content = std::fs::read_to_string(output_path.join(format!("{filename}.json")))
+        .expect("Failed to read generated JSON feed");
+    let json_feed: JsonFeed =
+        serde_json::from_str(&feed_content).expect("Failed to parse JSON feed");
+    assert_eq!(
+        json_feed.items[0].date_modified.as_deref(),
+        Some("2021-02-01T10:00:00-00:00")
+    );
+
+    generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA).unwrap();
+    let rss_content = std::fs::read_to_string(output_path.join(format!("{filename}.rss")))
+        .expect("Failed to read RSS feed");
+    let channel = rss::Channel::read_from(rss_content.as_bytes()).expect("Failed to parse RSS");
+    let dates = &channel.items()[0]
+        .dublin_core_ext()
+        .expect("Item should have dublin core extension")
+        .dates;
+    assert_eq!(dates, &vec!["2021-02-01T10:00:00Z".to_string()]);
+}
+
+#[test]
+fn test_generate_feeds_with_uuid() {
+    let (mut contents, output_path, config) = setup_test_environment();
+    contents[0].uuid = Some("0b5f3a52-8c1e-4f7a-9d2b-6e4c1a7f9e30".to_string());
+    let filename = "test_feed_uuid";
+
+    generate_json(&contents, &output_path, filename, &config).unwrap();
+    let feed_content = std::fs::read_to_string(output_path.join(format!("{filename}.json")))
+        .expect("Failed to read generated JSON feed");
+    let json_feed: JsonFeed =
+        serde_json::from_str(&feed_content).expect("Failed to parse JSON feed");
+    assert_eq!(
+        json_feed.items[0].id,
+        "urn:uuid:0b5f3a52-8c1e-4f7a-9d2b-6e4c1a7f9e30"
+    );
+
+    generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA).unwrap();
+    let rss_content = std::fs::read_to_string(output_path.join(format!("{filename}.rss")))
+        .expect("Failed to read RSS feed");
+    let channel = rss::Channel::read_from(rss_content.as_bytes()).expect("Failed to parse RSS");
+    let guid = channel.items()[0].guid().expect("Item should have a guid");
+    assert_eq!(
+        guid.value(),
+        "urn:uuid:0b5f3a52-8c1e-4f7a-9d2b-6e4c1a7f9e30"
+    );
+    assert!(!guid.is_permalink());
+}
+
+#[test]
+fn test_generate_feeds_with_canonical_url() {
+    let (mut contents, output_path, config) = setup_test_environment();
+    let canonical_url = "https://dev.to/rochacbruno/test-title";
+    contents[0].canonical_url = Some(canonical_url.to_string());
+    let filename = "test_feed_canonical";
+
+    generate_json(&contents, &output_path, filename, &config).unwrap();
+    let feed_content = std::fs::read_to_string(output_path.join(format!("{filename}.json")))
+        .expect("Failed to read generated JSON feed");
+    let json_feed: JsonFeed =
+        serde_json::from_str(&feed_content).expect("Failed to parse JSON feed");
+    assert_eq!(json_feed.items[0].url, canonical_url);
+    assert_eq!(json_feed.items[0].id, canonical_url);
+
+    generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA).unwrap();
+    let rss_content = std::fs::read_to_string(output_path.join(format!("{filename}.rss")))
+        .expect("Failed to read RSS feed");
+    let channel = rss::Channel::read_from(rss_content.as_bytes()).expect("Failed to parse RSS");
+    assert_eq!(channel.items()[0].link(), Some(canonical_url));
+    assert_eq!(
+        channel.items()[0].guid().map(rss::Guid::value),
+        Some(canonical_url)
+    );
+}
+
 #[test]
 fn test_generate_rss_with_https_config() {
     let (contents, output_path, mut config) = setup_test_environment();
@@ -94,7 +178,7 @@ fn test_generate_rss_with_https_config() {
     config.https = Some(true);
     let filename = "test_rss_https";
 
-    let result = generate_rss(&contents, &output_path, filename, &config);
+    let result = generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA);
     assert!(result.is_ok());
 
     let feed_path = output_path.join(format!("{filename}.rss"));
@@ -109,7 +193,7 @@ fn test_generate_rss_with_http_config() {
     config.https = Some(false);
     let filename = "test_rss_http";
 
-    let result = generate_rss(&contents, &output_path, filename, &config);
+    let result = generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA);
     assert!(result.is_ok());
 
     let feed_path = output_path.join(format!("{filename}.rss"));
@@ -123,7 +207,7 @@ fn test_generate_rss_with_full_url() {
     config.url = "https://fullurl.com".to_string();
     let filename = "test_rss_full_url";
 
-    let result = generate_rss(&contents, &output_path, filename, &config);
+    let result = generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA);
     assert!(result.is_ok());
 
     let feed_path = output_path.join(format!("{filename}.rss"));
@@ -137,7 +221,7 @@ fn test_generate_rss_with_card_image() {
     config.card_image = "site-image.png".to_string();
     let filename = "test_rss_image";
 
-    let result = generate_rss(&contents, &output_path, filename, &config);
+    let result = generate_rss(&contents, &output_path, filename, &config, &EMBEDDED_TERA);
     assert!(result.is_ok());
 
     let feed_path = output_path.join(format!("{filename}.rss"));
@@ -183,7 +267,13 @@ fn test_generate_feeds_filter_draft_content() {
     let config = Marmite::new();
 
     // Test RSS feed filtering
-    let rss_result = generate_rss(&contents, output_path, "test_filter", &config);
+    let rss_result = generate_rss(
+        &contents,
+        output_path,
+        "test_filter",
+        &config,
+        &EMBEDDED_TERA,
+    );
     assert!(rss_result.is_ok());
 
     let rss_path = output_path.join("test_filter.rss");
@@ -239,6 +329,7 @@ fn test_generate_json_with_authors_config() {
         bio: Some("Alice is a developer".to_string()),
         links: Some(alice_links),
         avatar: Some("alice.jpg".to_string()),
+        ..Default::default()
     };
 
     config.authors.insert("alice".to_string(), alice_author);
@@ -275,7 +366,7 @@ fn test_generate_feeds_empty_content() {
     let config = Marmite::new();
 
     // Test RSS with empty content
-    let rss_result = generate_rss(&contents, output_path, "empty_rss", &config);
+    let rss_result = generate_rss(&contents, output_path, "empty_rss", &config, &EMBEDDED_TERA);
     assert!(rss_result.is_ok());
 
     let rss_path = output_path.join("empty_rss.rss");
@@ -292,3 +383,97 @@ fn test_generate_feeds_empty_content() {
     let feed: JsonFeed = serde_json::from_str(&json_content).expect("Failed to parse JSON");
     assert_eq!(feed.items.len(), 0);
 }
+
+#[test]
+fn test_collect_backlink_activity() {
+    let date =
+        chrono::NaiveDateTime::parse_from_str("2024-05-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+    let contents = vec![
+        ContentBuilder::new()
+            .title("Source".to_string())
+            .slug("source".to_string())
+            .date(date)
+            .links_to(vec![
+                "target".to_string(),
+                "target#section".to_string(),
+                "source".to_string(),
+                "missing".to_string(),
+            ])
+            .build(),
+        ContentBuilder::new()
+            .title("Target".to_string())
+            .slug("target".to_string())
+            .date(date)
+            .build(),
+    ];
+
+    let items = collect_backlink_activity(&contents);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title, "Source linked to Target");
+    assert_eq!(items[0].source, "source.html");
+    assert_eq!(items[0].target, "target.html");
+    assert_eq!(items[0].date, date);
+}
+
+#[test]
+fn test_load_webmentions_and_generate_activity_rss() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let webmentions_path = temp_dir.path().join("webmentions.json");
+    std::fs::write(
+        &webmentions_path,
+        r#"{"children": [
+            {
+                "wm-source": "https://example.org/reply",
+                "wm-target": "https://mysite.com/target.html",
+                "wm-received": "2024-05-02T10:00:00Z",
+                "wm-property": "in-reply-to",
+                "author": {"name": "Alice"},
+                "content": {"text": "Nice post"}
+            },
+            {"wm-source": "https://example.org/no-date", "wm-target": "https://mysite.com/"}
+        ]}"#,
+    )
+    .unwrap();
+
+    let items = load_webmentions(&webmentions_path);
+    assert_eq!(items.len(), 1);
+    assert_eq!(
+        items[0].title,
+        "Alice replied to https://mysite.com/target.html"
+    );
+    assert_eq!(items[0].description, "Nice post");
+
+    assert!(load_webmentions(&temp_dir.path().join("missing.json")).is_empty());
+
+    let config = Marmite::new();
+    let result =
+        generate_activity_rss(&items, temp_dir.path(), "activity", &config, &EMBEDDED_TERA);
+    assert!(result.is_ok());
+
+    let rss_content = std::fs::read_to_string(temp_dir.path().join("activity.rss")).unwrap();
+    let channel = rss::Channel::read_from(rss_content.as_bytes()).expect("Failed to parse RSS");
+    assert_eq!(channel.items().len(), 1);
+    assert_eq!(channel.items()[0].link(), Some("https://example.org/reply"));
+}
+
+#[test]
+fn test_generate_rss_with_custom_template() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (contents, _, config) = setup_test_environment();
+    let mut tera = Tera::default();
+    tera.add_raw_template(
+        "rss.xml",
+        r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd"><channel>{% for item in items %}<item><title>{{ item.title | escape_xml }}</title><itunes:explicit>false</itunes:explicit></item>{% endfor %}</channel></rss>"#,
+    )
+    .unwrap();
+    tera.extend(&EMBEDDED_TERA).unwrap();
+
+    generate_rss(&contents, temp_dir.path(), "podcast", &config, &tera).unwrap();
+    let rss_content = std::fs::read_to_string(temp_dir.path().join("podcast.rss")).unwrap();
+    assert!(rss_content.contains("xmlns:itunes"));
+    assert!(rss_content.contains("<item><title>Test Title</title><itunes:explicit>"));
+}
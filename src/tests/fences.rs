@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn test_render_fences_without_renderers() {
+    let html = r#"<pre><code class="language-dot">digraph { a -&gt; b }</code></pre>"#;
+    assert_eq!(render_fences(html, &HashMap::new()), html);
+}
+
+#[test]
+fn test_render_fences_with_renderer() {
+    let html = concat!(
+        r#"<pre><code class="language-svgecho">&lt;svg id=&quot;test-fence&quot;&gt;&lt;/svg&gt;</code></pre>"#,
+        r#"<pre><code class="language-rust">fn main() {}</code></pre>"#
+    );
+    let mut renderers = HashMap::new();
+    renderers.insert("svgecho".to_string(), "cat".to_string());
+
+    let result = render_fences(html, &renderers);
+    assert!(result
+        .contains(r#"<div class="fence-render fence-svgecho"><svg id="test-fence"></svg></div>"#));
+    assert!(result.contains(r#"<code class="language-rust">fn main() {}</code>"#));
+}
+
+#[test]
+fn test_render_fences_keeps_code_on_failure() {
+    let html = r#"<pre><code class="language-dot">digraph { a -&gt; b }</code></pre>"#;
+    let mut renderers = HashMap::new();
+    renderers.insert(
+        "dot".to_string(),
+        "marmite-renderer-that-does-not-exist".to_string(),
+    );
+    assert_eq!(render_fences(html, &renderers), html);
+}
+
+#[test]
+fn test_unescape_html() {
+    assert_eq!(
+        unescape_html("a -&gt; b &amp;&amp; &quot;c&quot;"),
+        r#"a -> b && "c""#
+    );
+}
+
+#[test]
+fn test_diagram_containers() {
+    let html = concat!(
+        r#"<pre><code class="language-mermaid">graph LR
+    A --&gt; B
+</code></pre>"#,
+        r#"<pre><code class="language-dot">digraph { a -&gt; b }</code></pre>"#,
+        r#"<pre><code class="language-rust">fn main() {}</code></pre>"#
+    );
+    let result = diagram_containers(html);
+    assert!(result.contains("<pre class=\"mermaid\">graph LR\n    A --&gt; B\n</pre>"));
+    assert!(result.contains(r#"<pre class="graphviz">digraph { a -&gt; b }</pre>"#));
+    assert!(result.contains(r#"<pre><code class="language-rust">fn main() {}</code></pre>"#));
+}
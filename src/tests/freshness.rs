@@ -0,0 +1,51 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+fn post(slug: &str, published: NaiveDateTime, inbound_links: usize) -> Content {
+    let mut post = ContentBuilder::new()
+        .title(slug.to_uppercase())
+        .slug(slug.to_string())
+        .date(published)
+        .build();
+    post.back_links = vec![Content::default(); inbound_links];
+    post
+}
+
+#[test]
+fn test_last_updated_uses_latest_of_date_and_modified_time() {
+    let mut content = post("a", date(2024, 1, 1), 0);
+    assert_eq!(last_updated(&content), Some(date(2024, 1, 1)));
+
+    content.modified_time = Some(date(2025, 6, 1).and_utc().timestamp());
+    assert_eq!(last_updated(&content), Some(date(2025, 6, 1)));
+
+    content.modified_time = Some(date(2020, 6, 1).and_utc().timestamp());
+    assert_eq!(last_updated(&content), Some(date(2024, 1, 1)));
+}
+
+#[test]
+fn test_collect_stale_sorted_by_inbound_links() {
+    let posts = vec![
+        post("fresh", date(2025, 5, 1), 5),
+        post("old", date(2022, 1, 1), 0),
+        post("linked", date(2024, 1, 1), 3),
+        post("older", date(2021, 1, 1), 0),
+    ];
+    let stale = collect_stale(&posts, 12, date(2025, 6, 1));
+    let slugs: Vec<&str> = stale.iter().map(|s| s.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["linked", "older", "old"]);
+    assert_eq!(stale[0].inbound_links, 3);
+    assert_eq!(stale[0].months, 17);
+
+    let table = format_table(&stale);
+    assert!(table.starts_with("Months  Links  Updated"));
+    assert!(table.contains("    17      3  2024-01-01  linked  LINKED"));
+}
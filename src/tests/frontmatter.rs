@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn test_frontmatter_editor_keeps_untouched_lines() {
+    let source = "---\n# imported from hugo\ntitle: 'Hello'\ncategories:\n  - rust\n  - web\ndate: 2024-01-01\n---\n# Hello\n";
+    let mut editor = FrontmatterEditor::parse(source).unwrap();
+    assert_eq!(editor.get("title"), Some("Hello".to_string()));
+    assert_eq!(editor.get("categories"), None);
+
+    assert!(editor.rename("categories", "tags"));
+    editor.set("title", "Hello World");
+    editor.set("uuid", "b6c9a9d2-5f3e-4a8b-9c1d-2e3f4a5b6c7d");
+    assert_eq!(
+        editor.to_source(),
+        "---\n# imported from hugo\ntitle: 'Hello World'\ntags:\n  - rust\n  - web\ndate: 2024-01-01\nuuid: b6c9a9d2-5f3e-4a8b-9c1d-2e3f4a5b6c7d\n---\n# Hello\n"
+    );
+
+    assert!(editor.remove("tags"));
+    assert!(!editor.remove("tags"));
+    assert_eq!(
+        editor.to_source(),
+        "---\n# imported from hugo\ntitle: 'Hello World'\ndate: 2024-01-01\nuuid: b6c9a9d2-5f3e-4a8b-9c1d-2e3f4a5b6c7d\n---\n# Hello\n"
+    );
+}
+
+#[test]
+fn test_frontmatter_editor_rename_never_overwrites() {
+    let mut editor = FrontmatterEditor::parse("---\ntags: a\ncategories: b\n---\n").unwrap();
+    assert!(!editor.rename("categories", "tags"));
+    assert!(!editor.rename("missing", "other"));
+    assert_eq!(editor.to_source(), "---\ntags: a\ncategories: b\n---\n");
+}
+
+#[test]
+fn test_frontmatter_editor_quotes_values() {
+    let mut editor = FrontmatterEditor::parse("# No frontmatter\n").unwrap();
+    assert_eq!(editor.to_source(), "# No frontmatter\n");
+    editor.set("title", "Rust: the good parts");
+    editor.set("draft", "yes");
+    editor.set("description", "Say \"hi\"");
+    assert_eq!(
+        editor.to_source(),
+        "---\ntitle: \"Rust: the good parts\"\ndraft: \"yes\"\ndescription: Say \"hi\"\n---\n# No frontmatter\n"
+    );
+}
+
+#[test]
+fn test_frontmatter_editor_rejects_unsupported() {
+    assert!(FrontmatterEditor::parse("+++\ntitle = \"a\"\n+++\n").is_err());
+    assert!(FrontmatterEditor::parse("---\ntitle: a\n").is_err());
+}
@@ -58,7 +58,7 @@ fn test_process_galleries_empty_directory() {
     let media_path = temp_dir.path();
     fs::create_dir(media_path.join("gallery")).unwrap();
 
-    let galleries = process_galleries(media_path, "gallery", false, 50);
+    let galleries = process_galleries(media_path, "gallery", false, 50, false);
     assert!(galleries.is_empty());
 }
 
@@ -67,7 +67,7 @@ fn test_process_single_gallery_without_images() {
     let temp_dir = TempDir::new().unwrap();
     let gallery_path = temp_dir.path();
 
-    let gallery = process_single_gallery(gallery_path, "test", false, 50);
+    let gallery = process_single_gallery(gallery_path, "test", false, 50, false);
     assert_eq!(gallery.name, "test");
     assert!(gallery.files.is_empty());
     assert_eq!(gallery.cover, "");
@@ -86,7 +86,7 @@ cover: "main.jpg"
 "#;
     fs::write(gallery_path.join("gallery.yaml"), config_content).unwrap();
 
-    let gallery = process_single_gallery(gallery_path, "test", false, 50);
+    let gallery = process_single_gallery(gallery_path, "test", false, 50, false);
     assert_eq!(gallery.name, "Test Gallery");
     assert_eq!(gallery.ord, GalleryOrder::Desc);
     assert_eq!(gallery.cover, "main.jpg");
@@ -131,7 +131,7 @@ cover: "test1.jpg"
 "#;
     fs::write(gallery_dir.join("gallery.yaml"), config_content).unwrap();
 
-    let galleries = process_galleries(media_path, "gallery", true, 50);
+    let galleries = process_galleries(media_path, "gallery", true, 50, false);
 
     assert_eq!(galleries.len(), 1);
     assert!(galleries.contains_key("test-gallery"));
@@ -160,7 +160,7 @@ fn test_generate_thumbnail() {
     let img = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(200, 200);
     img.save(&image_path).unwrap();
 
-    let thumb_name = generate_thumbnail(&image_path, &thumbnails_dir, 50);
+    let thumb_name = generate_thumbnail(&image_path, &thumbnails_dir, 50, false);
 
     assert!(thumb_name.is_some());
     assert_eq!(thumb_name.unwrap(), "test.jpg");
@@ -185,8 +185,31 @@ fn test_generate_thumbnail_existing() {
     let thumb = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(50, 50);
     thumb.save(&thumb_path).unwrap();
 
-    let thumb_name = generate_thumbnail(&image_path, &thumbnails_dir, 50);
+    let thumb_name = generate_thumbnail(&image_path, &thumbnails_dir, 50, false);
 
     assert!(thumb_name.is_some());
     assert_eq!(thumb_name.unwrap(), "test.jpg");
 }
+
+#[test]
+fn test_process_galleries_low_memory() {
+    use image::{ImageBuffer, Rgb};
+
+    let temp_dir = TempDir::new().unwrap();
+    let media_path = temp_dir.path();
+    let gallery_dir = media_path.join("gallery").join("test-gallery");
+    fs::create_dir_all(&gallery_dir).unwrap();
+    let img = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(100, 100);
+    img.save(gallery_dir.join("small.png")).unwrap();
+    // decoding needs more than the low memory allocation limit
+    let img = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(5000, 5000);
+    img.save(gallery_dir.join("large.png")).unwrap();
+
+    let galleries = process_galleries(media_path, "gallery", true, 50, true);
+    let gallery = &galleries["test-gallery"];
+    assert_eq!(gallery.files.len(), 2);
+    assert_eq!(gallery.files[0].image, "large.png");
+    assert_eq!(gallery.files[0].thumb, "thumbnails/large.png");
+    assert!(gallery_dir.join("thumbnails").join("small.png").exists());
+    assert!(!gallery_dir.join("thumbnails").join("large.png").exists());
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+fn terms() -> Vec<GlossaryTerm> {
+    ["SSG", "Static Site", "Site"]
+        .iter()
+        .map(|term| GlossaryTerm {
+            term: (*term).to_string(),
+            slug: slug::slugify(term),
+            definition: format!("Definition of \"{term}\""),
+        })
+        .collect()
+}
+
+#[test]
+fn test_load_glossary() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("glossary.yaml");
+    fs::write(&path, "zettel: A note\nSSG: Static Site Generator\n").unwrap();
+
+    let glossary = load_glossary(&path);
+    assert_eq!(glossary.len(), 2);
+    assert_eq!(glossary[0].term, "SSG");
+    assert_eq!(glossary[0].slug, "ssg");
+    assert_eq!(glossary[0].definition, "Static Site Generator");
+    assert_eq!(glossary[1].term, "zettel");
+
+    assert!(load_glossary(&temp_dir.path().join("missing.yaml")).is_empty());
+}
+
+#[test]
+fn test_link_glossary_terms_first_occurrence_only() {
+    let html = "<p>An ssg is great, every SSG builds a static site.</p>";
+    let result = link_glossary_terms(html, &terms(), "glossary");
+
+    assert_eq!(result.matches("glossary-term").count(), 2);
+    assert!(result.contains(
+        r#"<a class="glossary-term" href="glossary.html#ssg"><abbr title="Definition of &quot;SSG&quot;">ssg</abbr></a> is great"#
+    ));
+    assert!(result.contains("every SSG builds"));
+    // longer term wins over the shorter one at the same position
+    assert!(result.contains(r#"href="glossary.html#static-site""#));
+    assert!(!result.contains(r#"href="glossary.html#site""#));
+}
+
+#[test]
+fn test_link_glossary_terms_skips_code_links_and_headings() {
+    let html = "<h2>SSG</h2><p><code>SSG</code> <a href=\"x.html\">SSG</a> SSGs SSG</p>";
+    let result = link_glossary_terms(html, &terms(), "glossary");
+
+    assert!(result.starts_with("<h2>SSG</h2><p><code>SSG</code> <a href=\"x.html\">SSG</a> SSGs "));
+    assert_eq!(result.matches("glossary-term").count(), 1);
+}
+
+#[test]
+fn test_link_glossary_terms_skips_definition_terms() {
+    let html = "<dl>\n<dt>SSG</dt>\n<dd>An SSG builds html</dd>\n</dl>\n";
+    let result = link_glossary_terms(html, &terms(), "glossary");
+
+    assert!(result.starts_with("<dl>\n<dt>SSG</dt>\n<dd>An <a class=\"glossary-term\""));
+    assert_eq!(result.matches("glossary-term").count(), 1);
+}
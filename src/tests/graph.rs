@@ -0,0 +1,54 @@
+use super::*;
+use crate::content::ContentBuilder;
+
+fn content(slug: &str, links_to: &[&str]) -> Content {
+    ContentBuilder::new()
+        .slug(slug.to_string())
+        .links_to(links_to.iter().map(ToString::to_string).collect())
+        .build()
+}
+
+#[test]
+fn test_compute_metrics() {
+    let contents = vec![
+        content("hub", &["a", "b", "hub", "missing"]),
+        content("a", &["b", "b#intro"]),
+        content("b", &["hub"]),
+        content("lonely", &[]),
+        ContentBuilder::new()
+            .slug("draft".to_string())
+            .stream("draft".to_string())
+            .links_to(vec!["b".to_string()])
+            .build(),
+    ];
+    let metrics = compute_metrics(contents.iter());
+
+    assert_eq!(metrics.len(), 4);
+    assert!(!metrics.contains_key("draft"));
+    // links to itself, to missing content and repeated links are ignored
+    assert_eq!(metrics["hub"].out_degree, 2);
+    assert_eq!(metrics["a"].out_degree, 1);
+    assert_eq!(metrics["b"].in_degree, 2);
+    assert_eq!(metrics["hub"].in_degree, 1);
+    assert_eq!(metrics["lonely"].in_degree, 0);
+
+    let total: f64 = metrics.values().map(|m| m.centrality).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(metrics["b"].centrality > metrics["a"].centrality);
+    assert!(metrics["a"].centrality > metrics["lonely"].centrality);
+}
+
+#[test]
+fn test_compute_metrics_without_content() {
+    assert!(compute_metrics(std::iter::empty()).is_empty());
+}
+
+#[test]
+fn test_format_table() {
+    let contents = vec![content("a", &["b"]), content("b", &[])];
+    let table = format_table(&compute_metrics(contents.iter()));
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "Slug  In  Out  Centrality");
+    assert!(lines[1].starts_with("b      1    0  0.6"));
+    assert!(lines[2].starts_with("a      0    1  0.3"));
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+fn enabled() -> SyntaxHighlightConfig {
+    SyntaxHighlightConfig {
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_highlight_code_disabled() {
+    let html = r#"<pre><code class="language-rust">fn main() {}</code></pre>"#;
+    assert_eq!(
+        highlight_code(html, &SyntaxHighlightConfig::default()),
+        html
+    );
+}
+
+#[test]
+fn test_highlight_code() {
+    let html = concat!(
+        r#"<pre><code class="language-rust">fn main() { let ok = 1 &lt; 2; }</code></pre>"#,
+        r#"<pre><code class="language-marmite-unknown">plain &amp; simple</code></pre>"#,
+        r"<pre><code>no language</code></pre>"
+    );
+    let result = highlight_code(html, &enabled());
+    assert!(result.contains(r#"<pre class="hl-code"><code class="language-rust">"#));
+    assert!(result.contains("hl-rust"));
+    assert!(result.contains("&lt;"));
+    assert!(!result.contains("1 < 2"));
+    assert!(result.contains(
+        r#"<pre><code class="language-marmite-unknown">plain &amp; simple</code></pre>"#
+    ));
+    assert!(result.contains(r"<pre><code>no language</code></pre>"));
+}
+
+#[test]
+fn test_theme_css() {
+    let css = theme_css(&enabled()).unwrap();
+    assert!(css.contains(".hl-code"));
+    assert!(css.contains("@media (prefers-color-scheme: dark)"));
+
+    let light_only = SyntaxHighlightConfig {
+        dark_theme: None,
+        ..enabled()
+    };
+    assert!(!theme_css(&light_only)
+        .unwrap()
+        .contains("prefers-color-scheme"));
+
+    let unknown = SyntaxHighlightConfig {
+        theme: "marmite-unknown".to_string(),
+        ..enabled()
+    };
+    assert!(theme_css(&unknown).is_err());
+}
+
+#[test]
+fn test_parse_info() {
+    let (lang, options) = parse_info("rust,linenos,linenostart=10,hl_lines=1,3-5");
+    assert_eq!(lang, "rust");
+    assert_eq!(
+        options,
+        FenceOptions {
+            linenos: true,
+            linenostart: 10,
+            hl_lines: vec![(1, 1), (3, 5)],
+        }
+    );
+
+    let (lang, options) = parse_info("python,hl_lines=2-1,unknown");
+    assert_eq!(lang, "python");
+    assert_eq!(options, FenceOptions::default());
+    assert!(!options.splits_lines());
+}
+
+#[test]
+fn test_split_lines_keeps_spans_balanced() {
+    let options = FenceOptions {
+        linenos: true,
+        linenostart: 1,
+        hl_lines: vec![(2, 2)],
+    };
+    let code = "<span class=\"hl-comment\">/* a\nb */</span>\nc\n";
+    assert_eq!(
+        split_lines(code, &options),
+        concat!(
+            "<span class=\"line\"><span class=\"lineno\">1</span><span class=\"hl-comment\">/* a</span></span>\n",
+            "<span class=\"line hl-line\"><span class=\"lineno\">2</span><span class=\"hl-comment\">b */</span></span>\n",
+            "<span class=\"line\"><span class=\"lineno\">3</span>c</span>\n",
+        )
+    );
+}
+
+#[test]
+fn test_highlight_code_line_options_without_syntect() {
+    let html = r#"<pre><code class="language-rust,hl_lines=2">let a = 1;
+let b = a &lt; 2;
+</code></pre>"#;
+    let result = highlight_code(html, &SyntaxHighlightConfig::default());
+    assert_eq!(
+        result,
+        concat!(
+            r#"<pre><code class="language-rust nohighlight">"#,
+            "<span class=\"line\">let a = 1;</span>\n",
+            "<span class=\"line hl-line\">let b = a &lt; 2;</span>\n",
+            "</code></pre>"
+        )
+    );
+}
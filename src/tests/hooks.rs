@@ -0,0 +1,60 @@
+use super::*;
+use crate::content::ContentBuilder;
+use tempfile::TempDir;
+
+#[test]
+fn test_hook_script() {
+    let script = hook_script("pre-commit", "blog", "marmite.yaml");
+    assert!(script.starts_with("#!/bin/sh\n"));
+    assert!(script.contains(HOOK_MARKER));
+    assert!(script.contains(
+        "marmite \"$(git rev-parse --show-toplevel)/blog\" --config \"marmite.yaml\" --hook-check pre-commit"
+    ));
+}
+
+#[test]
+fn test_check_contents_reports_changed_content_only() {
+    let content_dir = TempDir::new().unwrap();
+    let changed_post = content_dir.path().join("2024-01-01-hello.md");
+    let other_post = content_dir.path().join("2024-01-02-hello.md");
+    let about = content_dir.path().join("about.md");
+    for path in [&changed_post, &other_post, &about] {
+        fs::write(path, "# Hello").unwrap();
+    }
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("hello".to_string())
+            .links_to(vec!["about".to_string(), "missing#intro".to_string()])
+            .source_path(changed_post.clone())
+            .build(),
+    );
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("hello".to_string())
+            .links_to(vec!["gone".to_string()])
+            .source_path(other_post)
+            .build(),
+    );
+    site_data.pages.push(
+        ContentBuilder::new()
+            .slug("about".to_string())
+            .source_path(about)
+            .build(),
+    );
+    site_data.collect_all_urls();
+
+    let changed = HashSet::from([fs::canonicalize(&changed_post).unwrap()]);
+    let issues = check_contents(&site_data, &changed);
+    let messages: Vec<&str> = issues
+        .iter()
+        .inspect(|issue| assert_eq!(issue.path, changed_post))
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("slug 'hello' is also used by"));
+    assert_eq!(issues[0].kind, ErrorKind::Content);
+    assert_eq!(messages[1], "broken link to missing.html");
+    assert_eq!(issues[1].kind, ErrorKind::Links);
+}
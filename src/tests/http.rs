@@ -0,0 +1,53 @@
+use super::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// Serve one connection with `response`, or keep it open without answering
+fn serve_once(response: Option<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/data.json", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request);
+        match response {
+            Some(response) => {
+                let _ = stream.write_all(response.as_bytes());
+            }
+            None => thread::sleep(Duration::from_secs(5)),
+        }
+    });
+    url
+}
+
+fn ok_response(body: &str) -> Option<String> {
+    Some(format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    ))
+}
+
+#[test]
+fn test_get_text() {
+    let url = serve_once(ok_response(r#"{"tag_name": "v1.0"}"#));
+    assert_eq!(
+        get_text(&url, "application/json", 1024),
+        Ok(r#"{"tag_name": "v1.0"}"#.to_string())
+    );
+}
+
+#[test]
+fn test_get_text_limit() {
+    let url = serve_once(ok_response(&"x".repeat(2048)));
+    assert!(get_text(&url, "application/json", 1024).is_err());
+}
+
+#[test]
+fn test_get_with_timeout() {
+    let url = serve_once(None);
+    let started = std::time::Instant::now();
+    let agent = build_agent(Duration::from_millis(200));
+    assert!(get_with(&agent, &url, "application/json", 1024).is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,106 @@
+use super::*;
+use tempfile::TempDir;
+
+fn write(dir: &Path, name: &str, text: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn test_apply_includes() {
+    let dir = TempDir::new().unwrap();
+    let snippet = write(
+        dir.path(),
+        "snippets/warning.md",
+        "> **Warning** {{ include 'note.md' }}\n",
+    );
+    let nested = write(dir.path(), "snippets/note.md", "Nested");
+    let post = write(dir.path(), "posts/2024-01-01-hello.md", "");
+
+    let markdown =
+        "Intro\n{{ include \"snippets/warning.md\" }}\n  {{include 'snippets/note.md'}}\nOutro\n";
+    let (output, included) = apply_includes(markdown, &post, dir.path()).unwrap();
+    assert_eq!(
+        output,
+        "Intro\n> **Warning** {{ include 'note.md' }}\nNested\nOutro\n"
+    );
+    assert_eq!(included, vec![snippet, nested]);
+}
+
+#[test]
+fn test_apply_includes_nested_and_code_fences() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "snippets/outer.md",
+        "Outer\n{{ include \"inner.md\" }}\n",
+    );
+    write(dir.path(), "snippets/inner.md", "Inner\n");
+    let post = write(dir.path(), "post.md", "");
+
+    let markdown =
+        "{{ include \"snippets/outer.md\" }}\n```\n{{ include \"snippets/outer.md\" }}\n```\n";
+    let (output, included) = apply_includes(markdown, &post, dir.path()).unwrap();
+    assert_eq!(
+        output,
+        "Outer\nInner\n```\n{{ include \"snippets/outer.md\" }}\n```\n"
+    );
+    assert_eq!(included.len(), 2);
+
+    // indented code blocks are kept as code too
+    let markdown = "Code:\n\n    {{ include \"snippets/inner.md\" }}\n\t{{ include \"snippets/inner.md\" }}\n   {{ include \"snippets/inner.md\" }}\n";
+    let (output, _) = apply_includes(markdown, &post, dir.path()).unwrap();
+    assert_eq!(
+        output,
+        "Code:\n\n    {{ include \"snippets/inner.md\" }}\n\t{{ include \"snippets/inner.md\" }}\nInner\n"
+    );
+}
+
+#[test]
+fn test_apply_includes_errors() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "a.md", "{{ include \"b.md\" }}\n");
+    write(dir.path(), "b.md", "{{ include \"a.md\" }}\n");
+    let post = write(dir.path(), "post.md", "");
+
+    let error = apply_includes("{{ include \"a.md\" }}\n", &post, dir.path()).unwrap_err();
+    assert!(error.starts_with("Include cycle: "), "{error}");
+    assert!(error.ends_with("a.md"), "{error}");
+
+    let error = apply_includes("{{ include \"post.md\" }}\n", &post, dir.path()).unwrap_err();
+    assert!(error.starts_with("Include cycle: "), "{error}");
+
+    let error = apply_includes("{{ include \"missing.md\" }}\n", &post, dir.path()).unwrap_err();
+    assert!(
+        error.starts_with("Included file missing.md not found"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_apply_includes_stays_in_root() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path().join("content");
+    write(dir.path(), "snippets/secret.md", "Secret\n");
+    write(&root, "snippets/note.md", "Note\n");
+    let post = write(&root, "posts/post.md", "");
+
+    let (output, _) = apply_includes("{{ include \"snippets/note.md\" }}\n", &post, &root).unwrap();
+    assert_eq!(output, "Note\n");
+
+    // the snippets folder above the content folder is never searched
+    let error = apply_includes("{{ include \"snippets/secret.md\" }}\n", &post, &root).unwrap_err();
+    assert!(error.contains("not found"), "{error}");
+
+    for target in [
+        "/etc/passwd",
+        "../snippets/secret.md",
+        "snippets/../../x.md",
+    ] {
+        let markdown = format!("{{{{ include \"{target}\" }}}}\n");
+        let error = apply_includes(&markdown, &post, &root).unwrap_err();
+        assert!(error.contains("without `..`"), "{target}: {error}");
+    }
+}
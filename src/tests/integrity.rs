@@ -0,0 +1,109 @@
+use super::*;
+use crate::content::ContentBuilder;
+use tempfile::TempDir;
+
+#[test]
+fn test_file_sha256() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("abc.txt");
+    fs::write(&path, "abc").unwrap();
+    assert_eq!(
+        file_sha256(&path).unwrap(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_build_manifest_skips_manifest_and_signature() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir(dir.path().join("static")).unwrap();
+    fs::write(dir.path().join("index.html"), "<h1>Home</h1>").unwrap();
+    fs::write(dir.path().join("static/style.css"), "body {}").unwrap();
+    fs::write(dir.path().join("integrity.json"), "{}").unwrap();
+    fs::write(dir.path().join("integrity.json.minisig"), "signature").unwrap();
+
+    let now = DateTime::parse_from_rfc3339("2025-01-10T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    let manifest = build_manifest(
+        dir.path(),
+        &IntegrityConfig::default(),
+        "https://example.com",
+        now,
+    );
+    assert_eq!(manifest.generated_at, "2025-01-10T12:00:00Z");
+    assert_eq!(manifest.algorithm, "sha256");
+    let paths: Vec<&String> = manifest.files.keys().collect();
+    assert_eq!(paths, vec!["index.html", "static/style.css"]);
+    assert_eq!(
+        manifest.files["static/style.css"],
+        file_sha256(&dir.path().join("static/style.css")).unwrap()
+    );
+}
+
+#[test]
+fn test_write_integrity_manifest_with_sign_command() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("index.html"), "<h1>Home</h1>").unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.site.integrity = IntegrityConfig {
+        enabled: true,
+        manifest: "integrity.json".to_string(),
+        sign_command: Some("cp {manifest} {manifest}.sig".to_string()),
+    };
+
+    write_integrity_manifest(&site_data, dir.path()).unwrap();
+    let manifest = fs::read_to_string(dir.path().join("integrity.json")).unwrap();
+    let signature = fs::read_to_string(dir.path().join("integrity.json.sig")).unwrap();
+    assert_eq!(manifest, signature);
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(
+        manifest["files"]["index.html"],
+        file_sha256(&dir.path().join("index.html")).unwrap()
+    );
+    assert_eq!(manifest["files"].as_object().unwrap().len(), 1);
+    assert_eq!(manifest["pages"], serde_json::json!({}));
+}
+
+#[test]
+fn test_write_integrity_manifest_fails_when_signing_fails() {
+    let dir = TempDir::new().unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.site.integrity.sign_command = Some("false {manifest}".to_string());
+
+    let failure = write_integrity_manifest(&site_data, dir.path()).unwrap_err();
+    assert_eq!(failure.kind, ErrorKind::Other);
+    assert!(failure.message.starts_with("Failed to sign "), "{failure}");
+}
+
+#[test]
+fn test_manifest_path_stays_in_output_folder() {
+    let output = Path::new("site");
+    let config = |manifest: &str| IntegrityConfig {
+        manifest: manifest.to_string(),
+        ..IntegrityConfig::default()
+    };
+    assert_eq!(
+        manifest_path(output, &config("/meta/integrity.json")).unwrap(),
+        output.join("meta/integrity.json")
+    );
+    for manifest in ["../integrity.json", "meta/../../integrity.json", ""] {
+        let failure = manifest_path(output, &config(manifest)).unwrap_err();
+        assert_eq!(failure.kind, ErrorKind::Config, "{manifest}");
+    }
+}
+
+#[test]
+fn test_page_hashes() {
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("abc".to_string())
+            .html("abc".to_string())
+            .build(),
+    );
+    assert_eq!(
+        page_hashes(&site_data)["abc.html"],
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
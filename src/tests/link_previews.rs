@@ -0,0 +1,55 @@
+use super::*;
+use crate::content::ContentBuilder;
+
+fn previews() -> BTreeMap<String, LinkPreview> {
+    let mut about = ContentBuilder::new()
+        .title("About \"me\"".to_string())
+        .slug("about".to_string())
+        .description("Who I am".to_string())
+        .build();
+    about.card_image = Some("media/me.png".to_string());
+    let hello = ContentBuilder::new()
+        .title("Hello".to_string())
+        .slug("hello".to_string())
+        .build();
+    let mut draft = ContentBuilder::new()
+        .title("Draft".to_string())
+        .slug("draft".to_string())
+        .build();
+    draft.stream = Some("draft".to_string());
+    collect_previews([about, hello, draft].iter())
+}
+
+#[test]
+fn test_collect_previews() {
+    let previews = previews();
+    assert_eq!(previews.keys().collect::<Vec<_>>(), vec!["about", "hello"]);
+    assert_eq!(
+        previews["about"],
+        LinkPreview {
+            title: "About \"me\"".to_string(),
+            description: Some("Who I am".to_string()),
+            image: Some("media/me.png".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_add_preview_attributes() {
+    let html = concat!(
+        r#"<p><a href="about.html#bio">bio</a>, "#,
+        r#"<a class="x" href="./hello.html">hi</a>, "#,
+        r#"<a href="missing.html">404</a>, "#,
+        r#"<a href="https://example.com/about.html">ext</a></p>"#
+    );
+    assert_eq!(
+        add_preview_attributes(html, &previews()),
+        concat!(
+            r#"<p><a href="about.html#bio" data-preview-title="About &quot;me&quot;" "#,
+            r#"data-preview-description="Who I am" data-preview-image="media/me.png">bio</a>, "#,
+            r#"<a class="x" href="./hello.html" data-preview-title="Hello">hi</a>, "#,
+            r#"<a href="missing.html">404</a>, "#,
+            r#"<a href="https://example.com/about.html">ext</a></p>"#
+        )
+    );
+}
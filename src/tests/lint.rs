@@ -0,0 +1,86 @@
+use super::*;
+
+fn config(style: TitleCase) -> LintConfig {
+    LintConfig {
+        title_case: Some(style),
+        proper_nouns: vec!["Rust".to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_recase_title() {
+    let nouns = vec![];
+    assert_eq!(
+        recase("how to build a site with marmite", TitleCase::Title, &nouns),
+        "How to Build a Site With Marmite"
+    );
+    assert_eq!(
+        recase("the end: a story to think of", TitleCase::Title, &nouns),
+        "The End: A Story to Think Of"
+    );
+    assert_eq!(
+        recase("using `cargo` on GitHub and HTML", TitleCase::Title, &nouns),
+        "Using `cargo` on GitHub and HTML"
+    );
+}
+
+#[test]
+fn test_recase_sentence() {
+    let nouns = vec!["Rust".to_string()];
+    assert_eq!(
+        recase("Getting Started With Rust", TitleCase::Sentence, &nouns),
+        "Getting started with Rust"
+    );
+    assert_eq!(
+        recase("Why I Moved to GitHub Pages", TitleCase::Sentence, &nouns),
+        "Why I moved to GitHub pages"
+    );
+}
+
+#[test]
+fn test_lint_source_reports_and_fixes_casing() {
+    let text = "---\ntitle: \"Getting Started\"\n---\n## Next Steps\n```\n# Not A Heading\n```\n";
+    let (issues, fixed) = lint_source(text, &config(TitleCase::Sentence), false);
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].line, 2);
+    assert_eq!(
+        issues[0].message,
+        "expected `Getting started` (sentence case)"
+    );
+    assert_eq!(issues[1].line, 4);
+    assert_eq!(fixed, text);
+
+    let (issues, fixed) = lint_source(text, &config(TitleCase::Sentence), true);
+    assert!(issues.is_empty());
+    assert_eq!(
+        fixed,
+        "---\ntitle: \"Getting started\"\n---\n## Next steps\n```\n# Not A Heading\n```\n"
+    );
+}
+
+#[test]
+fn test_lint_source_heading_depth_and_description() {
+    let config = LintConfig {
+        max_heading_depth: Some(2),
+        description_min_length: Some(10),
+        description_max_length: Some(20),
+        ..Default::default()
+    };
+    let text = "---\ndescription: short\n---\n## Ok\n### Too deep\n";
+    let (issues, _) = lint_source(text, &config, true);
+    let lines: Vec<(usize, &str)> = issues
+        .iter()
+        .map(|i| (i.line, i.message.as_str()))
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            (5, "heading level 3 is deeper than 2"),
+            (2, "description has 5 characters, minimum is 10"),
+        ]
+    );
+
+    let (issues, _) = lint_source("# Title\n", &config, false);
+    assert_eq!(issues[0].message, "missing description");
+}
@@ -0,0 +1,37 @@
+use super::*;
+use chrono::NaiveDate;
+
+fn date() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 2, 3)
+        .unwrap()
+        .and_hms_opt(10, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_base_language() {
+    assert_eq!(base_language("pt-BR"), "pt");
+    assert_eq!(base_language("pt_PT"), "pt");
+    assert_eq!(base_language("en"), "en");
+}
+
+#[test]
+fn test_format_date_localized() {
+    assert_eq!(
+        format_date(&date(), "%A, %-d de %B de %Y", "pt"),
+        "sábado, 3 de fevereiro de 2024"
+    );
+    assert_eq!(format_date(&date(), "%a %d %b", "fr"), "sam. 03 févr.");
+    assert_eq!(
+        format_date(&date(), "%-d. %B %Y", "de-AT"),
+        "3. Februar 2024"
+    );
+    // escaped percent signs are kept
+    assert_eq!(format_date(&date(), "%%B %B", "it"), "%B febbraio");
+}
+
+#[test]
+fn test_format_date_in_english() {
+    assert_eq!(format_date(&date(), "%b %e, %Y", "en"), "Feb  3, 2024");
+    assert_eq!(format_date(&date(), "%B %Y", "xx"), "February 2024");
+}
@@ -5,19 +5,33 @@ fn create_test_cli(overrides: impl FnOnce(&mut cli::Cli)) -> cli::Cli {
         input_folder: PathBuf::from("test"),
         output_folder: None,
         verbose: 0,
+        error_format: "text".to_string(),
         watch: false,
         serve: false,
+        editor: None,
+        only_changed: None,
         bind: "0.0.0.0:8000".to_string(),
         config: "marmite.yaml".to_string(),
         debug: false,
         init_templates: false,
         start_theme: None,
+        new_theme: None,
         set_theme: None,
         generate_config: false,
         init_site: false,
         force: false,
+        install_hooks: false,
+        hook_check: None,
         shortcodes: false,
         show_urls: false,
+        spellcheck: false,
+        freshness: false,
+        orphans: false,
+        stats: false,
+        lint: false,
+        alt_text: false,
+        fix: false,
+        normalize_frontmatter: false,
         create: cli::Create {
             new: None,
             edit: false,
@@ -51,6 +65,12 @@ fn create_test_cli(overrides: impl FnOnce(&mut cli::Cli)) -> cli::Cli {
             publish_urls_json: None,
             enable_shortcodes: None,
             shortcode_pattern: None,
+            drafts: None,
+            future: None,
+            profile: None,
+            jobs: None,
+            low_memory: None,
+            private_key: None,
         },
     };
     overrides(&mut args);
@@ -134,3 +154,14 @@ fn test_run_cli_nonexistent_folder() {
         .to_string()
         .contains("Input folder does not exist"));
 }
+
+#[test]
+fn test_jobs_flag() {
+    use clap::Parser;
+
+    let args = cli::Cli::try_parse_from(["marmite", "example", "-j", "2"]).unwrap();
+    assert_eq!(args.configuration.jobs, Some(2));
+    let args = cli::Cli::try_parse_from(["marmite", "example"]).unwrap();
+    assert_eq!(args.configuration.jobs, None);
+    assert!(cli::Cli::try_parse_from(["marmite", "example", "--jobs", "many"]).is_err());
+}
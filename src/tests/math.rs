@@ -0,0 +1,54 @@
+use super::*;
+
+fn options(mode: MathMode) -> MathOptions {
+    MathOptions {
+        mode,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_render_math_disabled() {
+    let html = r#"<p><span data-math-style="inline">x^2</span></p>"#;
+    assert_eq!(render_math(html, &MathOptions::default()), html);
+}
+
+#[test]
+fn test_render_math_client() {
+    let html = concat!(
+        r#"<p>Where <span data-math-style="inline">a &lt; b</span> holds:</p>"#,
+        r#"<p><span data-math-style="display">\sum_{i=0}^n i</span></p>"#
+    );
+    assert_eq!(
+        render_math(html, &options(MathMode::Client)),
+        concat!(
+            r#"<p>Where <span class="math math-inline">\(a &lt; b\)</span> holds:</p>"#,
+            r#"<p><span class="math math-display">\[\sum_{i=0}^n i\]</span></p>"#
+        )
+    );
+}
+
+#[test]
+fn test_render_math_server() {
+    let html = r#"<span data-math-style="inline">&lt;mi&gt;x&lt;/mi&gt;</span>"#;
+    let math = MathOptions {
+        mode: MathMode::Server,
+        inline_command: "cat".to_string(),
+        display_command: "cat".to_string(),
+    };
+    assert_eq!(render_math(html, &math), "<mi>x</mi>");
+}
+
+#[test]
+fn test_render_math_server_falls_back_to_client() {
+    let html = r#"<span data-math-style="display">x^2</span>"#;
+    let math = MathOptions {
+        mode: MathMode::Server,
+        inline_command: "marmite-math-renderer-that-does-not-exist".to_string(),
+        display_command: "marmite-math-renderer-that-does-not-exist".to_string(),
+    };
+    assert_eq!(
+        render_math(html, &math),
+        r#"<span class="math math-display">\[x^2\]</span>"#
+    );
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+const NOTEBOOK: &str = r###"{
+  "cells": [
+    {"cell_type": "raw", "metadata": {}, "source": ["title: My Notebook\n", "tags: python, data"]},
+    {"cell_type": "markdown", "metadata": {}, "source": ["## Intro\n", "Some text"]},
+    {
+      "cell_type": "code",
+      "metadata": {},
+      "source": ["print('hello')"],
+      "outputs": [{"output_type": "stream", "name": "stdout", "text": ["hello\n"]}]
+    },
+    {
+      "cell_type": "code",
+      "metadata": {},
+      "source": "plot()",
+      "outputs": [
+        {"output_type": "display_data", "data": {"image/png": "iVBORw0\nKGgo=\n", "text/plain": "<Figure>"}},
+        {"output_type": "execute_result", "data": {"text/html": ["<table></table>"]}},
+        {"output_type": "error", "ename": "ValueError", "evalue": "bad value"}
+      ]
+    }
+  ],
+  "metadata": {"language_info": {"name": "python"}},
+  "nbformat": 4,
+  "nbformat_minor": 5
+}"###;
+
+#[test]
+fn test_notebook_to_markdown() {
+    let markdown = notebook_to_markdown(NOTEBOOK).unwrap();
+    assert!(markdown.starts_with("---\ntitle: My Notebook\ntags: python, data\n---\n\n"));
+    assert!(markdown.contains("## Intro\nSome text\n\n"));
+    assert!(markdown.contains("```python\nprint('hello')\n```\n\n```text\nhello\n```"));
+    assert!(markdown.contains("![output](data:image/png;base64,iVBORw0KGgo=)"));
+    assert!(markdown.contains("<div class=\"notebook-output\"><table></table></div>"));
+    assert!(markdown.contains("```text\nValueError: bad value\n```"));
+}
+
+#[test]
+fn test_notebook_to_markdown_invalid() {
+    assert!(notebook_to_markdown("not json").is_err());
+    assert!(notebook_to_markdown("{}").is_err());
+}
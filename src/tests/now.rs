@@ -0,0 +1,56 @@
+use super::*;
+use chrono::NaiveDate;
+use tempfile::TempDir;
+
+fn write_statuses(text: &str) -> (TempDir, std::path::PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("status.yaml");
+    fs::write(&path, text).unwrap();
+    (dir, path)
+}
+
+#[test]
+fn test_load_statuses() {
+    let (_dir, path) = write_statuses(
+        "- date: 2025-01-01 10:30\n  text: Reading **Dune**\n\
+         - date: 2025-02-10\n  text: |\n    Moving to Lisbon\n    more soon\n\
+         - date: last week\n  text: Skipped\n",
+    );
+    let statuses = load_statuses(&path, &ParserOptions::default());
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[0].text, "Moving to Lisbon\nmore soon");
+    assert_eq!(
+        statuses[0].date,
+        NaiveDate::from_ymd_opt(2025, 2, 10)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    );
+    assert_eq!(statuses[1].id, "status-20250101103000");
+    assert!(statuses[1].html.contains("<strong>Dune</strong>"));
+}
+
+#[test]
+fn test_load_statuses_missing_or_invalid() {
+    let dir = TempDir::new().unwrap();
+    let missing = load_statuses(&dir.path().join("status.yaml"), &ParserOptions::default());
+    assert!(missing.is_empty());
+
+    let (_dir, path) = write_statuses("not: a list\n");
+    assert!(load_statuses(&path, &ParserOptions::default()).is_empty());
+}
+
+#[test]
+fn test_status_items() {
+    let (_dir, path) = write_statuses(&format!(
+        "- date: 2025-01-01\n  text: Short\n- date: 2025-01-02\n  text: {}\n",
+        "a".repeat(100)
+    ));
+    let statuses = load_statuses(&path, &ParserOptions::default());
+    let items = status_items(&statuses, "now");
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].title, format!("{}…", "a".repeat(80)));
+    assert_eq!(items[1].title, "Short");
+    assert_eq!(items[1].link, "now.html");
+    assert_eq!(items[1].target, "status-20250101000000");
+}
@@ -0,0 +1,72 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+use std::path::Path;
+
+fn page(slug: &str) -> Content {
+    ContentBuilder::new()
+        .title(slug.to_uppercase())
+        .slug(slug.to_string())
+        .build()
+}
+
+#[test]
+fn test_link_slug() {
+    assert_eq!(link_slug("about.html", ""), "about");
+    assert_eq!(link_slug("./about.html#team", ""), "about");
+    assert_eq!(link_slug("/about.html", ""), "about");
+    assert_eq!(
+        link_slug("https://example.com/about.html", "https://example.com/"),
+        "about"
+    );
+}
+
+#[test]
+fn test_collect_orphans() {
+    let mut site_data = Data::new(
+        "menu:\n  - [About, about.html]\n",
+        Path::new("marmite.yaml"),
+    );
+    let post = ContentBuilder::new()
+        .title("Post".to_string())
+        .slug("post".to_string())
+        .date(
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .build();
+    let mut linked = page("linked");
+    linked.back_links = vec![post.clone()];
+    let mut tagged = page("tagged");
+    tagged.tags = vec!["rust".to_string()];
+    let mut forgotten = page("forgotten");
+    forgotten.source_path = Some("content/forgotten.md".into());
+    let mut draft = page("draft");
+    draft.stream = Some("draft".to_string());
+    site_data.posts = vec![post];
+    site_data.pages = vec![
+        page("about"),
+        linked,
+        tagged,
+        page("in-sidebar"),
+        page("alone"),
+        forgotten,
+        draft,
+    ];
+
+    let orphans = collect_orphans(&site_data, &HashSet::from(["in-sidebar".to_string()]));
+    let slugs: Vec<&str> = orphans.iter().map(|o| o.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["alone", "forgotten"]);
+    assert_eq!(
+        orphans[1].source_path.as_deref(),
+        Some("content/forgotten.md")
+    );
+
+    let table = format_table(&orphans);
+    assert_eq!(
+        table,
+        "Slug       Title\nalone      ALONE\nforgotten  FORGOTTEN\n"
+    );
+}
@@ -200,6 +200,37 @@ fn test_get_html_with_task_list() {
     let markdown = "- [x] Task 1\n- [ ] Task 2";
     let expected = "<ul>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> Task 1</li>\n<li><input type=\"checkbox\" disabled=\"\" /> Task 2</li>\n</ul>\n";
     assert_eq!(get_html(markdown), expected);
+    assert_eq!(task_counts(expected), (1, 2));
+}
+
+#[test]
+fn test_get_html_with_interactive_task_list() {
+    let mut options = ParserOptions::default();
+    options.render.tasklist_classes = true;
+    options.render.tasklist_interactive = true;
+    let html = get_html_with_options(
+        "- [x] Task 1\n- [ ] Task 2\n\n<input type=\"checkbox\" />",
+        &options,
+    );
+    assert_eq!(
+        html,
+        "<ul class=\"contains-task-list\">\n<li class=\"task-list-item\"><input type=\"checkbox\" class=\"task-list-item-checkbox\" checked=\"\" data-task=\"0\" /> Task 1</li>\n<li class=\"task-list-item\"><input type=\"checkbox\" class=\"task-list-item-checkbox\" data-task=\"1\" /> Task 2</li>\n</ul>\n<input type=\"checkbox\" />\n"
+    );
+    // checkboxes outside of the task lists are not counted
+    assert_eq!(task_counts(&html), (1, 2));
+    assert_eq!(task_counts("<p>No tasks</p>"), (0, 0));
+}
+
+#[test]
+fn test_get_html_with_description_list() {
+    let markdown = "SSG\n: Static site generator\n: Builds html ahead of time\n\n**Frontmatter**\n: Metadata of the content";
+    let expected = "<dl>\n<dt>SSG</dt>\n<dd>Static site generator</dd>\n<dd>Builds html ahead of time</dd>\n<dt><strong>Frontmatter</strong></dt>\n<dd>Metadata of the content</dd>\n</dl>\n";
+    assert_eq!(get_html(markdown), expected);
+
+    // a blank line after the term renders the details as paragraphs
+    let markdown = "SSG\n\n: Static site generator\n\n  Builds html ahead of time";
+    let expected = "<dl>\n<dt>SSG</dt>\n<dd>\n<p>Static site generator</p>\n<p>Builds html ahead of time</p>\n</dd>\n</dl>\n";
+    assert_eq!(get_html(markdown), expected);
 }
 
 #[test]
@@ -257,6 +288,44 @@ fn test_get_table_of_contents_from_html_with_mixed_content() {
     assert_eq!(get_table_of_contents_from_html(html), expected);
 }
 
+#[test]
+fn test_get_toc_entries_from_html() {
+    let html = r##"
+        <h2><a href="#install" aria-hidden="true" class="anchor" id="install"></a>Install</h2>
+        <h3><a href="#linux" aria-hidden="true" class="anchor" id="linux"></a>Linux</h3>
+        <h4>Arch Linux</h4>
+        <h3><a href="#macos" aria-hidden="true" class="anchor" id="macos"></a>macOS</h3>
+        <h2><a href="#usage" aria-hidden="true" class="anchor" id="usage"></a>Usage</h2>
+    "##;
+    let entry = |level, id: &str, title: &str, children| TocEntry {
+        level,
+        id: id.to_string(),
+        title: title.to_string(),
+        children,
+    };
+    assert_eq!(
+        get_toc_entries_from_html(html),
+        vec![
+            entry(
+                2,
+                "install",
+                "Install",
+                vec![
+                    entry(
+                        3,
+                        "linux",
+                        "Linux",
+                        vec![entry(4, "arch-linux", "Arch Linux", vec![])]
+                    ),
+                    entry(3, "macos", "macOS", vec![]),
+                ]
+            ),
+            entry(2, "usage", "Usage", vec![]),
+        ]
+    );
+    assert!(get_toc_entries_from_html("<p>No headers here</p>").is_empty());
+}
+
 // Helper function to create test site data
 fn create_test_site_data() -> Data {
     let mut posts = Vec::new();
@@ -287,11 +356,24 @@ fn create_test_site_data() -> Data {
         author: GroupedContent::new(Kind::Author),
         stream: GroupedContent::new(Kind::Stream),
         series: GroupedContent::new(Kind::Series),
+        section: GroupedContent::new(Kind::Section),
+        taxonomies: HashMap::new(),
         latest_timestamp: None,
         config_path: "".to_string(),
         force_render: false,
         generated_urls: crate::site::UrlCollection::default(),
         galleries: HashMap::new(),
+        glossary: Vec::new(),
+        statuses: Vec::new(),
+        uses: Vec::new(),
+        resume: None,
+        projects: Vec::new(),
+        archive_intros: HashMap::new(),
+        expired: Vec::new(),
+        photos: Vec::new(),
+        content_ids: std::collections::BTreeMap::new(),
+        bookmarks_cache: std::collections::BTreeMap::new(),
+        preview_sources: None,
     }
 }
 
@@ -369,3 +451,198 @@ fn test_decode_html_entities() {
         "Mixed & 'entities' <here>"
     );
 }
+
+#[test]
+fn test_process_color_scheme_images() {
+    let html = r#"<p><img src="media/shot.png#light" alt="Shot" /><img src="media/shot-dark.png#dark" alt="Shot" /><img src="media/other.png#section" alt="Other" /></p>"#;
+    let result = process_color_scheme_images(html);
+    assert!(
+        result.contains(r#"<img src="media/shot.png" class="color-scheme-light" alt="Shot" />"#)
+    );
+    assert!(result
+        .contains(r#"<img src="media/shot-dark.png" class="color-scheme-dark" alt="Shot" />"#));
+    assert!(result.contains(r#"<img src="media/other.png#section" alt="Other" />"#));
+}
+
+#[test]
+fn test_get_html_with_color_scheme_images() {
+    let markdown = "![Screenshot](media/shot.png#dark)";
+    let html = get_html_with_options(markdown, &ParserOptions::default());
+    assert!(html.contains(r#"src="media/shot.png" class="color-scheme-dark""#));
+}
+
+#[test]
+fn test_get_html_with_smart_punctuation() {
+    let markdown = "\"Quoted\" -- it's done... `\"code\" --`\n\n```\n\"block\"...\n```\n";
+    let mut options = ParserOptions::default();
+    assert!(get_html_with_options(markdown, &options).contains("&quot;Quoted&quot; -- it's"));
+
+    options.parse.smart = true;
+    let html = get_html_with_options(markdown, &options);
+    assert!(html.contains("“Quoted” – it’s done…"));
+    assert!(html.contains("<code>&quot;code&quot; --</code>"));
+    assert!(html.contains("&quot;block&quot;..."));
+}
+
+#[test]
+fn test_process_media_images() {
+    let html = r#"<p><img src="media/clip.MP4" alt="My clip" /> <img src="media/episode.mp3" alt="" /> <img src="media/photo.jpg" alt="Photo" /></p>"#;
+    let result = process_media_images(html);
+    assert!(result.contains(
+        r#"<video src="media/clip.MP4" controls preload="metadata" aria-label="My clip"></video>"#
+    ));
+    assert!(
+        result.contains(r#"<audio src="media/episode.mp3" controls preload="metadata"></audio>"#)
+    );
+    assert!(result.contains(r#"<img src="media/photo.jpg" alt="Photo" />"#));
+}
+
+#[test]
+fn test_footnote_definitions() {
+    let markdown = "Intro[^1] and more[^note].\n\n<!-- more -->\n\nBody\n\n[^1]: First source\n[^note]: A long note\n    continued here\n\nNot a footnote\n";
+    assert_eq!(
+        footnote_definitions(markdown),
+        "[^1]: First source\n[^note]: A long note\n    continued here\n\n"
+    );
+    assert_eq!(footnote_definitions("No footnotes"), "");
+}
+
+#[test]
+fn test_strip_footnotes() {
+    let html = concat!(
+        r##"<p>Intro<sup class="footnote-ref"><a href="#fn-1" id="fnref-1" data-footnote-ref>1</a></sup>.</p>"##,
+        "\n",
+        r##"<section class="footnotes" data-footnotes>
+<ol>
+<li id="fn-1">
+<p>First source <a href="#fnref-1" class="footnote-backref" data-footnote-backref>↩</a></p>
+</li>
+</ol>
+</section>"##
+    );
+    assert_eq!(strip_footnotes(html), "<p>Intro.</p>");
+}
+
+#[test]
+fn test_get_html_with_footnotes() {
+    let html = get_html_with_options(
+        "Claim[^1].\n\n[^1]: Source",
+        &crate::config::ParserOptions::default(),
+    );
+    assert!(html.contains("footnote-ref"));
+    assert!(html.contains(r#"<section class="footnotes""#));
+    assert!(html.contains("footnote-backref"));
+    assert!(!html.contains("[^1]"));
+}
+
+#[test]
+fn test_heading_anchors() {
+    let html = r##"<h2><a href="#done" aria-hidden="true" class="anchor" id="done"></a>Done</h2>
+<h2>Raw <em>Heading</em></h2>
+<h3 id="custom">Custom</h3>"##;
+    let result = heading_anchors(html, &HeadingAnchorsConfig::default());
+    assert!(result.contains(
+        r##"<h2><a href="#done" aria-hidden="true" class="anchor" id="done"></a>Done</h2>"##
+    ));
+    assert!(result.contains(
+        r##"<h2><a href="#raw-heading" aria-hidden="true" class="anchor" id="raw-heading"></a>Raw <em>Heading</em></h2>"##
+    ));
+    assert!(result.contains(
+        r##"<h3 id="custom"><a href="#custom" aria-hidden="true" class="anchor"></a>Custom</h3>"##
+    ));
+    // running again keeps the same html
+    assert_eq!(
+        heading_anchors(&result, &HeadingAnchorsConfig::default()),
+        result
+    );
+}
+
+#[test]
+fn test_heading_anchors_slugify_and_repeated_ids() {
+    let html = get_html("## Q&A_Session\n\n## Notes\n\n## Notes\n");
+    let config = HeadingAnchorsConfig {
+        link: false,
+        ..Default::default()
+    };
+    let result = heading_anchors(&html, &config);
+    assert!(result.contains(r#"<h2 id="q-a-session">Q&amp;A_Session</h2>"#));
+    assert!(result.contains(r#"<h2 id="notes">Notes</h2>"#));
+    assert!(result.contains(r#"<h2 id="notes-1">Notes</h2>"#));
+    // the fragment of internal links resolves to the same id
+    assert_eq!(
+        fix_internal_links(r#"<a href="post.md#Q&A_Session">x</a>"#),
+        r#"<a href="post.html#q-a-session">x</a>"#
+    );
+}
+
+#[test]
+fn test_heading_anchors_symbol_after_and_toc() {
+    let config = HeadingAnchorsConfig {
+        symbol: "#".to_string(),
+        position: AnchorPosition::After,
+        ..Default::default()
+    };
+    let result = heading_anchors(&get_html("## Install\n\n### Linux\n"), &config);
+    assert!(result.contains(
+        r##"<h2>Install <a href="#install" aria-hidden="true" class="anchor" id="install">#</a></h2>"##
+    ));
+    let toc = get_toc_entries_from_html(&result);
+    assert_eq!(toc[0].id, "install");
+    assert_eq!(toc[0].title, "Install");
+    assert_eq!(toc[0].children[0].id, "linux");
+    assert_eq!(toc[0].children[0].title, "Linux");
+}
+
+#[test]
+fn test_resolve_wikilinks() {
+    use crate::content::ContentBuilder;
+
+    let contents = vec![
+        ContentBuilder::new()
+            .title("Zettel Index".to_string())
+            .slug("zettel-index".to_string())
+            .source_path(std::path::PathBuf::from("content/202401011200 Index.md"))
+            .build(),
+        ContentBuilder::new()
+            .title("Reading List".to_string())
+            .slug("books".to_string())
+            .build(),
+    ];
+    let targets = wikilink_targets(&contents);
+    // hrefs as left by `fix_internal_links`
+    let html = r#"<p><a href="reading-list.html" data-wikilink="true">Reading List</a>
+<a href="books.html#to-read" data-wikilink="true">my list</a>
+<a href="202401011200-index.html" data-wikilink="true">index</a>
+<a href="tags.html" data-wikilink="true">tags</a></p>"#;
+    let (result, links) = resolve_wikilinks(html, &targets);
+    assert!(result.contains(
+        r#"<a href="books.html" data-wikilink="true" data-wikilink-resolved>Reading List</a>"#
+    ));
+    assert!(result.contains(
+        r#"<a href="books.html#to-read" data-wikilink="true" data-wikilink-resolved>my list</a>"#
+    ));
+    assert!(result.contains(
+        r#"<a href="zettel-index.html" data-wikilink="true" data-wikilink-resolved>index</a>"#
+    ));
+    assert!(result.contains(r#"<a href="tags.html" data-wikilink="true">tags</a>"#));
+    assert_eq!(
+        links,
+        vec![
+            ("reading-list".to_string(), "books".to_string()),
+            ("books".to_string(), "books".to_string()),
+            ("202401011200-index".to_string(), "zettel-index".to_string()),
+        ]
+    );
+
+    // resolved links are not changed again when the page is rendered
+    let site_data = create_test_site_data();
+    assert_eq!(fix_wikilinks(&result, &site_data), result);
+}
+
+#[test]
+fn test_fix_internal_links_decodes_wikilinks() {
+    let html = r#"<a href="Zettel%20Index#Open%20Questions" data-wikilink="true">Zettel Index</a>"#;
+    let expected =
+        r#"<a href="zettel-index.html#open-questions" data-wikilink="true">Zettel Index</a>"#;
+    assert_eq!(fix_internal_links(html), expected);
+}
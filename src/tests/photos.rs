@@ -0,0 +1,282 @@
+use super::*;
+use chrono::NaiveDate;
+use tempfile::TempDir;
+
+/// Minimal JPEG with an EXIF segment holding the ASCII `tags` of IFD0 and the
+/// `DateTimeOriginal` of the EXIF IFD
+fn exif_jpeg(little_endian: bool, tags: &[(u16, &str)], original: Option<&str>) -> Vec<u8> {
+    let u16_bytes = |value: u16| {
+        if little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    };
+    let u32_bytes = |value: u32| {
+        if little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    };
+    let ifd = |entries: &[(u16, u16, Vec<u8>)], start: usize| {
+        let mut ifd: Vec<u8> = Vec::new();
+        let mut data: Vec<u8> = Vec::new();
+        let data_start = start + 2 + entries.len() * 12 + 4;
+        ifd.extend(u16_bytes(entries.len() as u16));
+        for (tag, kind, value) in entries {
+            ifd.extend(u16_bytes(*tag));
+            ifd.extend(u16_bytes(*kind));
+            if *kind == TYPE_LONG {
+                ifd.extend(u32_bytes(1));
+                ifd.extend(value);
+            } else if value.len() <= 4 {
+                ifd.extend(u32_bytes(value.len() as u32));
+                let mut inline = value.clone();
+                inline.resize(4, 0);
+                ifd.extend(inline);
+            } else {
+                ifd.extend(u32_bytes(value.len() as u32));
+                ifd.extend(u32_bytes((data_start + data.len()) as u32));
+                data.extend(value);
+            }
+        }
+        ifd.extend(u32_bytes(0));
+        ifd.extend(data);
+        ifd
+    };
+    let ascii = |text: &str| format!("{text}\0").into_bytes();
+
+    let mut entries: Vec<(u16, u16, Vec<u8>)> = tags
+        .iter()
+        .map(|(tag, text)| (*tag, TYPE_ASCII, ascii(text)))
+        .collect();
+    let ifd0_size = 2 + (entries.len() + usize::from(original.is_some())) * 12 + 4;
+    let data_size: usize = entries
+        .iter()
+        .map(|(_, _, value)| value.len())
+        .filter(|len| *len > 4)
+        .sum();
+    let exif_ifd_offset = 8 + ifd0_size + data_size;
+    if original.is_some() {
+        entries.push((
+            TAG_EXIF_IFD,
+            TYPE_LONG,
+            u32_bytes(exif_ifd_offset as u32).to_vec(),
+        ));
+    }
+
+    let mut tiff = if little_endian {
+        b"II".to_vec()
+    } else {
+        b"MM".to_vec()
+    };
+    tiff.extend(u16_bytes(42));
+    tiff.extend(u32_bytes(8));
+    tiff.extend(ifd(&entries, 8));
+    if let Some(original) = original {
+        tiff.extend(ifd(
+            &[(TAG_DATE_TIME_ORIGINAL, TYPE_ASCII, ascii(original))],
+            exif_ifd_offset,
+        ));
+    }
+
+    let mut segment = b"Exif\0\0".to_vec();
+    segment.extend(tiff);
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00];
+    jpeg.extend([0xFF, 0xE1]);
+    jpeg.extend(((segment.len() + 2) as u16).to_be_bytes());
+    jpeg.extend(segment);
+    jpeg.extend([0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]);
+    jpeg
+}
+
+#[test]
+fn test_read_exif() {
+    for little_endian in [true, false] {
+        let jpeg = exif_jpeg(
+            little_endian,
+            &[
+                (TAG_IMAGE_DESCRIPTION, "Sunset at the beach"),
+                (TAG_MAKE, "FUJIFILM"),
+                (TAG_MODEL, "X-T3"),
+                (TAG_DATE_TIME, "2024:08:02 20:00:00"),
+            ],
+            Some("2024:08:01 19:30:15"),
+        );
+        let exif = read_exif(&jpeg).unwrap();
+        assert_eq!(
+            exif.date,
+            NaiveDate::from_ymd_opt(2024, 8, 1)
+                .unwrap()
+                .and_hms_opt(19, 30, 15)
+        );
+        assert_eq!(exif.camera.as_deref(), Some("FUJIFILM X-T3"));
+        assert_eq!(exif.caption.as_deref(), Some("Sunset at the beach"));
+    }
+}
+
+#[test]
+fn test_read_exif_fallbacks() {
+    // the model already names the make, the date comes from IFD0
+    let jpeg = exif_jpeg(
+        true,
+        &[
+            (TAG_MAKE, "Canon"),
+            (TAG_MODEL, "Canon EOS R6"),
+            (TAG_DATE_TIME, "2023:01:02 03:04:05"),
+        ],
+        None,
+    );
+    let exif = read_exif(&jpeg).unwrap();
+    assert_eq!(exif.camera.as_deref(), Some("Canon EOS R6"));
+    assert_eq!(
+        exif.date,
+        NaiveDate::from_ymd_opt(2023, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+    );
+    assert_eq!(exif.caption, None);
+
+    assert_eq!(read_exif(b"not an image"), None);
+    assert_eq!(read_exif(&[0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02]), None);
+}
+
+fn site(albums: bool) -> Marmite {
+    let mut site = Marmite::new();
+    site.photos.enabled = true;
+    site.photos.albums = albums;
+    site
+}
+
+fn write_photos(dir: &TempDir) {
+    let photos = dir.path().join("photos");
+    fs::create_dir_all(photos.join("italy")).unwrap();
+    let beach = exif_jpeg(
+        true,
+        &[(TAG_IMAGE_DESCRIPTION, "Beach")],
+        Some("2024:08:01 10:00:00"),
+    );
+    let rome = exif_jpeg(true, &[(TAG_MODEL, "X100V")], Some("2024:05:01 10:00:00"));
+    let venice = exif_jpeg(true, &[], Some("2024:05:03 10:00:00"));
+    fs::write(photos.join("beach.jpg"), beach).unwrap();
+    fs::write(photos.join("italy/rome.jpg"), rome).unwrap();
+    fs::write(photos.join("italy/venice.jpg"), venice).unwrap();
+}
+
+#[test]
+fn test_collect_photos() {
+    let dir = TempDir::new().unwrap();
+    write_photos(&dir);
+    let photos = collect_photos(dir.path(), &site(false));
+    let images: Vec<&str> = photos.iter().map(|photo| photo.image.as_str()).collect();
+    assert_eq!(
+        images,
+        vec![
+            "media/photos/beach.jpg",
+            "media/photos/italy/venice.jpg",
+            "media/photos/italy/rome.jpg",
+        ]
+    );
+    assert_eq!(photos[0].title, "Beach");
+    assert_eq!(photos[0].slug, "photos-beach");
+    assert_eq!(photos[2].title, "rome");
+    assert_eq!(photos[2].album.as_deref(), Some("italy"));
+    assert_eq!(photos[2].camera.as_deref(), Some("X100V"));
+    assert_eq!(photos[2].slug, "photos-italy-rome");
+
+    let contents = photo_contents(&photos, &site(false).photos);
+    assert_eq!(contents.len(), 3);
+    assert!(contents
+        .iter()
+        .all(|content| content.stream.as_deref() == Some("photos")));
+}
+
+#[test]
+fn test_photo_contents_per_album() {
+    let dir = TempDir::new().unwrap();
+    write_photos(&dir);
+    let site = site(true);
+    let photos = collect_photos(dir.path(), &site);
+    let contents = photo_contents(&photos, &site.photos);
+    assert_eq!(contents.len(), 2);
+
+    let album = contents
+        .iter()
+        .find(|content| content.slug == "photos-italy")
+        .unwrap();
+    assert_eq!(album.title, "italy");
+    assert_eq!(album.photos.len(), 2);
+    assert_eq!(
+        album.date,
+        NaiveDate::from_ymd_opt(2024, 5, 3)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+    );
+    assert_eq!(album.html.matches("<figure").count(), 2);
+
+    let beach = contents
+        .iter()
+        .find(|content| content.slug == "photos-beach")
+        .unwrap();
+    assert_eq!(beach.description.as_deref(), Some("Beach"));
+    assert_eq!(beach.card_image.as_deref(), Some("media/photos/beach.jpg"));
+}
+
+#[test]
+fn test_photo_slugs_keep_clear_of_the_wall() {
+    let dir = TempDir::new().unwrap();
+    let photos_dir = dir.path().join("photos");
+    fs::create_dir_all(&photos_dir).unwrap();
+    fs::write(photos_dir.join("wall.jpg"), exif_jpeg(true, &[], None)).unwrap();
+    let photos = collect_photos(dir.path(), &site(false));
+    assert_eq!(photos[0].slug, "photos-wall-photo");
+}
+
+#[test]
+fn test_figure_escapes_the_image_url() {
+    let photo = Photo {
+        image: "media/photos/a\"onerror=\"x.jpg".to_string(),
+        title: "A".to_string(),
+        ..Default::default()
+    };
+    let html = figure(&photo);
+    assert!(html.contains(r#"src="media/photos/a&quot;onerror=&quot;x.jpg""#));
+}
+
+#[test]
+fn test_update_photo_slugs() {
+    let dir = TempDir::new().unwrap();
+    write_photos(&dir);
+    let mut photos = collect_photos(dir.path(), &site(false));
+    let mut contents = photo_contents(&photos, &site(false).photos);
+    let beach = contents
+        .iter_mut()
+        .find(|content| content.slug == "photos-beach")
+        .unwrap();
+    beach.slug = "photos-beach-1".to_string();
+
+    update_photo_slugs(&mut photos, &mut contents);
+    assert_eq!(photos[0].slug, "photos-beach-1");
+    let beach = contents
+        .iter()
+        .find(|content| content.slug == "photos-beach-1")
+        .unwrap();
+    assert_eq!(beach.photos[0].slug, "photos-beach-1");
+    assert_eq!(photos[1].slug, "photos-italy-venice");
+}
+
+#[test]
+fn test_read_head_low_memory() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("large.jpg");
+    let mut jpeg = exif_jpeg(true, &[(TAG_MODEL, "X100V")], None);
+    jpeg.resize(LOW_MEMORY_EXIF_BYTES as usize * 2, 0);
+    fs::write(&path, &jpeg).unwrap();
+
+    assert_eq!(read_head(&path, false).unwrap().len(), jpeg.len());
+    let head = read_head(&path, true).unwrap();
+    assert_eq!(head.len(), LOW_MEMORY_EXIF_BYTES as usize);
+    assert_eq!(read_exif(&head).unwrap().camera.as_deref(), Some("X100V"));
+    assert_eq!(read_head(&dir.path().join("missing.jpg"), true), None);
+}
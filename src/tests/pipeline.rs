@@ -0,0 +1,88 @@
+use super::*;
+
+fn step(name: &str) -> PipelineStep {
+    PipelineStep {
+        step: name.to_string(),
+        enabled: true,
+        command: None,
+    }
+}
+
+#[test]
+fn test_external_links() {
+    let html = r#"<p><a href="https://example.org/post">out</a> <a href="https://www.mysite.com/about.html">in</a> <a href="about.html">local</a> <a href="//cdn.example.org/x" rel="me">cdn</a></p>"#;
+    let result = external_links(html, "www.mysite.com", &ExternalLinksConfig::default());
+    assert!(result.contains(
+        r#"<a href="https://example.org/post" target="_blank" rel="noopener noreferrer">out</a>"#
+    ));
+    assert!(result.contains(r#"<a href="https://www.mysite.com/about.html">in</a>"#));
+    assert!(result.contains(r#"<a href="about.html">local</a>"#));
+    assert!(result.contains(r#"<a href="//cdn.example.org/x" rel="me" target="_blank">cdn</a>"#));
+}
+
+#[test]
+fn test_external_links_config() {
+    let html = r#"<a href="https://example.org/">out</a> <a href="/about.html">in</a>"#;
+    let config = ExternalLinksConfig {
+        target: String::new(),
+        rel: "noopener".to_string(),
+        nofollow: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        external_links(html, "https://mysite.com", &config),
+        r#"<a href="https://example.org/" rel="noopener nofollow">out</a> <a href="/about.html">in</a>"#
+    );
+
+    // enabled outside of the pipeline
+    let mut site = Marmite::new();
+    assert_eq!(run_pipeline(html, &site), html);
+    site.external_links.enabled = true;
+    site.html_pipeline = vec![step("external_links")];
+    assert_eq!(
+        run_pipeline(html, &site),
+        r#"<a href="https://example.org/" target="_blank" rel="noopener noreferrer">out</a> <a href="/about.html">in</a>"#
+    );
+}
+
+#[test]
+fn test_lazy_images() {
+    let html = r#"<img src="a.png" alt="a"><img src="b.png" /><img src="c.png" loading="eager">"#;
+    assert_eq!(
+        lazy_images(html),
+        r#"<img src="a.png" alt="a" loading="lazy"><img src="b.png" loading="lazy"/><img src="c.png" loading="eager">"#
+    );
+}
+
+#[test]
+fn test_wrap_tables() {
+    let html = "<table>\n<tr><td>1</td></tr>\n</table>";
+    assert_eq!(
+        wrap_tables(html),
+        "<div class=\"table-wrapper\"><table>\n<tr><td>1</td></tr>\n</table></div>"
+    );
+}
+
+#[test]
+fn test_run_pipeline_order_and_toggles() {
+    let mut site = Marmite::new();
+    let html = r#"<table><tr><td><img src="a.png"></td></tr></table>"#;
+    assert_eq!(run_pipeline(html, &site), html);
+
+    let mut disabled = step("wrap_tables");
+    disabled.enabled = false;
+    site.html_pipeline = vec![step("lazy_images"), disabled, step("unknown")];
+    assert_eq!(
+        run_pipeline(html, &site),
+        r#"<table><tr><td><img src="a.png" loading="lazy"></td></tr></table>"#
+    );
+}
+
+#[test]
+fn test_run_pipeline_command_failure_keeps_html() {
+    let mut site = Marmite::new();
+    let mut command = step("command");
+    command.command = Some("marmite-missing-pipeline-command".to_string());
+    site.html_pipeline = vec![command, step("command")];
+    assert_eq!(run_pipeline("<p>kept</p>", &site), "<p>kept</p>");
+}
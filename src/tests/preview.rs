@@ -0,0 +1,40 @@
+use super::*;
+use crate::content::ContentBuilder;
+use tempfile::TempDir;
+
+#[test]
+fn test_affected_sources_and_preview_urls() {
+    let content_dir = TempDir::new().unwrap();
+    let changed_post = content_dir.path().join("2024-01-01-changed.md");
+    let other_post = content_dir.path().join("2024-01-02-other.md");
+    let template = content_dir.path().join("content.html");
+    for path in [&changed_post, &other_post, &template] {
+        fs::write(path, "# Hello").unwrap();
+    }
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("changed".to_string())
+            .source_path(changed_post.clone())
+            .build(),
+    );
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("other".to_string())
+            .source_path(other_post)
+            .build(),
+    );
+    assert!(preview_urls(&site_data).is_empty());
+
+    let changed: HashSet<PathBuf> = [&changed_post, &template]
+        .iter()
+        .map(|path| fs::canonicalize(path).unwrap())
+        .collect();
+    let sources = affected_sources(&site_data, &changed);
+    assert_eq!(sources.len(), 1);
+    assert!(sources.contains(&fs::canonicalize(&changed_post).unwrap()));
+
+    site_data.preview_sources = Some(sources);
+    assert_eq!(preview_urls(&site_data), vec!["changed.html"]);
+}
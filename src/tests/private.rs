@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+fn test_is_private_source() {
+    let site = Marmite::new();
+    let content_dir = Path::new("/site/content");
+    assert!(is_private_source(
+        Path::new("/site/content/private/plans.md.age"),
+        content_dir,
+        &site
+    ));
+    assert!(!is_private_source(
+        Path::new("/site/content/private/plans.md"),
+        content_dir,
+        &site
+    ));
+    assert!(!is_private_source(
+        Path::new("/site/content/plans.md.age"),
+        content_dir,
+        &site
+    ));
+}
+
+#[test]
+fn test_plain_path() {
+    assert_eq!(
+        plain_path(Path::new("private/2024-01-01-plans.md.age")),
+        PathBuf::from("private/2024-01-01-plans.md")
+    );
+}
+
+#[test]
+fn test_decrypt_missing_file() {
+    let result = decrypt(
+        Path::new("/nonexistent.md.age"),
+        Path::new("/nonexistent.txt"),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_decrypt_rejects_passphrase_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("plans.md.age");
+    std::fs::write(
+        &path,
+        "age-encryption.org/v1\n-> scrypt c2FsdA 18\nYm9keQ\n--- bWFj\n\u{1}binary",
+    )
+    .unwrap();
+    let error = decrypt(&path, Path::new("/nonexistent.txt")).unwrap_err();
+    assert!(error.contains("encrypted with a passphrase"), "{error}");
+}
+
+#[test]
+fn test_is_passphrase_encrypted() {
+    assert!(is_passphrase_encrypted(
+        b"age-encryption.org/v1\n-> scrypt c2FsdA 18\nYm9keQ\n--- bWFj\n"
+    ));
+    assert!(!is_passphrase_encrypted(
+        b"age-encryption.org/v1\n-> X25519 cHVi\nYm9keQ\n--- bWFj\n-> scrypt "
+    ));
+    assert!(!is_passphrase_encrypted(
+        b"-----BEGIN AGE ENCRYPTED FILE-----\n"
+    ));
+}
@@ -0,0 +1,134 @@
+use super::*;
+use chrono::NaiveDate;
+
+fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 6, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_parse_repo() {
+    assert_eq!(
+        parse_repo("github:rochacbruno/marmite"),
+        Some((
+            Forge::GitHub,
+            "rochacbruno".to_string(),
+            "marmite".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_repo("https://codeberg.org/forgejo/forgejo.git"),
+        Some((
+            Forge::Codeberg,
+            "forgejo".to_string(),
+            "forgejo".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_repo("rochacbruno/marmite/"),
+        Some((
+            Forge::GitHub,
+            "rochacbruno".to_string(),
+            "marmite".to_string()
+        ))
+    );
+    assert_eq!(parse_repo("marmite"), None);
+    assert_eq!(parse_repo("gitlab:owner/name"), None);
+}
+
+#[test]
+fn test_apply_metadata() {
+    let github: Value = serde_json::from_str(
+        r#"{"description": "Static site generator", "stargazers_count": 1200, "forks_count": 80,
+            "language": "Rust", "homepage": "", "topics": ["ssg", "blog"],
+            "html_url": "https://github.com/rochacbruno/marmite"}"#,
+    )
+    .unwrap();
+    let mut project = stub_project(
+        "github:rochacbruno/marmite",
+        Forge::GitHub,
+        "rochacbruno",
+        "marmite",
+    );
+    apply_metadata(&mut project, Forge::GitHub, &github);
+    assert_eq!(
+        project.description.as_deref(),
+        Some("Static site generator")
+    );
+    assert_eq!(project.stars, 1200);
+    assert_eq!(project.forks, 80);
+    assert_eq!(project.language.as_deref(), Some("Rust"));
+    assert_eq!(project.homepage, None);
+    assert_eq!(project.topics, vec!["ssg", "blog"]);
+
+    let codeberg: Value =
+        serde_json::from_str(r#"{"stars_count": 42, "website": "https://forgejo.org"}"#).unwrap();
+    let mut project = stub_project(
+        "codeberg:forgejo/forgejo",
+        Forge::Codeberg,
+        "forgejo",
+        "forgejo",
+    );
+    apply_metadata(&mut project, Forge::Codeberg, &codeberg);
+    assert_eq!(project.stars, 42);
+    assert_eq!(project.homepage.as_deref(), Some("https://forgejo.org"));
+    assert_eq!(project.url, "https://codeberg.org/forgejo/forgejo");
+}
+
+#[test]
+fn test_load_projects_from_cache() {
+    let config = ProjectsConfig {
+        repos: vec![
+            "github:rochacbruno/marmite".to_string(),
+            "codeberg:someone/marmite".to_string(),
+            "invalid".to_string(),
+        ],
+        ..Default::default()
+    };
+    let mut cache = BTreeMap::new();
+    let mut cached = stub_project(
+        "github:rochacbruno/marmite",
+        Forge::GitHub,
+        "rochacbruno",
+        "marmite",
+    );
+    cached.stars = 1200;
+    cached.fetched_at = Some(now());
+    cache.insert(cached.repo.clone(), cached);
+
+    let projects = load_projects(&config, &mut cache, false, now());
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0].stars, 1200);
+    // the repository never fetched is listed from its name
+    assert_eq!(projects[1].url, "https://codeberg.org/someone/marmite");
+    assert_eq!(projects[1].fetched_at, None);
+    // both are named `marmite` so the slugs get the owner
+    assert_eq!(projects[0].slug, "rochacbruno-marmite");
+    assert_eq!(projects[1].slug, "someone-marmite");
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_load_projects_out_of_range_refresh() {
+    let config = ProjectsConfig {
+        repos: vec!["github:rochacbruno/marmite".to_string()],
+        refresh_hours: i64::MAX,
+        ..Default::default()
+    };
+    let mut cache = BTreeMap::new();
+    let mut cached = stub_project(
+        "github:rochacbruno/marmite",
+        Forge::GitHub,
+        "rochacbruno",
+        "marmite",
+    );
+    cached.stars = 1200;
+    cached.fetched_at = Some(now());
+    cache.insert(cached.repo.clone(), cached);
+
+    // the cached project is kept instead of panicking or fetching it again
+    let projects = load_projects(&config, &mut cache, true, now());
+    assert_eq!(projects[0].stars, 1200);
+}
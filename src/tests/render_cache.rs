@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn test_cache_key() {
+    assert_eq!(cache_key("# Title", &1), cache_key("# Title", &1));
+    assert_ne!(cache_key("# Title", &1), cache_key("# Title!", &1));
+    assert_ne!(cache_key("# Title", &1), cache_key("# Title", &2));
+}
+
+#[test]
+fn test_render_cache_drops_entries_unused_by_the_previous_build() {
+    let mut cache = RenderCache::default();
+    cache.start_build();
+    cache.insert(1, "<p>one</p>".to_string());
+    cache.insert(2, "<p>two</p>".to_string());
+
+    cache.start_build();
+    assert_eq!(cache.get(1).as_deref(), Some("<p>one</p>"));
+    assert_eq!(cache.hits, 1);
+
+    // `2` was not used by the second build
+    cache.start_build();
+    assert_eq!(cache.get(2), None);
+    assert_eq!(cache.get(1).as_deref(), Some("<p>one</p>"));
+    assert_eq!(cache.hits, 1);
+}
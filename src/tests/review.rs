@@ -0,0 +1,58 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+
+fn post(slug: &str, year: i32, tags: &[&str], words: usize, inbound_links: usize) -> Content {
+    let mut post = ContentBuilder::new()
+        .title(slug.to_uppercase())
+        .slug(slug.to_string())
+        .date(
+            NaiveDate::from_ymd_opt(year, 6, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .tags(tags.iter().map(ToString::to_string).collect())
+        .build();
+    post.word_count = words;
+    post.back_links = vec![Content::default(); inbound_links];
+    post
+}
+
+#[test]
+fn test_collect_reviews() {
+    let posts = vec![
+        post("a", 2024, &["rust", "web"], 100, 2),
+        post("b", 2024, &["rust"], 250, 0),
+        post("c", 2024, &["python", "web"], 50, 5),
+        post("d", 2023, &["rust"], 10, 1),
+        post("e", 2025, &["rust"], 10, 1),
+    ];
+    let reviews = collect_reviews(&posts, 2, 2025, false);
+    let years: Vec<i32> = reviews.iter().map(|r| r.year).collect();
+    assert_eq!(years, vec![2024, 2023]);
+
+    let review = &reviews[0];
+    assert_eq!(review.posts, 3);
+    assert_eq!(review.words, 400);
+    assert_eq!(
+        review.top_tags,
+        vec![("rust".to_string(), 2), ("web".to_string(), 2)]
+    );
+    let linked: Vec<(&str, usize)> = review
+        .most_linked
+        .iter()
+        .map(|p| (p.slug.as_str(), p.inbound_links))
+        .collect();
+    assert_eq!(linked, vec![("c", 5), ("a", 2)]);
+
+    let reviews = collect_reviews(&posts, 2, 2025, true);
+    assert_eq!(reviews[0].year, 2025);
+}
+
+#[test]
+fn test_collect_reviews_skips_drafts() {
+    let mut draft = post("draft", 2024, &["rust"], 100, 0);
+    draft.stream = Some("draft".to_string());
+    assert!(collect_reviews(&[draft], 5, 2025, false).is_empty());
+}
@@ -0,0 +1,51 @@
+use super::*;
+use crate::content::ContentBuilder;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_noai_rules() {
+    let contents = [
+        ContentBuilder::new().slug("public".to_string()).build(),
+        ContentBuilder::new()
+            .slug("private".to_string())
+            .noai(true)
+            .build(),
+    ];
+    assert_eq!(noai_rules(contents[..1].iter(), ""), "");
+
+    let rules = noai_rules(contents.iter(), "/blog");
+    assert!(rules.starts_with(RULES_MARKER));
+    assert!(rules.contains("User-agent: GPTBot\n"));
+    assert!(rules.ends_with("Disallow: /blog/private.html\n"));
+    assert!(!rules.contains("public"));
+}
+
+#[test]
+fn test_write_robots_rules_replaces_previous_rules() {
+    let output = TempDir::new().unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.pages.push(
+        ContentBuilder::new()
+            .slug("private".to_string())
+            .noai(true)
+            .build(),
+    );
+    fs::write(
+        output.path().join("robots.txt"),
+        "User-agent: *\nAllow: /\n",
+    )
+    .unwrap();
+
+    write_robots_rules(&site_data, output.path());
+    write_robots_rules(&site_data, output.path());
+    let robots = fs::read_to_string(output.path().join("robots.txt")).unwrap();
+    assert!(robots.starts_with("User-agent: *\nAllow: /\n\n"));
+    assert_eq!(robots.matches(RULES_MARKER).count(), 1);
+    assert!(robots.contains("Disallow: /private.html"));
+
+    site_data.pages.clear();
+    write_robots_rules(&site_data, output.path());
+    let robots = fs::read_to_string(output.path().join("robots.txt")).unwrap();
+    assert_eq!(robots, "User-agent: *\nAllow: /\n");
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+fn schema() -> FrontmatterSchema {
+    FrontmatterSchema {
+        required: vec!["title".to_string(), "description".to_string()],
+        allowed: HashMap::from([
+            (
+                "stream".to_string(),
+                vec!["index".to_string(), "news".to_string()],
+            ),
+            (
+                "tags".to_string(),
+                vec!["rust".to_string(), "python".to_string()],
+            ),
+        ]),
+    }
+}
+
+#[test]
+fn test_validate_source_valid() {
+    let text = "---\ntitle: Hello\ndescription: A post\nstream: news\ntags: rust, python\ndate: 2024-10-10 10:30\n---\n# Hello\n";
+    assert!(validate_source(text, &schema(), &HashMap::new()).is_empty());
+}
+
+#[test]
+fn test_validate_source_reports_every_violation() {
+    let text = "---\ntitle: Hello\ndate: 10/10/2024\nstream: blog\ntags:\n  - rust\n  - go\nexpires: soon\n---\nBody\n";
+    let violations = validate_source(text, &schema(), &HashMap::new());
+    let found: Vec<(usize, &str)> = violations
+        .iter()
+        .map(|violation| (violation.line, violation.message.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (1, "missing required `description`"),
+            (3, "invalid `date` '10/10/2024', expected YYYY-MM-DD, YYYY-MM-DD HH:MM or YYYY-MM-DD HH:MM:SS"),
+            (4, "`stream` 'blog' is not allowed, expected one of: index, news"),
+            (5, "`tags` 'go' is not allowed, expected one of: rust, python"),
+            (8, "invalid `expires` 'soon', expected YYYY-MM-DD, YYYY-MM-DD HH:MM or YYYY-MM-DD HH:MM:SS"),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_source_with_aliases() {
+    let aliases = HashMap::from([("published".to_string(), "date".to_string())]);
+    let text = "---\ntitle: Hello\ndescription: A post\npublished: yesterday\n---\n";
+    let violations = validate_source(text, &schema(), &aliases);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].line, 4);
+}
+
+#[test]
+fn test_validate_source_without_frontmatter() {
+    let violations = validate_source("# Hello\n", &FrontmatterSchema::default(), &HashMap::new());
+    assert!(violations.is_empty());
+
+    let violations = validate_source("# Hello\n", &schema(), &HashMap::new());
+    assert_eq!(violations.len(), 2);
+    assert!(violations.iter().all(|violation| violation.line == 1));
+}
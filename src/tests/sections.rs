@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn test_section_of() {
+    let content_dir = Path::new("/site/content");
+    assert_eq!(
+        section_of(Path::new("/site/content/hello.md"), content_dir),
+        None
+    );
+    assert_eq!(
+        section_of(
+            Path::new("/site/content/docs/install/linux.md"),
+            content_dir
+        ),
+        Some("docs/install".to_string())
+    );
+}
+
+#[test]
+fn test_section_slug_and_name() {
+    assert_eq!(
+        section_slug("docs/Getting Started"),
+        "section-docs-getting-started"
+    );
+    assert_eq!(section_name("docs/getting_started"), "Getting started");
+    assert_eq!(section_name("docs"), "Docs");
+}
+
+#[test]
+fn test_breadcrumbs() {
+    assert_eq!(
+        breadcrumbs("docs/install"),
+        vec![
+            ("Docs".to_string(), "section-docs.html".to_string()),
+            (
+                "Install".to_string(),
+                "section-docs-install.html".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_collect_sections_adds_ancestors() {
+    let paths = ["docs/install".to_string(), "docs/api".to_string()];
+    let sections = collect_sections(paths.iter());
+    let section_paths: Vec<&str> = sections.iter().map(|s| s.path.as_str()).collect();
+    assert_eq!(section_paths, vec!["docs", "docs/api", "docs/install"]);
+    assert_eq!(sections[0].parent, None);
+    assert_eq!(
+        sections[0].children,
+        vec![
+            ("Api".to_string(), "section-docs-api.html".to_string()),
+            (
+                "Install".to_string(),
+                "section-docs-install.html".to_string()
+            ),
+        ]
+    );
+    assert_eq!(sections[2].parent.as_deref(), Some("docs"));
+    assert!(sections[2].children.is_empty());
+}
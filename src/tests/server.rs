@@ -42,3 +42,127 @@ fn test_render_not_found_fallback() {
     // Should return fallback 404 response
     // Testing the actual content is difficult without accessing internal data
 }
+
+#[test]
+fn test_editor_command() {
+    assert_eq!(
+        editor_command("code -g {file}:{line}", "/my site/post.md", 12),
+        vec!["code", "-g", "/my site/post.md:12"]
+    );
+    assert_eq!(
+        editor_command("vim +{line} {file}", "post.md", 3),
+        vec!["vim", "+3", "post.md"]
+    );
+}
+
+#[test]
+fn test_open_in_editor_only_opens_mapped_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("sources.json"),
+        r#"{"files": {"post.html": {"file": "/content/post.md"}}}"#,
+    )
+    .unwrap();
+
+    let result = open_in_editor("true {file}", temp_dir.path(), "page=post.html&line=3");
+    assert_eq!(result, Ok("/content/post.md".to_string()));
+
+    let result = open_in_editor("true {file}", temp_dir.path(), "page=../../etc/passwd");
+    assert!(result.unwrap_err().contains("No source found"));
+    assert!(open_in_editor("true {file}", temp_dir.path(), "line=3").is_err());
+}
+
+#[test]
+fn test_open_in_editor_decodes_each_value() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("sources.json"),
+        r#"{"files": {"a&b=c.html": {"file": "/content/a&b=c.md"}}}"#,
+    )
+    .unwrap();
+
+    let result = open_in_editor("true {file}", temp_dir.path(), "page=a%26b%3Dc.html&line=3");
+    assert_eq!(result, Ok("/content/a&b=c.md".to_string()));
+}
+
+#[test]
+fn test_editor_query() {
+    assert_eq!(editor_query("/__marmite__/open"), Some(""));
+    assert_eq!(
+        editor_query("/__marmite__/open?page=post.html&line=3"),
+        Some("page=post.html&line=3")
+    );
+    assert_eq!(editor_query("/__marmite__/openanything"), None);
+    assert_eq!(
+        editor_query("/__marmite__/openanything?page=post.html"),
+        None
+    );
+    assert_eq!(editor_query("/post.html"), None);
+}
+
+#[test]
+fn test_is_local_request() {
+    let local: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+    let mapped: SocketAddr = "[::ffff:127.0.0.1]:50000".parse().unwrap();
+    let lan: SocketAddr = "192.168.0.20:50000".parse().unwrap();
+
+    assert!(is_local_request(Some(&local), Some("localhost:8000"), None));
+    assert!(is_local_request(
+        Some(&mapped),
+        Some("127.0.0.1:8000"),
+        Some("http://127.0.0.1:8000")
+    ));
+    assert!(is_local_request(
+        Some(&local),
+        Some("[::1]:8000"),
+        Some("http://[::1]:8000")
+    ));
+    // the LAN, other sites and rebound host names can't open the editor
+    assert!(!is_local_request(Some(&lan), Some("localhost:8000"), None));
+    assert!(!is_local_request(
+        Some(&local),
+        Some("localhost:8000"),
+        Some("https://evil.example")
+    ));
+    assert!(!is_local_request(
+        Some(&local),
+        Some("localhost:8000"),
+        Some("null")
+    ));
+    assert!(!is_local_request(
+        Some(&local),
+        Some("evil.example:8000"),
+        None
+    ));
+    assert!(!is_local_request(Some(&local), None, None));
+    assert!(!is_local_request(None, Some("localhost:8000"), None));
+}
+
+#[test]
+fn test_nearest_error_page() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path();
+    fs::write(output.join("404.html"), "site").unwrap();
+    fs::write(output.join("docs-404.html"), "docs").unwrap();
+
+    assert_eq!(
+        nearest_error_page(output, "docs-missing.html"),
+        output.join("docs-404.html")
+    );
+    assert_eq!(
+        nearest_error_page(output, "docs-getting-started.html"),
+        output.join("docs-404.html")
+    );
+    assert_eq!(
+        nearest_error_page(output, "missing.html"),
+        output.join("404.html")
+    );
+    assert_eq!(
+        nearest_error_page(output, "blog-missing.html"),
+        output.join("404.html")
+    );
+    assert_eq!(
+        nearest_error_page(output, "docs/missing.html"),
+        output.join("404.html")
+    );
+}
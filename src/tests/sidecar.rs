@@ -0,0 +1,23 @@
+use super::*;
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+#[test]
+fn test_sidecar_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = sidecar_path(temp_dir.path(), "slug-history.json");
+    assert_eq!(path, temp_dir.path().join(".marmite/slug-history.json"));
+
+    let missing: BTreeMap<String, Vec<String>> = load_sidecar(&path);
+    assert!(missing.is_empty());
+
+    let mut history = BTreeMap::new();
+    history.insert("content/a.md".to_string(), vec!["old".to_string()]);
+    save_sidecar(&path, &history);
+    let loaded: BTreeMap<String, Vec<String>> = load_sidecar(&path);
+    assert_eq!(loaded, history);
+
+    fs::write(&path, "not json").unwrap();
+    let invalid: BTreeMap<String, Vec<String>> = load_sidecar(&path);
+    assert!(invalid.is_empty());
+}
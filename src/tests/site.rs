@@ -77,6 +77,64 @@ fn test_next_and_previous_links_are_stream_specific() {
     assert!(news_post.previous.is_none());
 }
 
+#[test]
+fn test_series_navigation_metadata() {
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    for (day, slug) in [(3, "part-3"), (1, "part-1"), (2, "part-2")] {
+        site_data.posts.push(
+            ContentBuilder::new()
+                .slug(slug.to_string())
+                .series("tutorial".to_string())
+                .date(
+                    NaiveDate::from_ymd_opt(2024, 1, day)
+                        .unwrap()
+                        .and_hms_opt(0, 0, 0)
+                        .unwrap(),
+                )
+                .build(),
+        );
+    }
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("standalone".to_string())
+            .date(
+                NaiveDate::from_ymd_opt(2024, 1, 4)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .build(),
+    );
+    site_data.sort_all();
+
+    let mutex = Mutex::new(site_data);
+    let mut site_data_guard = mutex.lock().unwrap();
+    set_next_and_previous_links(&mut site_data_guard);
+
+    let post = |slug: &str| {
+        site_data_guard
+            .posts
+            .iter()
+            .find(|p| p.slug == slug)
+            .unwrap()
+            .clone()
+    };
+    let part_2 = post("part-2");
+    assert_eq!(part_2.series_index, Some(2));
+    assert_eq!(part_2.series_total, Some(3));
+    assert_eq!(part_2.series_prev.unwrap().slug, "part-1");
+    assert_eq!(part_2.series_next.unwrap().slug, "part-3");
+
+    let part_1 = post("part-1");
+    assert_eq!(part_1.series_index, Some(1));
+    assert!(part_1.series_prev.is_none());
+    assert!(post("part-3").series_next.is_none());
+
+    let standalone = post("standalone");
+    assert_eq!(standalone.series_index, None);
+    assert!(standalone.series_next.is_none());
+}
+
 #[test]
 fn test_file_mapping() {
     use crate::config::FileMapping;
@@ -334,6 +392,61 @@ fn test_data_sort_all() {
     assert_eq!(data.pages[1].title, "A Page");
 }
 
+#[test]
+fn test_data_sort_all_by_weight() {
+    let mut data = Data::new("", Path::new("test.yaml"));
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+
+    // Posts sharing a date are ordered by weight
+    data.posts.push(
+        ContentBuilder::new()
+            .title("Unweighted".to_string())
+            .date(date)
+            .build(),
+    );
+    data.posts.push(
+        ContentBuilder::new()
+            .title("Second".to_string())
+            .date(date)
+            .weight(2)
+            .build(),
+    );
+    data.posts.push(
+        ContentBuilder::new()
+            .title("First".to_string())
+            .date(date)
+            .weight(1)
+            .build(),
+    );
+
+    // Weighted pages come first, the rest keep the title order
+    data.pages
+        .push(ContentBuilder::new().title("Z Page".to_string()).build());
+    data.pages.push(
+        ContentBuilder::new()
+            .title("About".to_string())
+            .weight(-1)
+            .build(),
+    );
+    data.pages.push(
+        ContentBuilder::new()
+            .title("Contact".to_string())
+            .weight(5)
+            .build(),
+    );
+
+    data.sort_all();
+
+    let titles = |contents: &[Content]| -> Vec<String> {
+        contents.iter().map(|c| c.title.clone()).collect()
+    };
+    assert_eq!(titles(&data.posts), ["First", "Second", "Unweighted"]);
+    assert_eq!(titles(&data.pages), ["About", "Contact", "Z Page"]);
+}
+
 #[test]
 fn test_data_push_content_post() {
     let mut data = Data::new("", Path::new("test.yaml"));
@@ -452,3 +565,770 @@ fn test_get_content_folder() {
     let content_folder_fallback = get_content_folder(&config_nonexistent, input_folder);
     assert_eq!(content_folder_fallback, input_folder);
 }
+
+#[test]
+fn test_handle_contact_page() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+    let site_data = Data::new(
+        "contact_form:\n  formspree: abc123\n  honeypot: _trap\n",
+        Path::new("marmite.yaml"),
+    );
+    let (tera, _) = initialize_tera(input_dir.path(), &site_data);
+    let mut context = Context::new();
+    context.insert("site_data", &site_data);
+    context.insert("site", &site_data.site);
+    context.insert("menu", &site_data.site.menu);
+    context.insert("language", &site_data.site.language);
+
+    let contact_form = site_data.site.contact_form.clone().unwrap();
+    handle_contact_page(&contact_form, &context, &tera, output_dir.path()).unwrap();
+
+    let html = fs::read_to_string(output_dir.path().join("contact.html")).unwrap();
+    assert!(html.contains(r#"action="https://formspree.io/f/abc123""#));
+    assert!(html.contains(r#"name="_trap""#));
+    assert!(html.contains(r#"name="message""#));
+}
+
+#[test]
+fn test_custom_templates_override_generated_pages() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+    let site_data = Data::new(
+        "contact_form:\n  formspree: abc123\nglossary:\n  slug: terms\nprojects:\n  repos: []\n",
+        Path::new("marmite.yaml"),
+    );
+    let (mut tera, _) = initialize_tera(input_dir.path(), &site_data);
+    for name in ["contact", "terms", "uses", "cv", "projects"] {
+        tera.add_raw_template(&format!("custom_{name}.html"), &format!("custom {name}"))
+            .unwrap();
+    }
+    let mut context = Context::new();
+    context.insert("site_data", &site_data);
+    context.insert("site", &site_data.site);
+
+    let contact_form = site_data.site.contact_form.clone().unwrap();
+    handle_contact_page(&contact_form, &context, &tera, output_dir.path()).unwrap();
+    handle_glossary_page(&site_data, &context, &tera, output_dir.path()).unwrap();
+    handle_uses_page(&site_data, &context, &tera, output_dir.path()).unwrap();
+    handle_cv_page(
+        &serde_json::json!({}),
+        &site_data,
+        &context,
+        &tera,
+        output_dir.path(),
+    )
+    .unwrap();
+    handle_projects_pages(&site_data, &context, &tera, output_dir.path()).unwrap();
+
+    for (file, name) in [
+        ("contact.html", "contact"),
+        ("terms.html", "terms"),
+        ("uses.html", "uses"),
+        ("cv/index.html", "cv"),
+        ("projects.html", "projects"),
+    ] {
+        let html = fs::read_to_string(output_dir.path().join(file)).unwrap();
+        assert!(
+            html.starts_with(&format!("custom {name}")),
+            "{file}: {html}"
+        );
+    }
+}
+
+#[test]
+fn test_copy_markdown_sources_applies_conditionals() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let content_folder = input_dir.path().join("content");
+    let source = content_folder.join("post.md");
+    fs::create_dir_all(&content_folder).unwrap();
+    fs::write(
+        &source,
+        "# Post\n<!-- if profile == \"preview\" -->\nDraft notes\n<!-- endif -->\nPublished\n",
+    )
+    .unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("post".to_string())
+            .source_path(source)
+            .build(),
+    );
+
+    let output = input_dir.path().join("site");
+    copy_markdown_sources(&site_data, &content_folder, &output);
+    let published = fs::read_to_string(output.join("post.md")).unwrap();
+    assert!(!published.contains("Draft notes"), "{published}");
+    assert!(published.contains("Published"));
+}
+
+#[test]
+fn test_build_metadata_comment() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let source = input_dir.path().join("content/post.md");
+    fs::create_dir_all(source.parent().unwrap()).unwrap();
+    fs::write(&source, "# Post").unwrap();
+    let content = ContentBuilder::new()
+        .slug("post".to_string())
+        .source_path(source)
+        .build();
+
+    let comment = build_metadata_comment(Some(&content), input_dir.path(), "2025-01-01T10:00:00");
+    assert!(comment.starts_with(&format!("<!-- marmite {} | ", env!("CARGO_PKG_VERSION"))));
+    assert!(comment.contains(" | source: content/post.md | hash: "));
+    assert!(comment.ends_with(" | built: 2025-01-01T10:00:00 -->"));
+
+    let comment = build_metadata_comment(None, input_dir.path(), "2025-01-01T10:00:00");
+    assert!(!comment.contains("source:"));
+}
+
+#[test]
+fn test_create_sources_json() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let source = input_dir.path().join("content/post.md");
+    fs::create_dir_all(source.parent().unwrap()).unwrap();
+    fs::write(
+        &source,
+        "# Post\n\n```\n# not a heading\n```\n## Getting Started!\n",
+    )
+    .unwrap();
+
+    let mut site_data = Data::new("editor_url: 'zed://file{file}'", Path::new("marmite.yaml"));
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("post".to_string())
+            .source_path(source.clone())
+            .build(),
+    );
+    site_data
+        .pages
+        .push(ContentBuilder::new().slug("generated".to_string()).build());
+    let other = input_dir.path().join("content/other.md");
+    fs::write(&other, "# Other\n").unwrap();
+    site_data.posts.push(
+        ContentBuilder::new()
+            .slug("other".to_string())
+            .source_path(other)
+            .template("missing.html".to_string())
+            .build(),
+    );
+
+    let json = create_sources_json(&site_data, input_dir.path(), &Tera::default(), true);
+    assert_eq!(json["editor_url"], "zed://file{file}");
+    assert_eq!(json["editor"], true);
+    let files = json["files"].as_object().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files["post.html"]["source"], "content/post.md");
+    assert_eq!(files["post.html"]["template"], "content.html");
+    assert_eq!(files["other.html"]["template"], "content.html");
+    assert_eq!(files["post.html"]["headings"]["post"], 1);
+    assert_eq!(files["post.html"]["headings"]["getting-started"], 6);
+    assert!(files["post.html"]["headings"]
+        .get("not-a-heading")
+        .is_none());
+    assert_eq!(
+        files["post.html"]["file"],
+        fs::canonicalize(&source).unwrap().display().to_string()
+    );
+}
+
+#[test]
+fn test_collect_content_skips_drafts() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-published.md"),
+        "# Published\n\nHello",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-02-wip.md"),
+        "---\ndraft: true\n---\n# Work in progress\n\nHello",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    let slugs: Vec<&str> = site_data.posts.iter().map(|p| p.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["published"]);
+
+    let mut site_data = Data::new("drafts: true", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    let draft = site_data.posts.iter().find(|p| p.slug == "wip").unwrap();
+    assert!(draft.draft);
+    assert_eq!(site_data.posts.len(), 2);
+}
+
+#[test]
+fn test_should_force_render_ignores_the_private_key() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.site.private_key = Some(PathBuf::from("key.txt"));
+    let build_info = |private| BuildInfo {
+        marmite_version: String::new(),
+        posts: 0,
+        pages: 0,
+        generated_at: String::new(),
+        timestamp: 0,
+        elapsed_time: 0.0,
+        config: Marmite {
+            private_key: None,
+            ..site_data.site.clone()
+        },
+        private,
+    };
+    let force_render = |info: &BuildInfo| {
+        should_force_render(
+            input_dir.path(),
+            &site_data,
+            i64::MAX,
+            input_dir.path(),
+            Some(info),
+        )
+    };
+
+    assert!(!force_render(&build_info(true)));
+    assert!(force_render(&build_info(false)));
+}
+
+#[test]
+fn test_collect_content_fails_on_reserved_slug() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(content_dir.path().join("tags.md"), "# Tags\n\nMy tags page").unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("slug:\n  reserved: [tags]", Path::new("marmite.yaml"));
+    let error = collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Content);
+    assert!(site_data.pages.is_empty());
+}
+
+#[test]
+fn test_collect_content_fails_on_invalid_date() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("post.html"),
+        "---\ndate: not-a-date\n---\n<p>Hello</p>",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    let error = collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Content);
+    assert!(site_data.pages.is_empty());
+}
+
+#[test]
+fn test_handle_static_artifacts_fills_embedded_assets() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+    let static_dir = input_dir.path().join("static");
+    fs::create_dir_all(&static_dir).unwrap();
+    fs::write(static_dir.join("custom.css"), "body { color: red; }").unwrap();
+
+    let site_data = Data::new("", Path::new("marmite.yaml"));
+    let output_folder = Arc::new(output_dir.path().to_path_buf());
+    handle_static_artifacts(
+        input_dir.path(),
+        &site_data,
+        &output_folder,
+        input_dir.path(),
+    );
+
+    // embedded assets are there for the embedded base.html, user files win
+    let output_static = output_dir.path().join("static");
+    assert!(output_static.join("marmite.css").exists());
+    assert!(output_static.join("pico.min.css").exists());
+    assert_eq!(
+        fs::read_to_string(output_static.join("custom.css")).unwrap(),
+        "body { color: red; }"
+    );
+
+    // a site with its own base.html gets only its own static files
+    let output_dir = TempDir::new().unwrap();
+    let templates_dir = input_dir.path().join("templates");
+    fs::create_dir_all(&templates_dir).unwrap();
+    fs::write(
+        templates_dir.join("base.html"),
+        "{% block main %}{% endblock %}",
+    )
+    .unwrap();
+    let output_folder = Arc::new(output_dir.path().to_path_buf());
+    handle_static_artifacts(
+        input_dir.path(),
+        &site_data,
+        &output_folder,
+        input_dir.path(),
+    );
+    assert!(output_dir.path().join("static/custom.css").exists());
+    assert!(!output_dir.path().join("static/marmite.css").exists());
+}
+
+#[test]
+fn test_handle_stream_error_pages() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    let output_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("_docs_404.md"),
+        "# Not in the docs\n\nTry the search",
+    )
+    .unwrap();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    for stream in ["docs", "blog"] {
+        let post = ContentBuilder::default()
+            .title(format!("{stream} post"))
+            .slug(format!("{stream}-post"))
+            .stream(stream.to_string())
+            .date(
+                NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+            )
+            .build();
+        site_data.push_content(post);
+    }
+
+    let mut tera = Tera::default();
+    tera.add_raw_template(
+        "custom_docs_404.html",
+        "{{ stream }}|{{ title }}|{{ content.html }}",
+    )
+    .unwrap();
+    tera.add_raw_template("custom_docs_410.html", "{{ stream }}|{{ title }}")
+        .unwrap();
+
+    handle_stream_error_pages(
+        &site_data,
+        content_dir.path(),
+        &Context::new(),
+        &tera,
+        output_dir.path(),
+    )
+    .unwrap();
+
+    let not_found = fs::read_to_string(output_dir.path().join("docs-404.html")).unwrap();
+    assert!(not_found.starts_with("docs|Not in the docs|"));
+    assert!(not_found.contains("Try the search"));
+    let gone = fs::read_to_string(output_dir.path().join("docs-410.html")).unwrap();
+    assert_eq!(gone, "docs|Page gone");
+    assert!(!output_dir.path().join("blog-404.html").exists());
+}
+
+#[test]
+fn test_collect_content_skips_future_posts() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-published.md"),
+        "# Published\n\nHello",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("9999-01-01-scheduled.md"),
+        "# Scheduled\n\nHello from the future",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    let slugs: Vec<&str> = site_data.posts.iter().map(|p| p.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["published"]);
+
+    let mut site_data = Data::new("future: true", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    assert!(site_data.posts.iter().any(|p| p.slug == "scheduled"));
+}
+
+#[test]
+fn test_handle_taxonomy_pages() {
+    use tempfile::TempDir;
+
+    let output_dir = TempDir::new().unwrap();
+    let mut site_data = Data::new(
+        "taxonomies:\n  categories:\n    title: Topics\n  series: {}",
+        Path::new("marmite.yaml"),
+    );
+    // `series` is a builtin group so it is not a custom taxonomy
+    assert_eq!(
+        site_data.taxonomies.keys().collect::<Vec<_>>(),
+        vec!["categories"]
+    );
+
+    let post = ContentBuilder::default()
+        .title("Rust on the web".to_string())
+        .slug("rust-on-the-web".to_string())
+        .taxonomies(HashMap::from([(
+            "categories".to_string(),
+            vec!["Web Dev".to_string()],
+        )]))
+        .date(
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+        .build();
+    site_data.push_content(post);
+    assert_eq!(site_data.taxonomies["categories"].map["web-dev"].len(), 1);
+
+    let mut tera = Tera::default();
+    tera.add_raw_template(
+        "list.html",
+        "{{ title }}|{% for c in content_list %}{{ c.slug }}{% endfor %}",
+    )
+    .unwrap();
+    tera.add_raw_template("group.html", "{{ title }}|{{ kind }}")
+        .unwrap();
+
+    handle_taxonomy_pages(output_dir.path(), &site_data, &Context::new(), &tera).unwrap();
+
+    let term_page = fs::read_to_string(output_dir.path().join("categories-web-dev.html")).unwrap();
+    assert_eq!(term_page, "Posts in 'Web Dev'|rust-on-the-web");
+    let group_page = fs::read_to_string(output_dir.path().join("categories.html")).unwrap();
+    assert_eq!(group_page, "Topics|categories");
+    assert!(output_dir.path().join("categories-web-dev.rss").exists());
+}
+
+#[test]
+fn test_collect_content_assigns_content_ids() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-hello.md"),
+        "# Hello\n\nHi",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-02-pinned.md"),
+        "---\nuuid: 0b5f3a52-8c1e-4f7a-9d2b-6e4c1a7f9e30\n---\n# Pinned\n\nHi",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    // ids are only generated with `feed_guid: uuid`
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    assert!(site_data.content_ids.is_empty());
+
+    let mut site_data = Data::new("feed_guid: uuid", Path::new("marmite.yaml"));
+    site_data
+        .content_ids
+        .insert("2024-01-01-hello.md".to_string(), "kept-id".to_string());
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    let uuid_of = |slug: &str| {
+        site_data
+            .posts
+            .iter()
+            .find(|p| p.slug == slug)
+            .and_then(|p| p.uuid.clone())
+    };
+    assert_eq!(uuid_of("hello").as_deref(), Some("kept-id"));
+    assert_eq!(
+        uuid_of("pinned").as_deref(),
+        Some("0b5f3a52-8c1e-4f7a-9d2b-6e4c1a7f9e30")
+    );
+    // frontmatter uuids are not copied to the sidecar
+    assert_eq!(site_data.content_ids.len(), 1);
+}
+
+#[test]
+fn test_collect_content_expired() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-meetup.md"),
+        "---\nexpires: 2024-01-15\n---\n# Meetup\n\nJoin us",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-02-hiring.md"),
+        "---\nexpires: 2999-01-01\n---\n# Hiring\n\nJoin us",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    let slugs: Vec<&str> = site_data.posts.iter().map(|p| p.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["hiring"]);
+    assert!(site_data.expired.is_empty());
+
+    // with `render_expired` the content is kept out of the listings only
+    let mut site_data = Data::new("render_expired: true", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+    assert_eq!(site_data.posts.len(), 1);
+    assert!(site_data.archive.map.values().flatten().all(|p| !p.expired));
+    assert_eq!(site_data.expired.len(), 1);
+    assert_eq!(site_data.expired[0].slug, "meetup");
+    assert!(site_data.expired[0].expired);
+}
+
+#[test]
+fn test_collect_content_tag_landings() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::create_dir(content_dir.path().join("tags")).unwrap();
+    fs::write(
+        content_dir.path().join("tags/rust.md"),
+        "---\ndescription: Posts about Rust\ncard_image: media/rust.png\n---\nStart with the **book**.\n",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-hello.md"),
+        "---\ntags: Rust\n---\n# Hello\n\nHello world",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new(
+        "tags:\n  Rust:\n    banner_image: media/banner.png\n",
+        Path::new("marmite.yaml"),
+    );
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+
+    // the landing file is not a content, it is merged with the `tags` config
+    assert_eq!(site_data.posts.len(), 1);
+    assert!(site_data.pages.is_empty());
+    let tag_config = &site_data.site.tags["Rust"];
+    assert_eq!(tag_config.description.as_deref(), Some("Posts about Rust"));
+    assert_eq!(tag_config.card_image.as_deref(), Some("media/rust.png"));
+    assert_eq!(tag_config.banner_image.as_deref(), Some("media/banner.png"));
+    assert_eq!(
+        tag_config.intro.as_deref(),
+        Some("Start with the **book**.")
+    );
+    assert_eq!(
+        site_data.posts[0].description.as_deref(),
+        Some("Posts about Rust")
+    );
+}
+
+#[test]
+fn test_collect_content_archive_intros() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::create_dir(content_dir.path().join("archive")).unwrap();
+    fs::write(
+        content_dir.path().join("archive/2024.md"),
+        "---\ntitle: 2024 in review\n---\nThe year we moved to **Rust**.\n",
+    )
+    .unwrap();
+    fs::write(content_dir.path().join("archive/2023.md"), "\n").unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-hello.md"),
+        "# Hello\n\nHello world",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+
+    assert_eq!(site_data.posts.len(), 1);
+    assert!(site_data.pages.is_empty());
+    assert_eq!(
+        site_data.archive_intros,
+        HashMap::from([(
+            "2024".to_string(),
+            "The year we moved to **Rust**.".to_string()
+        )])
+    );
+}
+
+#[test]
+fn test_collect_content_sections() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::create_dir_all(content_dir.path().join("docs/install")).unwrap();
+    fs::write(
+        content_dir.path().join("docs/install/setup.md"),
+        "# Setup\n\nInstall it",
+    )
+    .unwrap();
+    fs::write(content_dir.path().join("about.md"), "# About\n\nAbout me").unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new("enable_sections: true", Path::new("marmite.yaml"));
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+
+    let setup = site_data.pages.iter().find(|p| p.slug == "setup").unwrap();
+    assert_eq!(setup.section.as_deref(), Some("docs/install"));
+    assert_eq!(
+        setup.breadcrumbs,
+        vec![
+            ("Docs".to_string(), "section-docs.html".to_string()),
+            (
+                "Install".to_string(),
+                "section-docs-install.html".to_string()
+            ),
+        ]
+    );
+    let about = site_data.pages.iter().find(|p| p.slug == "about").unwrap();
+    assert!(about.section.is_none());
+    assert!(about.breadcrumbs.is_empty());
+    assert_eq!(
+        site_data.section.map.keys().collect::<Vec<_>>(),
+        vec!["docs/install"]
+    );
+}
+
+#[test]
+fn test_collect_content_authors_file() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    fs::write(
+        content_dir.path().join("authors.yaml"),
+        "alice:\n  name: Alice Smith\n  bio: Writes about Rust\n  location: Lisbon\n  links:\n    - [Mastodon, https://mastodon.social/@alice]\nbob:\n  name: Not Bob\ncarol:\n  url: https://carol.dev\n",
+    )
+    .unwrap();
+    fs::write(
+        content_dir.path().join("2024-01-01-hello.md"),
+        "---\nauthors: [alice, carol, dave]\n---\n# Hello\n\nHello world",
+    )
+    .unwrap();
+    let content_path = content_dir.path().to_path_buf();
+
+    let mut site_data = Data::new(
+        "authors:\n  bob:\n    name: Bob\n",
+        Path::new("marmite.yaml"),
+    );
+    collect_content(&content_path, &mut site_data, &HashMap::new()).unwrap();
+
+    // configured authors take precedence over the authors file
+    assert_eq!(site_data.site.authors["bob"].name, "Bob");
+    assert_eq!(site_data.site.authors["bob"].username, "bob");
+
+    let profiles = &site_data.posts[0].author_profiles;
+    assert_eq!(profiles.len(), 3);
+    assert_eq!(profiles[0].name, "Alice Smith");
+    assert_eq!(profiles[0].location.as_deref(), Some("Lisbon"));
+    assert_eq!(profiles[1].name, "carol");
+    assert_eq!(profiles[1].url.as_deref(), Some("https://carol.dev"));
+    // authors not configured get a placeholder profile
+    assert_eq!(profiles[2].username, "dave");
+    assert_eq!(
+        profiles[2].avatar.as_deref(),
+        Some("static/avatar-placeholder.png")
+    );
+}
+
+#[test]
+fn test_content_template_override() {
+    let mut tera = Tera::default();
+    tera.add_raw_template("content.html", "{{ content.title }}")
+        .unwrap();
+    tera.add_raw_template("landing.html", "{{ content.title }}")
+        .unwrap();
+
+    let content = ContentBuilder::new().slug("post".to_string()).build();
+    assert_eq!(content_template(&content, &tera), "content.html");
+
+    let content = ContentBuilder::new()
+        .slug("post".to_string())
+        .template("landing.html".to_string())
+        .build();
+    assert_eq!(content_template(&content, &tera), "landing.html");
+
+    // missing templates fall back to content.html
+    let content = ContentBuilder::new()
+        .slug("post".to_string())
+        .template("missing.html".to_string())
+        .build();
+    assert_eq!(content_template(&content, &tera), "content.html");
+}
+
+#[test]
+fn test_sources_json_lists_the_template_override() {
+    use tempfile::TempDir;
+
+    let input_dir = TempDir::new().unwrap();
+    let source = input_dir.path().join("landing.md");
+    fs::write(&source, "# Landing\n").unwrap();
+    let mut tera = Tera::default();
+    tera.add_raw_template("landing.html", "{{ content.title }}")
+        .unwrap();
+    let mut site_data = Data::new("", Path::new("marmite.yaml"));
+    site_data.pages.push(
+        ContentBuilder::new()
+            .slug("landing".to_string())
+            .source_path(source)
+            .template("landing.html".to_string())
+            .build(),
+    );
+
+    let json = create_sources_json(&site_data, input_dir.path(), &tera, false);
+    assert_eq!(json["files"]["landing.html"]["template"], "landing.html");
+}
+
+#[test]
+fn test_render_backlinks_section() {
+    let mut content = ContentBuilder::new().slug("target".to_string()).build();
+    assert_eq!(render_backlinks_section(&content, "Backlinks"), "");
+
+    let mut draft = ContentBuilder::new()
+        .title("Draft".to_string())
+        .slug("draft".to_string())
+        .build();
+    draft.stream = Some("draft".to_string());
+    content.back_links = vec![
+        ContentBuilder::new()
+            .title("Rust & <Web>".to_string())
+            .slug("rust-web".to_string())
+            .build(),
+        draft,
+    ];
+    assert_eq!(
+        render_backlinks_section(&content, "Linked from"),
+        "\n<section class=\"backlinks\">\n<h2>Linked from</h2>\n<ul>\n<li><a href=\"rust-web.html\">Rust &amp; &lt;Web&gt;</a></li>\n</ul>\n</section>\n"
+    );
+}
+
+#[test]
+fn test_is_stray_intro_file() {
+    use tempfile::TempDir;
+
+    let content_dir = TempDir::new().unwrap();
+    let tags = content_dir.path().join("tags");
+    fs::create_dir_all(tags.join("rust")).unwrap();
+    fs::write(tags.join("rust.md"), "Intro").unwrap();
+    fs::write(tags.join("rust/post.md"), "# Post").unwrap();
+    fs::write(tags.join("notes.txt"), "notes").unwrap();
+
+    assert!(!is_stray_intro_file(&tags.join("rust.md"), &tags));
+    assert!(!is_stray_intro_file(&tags.join("rust"), &tags));
+    assert!(is_stray_intro_file(&tags.join("rust/post.md"), &tags));
+    assert!(is_stray_intro_file(&tags.join("notes.txt"), &tags));
+}
@@ -0,0 +1,91 @@
+use super::*;
+use tempfile::TempDir;
+
+fn sources(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|(source, slug)| ((*source).to_string(), (*slug).to_string()))
+        .collect()
+}
+
+#[test]
+fn test_update_slug_history() {
+    let mut history = SlugHistory::new();
+    let redirects = update_slug_history(&mut history, &sources(&[("content/a.md", "hello")]));
+    assert!(redirects.is_empty());
+
+    // Title edited twice, both previous slugs redirect to the current one
+    update_slug_history(&mut history, &sources(&[("content/a.md", "hello-world")]));
+    let redirects = update_slug_history(&mut history, &sources(&[("content/a.md", "hi")]));
+    assert_eq!(history["content/a.md"], vec!["hello", "hello-world", "hi"]);
+    assert_eq!(
+        redirects,
+        vec![
+            ("hello".to_string(), "hi".to_string()),
+            ("hello-world".to_string(), "hi".to_string()),
+        ]
+    );
+
+    // Going back to an old slug removes its redirect
+    let redirects = update_slug_history(&mut history, &sources(&[("content/a.md", "hello")]));
+    assert_eq!(history["content/a.md"], vec!["hello-world", "hi", "hello"]);
+    assert_eq!(redirects.len(), 2);
+    assert!(!redirects.iter().any(|(old, _)| old == "hello"));
+}
+
+#[test]
+fn test_update_slug_history_skips_slugs_in_use() {
+    let mut history = SlugHistory::new();
+    update_slug_history(&mut history, &sources(&[("content/a.md", "hello")]));
+    let redirects = update_slug_history(
+        &mut history,
+        &sources(&[("content/a.md", "hello-world"), ("content/b.md", "hello")]),
+    );
+    assert!(redirects.is_empty());
+}
+
+#[test]
+fn test_write_redirects() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = temp_dir.path().join("site");
+    write_redirects(
+        &[
+            ("old".to_string(), "new".to_string()),
+            ("docs/old".to_string(), "docs/new".to_string()),
+        ],
+        &output,
+    );
+    let html = fs::read_to_string(output.join("old.html")).unwrap();
+    assert!(html.contains(r#"<meta http-equiv="refresh" content="0; url=new.html">"#));
+    let nested = fs::read_to_string(output.join("docs/old.html")).unwrap();
+    assert!(nested.contains(r#"<link rel="canonical" href="../docs/new.html">"#));
+}
+
+#[test]
+fn test_alias_redirects() {
+    use crate::content::ContentBuilder;
+
+    let contents = [
+        ContentBuilder::new()
+            .slug("new-post".to_string())
+            .aliases(vec![
+                "/2019/05/old-post/".to_string(),
+                "/old-post.html".to_string(),
+                "/blog/old-post".to_string(),
+                "/about".to_string(),
+                "/tags.html".to_string(),
+                "/".to_string(),
+                "/../outside".to_string(),
+            ])
+            .build(),
+        ContentBuilder::new().slug("about".to_string()).build(),
+    ];
+    assert_eq!(
+        alias_redirects(contents.iter(), &["tags.html".to_string()]),
+        vec![
+            ("2019/05/old-post/index".to_string(), "new-post".to_string()),
+            ("old-post".to_string(), "new-post".to_string()),
+            ("blog/old-post".to_string(), "new-post".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+fn dictionary() -> Dictionary {
+    let mut dictionary = Dictionary::default();
+    dictionary.add_words("3\nhello/S\nworld\nsite\nis\na\nand\nthe\nbuild\nwith\nJSON\n");
+    dictionary
+}
+
+#[test]
+fn test_dictionary_contains() {
+    let dictionary = dictionary();
+    assert!(dictionary.contains("Hello"));
+    assert!(dictionary.contains("sites"));
+    assert!(dictionary.contains("JSON"));
+    assert!(dictionary.contains("json"));
+    assert!(dictionary.contains("JSONs"));
+    assert!(!dictionary.contains("HELLO"));
+    assert!(!dictionary.contains("wrold"));
+    assert!(!dictionary.contains("3"));
+}
+
+#[test]
+fn test_check_source() {
+    let text = "---\ntitle: Hello wrold\ntags: typpo\ndescription: the sitte\n---\n\
+        Hello `wrold` [world](https://wrold.com) <span class=\"wrold\">world</span>\n\
+        ```rust\nlet wrold = 1;\n```\n\
+        The site is a buld with JSON and XMLL\n";
+    let findings = check_source(text, &dictionary());
+
+    let found: Vec<(usize, &str)> = findings.iter().map(|f| (f.line, f.word.as_str())).collect();
+    assert_eq!(
+        found,
+        vec![(2, "wrold"), (4, "sitte"), (10, "buld"), (10, "XMLL")]
+    );
+}
+
+#[test]
+fn test_load_dictionary_from_project() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let dictionaries = temp_dir.path().join("dictionaries");
+    fs::create_dir(&dictionaries).unwrap();
+    fs::write(dictionaries.join("words.txt"), "zettelkasten\nMARMITEDEV\n").unwrap();
+
+    let dictionary = load_dictionary(temp_dir.path(), "zz").unwrap();
+    assert!(dictionary.contains("Zettelkasten"));
+    assert!(dictionary.contains("MARMITEDEV"));
+    // bundled dictionaries are always loaded
+    assert!(dictionary.contains("HTML"));
+    assert!(dictionary.contains("frontmatter"));
+
+    let empty_dir = TempDir::new().unwrap();
+    assert!(load_dictionary(empty_dir.path(), "zz").is_err());
+}
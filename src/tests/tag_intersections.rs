@@ -0,0 +1,46 @@
+use super::*;
+use crate::content::ContentBuilder;
+
+fn post(slug: &str, tags: &[&str]) -> Content {
+    ContentBuilder::new()
+        .slug(slug.to_string())
+        .tags(tags.iter().map(ToString::to_string).collect())
+        .build()
+}
+
+#[test]
+fn test_parse_tag_intersection() {
+    let intersection = TagIntersection::parse("Rust + Async").unwrap();
+    assert_eq!(intersection.tags, vec!["Rust", "Async"]);
+    assert_eq!(intersection.slug, "rust+async");
+    assert_eq!(intersection.filename(), "tag-rust+async");
+    assert_eq!(intersection.title(), "Rust + Async");
+
+    // repeated tags are dropped, a single tag is not an intersection
+    assert_eq!(TagIntersection::parse("rust+Rust"), None);
+    assert_eq!(TagIntersection::parse("rust+"), None);
+}
+
+#[test]
+fn test_parse_all_skips_invalid() {
+    let specs = vec!["rust+async".to_string(), "python".to_string()];
+    let intersections = parse_all(&specs);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].slug, "rust+async");
+}
+
+#[test]
+fn test_tag_intersection_contents() {
+    let mut draft = post("draft", &["rust", "async"]);
+    draft.stream = Some("draft".to_string());
+    let posts = vec![
+        post("both", &["Rust", "async", "web"]),
+        post("rust-only", &["rust"]),
+        post("async-only", &["async"]),
+        draft,
+    ];
+    let intersection = TagIntersection::parse("rust+async").unwrap();
+    let contents = intersection.contents(&posts);
+    assert_eq!(contents.len(), 1);
+    assert_eq!(contents[0].slug, "both");
+}
@@ -79,3 +79,33 @@ fn test_initialize_theme_existing_directory() {
     // Should still exist but not be modified
     assert!(theme_path.exists());
 }
+
+#[test]
+fn test_new_theme() {
+    let temp_dir = TempDir::new().unwrap();
+    let input_folder = temp_dir.path();
+
+    new_theme(input_folder, "minimal");
+
+    let theme_path = input_folder.join("minimal");
+    for file in [
+        "templates/base.html",
+        "templates/content.html",
+        "templates/list.html",
+        "templates/group.html",
+        "templates/custom_404.html",
+        "static/style.css",
+    ] {
+        assert!(theme_path.join(file).exists(), "missing {file}");
+    }
+
+    let theme_json = std::fs::read_to_string(theme_path.join("theme.json")).unwrap();
+    assert!(theme_json.contains("\"name\": \"minimal\""));
+    assert!(!theme_json.contains("THEME_NAME"));
+
+    // Existing themes are never overwritten
+    std::fs::write(theme_path.join("theme.json"), "{}").unwrap();
+    new_theme(input_folder, "minimal");
+    let theme_json = std::fs::read_to_string(theme_path.join("theme.json")).unwrap();
+    assert_eq!(theme_json, "{}");
+}
@@ -7,6 +7,8 @@ use tera::Value;
 fn test_default_date_format_valid_date() {
     let filter = DefaultDateFormat {
         date_format: "%Y-%m-%d".to_string(),
+        language: "en".to_string(),
+        date_formats: HashMap::new(),
     };
     let value = Value::String("2023-12-25T10:30:00".to_string());
     let args = HashMap::new();
@@ -15,10 +17,39 @@ fn test_default_date_format_valid_date() {
     assert_eq!(result, Value::String("2023-12-25".to_string()));
 }
 
+#[test]
+fn test_default_date_format_with_lang() {
+    let mut date_formats = HashMap::new();
+    date_formats.insert("pt".to_string(), "%-d de %B de %Y".to_string());
+    let filter = DefaultDateFormat {
+        date_format: "%b %e, %Y".to_string(),
+        language: "en".to_string(),
+        date_formats,
+    };
+    let value = Value::String("2023-03-05T10:30:00".to_string());
+
+    let mut args = HashMap::new();
+    args.insert("lang".to_string(), json!("pt-BR"));
+    let result = filter.filter(&value, &args).unwrap();
+    assert_eq!(result, Value::String("5 de março de 2023".to_string()));
+
+    // without a format for the language the default format is localized
+    args.insert("lang".to_string(), json!("es"));
+    let result = filter.filter(&value, &args).unwrap();
+    assert_eq!(result, Value::String("mar  5, 2023".to_string()));
+
+    // a null lang (content without `lang`) is the site language
+    args.insert("lang".to_string(), Value::Null);
+    let result = filter.filter(&value, &args).unwrap();
+    assert_eq!(result, Value::String("Mar  5, 2023".to_string()));
+}
+
 #[test]
 fn test_default_date_format_invalid_date_string() {
     let filter = DefaultDateFormat {
         date_format: "%Y-%m-%d".to_string(),
+        language: "en".to_string(),
+        date_formats: HashMap::new(),
     };
     let value = Value::String("invalid-date".to_string());
     let args = HashMap::new();
@@ -31,6 +62,8 @@ fn test_default_date_format_invalid_date_string() {
 fn test_default_date_format_non_string_value() {
     let filter = DefaultDateFormat {
         date_format: "%Y-%m-%d".to_string(),
+        language: "en".to_string(),
+        date_formats: HashMap::new(),
     };
     let value = Value::Number(123.into());
     let args = HashMap::new();
@@ -31,6 +31,33 @@ fn test_url_for_absolute_path() {
     );
 }
 
+#[test]
+fn test_url_for_origin() {
+    let url_for = UrlFor {
+        base_url: "https://example.com/blog".to_string(),
+    };
+    let mut args = HashMap::new();
+    args.insert("path".to_string(), Value::String("about.html".to_string()));
+    args.insert("abs".to_string(), Value::Bool(true));
+    args.insert(
+        "origin".to_string(),
+        Value::String("http://marmitexyz.onion/blog/".to_string()),
+    );
+
+    let result = url_for.call(&args).unwrap();
+    assert_eq!(
+        result,
+        Value::String("http://marmitexyz.onion/blog/about.html".to_string())
+    );
+
+    args.remove("origin");
+    let result = url_for.call(&args).unwrap();
+    assert_eq!(
+        result,
+        Value::String("https://example.com/blog/about.html".to_string())
+    );
+}
+
 #[test]
 fn test_url_for_external_url() {
     let url_for = UrlFor {
@@ -256,3 +283,155 @@ fn test_get_data_by_slug_tag_not_found() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("Tag not found"));
 }
+
+#[test]
+fn test_parse_delimited() {
+    let rows = parse_delimited(
+        "name,note\r\n\"Doe, John\",\"said \"\"hi\"\"\"\n\nAna,\"multi\nline\"\n",
+        ',',
+    );
+    assert_eq!(
+        rows,
+        vec![
+            vec!["name", "note"],
+            vec!["Doe, John", "said \"hi\""],
+            vec!["Ana", "multi\nline"],
+        ]
+    );
+    assert_eq!(
+        parse_delimited("a\tb\n1\t2", '\t'),
+        vec![vec!["a", "b"], vec!["1", "2"]]
+    );
+}
+
+#[test]
+fn test_load_csv_from_data_folder() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.path().join("data")).unwrap();
+    std::fs::write(
+        temp_dir.path().join("data").join("scores.tsv"),
+        "name\tscore\nana\t9\nbob\t10\ncarl\t2\n",
+    )
+    .unwrap();
+
+    let load_csv = LoadCsv {
+        input_folder: temp_dir.path().to_path_buf(),
+        content_folder: temp_dir.path().join("content"),
+    };
+    let mut args = HashMap::new();
+    args.insert("file".to_string(), json!("scores.tsv"));
+    args.insert("sort".to_string(), json!("score"));
+    args.insert("order".to_string(), json!("desc"));
+
+    let result = load_csv.call(&args).unwrap();
+    assert_eq!(result["headers"], json!(["name", "score"]));
+    assert_eq!(
+        result["rows"],
+        json!([["bob", "10"], ["ana", "9"], ["carl", "2"]])
+    );
+
+    args.insert("file".to_string(), json!("missing.csv"));
+    assert!(load_csv.call(&args).is_err());
+}
+
+#[test]
+fn test_load_csv_rejects_paths_leaving_the_site() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let site = temp_dir.path().join("site");
+    std::fs::create_dir_all(site.join("data")).unwrap();
+    let secret = temp_dir.path().join("secret.csv");
+    std::fs::write(&secret, "user,password\nroot,hunter2\n").unwrap();
+
+    let load_csv = LoadCsv {
+        input_folder: site.clone(),
+        content_folder: site.join("content"),
+    };
+    for file in [
+        "../../secret.csv",
+        "../secret.csv",
+        secret.to_str().unwrap(),
+    ] {
+        let mut args = HashMap::new();
+        args.insert("file".to_string(), json!(file));
+        let error = load_csv.call(&args).unwrap_err();
+        assert!(
+            error.to_string().contains("without `..`"),
+            "{file}: {error}"
+        );
+    }
+}
+
+#[test]
+fn test_fetch_json_uses_cache_and_default() {
+    use tempfile::TempDir;
+    let temp_dir = TempDir::new().unwrap();
+    let fetch_json = FetchJson {
+        cache_folder: temp_dir.path().to_path_buf(),
+        offline: false,
+        ttl: 3600,
+        fetched: Mutex::default(),
+    };
+    // a fresh cached response is used without fetching
+    let url = "http://127.0.0.1:9/release.json";
+    save_sidecar(
+        &fetch_json.cache_path(url),
+        &FetchedJson {
+            url: url.to_string(),
+            fetched_at: Utc::now().timestamp(),
+            data: json!({"tag_name": "v1.0"}),
+        },
+    );
+    let mut args = HashMap::new();
+    args.insert("url".to_string(), json!(url));
+    assert_eq!(fetch_json.call(&args).unwrap(), json!({"tag_name": "v1.0"}));
+
+    // a huge ttl never expires
+    let forever = FetchJson {
+        ttl: i64::MAX,
+        fetched: Mutex::default(),
+        ..fetch_json
+    };
+    assert_eq!(forever.call(&args).unwrap(), json!({"tag_name": "v1.0"}));
+
+    // a stale cached response is used when the fetch fails
+    let stale = FetchJson {
+        ttl: 0,
+        fetched: Mutex::default(),
+        ..forever
+    };
+    assert_eq!(stale.call(&args).unwrap(), json!({"tag_name": "v1.0"}));
+
+    args.insert("url".to_string(), json!("http://127.0.0.1:9/weather.json"));
+    assert!(stale.call(&args).is_err());
+    args.insert("default".to_string(), json!({}));
+    assert_eq!(stale.call(&args).unwrap(), json!({}));
+}
+
+#[test]
+fn test_fetch_json_offline() {
+    use tempfile::TempDir;
+    let temp_dir = TempDir::new().unwrap();
+    let fetch_json = FetchJson {
+        cache_folder: temp_dir.path().to_path_buf(),
+        offline: true,
+        ttl: 0,
+        fetched: Mutex::default(),
+    };
+    let url = "https://example.com/weather.json";
+    save_sidecar(
+        &fetch_json.cache_path(url),
+        &FetchedJson {
+            url: url.to_string(),
+            fetched_at: 0,
+            data: json!({"celsius": 21}),
+        },
+    );
+    let mut args = HashMap::new();
+    args.insert("url".to_string(), json!(url));
+    args.insert("ttl".to_string(), json!(60));
+    assert_eq!(fetch_json.call(&args).unwrap(), json!({"celsius": 21}));
+}
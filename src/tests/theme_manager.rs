@@ -305,3 +305,100 @@ fn test_update_config_theme_no_existing_config() {
     // This might succeed or fail depending on environment, both are acceptable
     // The important thing is it doesn't panic
 }
+
+#[test]
+fn test_version_satisfies() {
+    assert!(version_satisfies(">=0.2.6", "0.2.6"));
+    assert!(version_satisfies(">=0.2", "0.2.6"));
+    assert!(!version_satisfies(">=0.3.0", "0.2.6"));
+    assert!(version_satisfies("<1.0", "0.2.6"));
+    assert!(version_satisfies("0.2.6", "0.2.6"));
+    assert!(!version_satisfies("=0.2.5", "0.2.6"));
+    assert!(version_satisfies("latest", "0.2.6"));
+}
+
+#[test]
+fn test_check_theme_reports_incompatibilities() {
+    let temp_dir = TempDir::new().unwrap();
+    let theme_path = temp_dir.path().join("mytheme");
+    let templates_path = theme_path.join("templates");
+    fs::create_dir_all(&templates_path).unwrap();
+    fs::write(
+        theme_path.join("theme.json"),
+        json!({
+            "name": "mytheme",
+            "version": "1.0.0",
+            "author": "Someone",
+            "description": "Test",
+            "marmite_version": ">=99.0.0"
+        })
+        .to_string(),
+    )
+    .unwrap();
+    fs::write(
+        templates_path.join("content.html"),
+        r#"{% extends "base.html" %}
+{% block main %}
+{# {{ commented_out }} #}
+<h1>{{ content.title | upper }}</h1>
+<p>{{ content.subtitle }} {{ site.name }} {{ site_data.site.nonexistent }}</p>
+{% for tag in content.tags %}<a href="{{ url_for(path=tag) }}">{{ tag }}</a>{% endfor %}
+{% if hero is defined %}{{ hero }}{% endif %}
+{% if promo is defined %}{{ promo }}{% endif %}
+{{ sponsor }}
+{% include "missing.html" %}
+{% include "optional.html" ignore missing %}
+{% endblock %}
+"#,
+    )
+    .unwrap();
+
+    let issues = check_theme(Some(&theme_path), &templates_path);
+    let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+
+    assert!(messages
+        .iter()
+        .any(|m| m.starts_with("theme.json: requires marmite >=99.0.0")));
+    for name in ["base.html", "list.html", "group.html"] {
+        assert!(messages.contains(&format!(
+            "{name}: missing from the theme, the embedded template will be used"
+        )));
+    }
+    assert!(!messages
+        .iter()
+        .any(|m| m.starts_with("content.html: missing")));
+    let content_issues: Vec<&str> = issues
+        .iter()
+        .filter(|issue| issue.template == "content.html")
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert_eq!(
+        content_issues,
+        vec![
+            "references template `missing.html` which does not exist",
+            "uses `content.subtitle` which is not part of the template context",
+            "uses `site.nonexistent` which is not part of the template context",
+            "uses undefined variable `sponsor`",
+        ]
+    );
+}
+
+#[test]
+fn test_check_theme_accepts_bundled_themes() {
+    let temp_dir = TempDir::new().unwrap();
+    crate::templates::new_theme(temp_dir.path(), "minimal");
+    crate::templates::initialize_theme(temp_dir.path(), "clean");
+
+    for name in ["minimal", "clean"] {
+        let theme_path = temp_dir.path().join(name);
+        let issues = check_theme(Some(&theme_path), &theme_path.join("templates"));
+        assert!(issues.is_empty(), "{name}: {issues:?}");
+    }
+}
+
+#[test]
+fn test_check_theme_accepts_example_templates() {
+    let templates_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("example/templates");
+    let issues = check_theme(None, &templates_path);
+    assert!(issues.is_empty(), "{issues:?}");
+}
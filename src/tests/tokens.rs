@@ -0,0 +1,51 @@
+use super::*;
+use crate::content::ContentBuilder;
+use chrono::NaiveDate;
+
+fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 7, 4)
+        .unwrap()
+        .and_hms_opt(10, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_replace_tokens_skips_code() {
+    let site = json!({"name": "Tom & Jerry", "url": "https://example.com", "pagination": 10});
+    let build = json!({"year": "2025"});
+    let content = json!({"word_count": 42});
+    let html = concat!(
+        "<p>Visit {{ site.url }} ({{site.name}}), {{ content.word_count }} words, ",
+        "&copy; {{ build.year }} {{ site.pagination }}</p>",
+        "<pre><code>{{ site.url }}</code></pre><p><code>{{ build.year }}</code></p>"
+    );
+    assert_eq!(
+        replace_tokens(html, &site, &build, &content),
+        concat!(
+            "<p>Visit https://example.com (Tom &amp; Jerry), 42 words, ",
+            "&copy; 2025 10</p>",
+            "<pre><code>{{ site.url }}</code></pre><p><code>{{ build.year }}</code></p>"
+        )
+    );
+}
+
+#[test]
+fn test_replace_tokens_keeps_unknown() {
+    let site = json!({"menu": [["Home", "index.html"]]});
+    let html = "<p>{{ site.menu }} {{ site.missing }} {{ page.title }}</p>";
+    assert_eq!(replace_tokens(html, &site, &json!({}), &json!({})), html);
+}
+
+#[test]
+fn test_apply_tokens() {
+    let site = Marmite::new();
+    let build = build_values(&site, now());
+    assert_eq!(build["year"], "2025");
+
+    let mut content = ContentBuilder::new()
+        .title("Stats".to_string())
+        .html("<p>{{ content.title }} built in {{ build.year }}</p>".to_string())
+        .build();
+    apply_tokens(&mut content, &serde_json::to_value(&site).unwrap(), &build);
+    assert_eq!(content.html, "<p>Stats built in 2025</p>");
+}
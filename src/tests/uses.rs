@@ -0,0 +1,34 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_load_uses() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("uses.yaml");
+    fs::write(
+        &path,
+        "software:\n  - Neovim\n  - name: Marmite\n    description: Builds this site\n    url: https://marmite.blog\nhardware:\n  - name: ThinkPad X1\n    description: 32GB RAM\nbroken: 42\n",
+    )
+    .unwrap();
+    let uses = load_uses(&path);
+    let names: Vec<&str> = uses.iter().map(|category| category.name.as_str()).collect();
+    assert_eq!(names, vec!["software", "hardware"]);
+    assert_eq!(uses[0].items.len(), 2);
+    assert_eq!(uses[0].items[0].name, "Neovim");
+    assert_eq!(uses[0].items[0].url, None);
+    assert_eq!(
+        uses[0].items[1].url.as_deref(),
+        Some("https://marmite.blog")
+    );
+    assert_eq!(uses[1].items[0].description.as_deref(), Some("32GB RAM"));
+}
+
+#[test]
+fn test_load_uses_missing_or_invalid() {
+    let dir = TempDir::new().unwrap();
+    assert!(load_uses(&dir.path().join("uses.yaml")).is_empty());
+
+    let path = dir.path().join("invalid.yaml");
+    fs::write(&path, "- not\n- a mapping\n").unwrap();
+    assert!(load_uses(&path).is_empty());
+}
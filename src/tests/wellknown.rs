@@ -0,0 +1,51 @@
+use super::*;
+use crate::config::Author;
+use chrono::TimeZone;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap()
+}
+
+#[test]
+fn test_humans_txt() {
+    let mut site = Marmite::new();
+    site.name = "My Blog".to_string();
+    site.url = "https://example.com".to_string();
+    site.authors.insert(
+        "rochacbruno".to_string(),
+        Author {
+            name: "Bruno Rocha".to_string(),
+            avatar: None,
+            bio: Some("Writes Rust".to_string()),
+            links: Some(vec![(
+                "Mastodon".to_string(),
+                "https://mastodon.social/@rochacbruno".to_string(),
+            )]),
+            ..Default::default()
+        },
+    );
+    let humans = humans_txt(&site, now());
+    assert!(humans.starts_with(
+        "/* TEAM */\nName: Bruno Rocha\nBio: Writes Rust\nMastodon: https://mastodon.social/@rochacbruno\n\n"
+    ));
+    assert!(humans.contains("URL: https://example.com\nLast update: 2025/01/15\nLanguage: en\n"));
+}
+
+#[test]
+fn test_security_txt() {
+    let mut site = Marmite::new();
+    site.url = "https://example.com/".to_string();
+    let config: SecurityTxtConfig = serde_yaml::from_str(
+        "contact: [security@example.com, https://example.com/report]\npolicy: https://example.com/policy.html\n",
+    )
+    .unwrap();
+    assert_eq!(
+        security_txt(&config, &site, now()),
+        "Contact: mailto:security@example.com\n\
+         Contact: https://example.com/report\n\
+         Expires: 2025-07-14T10:00:00Z\n\
+         Policy: https://example.com/policy.html\n\
+         Preferred-Languages: en\n\
+         Canonical: https://example.com/.well-known/security.txt\n"
+    );
+}
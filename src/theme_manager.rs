@@ -1,8 +1,105 @@
+use crate::content::Content;
+use crate::embedded::Templates;
+use crate::re;
+use crate::site::Data;
 use log::{error, info};
-use serde::Deserialize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Templates every theme is expected to provide, the embedded ones are used otherwise
+const REQUIRED_TEMPLATES: [&str; 4] = ["base.html", "content.html", "list.html", "group.html"];
+
+/// Variables marmite inserts in the template context, besides these only
+/// variables created by the templates and the ones guarded by `is defined`
+/// are accepted by the theme check
+const CONTEXT_VARIABLES: [&str; 60] = [
+    "site",
+    "site_data",
+    "menu",
+    "head_meta",
+    "language",
+    "title",
+    "current_page",
+    "content",
+    "content_list",
+    "current_page_number",
+    "total_pages",
+    "total_content",
+    "previous_page",
+    "next_page",
+    "per_page",
+    "kind",
+    "stream",
+    "author",
+    "author_activity",
+    "tag",
+    "tag_intro",
+    "tag_intersection",
+    "archive_intro",
+    "section",
+    "breadcrumbs",
+    "markdown_fragments",
+    "announce",
+    "header",
+    "hero",
+    "sidebar",
+    "footer",
+    "comments",
+    "htmlhead",
+    "htmltail",
+    "build_metadata",
+    "contact_form",
+    "form_action",
+    "months",
+    "stale",
+    "terms",
+    "review",
+    "orphans",
+    "bookmarks",
+    "photos",
+    "statuses",
+    "now_page",
+    "uses",
+    "resume",
+    "pdf",
+    "projects",
+    "project",
+    "sitemap_urls",
+    "content_hash",
+    // rss.xml
+    "feed_name",
+    "feed_title",
+    "feed_link",
+    "items",
+    "pub_date",
+    "last_build_date",
+    "image",
+];
+
+/// Words that look like variables in Tera expressions
+const TERA_KEYWORDS: [&str; 15] = [
+    "and",
+    "or",
+    "not",
+    "in",
+    "is",
+    "if",
+    "else",
+    "as",
+    "true",
+    "false",
+    "True",
+    "False",
+    "loop",
+    "super",
+    "__tera_context",
+];
 
 #[derive(Deserialize, Debug)]
 #[allow(dead_code)]
@@ -279,6 +376,366 @@ fn find_theme_root(dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
     Err("theme.json not found in downloaded theme".into())
 }
 
+/// A problem found by `check_theme` on one of the theme files
+#[derive(Debug, PartialEq)]
+pub struct ThemeIssue {
+    pub template: String,
+    pub message: String,
+}
+
+impl fmt::Display for ThemeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.template, self.message)
+    }
+}
+
+/// Field names of the objects whose fields are checked in the templates
+struct ContextSchema {
+    site: HashSet<String>,
+    site_data: HashSet<String>,
+    content: HashSet<String>,
+}
+
+impl ContextSchema {
+    /// Field names are taken from the serialized structs, so the schema is
+    /// always the one used to render the templates
+    fn current() -> Self {
+        let site_data = Data::new("", Path::new(""));
+        Self {
+            site: object_keys(&site_data.site),
+            site_data: object_keys(&site_data),
+            content: object_keys(&Content::default()),
+        }
+    }
+
+    fn fields(&self, object: &str) -> Option<&HashSet<String>> {
+        match object {
+            "site" => Some(&self.site),
+            "site_data" => Some(&self.site_data),
+            "content" => Some(&self.content),
+            _ => None,
+        }
+    }
+}
+
+fn object_keys<T: Serialize>(value: &T) -> HashSet<String> {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| {
+            value
+                .as_object()
+                .map(|object| object.keys().cloned().collect())
+        })
+        .unwrap_or_default()
+}
+
+enum TeraTag {
+    Expression(String),
+    Statement(String),
+}
+
+/// Validates the templates of a theme against the current template context,
+/// reporting an incompatible `marmite_version`, missing required templates,
+/// references to templates that do not exist, undefined variables and fields
+/// that are not part of `site`, `site_data` or `content`.
+pub fn check_theme(theme_path: Option<&Path>, templates_path: &Path) -> Vec<ThemeIssue> {
+    let mut issues = Vec::new();
+
+    if let Some(theme_path) = theme_path {
+        let current_version = env!("CARGO_PKG_VERSION");
+        if let Ok(metadata) = read_theme_metadata(&theme_path.join("theme.json")) {
+            if let Some(required) = metadata.marmite_version {
+                if !version_satisfies(&required, current_version) {
+                    issues.push(ThemeIssue {
+                        template: "theme.json".to_string(),
+                        message: format!(
+                            "requires marmite {required} but this is marmite {current_version}"
+                        ),
+                    });
+                }
+            }
+        }
+        for name in REQUIRED_TEMPLATES {
+            if !templates_path.join(name).exists() {
+                issues.push(ThemeIssue {
+                    template: name.to_string(),
+                    message: "missing from the theme, the embedded template will be used"
+                        .to_string(),
+                });
+            }
+        }
+    }
+
+    let templates = read_templates(templates_path);
+    let embedded: BTreeMap<String, String> = Templates::iter()
+        .filter_map(|name| {
+            let file = Templates::get(name.as_ref())?;
+            let source = std::str::from_utf8(file.data.as_ref()).ok()?.to_string();
+            Some((name.to_string(), source))
+        })
+        .collect();
+    let known_templates: HashSet<&str> = templates
+        .keys()
+        .chain(embedded.keys())
+        .map(String::as_str)
+        .collect();
+
+    // Variables created by any template count as defined everywhere,
+    // a snippet can be included from inside a loop of another template
+    let mut locals = HashSet::new();
+    for source in templates.values().chain(embedded.values()) {
+        collect_locals(&tera_tags(source), &mut locals);
+    }
+
+    let schema = ContextSchema::current();
+    for (name, source) in &templates {
+        let found = check_template(source, &locals, &known_templates, &schema);
+        issues.extend(found.into_iter().map(|message| ThemeIssue {
+            template: name.clone(),
+            message,
+        }));
+    }
+
+    issues
+}
+
+/// Reads every template under `templates_path` keyed by its template name
+fn read_templates(templates_path: &Path) -> BTreeMap<String, String> {
+    WalkDir::new(templates_path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.path().is_file())
+        .filter_map(|entry| {
+            let name = entry
+                .path()
+                .strip_prefix(templates_path)
+                .ok()?
+                .to_str()?
+                .replace('\\', "/");
+            let source = fs::read_to_string(entry.path()).ok()?;
+            Some((name, source))
+        })
+        .collect()
+}
+
+/// Splits a template in the bodies of its `{{ }}` expressions and `{% %}`
+/// statements, skipping comments and `raw` blocks
+fn tera_tags(source: &str) -> Vec<TeraTag> {
+    let comments = Regex::new(re::MATCH_TERA_COMMENTS).expect("Tera comments regex should compile");
+    let tags = Regex::new(re::CAPTURE_TERA_TAGS).expect("Tera tags regex should compile");
+    let source = comments.replace_all(source, "");
+
+    let mut result = Vec::new();
+    let mut in_raw = false;
+    for captures in tags.captures_iter(&source) {
+        if let Some(expression) = captures.get(1) {
+            if !in_raw {
+                result.push(TeraTag::Expression(expression.as_str().trim().to_string()));
+            }
+        } else if let Some(statement) = captures.get(2) {
+            let statement = statement.as_str().trim();
+            match statement {
+                "raw" => in_raw = true,
+                "endraw" => in_raw = false,
+                _ if !in_raw => result.push(TeraTag::Statement(statement.to_string())),
+                _ => {}
+            }
+        }
+    }
+    result
+}
+
+/// Adds the names created by `set`, `for`, macro arguments and imports
+fn collect_locals(tags: &[TeraTag], locals: &mut HashSet<String>) {
+    for tag in tags {
+        let TeraTag::Statement(statement) = tag else {
+            continue;
+        };
+        let (keyword, rest) = statement
+            .split_once(char::is_whitespace)
+            .unwrap_or((statement.as_str(), ""));
+        match keyword {
+            "for" => {
+                let names = rest.split_once(" in ").map_or(rest, |(names, _)| names);
+                locals.extend(names.split(',').map(|name| name.trim().to_string()));
+            }
+            "set" | "set_global" => {
+                let name = rest.split_once('=').map_or(rest, |(name, _)| name);
+                locals.insert(name.trim().to_string());
+            }
+            "macro" => {
+                let arguments = rest
+                    .split_once('(')
+                    .map_or("", |(_, arguments)| arguments.trim_end_matches(')'));
+                locals.extend(arguments.split(',').map(|argument| {
+                    let name = argument.split_once('=').map_or(argument, |(name, _)| name);
+                    name.trim().to_string()
+                }));
+            }
+            "import" => {
+                if let Some((_, alias)) = rest.rsplit_once(" as ") {
+                    locals.insert(alias.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks a single template and returns the messages for the problems found
+fn check_template(
+    source: &str,
+    locals: &HashSet<String>,
+    known_templates: &HashSet<&str>,
+    schema: &ContextSchema,
+) -> BTreeSet<String> {
+    let strings = Regex::new(re::CAPTURE_TERA_STRINGS).expect("Tera strings regex should compile");
+    let identifier =
+        Regex::new(re::MATCH_TERA_IDENTIFIER).expect("Tera identifier regex should compile");
+    let mut found = BTreeSet::new();
+    let mut references = Vec::new();
+
+    for tag in tera_tags(source) {
+        let expression = match &tag {
+            TeraTag::Expression(expression) => expression.as_str(),
+            TeraTag::Statement(statement) => {
+                let (keyword, rest) = statement
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((statement.as_str(), ""));
+                match keyword {
+                    "if" | "elif" => rest,
+                    "for" => rest.split_once(" in ").map_or("", |(_, iterable)| iterable),
+                    "set" | "set_global" => rest.split_once('=').map_or("", |(_, value)| value),
+                    "extends" | "include" | "import" => {
+                        let target = strings.captures(rest).and_then(|captures| {
+                            (1..=3).find_map(|i| captures.get(i)).map(|m| m.as_str())
+                        });
+                        if let Some(target) = target {
+                            if !rest.contains("ignore missing") && !known_templates.contains(target)
+                            {
+                                found.insert(format!(
+                                    "references template `{target}` which does not exist"
+                                ));
+                            }
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                }
+            }
+        };
+        let expression = strings.replace_all(expression, "\"\"");
+        references.extend(variable_references(&expression, &identifier));
+    }
+
+    let guarded: HashSet<&str> = references
+        .iter()
+        .filter(|(_, guarded)| *guarded)
+        .map(|(reference, _)| reference.as_str())
+        .collect();
+
+    for (reference, _) in &references {
+        let segments: Vec<&str> = reference.split('.').collect();
+        let root = segments[0];
+        if !CONTEXT_VARIABLES.contains(&root) && !locals.contains(root) && !guarded.contains(root) {
+            found.insert(format!("uses undefined variable `{root}`"));
+        }
+
+        let (object, field) = match segments.as_slice() {
+            ["site_data", "site", field, ..] => ("site", *field),
+            [object, field, ..] => (*object, *field),
+            _ => continue,
+        };
+        if let Some(fields) = schema.fields(object) {
+            if !fields.contains(field) && !guarded.contains(reference.as_str()) {
+                found.insert(format!(
+                    "uses `{object}.{field}` which is not part of the template context"
+                ));
+            }
+        }
+    }
+
+    found
+}
+
+/// Returns the dotted variables used in an expression (string literals
+/// already removed) and whether each one is only tested with `is defined`
+fn variable_references(expression: &str, identifier: &Regex) -> Vec<(String, bool)> {
+    let mut references = Vec::new();
+    for m in identifier.find_iter(expression) {
+        let before = expression[..m.start()].trim_end();
+        let after = expression[m.end()..].trim_start();
+
+        // filters, macro namespaces, attributes of indexed values and tests
+        if before.ends_with('|') || before.ends_with("::") || before.ends_with('.') {
+            continue;
+        }
+        let mut previous = before.split_whitespace().rev();
+        match (previous.next(), previous.next()) {
+            (Some("is"), _) | (Some("not"), Some("is")) => continue,
+            _ => {}
+        }
+        // function calls and keyword arguments
+        if after.starts_with('(')
+            || after.starts_with("::")
+            || (after.starts_with('=') && !after.starts_with("=="))
+        {
+            continue;
+        }
+
+        let reference = m.as_str();
+        let root = reference.split('.').next().unwrap_or(reference);
+        if TERA_KEYWORDS.contains(&root) {
+            continue;
+        }
+
+        let guarded = after.strip_prefix("is").is_some_and(|test| {
+            let test = test.trim_start();
+            let test = test.strip_prefix("not").map_or(test, str::trim_start);
+            test.starts_with("defined") || test.starts_with("undefined")
+        });
+        references.push((reference.to_string(), guarded));
+    }
+    references
+}
+
+/// Compares `current` with a requirement like `>=0.2.6`, requirements that
+/// can not be parsed are considered satisfied
+fn version_satisfies(requirement: &str, current: &str) -> bool {
+    fn parse(version: &str) -> Option<Vec<u64>> {
+        version
+            .trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    }
+
+    let requirement = requirement.trim();
+    let (operator, version) = ["==", ">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|operator| {
+            requirement
+                .strip_prefix(operator)
+                .map(|version| (*operator, version))
+        })
+        .unwrap_or(("==", requirement));
+    let (Some(mut required), Some(mut current)) = (parse(version), parse(current)) else {
+        return true;
+    };
+    let len = required.len().max(current.len());
+    required.resize(len, 0);
+    current.resize(len, 0);
+
+    match operator {
+        ">=" => current >= required,
+        "<=" => current <= required,
+        ">" => current > required,
+        "<" => current < required,
+        _ => current == required,
+    }
+}
+
 /// Reads theme metadata from theme.json
 fn read_theme_metadata(path: &Path) -> Result<ThemeMetadata, Box<dyn std::error::Error>> {
     let content = fs::read_to_string(path)?;
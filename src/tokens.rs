@@ -0,0 +1,95 @@
+use crate::config::Marmite;
+use crate::content::Content;
+use crate::re;
+use chrono::NaiveDateTime;
+use log::warn;
+use regex::{Captures, Regex};
+use serde_json::{json, Value};
+
+/// Values available to the `{{ build.* }}` tokens
+pub fn build_values(site: &Marmite, now: NaiveDateTime) -> Value {
+    json!({
+        "date": now.format(&site.default_date_format).to_string(),
+        "year": now.format("%Y").to_string(),
+        "version": env!("CARGO_PKG_VERSION"),
+    })
+}
+
+fn lookup<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(root, |value, key| value.get(key))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Replace the `{{ site.* }}`, `{{ build.* }}` and `{{ content.* }}` tokens of
+/// the html, the contents of `<pre>`, `<code>` and `<script>` are kept as written.
+/// Only text and numbers are replaced, other tokens are kept and a warning logged
+pub fn replace_tokens(html: &str, site: &Value, build: &Value, content: &Value) -> String {
+    if !html.contains("{{") {
+        return html.to_string();
+    }
+    let skip_re =
+        Regex::new(re::MATCH_CODE_OR_SCRIPT_ELEMENT).expect("Code element regex should compile");
+    let token_re = Regex::new(re::CAPTURE_BUILD_TOKEN).expect("Token regex should compile");
+    let replace = |text: &str| {
+        token_re
+            .replace_all(text, |caps: &Captures| {
+                let root = match &caps[1] {
+                    "site" => site,
+                    "build" => build,
+                    _ => content,
+                };
+                match lookup(root, &caps[2]) {
+                    Some(Value::String(text)) => escape_html(text),
+                    Some(value @ (Value::Number(_) | Value::Bool(_))) => value.to_string(),
+                    _ => {
+                        warn!("Token `{}` has no text value, kept as written", &caps[0]);
+                        caps[0].to_string()
+                    }
+                }
+            })
+            .to_string()
+    };
+
+    let mut output = String::with_capacity(html.len());
+    let mut last = 0;
+    for element in skip_re.find_iter(html) {
+        output.push_str(&replace(&html[last..element.start()]));
+        output.push_str(element.as_str());
+        last = element.end();
+    }
+    output.push_str(&replace(&html[last..]));
+    output
+}
+
+/// Replace the tokens of the content html and summary, `content.*` tokens
+/// read the fields of the content itself, e.g: `{{ content.word_count }}`
+pub fn apply_tokens(content: &mut Content, site: &Value, build: &Value) {
+    let has_tokens = content.html.contains("{{")
+        || content
+            .summary_html
+            .as_ref()
+            .is_some_and(|summary| summary.contains("{{"));
+    if !has_tokens {
+        return;
+    }
+    let values = match serde_json::to_value(&*content) {
+        Ok(values) => values,
+        Err(e) => {
+            warn!("Failed to read the tokens of {}: {e}", content.slug);
+            return;
+        }
+    };
+    content.html = replace_tokens(&content.html, site, build, &values);
+    if let Some(summary) = &content.summary_html {
+        content.summary_html = Some(replace_tokens(summary, site, build, &values));
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/tokens.rs"]
+mod tests;
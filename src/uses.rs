@@ -0,0 +1,82 @@
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A thing listed on the uses page, e.g: a laptop, an editor or a hosting service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsesItem {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Items are either `- Neovim` or `- {name: Neovim, description: ..., url: ...}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UsesEntry {
+    Name(String),
+    Item(UsesItem),
+}
+
+/// A group of the uses page, e.g: `hardware`, in the order of the data file
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UsesCategory {
+    pub name: String,
+    pub slug: String,
+    pub items: Vec<UsesItem>,
+}
+
+/// Load the `category: [items]` mapping of the uses page keeping the order
+/// of the file, returns an empty list if the file doesn't exist
+pub fn load_uses(path: &Path) -> Vec<UsesCategory> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            error!("Failed to read uses {}: {e}", path.display());
+            return Vec::new();
+        }
+    };
+    match serde_yaml::from_str::<serde_yaml::Mapping>(&text) {
+        Ok(mapping) => mapping
+            .into_iter()
+            .filter_map(|(name, entries)| {
+                let name = name.as_str()?.to_string();
+                match serde_yaml::from_value::<Vec<UsesEntry>>(entries) {
+                    Ok(entries) => Some(UsesCategory {
+                        slug: slug::slugify(&name),
+                        items: entries
+                            .into_iter()
+                            .map(|entry| match entry {
+                                UsesEntry::Name(name) => UsesItem {
+                                    name,
+                                    description: None,
+                                    url: None,
+                                },
+                                UsesEntry::Item(item) => item,
+                            })
+                            .collect(),
+                        name,
+                    }),
+                    Err(e) => {
+                        error!("Invalid uses category '{name}' in {}: {e}", path.display());
+                        None
+                    }
+                }
+            })
+            .collect(),
+        Err(e) => {
+            error!("Failed to parse uses {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/uses.rs"]
+mod tests;
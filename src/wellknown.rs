@@ -0,0 +1,120 @@
+use crate::config::{Marmite, SecurityTxtConfig};
+use crate::site::Data;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use log::{error, info};
+use std::fs;
+use std::path::Path;
+
+/// Content of `humans.txt` (<https://humanstxt.org>) listing the `authors`
+/// of the configuration, sorted by username, and the site information
+pub fn humans_txt(site: &Marmite, now: DateTime<Utc>) -> String {
+    let mut usernames: Vec<&String> = site.authors.keys().collect();
+    usernames.sort();
+
+    let mut humans = String::from("/* TEAM */\n");
+    for username in usernames {
+        let author = &site.authors[username];
+        humans.push_str(&format!("Name: {}\n", author.name));
+        if let Some(bio) = author.bio.as_ref().filter(|bio| !bio.is_empty()) {
+            humans.push_str(&format!("Bio: {}\n", bio.replace('\n', " ")));
+        }
+        if let Some(url) = &author.url {
+            humans.push_str(&format!("Site: {url}\n"));
+        }
+        if let Some(location) = &author.location {
+            humans.push_str(&format!("Location: {location}\n"));
+        }
+        for (label, url) in author.links.iter().flatten() {
+            humans.push_str(&format!("{label}: {url}\n"));
+        }
+        humans.push('\n');
+    }
+    humans.push_str("/* SITE */\n");
+    humans.push_str(&format!("Name: {}\n", site.name));
+    if !site.url.is_empty() {
+        humans.push_str(&format!("URL: {}\n", site.url));
+    }
+    humans.push_str(&format!("Last update: {}\n", now.format("%Y/%m/%d")));
+    humans.push_str(&format!("Language: {}\n", site.language));
+    humans.push_str("Software: Marmite\n");
+    humans
+}
+
+/// Content of `security.txt` (RFC 9116), e-mail contacts get the `mailto:` scheme,
+/// `Expires` is computed from `now` so the file stays valid as long as the site is built
+pub fn security_txt(config: &SecurityTxtConfig, site: &Marmite, now: DateTime<Utc>) -> String {
+    let mut security = String::new();
+    for contact in &config.contact {
+        if contact.contains('@') && !contact.contains(':') {
+            security.push_str(&format!("Contact: mailto:{contact}\n"));
+        } else {
+            security.push_str(&format!("Contact: {contact}\n"));
+        }
+    }
+    let expires = now + Duration::days(i64::from(config.expires_days));
+    security.push_str(&format!(
+        "Expires: {}\n",
+        expires.to_rfc3339_opts(SecondsFormat::Secs, true)
+    ));
+    let fields = [
+        ("Encryption", &config.encryption),
+        ("Policy", &config.policy),
+        ("Acknowledgments", &config.acknowledgments),
+        ("Hiring", &config.hiring),
+    ];
+    for (field, value) in fields {
+        if let Some(value) = value {
+            security.push_str(&format!("{field}: {value}\n"));
+        }
+    }
+    security.push_str(&format!(
+        "Preferred-Languages: {}\n",
+        config
+            .preferred_languages
+            .as_deref()
+            .unwrap_or(&site.language)
+    ));
+    if !site.url.is_empty() {
+        security.push_str(&format!(
+            "Canonical: {}/.well-known/security.txt\n",
+            site.url.trim_end_matches('/')
+        ));
+    }
+    security
+}
+
+/// Write `humans.txt` and `.well-known/security.txt` when enabled in the configuration
+pub fn write_wellknown_files(site_data: &Data, output_path: &Path) {
+    let site = &site_data.site;
+    let now = Utc::now();
+    if site.humans_txt {
+        write_file(&output_path.join("humans.txt"), &humans_txt(site, now));
+    }
+    if let Some(config) = &site.security_txt {
+        if config.contact.is_empty() {
+            error!("security_txt needs at least one `contact`, skipping security.txt");
+            return;
+        }
+        let well_known = output_path.join(".well-known");
+        if let Err(e) = fs::create_dir_all(&well_known) {
+            error!("Unable to create {}: {e}", well_known.display());
+            return;
+        }
+        write_file(
+            &well_known.join("security.txt"),
+            &security_txt(config, site, now),
+        );
+    }
+}
+
+fn write_file(path: &Path, content: &str) {
+    if let Err(e) = fs::write(path, content) {
+        error!("Failed to write {}: {e}", path.display());
+    } else {
+        info!("Generated {}", path.display());
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/wellknown.rs"]
+mod tests;